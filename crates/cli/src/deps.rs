@@ -0,0 +1,165 @@
+//! `roc deps`, which lists every package and platform an app or package depends on by URL, for
+//! auditing what it runs.
+
+use bumpalo::Bump;
+use roc_build::program::handle_loading_problem;
+use roc_load::deps::{load_url_dependencies, UrlDependency};
+use roc_reporting::report::{strip_colors, ANSI_STYLE_CODES};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+use crate::package_cache::format_size;
+use crate::PackageDirs;
+
+/// Prints the URL dependencies of the given module, as text or as JSON, or writes them to the
+/// given file instead. Text is only colored when it's printed to a terminal.
+pub fn deps(
+    main_module: &Path,
+    as_json: bool,
+    opt_output: Option<&Path>,
+    has_color: bool,
+) -> io::Result<i32> {
+    let arena = Bump::new();
+    let package_dirs = PackageDirs::new(main_module);
+
+    let dependencies =
+        match load_url_dependencies(&arena, main_module, package_dirs.roc_cache_dir()) {
+            Ok(dependencies) => dependencies,
+            Err(problem) => return handle_loading_problem(problem),
+        };
+
+    let multiple_versions = multiple_versions(&dependencies);
+
+    match (opt_output, as_json) {
+        (Some(path), true) => std::fs::write(path, to_json(&dependencies, &multiple_versions))?,
+        (Some(path), false) => std::fs::write(
+            path,
+            strip_colors(&to_text(&dependencies, &multiple_versions)),
+        )?,
+        (None, true) => print!("{}", to_json(&dependencies, &multiple_versions)),
+        (None, false) if has_color && io::stdout().is_terminal() => {
+            print!("{}", to_text(&dependencies, &multiple_versions))
+        }
+        (None, false) => print!(
+            "{}",
+            strip_colors(&to_text(&dependencies, &multiple_versions))
+        ),
+    }
+
+    Ok(0)
+}
+
+/// The URLs of each host path that more than one URL has, which usually means several versions
+/// of the same package are in use.
+fn multiple_versions(dependencies: &[UrlDependency]) -> BTreeMap<String, Vec<&str>> {
+    let mut by_host_path: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+
+    for dependency in dependencies {
+        by_host_path
+            .entry(dependency.host_path())
+            .or_default()
+            .push(&dependency.url);
+    }
+
+    by_host_path.retain(|_, urls| urls.len() > 1);
+
+    by_host_path
+}
+
+fn to_text(
+    dependencies: &[UrlDependency],
+    multiple_versions: &BTreeMap<String, Vec<&str>>,
+) -> String {
+    let mut buf = String::new();
+
+    if dependencies.is_empty() {
+        buf.push_str("There are no URL packages or platforms.\n");
+
+        return buf;
+    }
+
+    for dependency in dependencies {
+        let kind = if dependency.is_platform {
+            "platform"
+        } else {
+            "package"
+        };
+        let shorthands: Vec<&str> = dependency.shorthands.iter().map(String::as_str).collect();
+
+        let _ = writeln!(
+            buf,
+            "{}{}{}",
+            ANSI_STYLE_CODES.cyan, dependency.url, ANSI_STYLE_CODES.reset
+        );
+        let _ = writeln!(buf, "    {kind}, as {}", shorthands.join(", "));
+        let _ = writeln!(
+            buf,
+            "    {} in {}",
+            format_size(dependency.size),
+            dependency.package_dir.display()
+        );
+
+        for importer in dependency.importers.iter() {
+            let _ = writeln!(buf, "    imported by {}", importer.display());
+        }
+
+        buf.push('\n');
+    }
+
+    for (host_path, urls) in multiple_versions {
+        let _ = writeln!(
+            buf,
+            "{}{} versions of {host_path} are in use:{}",
+            ANSI_STYLE_CODES.yellow,
+            urls.len(),
+            ANSI_STYLE_CODES.reset
+        );
+
+        for url in urls {
+            let _ = writeln!(buf, "    {url}");
+        }
+
+        buf.push('\n');
+    }
+
+    let _ = writeln!(buf, "{} URL package(s) and platform(s)", dependencies.len());
+
+    buf
+}
+
+fn to_json(
+    dependencies: &[UrlDependency],
+    multiple_versions: &BTreeMap<String, Vec<&str>>,
+) -> String {
+    let packages: Vec<_> = dependencies
+        .iter()
+        .map(|dependency| {
+            json!({
+                "url": dependency.url,
+                "kind": if dependency.is_platform { "platform" } else { "package" },
+                "shorthands": dependency.shorthands,
+                "importers": dependency.importers,
+                "dir": dependency.package_dir,
+                "size": dependency.size,
+                "host_path": dependency.host_path(),
+            })
+        })
+        .collect();
+    let multiple_versions: Vec<_> = multiple_versions
+        .iter()
+        .map(|(host_path, urls)| json!({ "host_path": host_path, "urls": urls }))
+        .collect();
+
+    let mut buf = serde_json::to_string_pretty(&json!({
+        "packages": packages,
+        "multiple_versions": multiple_versions,
+    }))
+    .unwrap();
+
+    buf.push('\n');
+
+    buf
+}
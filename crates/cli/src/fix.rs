@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use roc_region::all::Region;
+use roc_reporting::cli::FileFix;
+use roc_reporting::report::Edit;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AppliedFixes {
+    pub fixes: usize,
+    pub files: usize,
+}
+
+/// Applies each fix to the file it's for, skipping files outside `root_dir` (like packages in the
+/// cache) and any fix that overlaps one applied before it in the same file. Those are left for the
+/// next run, once the source has settled.
+pub fn apply_fixes(root_dir: &Path, fixes: Vec<FileFix>) -> io::Result<AppliedFixes> {
+    let root_dir = root_dir.canonicalize()?;
+    let mut by_file: BTreeMap<PathBuf, Vec<Vec<Edit>>> = BTreeMap::new();
+
+    for FileFix { filename, fix } in fixes {
+        match filename.canonicalize() {
+            Ok(path) if path.starts_with(&root_dir) => {
+                by_file.entry(path).or_default().push(fix.edits);
+            }
+            _ => {}
+        }
+    }
+
+    let mut applied = AppliedFixes::default();
+
+    for (filename, fixes) in by_file {
+        let src = std::fs::read_to_string(&filename)?;
+        let (new_src, count) = apply_edits(&src, fixes);
+
+        if count > 0 {
+            std::fs::write(&filename, new_src)?;
+
+            applied.fixes += count;
+            applied.files += 1;
+        }
+    }
+
+    Ok(applied)
+}
+
+/// Returns the source with as many of the fixes applied as don't overlap each other, and how many
+/// that was.
+fn apply_edits(src: &str, fixes: Vec<Vec<Edit>>) -> (String, usize) {
+    let mut accepted: Vec<Edit> = Vec::new();
+    let mut count = 0;
+
+    for edits in fixes {
+        let fits = edits.iter().all(|edit| {
+            in_bounds(src, edit.region)
+                && accepted
+                    .iter()
+                    .all(|other| !overlaps(edit.region, other.region))
+        });
+
+        if fits {
+            accepted.extend(edits);
+            count += 1;
+        }
+    }
+
+    // Going back to front keeps the offsets of the edits we haven't made yet valid
+    accepted.sort_by_key(|edit| std::cmp::Reverse(edit.region.start()));
+
+    let mut new_src = src.to_string();
+
+    for Edit {
+        region,
+        replacement,
+    } in accepted
+    {
+        let start = region.start().offset as usize;
+        let end = region.end().offset as usize;
+
+        new_src.replace_range(start..end, &replacement);
+    }
+
+    (new_src, count)
+}
+
+fn in_bounds(src: &str, region: Region) -> bool {
+    let start = region.start().offset as usize;
+    let end = region.end().offset as usize;
+
+    start <= end && src.get(start..end).is_some()
+}
+
+fn overlaps(a: Region, b: Region) -> bool {
+    // Two insertions at the same spot would each make the other's result ambiguous
+    (a.start() < b.end() && b.start() < a.end()) || a.start() == b.start()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use roc_region::all::Position;
+
+    fn edit(start: u32, end: u32, replacement: &str) -> Edit {
+        Edit {
+            region: Region::new(Position::new(start), Position::new(end)),
+            replacement: replacement.to_string(),
+        }
+    }
+
+    #[test]
+    fn applies_back_to_front() {
+        let (src, count) = apply_edits(
+            "a = fo\nb = br\n",
+            vec![vec![edit(4, 6, "foo")], vec![edit(11, 13, "bar")]],
+        );
+
+        assert_eq!(src, "a = foo\nb = bar\n");
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn skips_overlapping_fixes() {
+        let (src, count) = apply_edits(
+            "import Foo exposing [a, b]\n",
+            vec![vec![edit(21, 24, "")], vec![edit(22, 25, "")]],
+        );
+
+        assert_eq!(src, "import Foo exposing [b]\n");
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn skips_out_of_bounds_fixes() {
+        let (src, count) = apply_edits("x = 1\n", vec![vec![edit(4, 100, "2")]]);
+
+        assert_eq!(src, "x = 1\n");
+        assert_eq!(count, 0);
+    }
+}
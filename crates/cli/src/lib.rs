@@ -23,7 +23,9 @@ use roc_load::{ExpectMetadata, Threading};
 use roc_module::symbol::ModuleId;
 use roc_mono::ir::OptLevel;
 use roc_packaging::cache::RocCacheDir;
-use roc_packaging::tarball::Compression;
+use roc_packaging::tarball::{Bundle, Compression, ManifestEntry, MANIFEST_FILENAME};
+use roc_reporting::cli::{WarningLevel, WarningLevels};
+use roc_reporting::report::RenderTarget;
 #[cfg(not(windows))]
 use roc_reporting::report::ANSI_STYLE_CODES;
 use roc_target::{Architecture, Target};
@@ -41,11 +43,21 @@ use strum::IntoEnumIterator;
 #[cfg(not(target_os = "linux"))]
 use tempfile::TempDir;
 
+mod deps;
+mod fix;
 mod format;
+mod package_cache;
+#[cfg(not(windows))]
+mod test_output;
+mod vendor;
+pub use deps::deps;
+pub use fix::{apply_fixes, AppliedFixes};
 pub use format::{
     annotate_file, annotation_edit, annotation_edits, format_files, format_src, AnnotationProblem,
     FormatMode,
 };
+pub use package_cache::{cache_list, cache_prune, cache_verify};
+pub use vendor::{vendor, PackageDirs};
 
 pub const CMD_BUILD: &str = "build";
 pub const CMD_RUN: &str = "run";
@@ -60,6 +72,13 @@ pub const CMD_TEST: &str = "test";
 pub const CMD_GLUE: &str = "glue";
 pub const CMD_PREPROCESS_HOST: &str = "preprocess-host";
 pub const CMD_LICENSES: &str = "licenses";
+pub const CMD_EXPLAIN: &str = "explain";
+pub const CMD_VENDOR: &str = "vendor";
+pub const CMD_DEPS: &str = "deps";
+pub const CMD_CACHE: &str = "cache";
+pub const CMD_CACHE_LIST: &str = "list";
+pub const CMD_CACHE_VERIFY: &str = "verify";
+pub const CMD_CACHE_PRUNE: &str = "prune";
 
 pub const FLAG_EMIT_LLVM_IR: &str = "emit-llvm-ir";
 pub const FLAG_PROFILING: &str = "profiling";
@@ -75,6 +94,7 @@ pub const FLAG_TIME: &str = "time";
 pub const FLAG_VERBOSE: &str = "verbose";
 pub const FLAG_NO_COLOR: &str = "no-color";
 pub const FLAG_NO_HEADER: &str = "no-header";
+pub const FLAG_PLATFORM: &str = "platform";
 pub const FLAG_LINKER: &str = "linker";
 pub const FLAG_BUILD_HOST: &str = "build-host";
 pub const FLAG_SUPPRESS_BUILD_HOST_WARNING: &str = "suppress-build-host-warning";
@@ -85,10 +105,20 @@ pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
 pub const FLAG_OUTPUT: &str = "output";
 pub const FLAG_FUZZ: &str = "fuzz";
 pub const FLAG_MAIN: &str = "main";
+pub const FLAG_FILTER: &str = "filter";
+pub const FLAG_LIST: &str = "list";
+pub const FLAG_TIMEOUT: &str = "timeout";
+pub const FLAG_FORMAT: &str = "format";
+pub const FLAG_FIX: &str = "fix";
+pub const FLAG_ALLOW: &str = "allow";
+pub const FLAG_DENY: &str = "deny";
+pub const FLAG_OLDER_THAN: &str = "older-than";
 pub const ROC_FILE: &str = "ROC_FILE";
+pub const ROC_FILES: &str = "ROC_FILES";
 pub const GLUE_DIR: &str = "GLUE_DIR";
 pub const GLUE_SPEC: &str = "GLUE_SPEC";
 pub const DIRECTORY_OR_FILES: &str = "DIRECTORY_OR_FILES";
+pub const ERROR_CODE: &str = "ERROR_CODE";
 pub const ARGS_FOR_APP: &str = "ARGS_FOR_APP";
 pub const FLAG_PP_HOST: &str = "host";
 pub const FLAG_PP_PLATFORM: &str = "platform";
@@ -108,7 +138,7 @@ pub fn build_app() -> Command {
 
     let flag_max_threads = Arg::new(FLAG_MAX_THREADS)
         .long(FLAG_MAX_THREADS)
-        .help("Limit the number of threads (and hence cores) used during compilation")
+        .help("Limit the number of threads (and hence cores) used during compilation\n(For `roc test`, this also limits how many modules run their expects at once.)")
         .value_parser(value_parser!(usize))
         .required(false);
 
@@ -172,6 +202,25 @@ pub fn build_app() -> Command {
         .action(ArgAction::SetTrue)
         .required(false);
 
+    let flag_diagnostics_format = Arg::new(FLAG_FORMAT)
+        .long(FLAG_FORMAT)
+        .help("How to report errors and warnings\n(json prints one JSON object per report, for tools to read.)")
+        .value_parser(["text", "json"])
+        .default_value("text")
+        .required(false);
+
+    let flag_allow = Arg::new(FLAG_ALLOW)
+        .long(FLAG_ALLOW)
+        .help("Don't report this kind of warning, like unused-import or E2076\n(Can be given more than once. A `# roc: deny ...` comment in a module still takes precedence there.)")
+        .action(ArgAction::Append)
+        .required(false);
+
+    let flag_deny = Arg::new(FLAG_DENY)
+        .long(FLAG_DENY)
+        .help("Report this kind of warning as an error, like unused-import or E2076\n(Can be given more than once. A `# roc: allow ...` comment in a module still takes precedence there.)")
+        .action(ArgAction::Append)
+        .required(false);
+
     let flag_main = Arg::new(FLAG_MAIN)
         .long(FLAG_MAIN)
         .help("The .roc file of the main app/package module to resolve dependencies from")
@@ -213,6 +262,9 @@ pub fn build_app() -> Command {
             )
             .arg(flag_optimize.clone())
             .arg(flag_max_threads.clone())
+            .arg(flag_diagnostics_format.clone())
+            .arg(flag_allow.clone())
+            .arg(flag_deny.clone())
             .arg(flag_opt_size.clone())
             .arg(flag_dev.clone())
             .arg(flag_emit_llvm_ir.clone())
@@ -241,9 +293,9 @@ pub fn build_app() -> Command {
             .arg(
                 Arg::new(FLAG_BUNDLE)
                     .long(FLAG_BUNDLE)
-                    .help("Create an archive of a package (for example, a .tar, .tar.gz, or .tar.br file), so others can add it as a HTTPS dependency.")
+                    .help("Create an archive of a package (for example, a .tar, .tar.gz, .tar.br, or .tar.zst file), so others can add it as a HTTPS dependency.")
                     .conflicts_with(FLAG_TARGET)
-                    .value_parser([".tar", ".tar.gz", ".tar.br"])
+                    .value_parser([".tar", ".tar.gz", ".tar.br", ".tar.zst"])
                     .required(false),
             )
             .arg(
@@ -289,6 +341,35 @@ pub fn build_app() -> Command {
                     .action(ArgAction::SetTrue)
                     .required(false)
             )
+            .arg(
+                Arg::new(FLAG_FILTER)
+                    .long(FLAG_FILTER)
+                    .help("Only run the `expect`s whose module name, source, or doc comment matches this regex\n(If it isn't a valid regex, it's matched as a plain substring.)")
+                    .value_parser(value_parser!(String))
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_LIST)
+                    .long(FLAG_LIST)
+                    .help("List the `expect`s that would run, without running them")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_TIMEOUT)
+                    .long(FLAG_TIMEOUT)
+                    .help("Stop any `expect` that is still running after this many seconds, and count it as a failure")
+                    .value_parser(value_parser!(u64))
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_FORMAT)
+                    .long(FLAG_FORMAT)
+                    .help("How to report the results\n(junit, json and tap are for CI systems and other tools to read.)")
+                    .value_parser(["text", "junit", "json", "tap"])
+                    .default_value("text")
+                    .required(false)
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file to test")
@@ -315,6 +396,13 @@ pub fn build_app() -> Command {
                     .action(ArgAction::SetTrue)
                     .required(false)
             )
+            .arg(
+                Arg::new(FLAG_PLATFORM)
+                    .long(FLAG_PLATFORM)
+                    .help("The path or URL of a platform, so expressions can use its modules and effects")
+                    .value_parser(value_parser!(String))
+                    .required(false)
+            )
         )
         .subcommand(Command::new(CMD_RUN)
             .about("Run a .roc file even if it has build errors")
@@ -329,6 +417,8 @@ pub fn build_app() -> Command {
             .arg(flag_build_host.clone())
             .arg(flag_suppress_build_host_warning.clone())
             .arg(flag_fuzz.clone())
+            .arg(flag_allow.clone())
+            .arg(flag_deny.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone().last(true))
         )
@@ -345,6 +435,8 @@ pub fn build_app() -> Command {
             .arg(flag_build_host.clone())
             .arg(flag_suppress_build_host_warning.clone())
             .arg(flag_fuzz.clone())
+            .arg(flag_allow.clone())
+            .arg(flag_deny.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone().last(true))
         )
@@ -400,11 +492,29 @@ pub fn build_app() -> Command {
             .about(concatcp!("Print the Roc compiler’s version, which is currently ", VERSION)))
         .subcommand(Command::new(CMD_LICENSES)
             .about("Prints license info for Roc as well as attributions to other projects used by Roc."))
+        .subcommand(Command::new(CMD_EXPLAIN)
+            .about("Explain a kind of problem the compiler reports, with an example")
+            .arg(
+                Arg::new(ERROR_CODE)
+                    .help("The code shown next to the problem’s title, like E3016")
+                    .required(true)
+            )
+        )
         .subcommand(Command::new(CMD_CHECK)
             .about("Check the code for problems, but don’t build or run it")
             .arg(flag_main.clone())
             .arg(flag_time.clone())
             .arg(flag_max_threads.clone())
+            .arg(flag_diagnostics_format)
+            .arg(flag_allow.clone())
+            .arg(flag_deny.clone())
+            .arg(
+                Arg::new(FLAG_FIX)
+                    .long(FLAG_FIX)
+                    .help("Apply the suggested fix for each problem that has exactly one\n(Files outside the directory of the .roc file, like packages, are left alone.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file to check")
@@ -413,6 +523,76 @@ pub fn build_app() -> Command {
                     .default_value(DEFAULT_ROC_FILENAME),
             )
             )
+        .subcommand(Command::new(CMD_VENDOR)
+            .about("Copy the URL packages an app or package uses into a vendor directory next to it\n(Building it then uses those copies, so it doesn't need to download anything.)")
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The .roc file of the app or package")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
+        )
+        .subcommand(Command::new(CMD_DEPS)
+            .about("List every package and platform an app or package depends on by URL, including indirectly\n(Each one is shown with its shorthands, the modules that import it, and its size on disk.)")
+            .arg(
+                Arg::new(FLAG_FORMAT)
+                    .long(FLAG_FORMAT)
+                    .help("How to report the dependencies\n(json is for security audits and other tools to read.)")
+                    .value_parser(["text", "json"])
+                    .default_value("text")
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_OUTPUT)
+                    .long(FLAG_OUTPUT)
+                    .help("Write the report to this file instead of printing it")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_NO_COLOR)
+                    .long(FLAG_NO_COLOR)
+                    .help("Do not use any ANSI color codes in the text report\n(They're also left out when the report isn't printed to a terminal.)")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The .roc file of the app or package")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
+        )
+        .subcommand(Command::new(CMD_CACHE)
+            .about("Inspect and clean up the cache of downloaded packages")
+            .subcommand_required(true)
+            .subcommand(Command::new(CMD_CACHE_LIST)
+                .about("List the cached packages, with their size and when a build last used them")
+            )
+            .subcommand(Command::new(CMD_CACHE_VERIFY)
+                .about("Check that the files of each cached package are still the ones that were downloaded")
+            )
+            .subcommand(Command::new(CMD_CACHE_PRUNE)
+                .about("Delete cached packages that the given apps and packages don't use, or that haven't been used recently")
+                .arg(
+                    Arg::new(FLAG_OLDER_THAN)
+                        .long(FLAG_OLDER_THAN)
+                        .help("Delete packages that no build has used in this many days")
+                        .value_parser(value_parser!(u64))
+                        .required(false)
+                )
+                .arg(
+                    Arg::new(ROC_FILES)
+                        .help("The .roc files of the apps and packages whose packages to keep\n(Their dependencies are followed as far as they're in the cache.)")
+                        .value_parser(value_parser!(PathBuf))
+                        .num_args(0..)
+                        .required(false)
+                )
+                .arg_required_else_help(true)
+            )
+        )
         .subcommand(
             Command::new(CMD_DOCS)
                 .about("Generate documentation for a Roc package")
@@ -502,6 +682,8 @@ pub fn build_app() -> Command {
         .arg(flag_build_host)
         .arg(flag_suppress_build_host_warning)
         .arg(flag_fuzz)
+        .arg(flag_allow)
+        .arg(flag_deny)
         .arg(roc_file_to_run)
         .arg(args_for_app.trailing_var_arg(true))
 }
@@ -540,11 +722,108 @@ struct ModuleTestResults {
     tests_duration: Duration,
 }
 
+/// A file `roc test` compiled, whose modules' expects may still be running
+#[cfg(not(windows))]
+struct TestedFile {
+    interns: roc_module::symbol::Interns,
+    sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
+    compilation_duration: Duration,
+    /// How many of its modules' expects haven't come back from the workers yet
+    pending_modules: usize,
+    results_by_module: Vec<ModuleTestResults>,
+    failed_count: usize,
+    passed_count: usize,
+}
+
+/// The results of `roc test` so far, which it reports as each module's expects come back
+#[cfg(not(windows))]
+struct TestRun {
+    format: test_output::TestFormat,
+    verbose: bool,
+    start_time: Instant,
+    files: Vec<TestedFile>,
+    failed_count: usize,
+    passed_count: usize,
+    test_cases: Vec<test_output::TestCase>,
+}
+
+#[cfg(not(windows))]
+impl TestRun {
+    fn record_module_results(
+        &mut self,
+        file_index: usize,
+        module_id: ModuleId,
+        results: Vec<roc_repl_expect::run::ExpectResult>,
+    ) {
+        use test_output::{TestCase, TestFormat};
+
+        let file = &mut self.files[file_index];
+        let tests_duration = results.iter().map(|result| result.duration).sum();
+        let test_cases = TestCase::from_results(results, &file.interns, &file.sources);
+        let failed_count = test_cases
+            .iter()
+            .filter(|case| case.opt_failure.is_some())
+            .count();
+        let passed_count = test_cases.len() - failed_count;
+
+        match self.format {
+            TestFormat::Text => {
+                for failure in test_cases
+                    .iter()
+                    .filter_map(|case| case.opt_failure.as_ref())
+                {
+                    print!("{}", failure.report);
+                }
+            }
+            TestFormat::Json => {
+                for case in test_cases.iter() {
+                    println!("{}", test_output::json_test_event(case));
+                }
+            }
+            TestFormat::Junit | TestFormat::Tap => {}
+        }
+
+        self.test_cases.extend(test_cases);
+        self.failed_count += failed_count;
+        self.passed_count += passed_count;
+
+        file.results_by_module.push(ModuleTestResults {
+            module_id,
+            failed_count,
+            passed_count,
+            tests_duration,
+        });
+        file.failed_count += failed_count;
+        file.passed_count += passed_count;
+        file.pending_modules -= 1;
+
+        // The other formats report everything at the end
+        if file.pending_modules == 0 && self.format == TestFormat::Text {
+            if self.verbose {
+                println!("Compiled in {} ms.", file.compilation_duration.as_millis());
+
+                for module_test_results in std::mem::take(&mut file.results_by_module) {
+                    print_test_results(module_test_results, &file.sources);
+                }
+            } else {
+                let test_summary_str = test_summary(
+                    file.failed_count,
+                    file.passed_count,
+                    self.start_time.elapsed(),
+                );
+                println!("{test_summary_str}");
+            }
+        }
+    }
+}
+
 #[cfg(not(windows))]
 pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
+    use regex::Regex;
     use roc_build::program::report_problems_monomorphized;
     use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError};
-    use roc_packaging::cache;
+    use roc_repl_expect::run::ExpectWorkers;
+    use test_output::TestFormat;
 
     let start_time = Instant::now();
     let arena = Bump::new();
@@ -557,6 +836,19 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
         Some(n) => Threading::AtMost(*n),
     };
 
+    let opt_filter = matches.get_one::<String>(FLAG_FILTER).map(|pattern| {
+        Regex::new(pattern).unwrap_or_else(|_| Regex::new(&regex::escape(pattern)).unwrap())
+    });
+    let list_only = matches.get_flag(FLAG_LIST);
+    let opt_timeout = matches
+        .get_one::<u64>(FLAG_TIMEOUT)
+        .map(|secs| Duration::from_secs(*secs));
+    let format = TestFormat::from_flag(matches.get_one::<String>(FLAG_FORMAT).unwrap());
+    let render_target = match format {
+        TestFormat::Text => RenderTarget::ColorTerminal,
+        TestFormat::Junit | TestFormat::Json | TestFormat::Tap => RenderTarget::Generic,
+    };
+
     let paths: Vec<_> = matches.get_many::<PathBuf>(ROC_FILE).unwrap().collect();
 
     let paths: Vec<_> = {
@@ -589,21 +881,39 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
         flatten_paths
     };
 
-    let mut all_files_total_failed_count = 0;
-    let mut all_files_total_passed_count = 0;
+    let max_workers = match threading {
+        Threading::Single => 1,
+        Threading::AtMost(n) => n,
+        Threading::AllAvailable => std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let mut workers = ExpectWorkers::new(max_workers);
+    let mut run = TestRun {
+        format,
+        verbose: matches.get_flag(FLAG_VERBOSE),
+        start_time,
+        files: Vec::new(),
+        failed_count: 0,
+        passed_count: 0,
+        test_cases: Vec::new(),
+    };
+    let mut listed_count = 0;
+
+    if format == TestFormat::Json && !list_only {
+        println!("{}", test_output::json_started_event());
+    }
 
     for path in paths.iter() {
         let arena = &arena;
         let function_kind = FunctionKind::from_env();
 
         let opt_main_path = matches.get_one::<PathBuf>(FLAG_MAIN);
+        let package_dirs = PackageDirs::new(opt_main_path.map_or(path.as_path(), PathBuf::as_path));
 
         // Step 1: compile the app and generate the .o file
         let load_config = LoadConfig {
             target,
             function_kind,
-            // TODO: expose this from CLI?
-            render: roc_reporting::report::RenderTarget::ColorTerminal,
+            render: render_target,
             palette: roc_reporting::report::DEFAULT_PALETTE,
             threading,
             exec_mode: ExecutionMode::Test,
@@ -612,7 +922,7 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
             arena,
             path.to_path_buf(),
             opt_main_path.cloned(),
-            RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
+            package_dirs.roc_cache_dir(),
             load_config,
         );
 
@@ -622,10 +932,26 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
                 return handle_loading_problem(problem);
             }
             Err(LoadMonomorphizedError::ErrorModule(module)) => {
-                return handle_error_module(module, start_time.elapsed());
+                return handle_error_module(
+                    module,
+                    start_time.elapsed(),
+                    render_target,
+                    &WarningLevels::default(),
+                );
             }
         };
-        let problems = report_problems_monomorphized(&mut loaded);
+        let problems =
+            report_problems_monomorphized(&mut loaded, render_target, &WarningLevels::default());
+
+        if let Some(filter) = &opt_filter {
+            retain_matching_expects(&mut loaded, filter);
+        }
+
+        if list_only {
+            listed_count += list_expects(&loaded);
+
+            continue;
+        }
 
         let mut expectations = std::mem::take(&mut loaded.expectations);
 
@@ -648,7 +974,7 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
                 problems.errors, 0,
                 "if there were errors, we would have already exited."
             );
-            if problems.warnings > 0 {
+            if problems.warnings > 0 && format == TestFormat::Text {
                 problems.print_error_warning_count(start_time.elapsed());
                 println!(".\n\nRunning tests…\n\n\x1B[36m{}\x1B[39m", "─".repeat(80));
             }
@@ -656,66 +982,102 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
 
         // Run the tests.
         let arena = &bumpalo::Bump::new();
-        let interns = arena.alloc(interns);
-
-        let mut writer = std::io::stdout();
+        let file_index = run.files.len();
+
+        run.files.push(TestedFile {
+            interns: interns.clone(),
+            sources,
+            compilation_duration: start_time.elapsed(),
+            pending_modules: expects_by_module.len(),
+            results_by_module: Vec::new(),
+            failed_count: 0,
+            passed_count: 0,
+        });
 
-        let mut total_failed_count = 0;
-        let mut total_passed_count = 0;
-
-        let mut results_by_module = Vec::new();
+        let interns = arena.alloc(interns);
         let global_layout_interner = layout_interner.into_global();
 
-        let compilation_duration = start_time.elapsed();
-
+        // Each worker gets its own copy of the dylib, so this file's can be dropped once they've
+        // all been spawned, while later files compile.
         for (module_id, expects) in expects_by_module.into_iter() {
-            let test_start_time = Instant::now();
-
-            let (failed_count, passed_count) = roc_repl_expect::run::run_toplevel_expects(
-                &mut writer,
-                roc_reporting::report::RenderTarget::ColorTerminal,
+            let finished = workers.spawn(
+                (file_index, module_id),
+                render_target,
                 arena,
                 interns,
                 &global_layout_interner,
                 &dyn_lib,
                 &mut expectations,
                 expects,
-            )
-            .unwrap();
+                opt_timeout,
+            )?;
+
+            for ((file_index, module_id), results) in finished {
+                run.record_module_results(file_index, module_id, results);
+            }
+        }
+    }
 
-            let tests_duration = test_start_time.elapsed();
+    for ((file_index, module_id), results) in workers.finish()? {
+        run.record_module_results(file_index, module_id, results);
+    }
 
-            results_by_module.push(ModuleTestResults {
-                module_id,
-                failed_count,
-                passed_count,
-                tests_duration,
-            });
+    let TestRun {
+        failed_count: all_files_total_failed_count,
+        passed_count: all_files_total_passed_count,
+        test_cases: mut all_test_cases,
+        ..
+    } = run;
 
-            total_failed_count += failed_count;
-            total_passed_count += passed_count;
+    if list_only {
+        if listed_count == 0 {
+            println!("No expectations were found.");
         }
 
-        let total_duration = start_time.elapsed();
-        all_files_total_failed_count += total_failed_count;
-        all_files_total_passed_count += total_passed_count;
-        if total_failed_count == 0 && total_passed_count == 0 {
-            // Only report no expectations found once.
-            continue;
-        } else if matches.get_flag(FLAG_VERBOSE) {
-            println!("Compiled in {} ms.", compilation_duration.as_millis());
-            for module_test_results in results_by_module {
-                print_test_results(module_test_results, &sources);
-            }
-        } else {
-            let test_summary_str =
-                test_summary(total_failed_count, total_passed_count, total_duration);
-            println!("{test_summary_str}");
+        return Ok(0);
+    }
+
+    // Modules run in no particular order, so sort them for reports that are easy to diff
+    all_test_cases.sort_by(|a, b| {
+        (&a.module_name, a.line, a.column).cmp(&(&b.module_name, b.line, b.column))
+    });
+
+    match format {
+        TestFormat::Text => {}
+        TestFormat::Junit => {
+            print!(
+                "{}",
+                test_output::junit_xml(&all_test_cases, start_time.elapsed())
+            );
+        }
+        TestFormat::Json => {
+            println!(
+                "{}",
+                test_output::json_finished_event(
+                    all_files_total_passed_count,
+                    all_files_total_failed_count,
+                    start_time.elapsed()
+                )
+            );
+        }
+        TestFormat::Tap => {
+            print!("{}", test_output::tap(&all_test_cases));
         }
     }
+
     if all_files_total_failed_count == 0 && all_files_total_passed_count == 0 {
         // TODO print this in a more nicely formatted way!
-        println!("No expectations were found.");
+        let message = if opt_filter.is_some() {
+            "No expectations matched the filter."
+        } else {
+            "No expectations were found."
+        };
+
+        // Keep stdout parseable for the other formats
+        match format {
+            TestFormat::Text => println!("{message}"),
+            TestFormat::Junit | TestFormat::Json | TestFormat::Tap => eprintln!("{message}"),
+        }
 
         // If no tests ran, treat that as an error. This is perhaps
         // briefly annoying at the very beginning of a project when
@@ -728,6 +1090,76 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
     }
 }
 
+/// Drop the top-level `expect`s that don't match the `--filter` from `roc test`
+#[cfg(not(windows))]
+fn retain_matching_expects(loaded: &mut roc_load::MonomorphizedModule, filter: &regex::Regex) {
+    for (module_id, expects) in loaded.toplevel_expects.iter_mut() {
+        let module_name: &str = loaded.interns.module_name(*module_id);
+        let (_, src) = &loaded.sources[module_id];
+
+        expects.pure = std::mem::take(&mut expects.pure)
+            .into_iter()
+            .filter(|(_, region)| {
+                let expect_src = &src[region.start().offset as usize..region.end().offset as usize];
+
+                filter.is_match(module_name)
+                    || filter.is_match(expect_src)
+                    || filter.is_match(&doc_comment_above(src, *region))
+            })
+            .collect();
+    }
+
+    loaded
+        .toplevel_expects
+        .retain(|_, expects| !expects.pure.is_empty());
+}
+
+/// The `##` doc comment lines directly above `region`, without their `##`s
+#[cfg(not(windows))]
+fn doc_comment_above(src: &str, region: roc_region::all::Region) -> String {
+    let before = &src[..region.start().offset as usize];
+    let above = before.rsplit_once('\n').map_or("", |(above, _)| above);
+
+    let mut lines: Vec<&str> = above
+        .lines()
+        .rev()
+        .map(str::trim_start)
+        .take_while(|line| line.starts_with("##"))
+        .map(|line| line.trim_start_matches('#').trim())
+        .collect();
+
+    lines.reverse();
+    lines.join("\n")
+}
+
+/// Print each top-level `expect` as `Module:line  <its first line>`, and return how many there were
+#[cfg(not(windows))]
+fn list_expects(loaded: &roc_load::MonomorphizedModule) -> usize {
+    let mut listed = Vec::new();
+
+    for (module_id, expects) in loaded.toplevel_expects.iter() {
+        let module_name: &str = loaded.interns.module_name(*module_id);
+        let (_, src) = &loaded.sources[module_id];
+        let line_info = roc_region::all::LineInfo::new(src);
+
+        for (_, region) in expects.pure.iter() {
+            let line = line_info.convert_pos(region.start()).line + 1;
+            let expect_src = &src[region.start().offset as usize..region.end().offset as usize];
+            let first_line = expect_src.lines().next().unwrap_or_default();
+
+            listed.push((module_name, line, first_line));
+        }
+    }
+
+    listed.sort();
+
+    for (module_name, line, first_line) in listed.iter() {
+        println!("{module_name}:{line}  {first_line}");
+    }
+
+    listed.len()
+}
+
 fn find_all_roc_files(path: &PathBuf, flatten_paths: &mut Vec<PathBuf>) {
     if path.is_dir() {
         if let Ok(entries) = std::fs::read_dir(path) {
@@ -899,6 +1331,9 @@ pub fn build(
                 Compression::Gzip => {
                     println!("Compressing with gzip at minimum quality…\n\n(Note: Gzip usually runs faster than Brotli but typically produces significantly larger output files. Consider using --{FLAG_BUNDLE} .tar.br if this is a file people will be downloading!)\n");
                 }
+                Compression::Zstd => {
+                    println!("Compressing with zstd at a high quality level…\n\n(Note: zstd output is usually a bit larger than Brotli's, but much faster to decompress. Consider using --{FLAG_BUNDLE} .tar.br if download size matters most!)\n");
+                }
                 Compression::Uncompressed => {
                     println!("Building .tar archive without compression…\n\n(Note: Compression takes more time to run but typically produces much smaller output files. Consider using --{FLAG_BUNDLE} .tar.br if this is a file people will be downloading!)\n");
                 }
@@ -906,7 +1341,7 @@ pub fn build(
 
             // Rather than building an executable or library, we're building
             // a tarball so this code can be distributed via HTTPS
-            let filename = roc_packaging::tarball::build(path, compression)?;
+            let Bundle { filename, manifest } = roc_packaging::tarball::build(path, compression)?;
             let total_time_ms = start_time.elapsed().as_millis();
             let total_time = if total_time_ms > 1000 {
                 format!("{}s {}ms", total_time_ms / 1000, total_time_ms % 1000)
@@ -921,6 +1356,16 @@ pub fn build(
                 created_path.to_string_lossy()
             );
 
+            // The archive includes this list too, and installing the package checks the files
+            // against it, so printing it lets authors review exactly what they're publishing.
+            println!("It contains these files, listed with their BLAKE3 hashes in {MANIFEST_FILENAME}:\n");
+
+            for ManifestEntry { hash, size, path } in manifest.entries.iter() {
+                println!("\t{hash}  {size:>10}  {path}");
+            }
+
+            println!();
+
             return Ok(0);
         }
     }
@@ -1007,7 +1452,10 @@ pub fn build(
         fuzz,
     };
 
-    let load_config = standard_load_config(target, build_ordering, threading);
+    let render = diagnostics_render_target(matches);
+    let warning_levels = warning_levels(matches);
+    let mut load_config = standard_load_config(target, build_ordering, threading);
+    load_config.render = render;
 
     let res_binary_path = roc_build::program::build_file(
         &arena,
@@ -1024,6 +1472,7 @@ pub fn build(
         load_config,
         out_path,
         verbose,
+        &warning_levels,
     );
 
     match res_binary_path {
@@ -1046,8 +1495,11 @@ pub fn build(
                     // since the process is about to exit anyway.
                     // std::mem::forget(arena);
 
-                    problems.print_error_warning_count(total_time);
-                    println!(" while successfully building:\n\n    {generated_filename}");
+                    // Keep stdout to one JSON report per line
+                    if !matches!(render, RenderTarget::Json) {
+                        problems.print_error_warning_count(total_time);
+                        println!(" while successfully building:\n\n    {generated_filename}");
+                    }
 
                     // Return a nonzero exit code if there were problems
                     Ok(problems.exit_code())
@@ -1101,10 +1553,16 @@ pub fn build(
                         // Return a nonzero exit code due to fatal problem
                         return Ok(problems.exit_code());
                     }
-                    debug_assert_eq!(
-                        problems.errors, 0,
-                        "if there are non-fatal errors, they should have been returned as an error variant"
-                    );
+                    // Type errors come back as an error variant, so these are denied warnings
+                    if problems.errors > 0 {
+                        problems.print_error_warning_count(total_time);
+                        println!(
+                            ".\n\nCannot run program due to errors…\n\n\x1B[36m{}\x1B[39m",
+                            "─".repeat(80)
+                        );
+
+                        return Ok(problems.exit_code());
+                    }
 
                     if problems.warnings > 0 {
                         problems.print_error_warning_count(total_time);
@@ -1136,12 +1594,44 @@ pub fn build(
             }
         }
         Err(BuildFileError::ErrorModule { module, total_time }) => {
-            handle_error_module(module, total_time)
+            handle_error_module(module, total_time, render, &warning_levels)
         }
         Err(BuildFileError::LoadingProblem(problem)) => handle_loading_problem(problem),
     }
 }
 
+/// How `roc check --format` and `roc build --format` want errors and warnings reported.
+/// Other commands that build don't take the flag, and always report them as colored text.
+pub fn diagnostics_render_target(matches: &ArgMatches) -> RenderTarget {
+    match matches.try_get_one::<String>(FLAG_FORMAT).ok().flatten() {
+        Some(format) if format == "json" => RenderTarget::Json,
+        _ => RenderTarget::ColorTerminal,
+    }
+}
+
+/// The warning levels from `--allow` and `--deny`. Modules can override them with
+/// `# roc: allow ...` and `# roc: deny ...` comments.
+pub fn warning_levels(matches: &ArgMatches) -> WarningLevels {
+    let mut levels = WarningLevels::default();
+
+    for (flag, level) in [
+        (FLAG_ALLOW, WarningLevel::Allow),
+        (FLAG_DENY, WarningLevel::Deny),
+    ] {
+        let kinds = matches.try_get_many::<String>(flag).ok().flatten();
+
+        for kind in kinds.unwrap_or_default() {
+            if let Err(kind) = levels.set(kind, level) {
+                user_error!(
+                    "`--{flag} {kind}` isn't a kind of warning I know. Use a code like E2076 or a name like unused-import."
+                );
+            }
+        }
+    }
+
+    levels
+}
+
 fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
     arena: &Bump,
     script_path: &Path,
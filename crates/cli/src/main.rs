@@ -3,20 +3,24 @@ use bumpalo::Bump;
 use roc_build::link::LinkType;
 use roc_build::program::{check_file, CodeGenBackend};
 use roc_cli::{
-    annotate_file, build_app, default_linking_strategy, format_files, format_src, test,
-    AnnotationProblem, BuildConfig, FormatMode, CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_DOCS,
-    CMD_FORMAT, CMD_FORMAT_ANNOTATE, CMD_GLUE, CMD_LICENSES, CMD_PREPROCESS_HOST, CMD_REPL,
-    CMD_RUN, CMD_TEST, CMD_VERSION, DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_DEV, FLAG_DOCS_ROOT,
-    FLAG_LIB, FLAG_MAIN, FLAG_MIGRATE, FLAG_NO_COLOR, FLAG_NO_HEADER, FLAG_NO_LINK, FLAG_OUTPUT,
-    FLAG_PP_DYLIB, FLAG_PP_HOST, FLAG_PP_PLATFORM, FLAG_STDIN, FLAG_STDOUT, FLAG_TARGET, FLAG_TIME,
-    FLAG_VERBOSE, GLUE_DIR, GLUE_SPEC, ROC_FILE, VERSION,
+    annotate_file, apply_fixes, build_app, cache_list, cache_prune, cache_verify,
+    default_linking_strategy, deps, diagnostics_render_target, format_files, format_src, test,
+    vendor, warning_levels, AnnotationProblem, BuildConfig, FormatMode, PackageDirs, CMD_BUILD,
+    CMD_CACHE, CMD_CACHE_LIST, CMD_CACHE_PRUNE, CMD_CACHE_VERIFY, CMD_CHECK, CMD_DEPS, CMD_DEV,
+    CMD_DOCS, CMD_EXPLAIN, CMD_FORMAT, CMD_FORMAT_ANNOTATE, CMD_GLUE, CMD_LICENSES,
+    CMD_PREPROCESS_HOST, CMD_REPL, CMD_RUN, CMD_TEST, CMD_VENDOR, CMD_VERSION, DIRECTORY_OR_FILES,
+    ERROR_CODE, FLAG_CHECK, FLAG_DEV, FLAG_DOCS_ROOT, FLAG_FIX, FLAG_FORMAT, FLAG_LIB, FLAG_MAIN,
+    FLAG_MIGRATE, FLAG_NO_COLOR, FLAG_NO_HEADER, FLAG_NO_LINK, FLAG_OLDER_THAN, FLAG_OUTPUT,
+    FLAG_PLATFORM, FLAG_PP_DYLIB, FLAG_PP_HOST, FLAG_PP_PLATFORM, FLAG_STDIN, FLAG_STDOUT,
+    FLAG_TARGET, FLAG_TIME, FLAG_VERBOSE, GLUE_DIR, GLUE_SPEC, ROC_FILE, ROC_FILES, VERSION,
 };
 use roc_docs::generate_docs_html;
 use roc_error_macros::{internal_error, user_error};
 use roc_gen_dev::AssemblyBackendMode;
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
 use roc_load::{LoadingProblem, Threading};
-use roc_packaging::cache::{self, RocCacheDir};
+use roc_reporting::error::codes;
+use roc_reporting::report::RenderTarget;
 use roc_target::Target;
 use std::fs::{self, FileType};
 use std::io::BufRead;
@@ -54,7 +58,7 @@ fn main() -> io::Result<()> {
                     BuildConfig::BuildAndRunIfNoErrors,
                     Triple::host().into(),
                     None,
-                    RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
+                    PackageDirs::new(matches.get_one::<PathBuf>(ROC_FILE).unwrap()).roc_cache_dir(),
                     LinkType::Executable,
                     false,
                 )
@@ -70,7 +74,7 @@ fn main() -> io::Result<()> {
                     BuildConfig::BuildAndRun,
                     Triple::host().into(),
                     None,
-                    RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
+                    PackageDirs::new(matches.get_one::<PathBuf>(ROC_FILE).unwrap()).roc_cache_dir(),
                     LinkType::Executable,
                     false,
                 )
@@ -97,7 +101,7 @@ fn main() -> io::Result<()> {
                     BuildConfig::BuildAndRunIfNoErrors,
                     Triple::host().into(),
                     None,
-                    RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
+                    PackageDirs::new(matches.get_one::<PathBuf>(ROC_FILE).unwrap()).roc_cache_dir(),
                     LinkType::Executable,
                     false,
                 )
@@ -208,7 +212,7 @@ fn main() -> io::Result<()> {
                 BuildConfig::BuildOnly,
                 target,
                 out_path,
-                RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
+                PackageDirs::new(matches.get_one::<PathBuf>(ROC_FILE).unwrap()).roc_cache_dir(),
                 link_type,
                 verbose,
             )?)
@@ -226,6 +230,11 @@ fn main() -> io::Result<()> {
             };
 
             let opt_main_path = matches.get_one::<PathBuf>(FLAG_MAIN);
+            let package_dirs = PackageDirs::new(opt_main_path.unwrap_or(roc_file_path));
+            let render = diagnostics_render_target(matches);
+            let warning_levels = warning_levels(matches);
+            // Keep stdout to one JSON report per line
+            let print_summary = !matches!(render, RenderTarget::Json);
 
             match roc_file_path.extension().and_then(OsStr::to_str) {
                 Some("md") => {
@@ -262,12 +271,17 @@ fn main() -> io::Result<()> {
                             file.path().to_owned(),
                             opt_main_path.cloned(),
                             emit_timings,
-                            RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
+                            package_dirs.roc_cache_dir(),
                             threading,
+                            render,
+                            &warning_levels,
                         ) {
-                            Ok((problems, total_time)) => {
-                                problems.print_error_warning_count(total_time);
-                                println!(".\n");
+                            // The blocks only exist in temp files, so there's nowhere to apply fixes
+                            Ok((problems, _fixes, total_time)) => {
+                                if print_summary {
+                                    problems.print_error_warning_count(total_time);
+                                    println!(".\n");
+                                }
 
                                 exit_code = problems.exit_code();
                             }
@@ -295,12 +309,35 @@ fn main() -> io::Result<()> {
                         roc_file_path.to_owned(),
                         opt_main_path.cloned(),
                         emit_timings,
-                        RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
+                        package_dirs.roc_cache_dir(),
                         threading,
+                        render,
+                        &warning_levels,
                     ) {
-                        Ok((problems, total_time)) => {
-                            problems.print_error_warning_count(total_time);
-                            println!(".\n");
+                        Ok((problems, fixes, total_time)) => {
+                            if matches.get_flag(FLAG_FIX) {
+                                let root_dir = match roc_file_path.parent() {
+                                    Some(dir) if !dir.as_os_str().is_empty() => dir,
+                                    _ => Path::new("."),
+                                };
+                                let applied = apply_fixes(root_dir, fixes)?;
+
+                                if print_summary {
+                                    println!(
+                                        "Applied {} {} to {} {}.",
+                                        applied.fixes,
+                                        if applied.fixes == 1 { "fix" } else { "fixes" },
+                                        applied.files,
+                                        if applied.files == 1 { "file" } else { "files" },
+                                    );
+                                }
+                            }
+
+                            if print_summary {
+                                problems.print_error_warning_count(total_time);
+                                println!(".\n");
+                            }
+
                             Ok(problems.exit_code())
                         }
 
@@ -319,8 +356,13 @@ fn main() -> io::Result<()> {
         Some((CMD_REPL, matches)) => {
             let has_color = !matches.get_one::<bool>(FLAG_NO_COLOR).unwrap();
             let has_header = !matches.get_one::<bool>(FLAG_NO_HEADER).unwrap();
+            let opt_platform = matches.get_one::<String>(FLAG_PLATFORM);
 
-            Ok(roc_repl_cli::main(has_color, has_header))
+            Ok(roc_repl_cli::main(
+                has_color,
+                has_header,
+                opt_platform.map(String::as_str),
+            ))
         }
         Some((CMD_DOCS, matches)) => {
             let root_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
@@ -499,6 +541,65 @@ fn main() -> io::Result<()> {
             println!("{LEGAL_DETAILS_TEXT}");
             Ok(0)
         }
+        Some((CMD_VENDOR, matches)) => {
+            let roc_file_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+
+            vendor(roc_file_path)
+        }
+        Some((CMD_DEPS, matches)) => {
+            let roc_file_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+            let as_json = matches.get_one::<String>(FLAG_FORMAT).unwrap() == "json";
+            let opt_output = matches.get_one::<PathBuf>(FLAG_OUTPUT);
+            let has_color = !matches.get_one::<bool>(FLAG_NO_COLOR).unwrap();
+
+            deps(
+                roc_file_path,
+                as_json,
+                opt_output.map(PathBuf::as_path),
+                has_color,
+            )
+        }
+        Some((CMD_CACHE, matches)) => match matches.subcommand() {
+            Some((CMD_CACHE_LIST, _)) => cache_list(),
+            Some((CMD_CACHE_VERIFY, _)) => cache_verify(),
+            Some((CMD_CACHE_PRUNE, matches)) => {
+                let projects: Vec<PathBuf> = matches
+                    .get_many::<PathBuf>(ROC_FILES)
+                    .unwrap_or_default()
+                    .cloned()
+                    .collect();
+                let older_than_days = matches.get_one::<u64>(FLAG_OLDER_THAN).copied();
+
+                cache_prune(&projects, older_than_days)
+            }
+            _ => unreachable!(),
+        },
+        Some((CMD_EXPLAIN, matches)) => {
+            let code = matches.get_one::<String>(ERROR_CODE).unwrap();
+
+            match codes::lookup(code) {
+                Some(error_code) => {
+                    println!("{} [{}]\n", error_code.title, error_code.code);
+
+                    match error_code.explanation() {
+                        Some(explanation) => print!("{explanation}"),
+                        None => println!(
+                            "No explanation is available for {} ({}) yet. The report itself should say what went wrong.",
+                            error_code.code, error_code.kind
+                        ),
+                    }
+
+                    Ok(0)
+                }
+                None => {
+                    eprintln!(
+                        "I don't know the code {code}. Codes look like E3016, and appear next to the title of each problem the compiler reports."
+                    );
+
+                    Ok(1)
+                }
+            }
+        }
         _ => unreachable!(),
     }?;
 
@@ -0,0 +1,224 @@
+use std::collections::BTreeSet;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use bumpalo::Bump;
+use roc_build::program::handle_loading_problem;
+use roc_load::deps::{root_module_path, walk_packages};
+use roc_load::LoadingProblem;
+use roc_packaging::cache;
+use roc_packaging::https::{PackageMetadata, Problem};
+use roc_reporting::report::ANSI_STYLE_CODES;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Prints every package in the cache, with how much space it takes up and when a build last used it.
+pub fn cache_list() -> io::Result<i32> {
+    let cache_dir = cache::roc_cache_packages_dir();
+    let entries = cache::cache_entries(&cache_dir)?;
+
+    if entries.is_empty() {
+        println!("There are no packages in {}", cache_dir.display());
+
+        return Ok(0);
+    }
+
+    let now = SystemTime::now();
+    let mut total_size = 0;
+
+    println!("{:>10}  {:<14}  PACKAGE", "SIZE", "LAST USED");
+
+    for entry in entries.iter() {
+        total_size += entry.size;
+
+        println!(
+            "{:>10}  {:<14}  {}",
+            format_size(entry.size),
+            format_last_used(entry.last_used, now),
+            entry.name
+        );
+    }
+
+    println!(
+        "\n{} package(s), {} in total, in {}",
+        entries.len(),
+        format_size(total_size),
+        cache_dir.display()
+    );
+
+    Ok(0)
+}
+
+/// Re-hashes the extracted files of every package in the cache, to find any that were changed
+/// after extraction or never finished extracting. Returns 1 if any were changed.
+pub fn cache_verify() -> io::Result<i32> {
+    let cache_dir = cache::roc_cache_packages_dir();
+    let entries = cache::cache_entries(&cache_dir)?;
+
+    let mut changed = Vec::new();
+    let mut unverified = 0;
+
+    for entry in entries {
+        let (status, color) = match entry.verify() {
+            Ok(()) => ("ok", ANSI_STYLE_CODES.green),
+            Err(Problem::ContentsChanged(_)) => {
+                changed.push(entry.name.clone());
+
+                ("CHANGED", ANSI_STYLE_CODES.red)
+            }
+            Err(Problem::ContentsHashMissing(_)) => {
+                unverified += 1;
+
+                ("UNVERIFIED", ANSI_STYLE_CODES.yellow)
+            }
+            Err(Problem::IoErr(err)) => return Err(err),
+            Err(other) => {
+                return Err(io::Error::new(io::ErrorKind::Other, format!("{other:?}")));
+            }
+        };
+
+        println!("{}  {}", paint(&format!("{status:<10}"), color), entry.name);
+    }
+
+    if unverified > 0 {
+        println!(
+            "\n{unverified} package(s) have no record of their extracted files, so there's nothing to check them against. Either an older Roc extracted them, or extraction didn't finish."
+        );
+    }
+
+    if changed.is_empty() {
+        Ok(0)
+    } else {
+        println!(
+            "\nThe files of {} package(s) no longer match what was downloaded. Delete them from {} and the next build will download them again.",
+            changed.len(),
+            cache_dir.display()
+        );
+
+        Ok(1)
+    }
+}
+
+/// Deletes packages from the cache that none of the given projects use, or that no build has used
+/// in the given number of days. With both, a package is deleted if either applies.
+pub fn cache_prune(projects: &[PathBuf], older_than_days: Option<u64>) -> io::Result<i32> {
+    let cache_dir = cache::roc_cache_packages_dir();
+
+    let used = if projects.is_empty() {
+        None
+    } else {
+        let arena = Bump::new();
+        let mut used = BTreeSet::new();
+
+        for project in projects {
+            // If we can't tell what a project uses, pruning might delete something it needs
+            if let Err(problem) = add_used_packages(&arena, &cache_dir, project, &mut used) {
+                return handle_loading_problem(problem);
+            }
+        }
+
+        Some(used)
+    };
+
+    let cutoff = older_than_days.and_then(|days| {
+        SystemTime::now().checked_sub(Duration::from_secs(days.saturating_mul(SECONDS_PER_DAY)))
+    });
+
+    let mut pruned_count = 0;
+    let mut pruned_size = 0;
+
+    for entry in cache::cache_entries(&cache_dir)? {
+        let unused = used
+            .as_ref()
+            .is_some_and(|used| !used.contains(&entry.path));
+        let stale = match (cutoff, entry.last_used) {
+            (Some(cutoff), Some(last_used)) => last_used < cutoff,
+            _ => false,
+        };
+
+        if unused || stale {
+            println!("Removed {}", entry.name);
+
+            pruned_count += 1;
+            pruned_size += entry.size;
+
+            entry.remove()?;
+        }
+    }
+
+    println!(
+        "\nRemoved {} package(s), freeing {}",
+        pruned_count,
+        format_size(pruned_size)
+    );
+
+    Ok(0)
+}
+
+/// Adds the cache dirs of all the URL packages the project uses, transitively, as far as they've
+/// been downloaded. This never downloads anything.
+fn add_used_packages<'a>(
+    arena: &'a Bump,
+    cache_dir: &Path,
+    main_module: &Path,
+    used: &mut BTreeSet<PathBuf>,
+) -> Result<(), LoadingProblem<'a>> {
+    walk_packages(arena, main_module, |package, _| {
+        let Ok(metadata) = PackageMetadata::try_from(package.url) else {
+            return Ok(None);
+        };
+
+        let package_dir =
+            cache::package_dir(cache_dir, metadata.cache_subdir, &metadata.content_hash);
+
+        if !package_dir.exists() {
+            return Ok(None);
+        }
+
+        let root_module = root_module_path(&package_dir, metadata.root_module_filename);
+
+        used.insert(package_dir);
+
+        Ok(Some(root_module))
+    })
+}
+
+/// The text in the given color if stdout is a terminal, or as it is otherwise.
+pub(crate) fn paint(text: &str, color: &str) -> String {
+    if io::stdout().is_terminal() {
+        format!("{color}{text}{}", ANSI_STYLE_CODES.reset)
+    } else {
+        text.to_string()
+    }
+}
+
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+fn format_last_used(last_used: Option<SystemTime>, now: SystemTime) -> String {
+    let Some(last_used) = last_used else {
+        return "unknown".to_string();
+    };
+
+    match now.duration_since(last_used).unwrap_or_default().as_secs() / SECONDS_PER_DAY {
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        days => format!("{days} days ago"),
+    }
+}
@@ -0,0 +1,282 @@
+//! The machine-readable formats `roc test --format` can report results in, for CI systems and
+//! other tools to consume.
+
+use roc_collections::MutMap;
+use roc_module::symbol::{Interns, ModuleId};
+use roc_region::all::LineInfo;
+use roc_repl_expect::run::{ExpectFailure, ExpectResult};
+use serde_json::json;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestFormat {
+    /// Colored reports and a summary, for people
+    Text,
+    Junit,
+    /// One JSON event per line, in the shape of libtest's `--format json`
+    Json,
+    Tap,
+}
+
+impl TestFormat {
+    pub fn from_flag(flag: &str) -> Self {
+        match flag {
+            "junit" => TestFormat::Junit,
+            "json" => TestFormat::Json,
+            "tap" => TestFormat::Tap,
+            _ => TestFormat::Text,
+        }
+    }
+}
+
+/// A top-level `expect`'s result, along with where to find it
+pub struct TestCase {
+    pub module_name: String,
+    pub path: PathBuf,
+    /// 1-based, like an editor shows it
+    pub line: u32,
+    /// 1-based, like an editor shows it
+    pub column: u32,
+    pub duration: Duration,
+    pub opt_failure: Option<ExpectFailure>,
+}
+
+impl TestCase {
+    pub fn from_results(
+        results: Vec<ExpectResult>,
+        interns: &Interns,
+        sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    ) -> Vec<Self> {
+        let mut line_infos: MutMap<ModuleId, LineInfo> = MutMap::default();
+
+        results
+            .into_iter()
+            .map(|result| {
+                let module_id = result.symbol.module_id();
+                let (path, src) = &sources[&module_id];
+                let line_info = line_infos
+                    .entry(module_id)
+                    .or_insert_with(|| LineInfo::new(src));
+                let position = line_info.convert_pos(result.region.start());
+
+                TestCase {
+                    module_name: interns.module_name(module_id).to_string(),
+                    path: path.clone(),
+                    line: position.line + 1,
+                    column: position.column + 1,
+                    duration: result.duration,
+                    opt_failure: result.opt_failure,
+                }
+            })
+            .collect()
+    }
+
+    /// e.g. `Dict:42`, the same way `roc test --list` shows it
+    pub fn name(&self) -> String {
+        format!("{}:{}", self.module_name, self.line)
+    }
+}
+
+pub fn json_started_event() -> String {
+    json!({ "type": "suite", "event": "started" }).to_string()
+}
+
+pub fn json_test_event(case: &TestCase) -> String {
+    let mut event = json!({
+        "type": "test",
+        "event": if case.opt_failure.is_some() { "failed" } else { "ok" },
+        "name": case.name(),
+        "module": case.module_name,
+        "path": case.path.display().to_string(),
+        "line": case.line,
+        "column": case.column,
+        "exec_time": case.duration.as_secs_f64(),
+    });
+
+    if let Some(failure) = &case.opt_failure {
+        event["report"] = json!(failure.report.trim_end());
+        event["values"] = failure
+            .values
+            .iter()
+            .map(|(name, value)| json!({ "name": name, "value": value }))
+            .collect();
+    }
+
+    event.to_string()
+}
+
+pub fn json_finished_event(passed: usize, failed: usize, duration: Duration) -> String {
+    json!({
+        "type": "suite",
+        "event": if failed == 0 { "ok" } else { "failed" },
+        "passed": passed,
+        "failed": failed,
+        "exec_time": duration.as_secs_f64(),
+    })
+    .to_string()
+}
+
+/// A `<testsuites>` document with one `<testsuite>` per module, in the order they ran
+pub fn junit_xml(cases: &[TestCase], duration: Duration) -> String {
+    let mut suites: Vec<(&str, Vec<&TestCase>)> = Vec::new();
+
+    for case in cases {
+        match suites
+            .iter_mut()
+            .find(|(name, _)| *name == case.module_name)
+        {
+            Some((_, suite_cases)) => suite_cases.push(case),
+            None => suites.push((&case.module_name, vec![case])),
+        }
+    }
+
+    let failures = cases.iter().filter(|case| case.opt_failure.is_some());
+    let mut buf = String::new();
+
+    buf.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        buf,
+        "<testsuites name=\"roc test\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+        cases.len(),
+        failures.count(),
+        duration.as_secs_f64()
+    );
+
+    for (name, suite_cases) in suites {
+        let suite_failures = suite_cases
+            .iter()
+            .filter(|case| case.opt_failure.is_some())
+            .count();
+        let suite_duration: Duration = suite_cases.iter().map(|case| case.duration).sum();
+
+        let _ = writeln!(
+            buf,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{suite_failures}\" time=\"{:.3}\" file=\"{}\">",
+            xml_escape(name),
+            suite_cases.len(),
+            suite_duration.as_secs_f64(),
+            xml_escape(&suite_cases[0].path.display().to_string()),
+        );
+
+        for case in suite_cases {
+            let _ = write!(
+                buf,
+                "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\" line=\"{}\" time=\"{:.3}\"",
+                xml_escape(&case.name()),
+                xml_escape(name),
+                xml_escape(&case.path.display().to_string()),
+                case.line,
+                case.duration.as_secs_f64(),
+            );
+
+            match &case.opt_failure {
+                None => buf.push_str("/>\n"),
+                Some(failure) => {
+                    buf.push_str(">\n");
+
+                    if !failure.values.is_empty() {
+                        buf.push_str("      <properties>\n");
+
+                        for (name, value) in failure.values.iter() {
+                            let _ = writeln!(
+                                buf,
+                                "        <property name=\"{}\" value=\"{}\"/>",
+                                xml_escape(name),
+                                xml_escape(value)
+                            );
+                        }
+
+                        buf.push_str("      </properties>\n");
+                    }
+
+                    let _ = writeln!(
+                        buf,
+                        "      <failure message=\"expect failed\">{}</failure>",
+                        xml_escape(failure.report.trim_end())
+                    );
+                    buf.push_str("    </testcase>\n");
+                }
+            }
+        }
+
+        buf.push_str("  </testsuite>\n");
+    }
+
+    buf.push_str("</testsuites>\n");
+
+    buf
+}
+
+/// A TAP version 13 stream, with a YAML block describing each failure
+pub fn tap(cases: &[TestCase]) -> String {
+    let mut buf = String::new();
+
+    buf.push_str("TAP version 13\n");
+    let _ = writeln!(buf, "1..{}", cases.len());
+
+    for (index, case) in cases.iter().enumerate() {
+        let number = index + 1;
+
+        match &case.opt_failure {
+            None => {
+                let _ = writeln!(buf, "ok {number} - {}", case.name());
+            }
+            Some(failure) => {
+                let _ = writeln!(buf, "not ok {number} - {}", case.name());
+                buf.push_str("  ---\n");
+                let _ = writeln!(
+                    buf,
+                    "  at: {}",
+                    json!(format!(
+                        "{}:{}:{}",
+                        case.path.display(),
+                        case.line,
+                        case.column
+                    ))
+                );
+                let _ = writeln!(buf, "  duration_ms: {}", case.duration.as_millis());
+
+                if !failure.values.is_empty() {
+                    buf.push_str("  values:\n");
+
+                    for (name, value) in failure.values.iter() {
+                        // JSON strings are valid YAML, and escape anything YAML would trip on
+                        let _ = writeln!(buf, "    {}: {}", json!(name), json!(value));
+                    }
+                }
+
+                buf.push_str("  message: |\n");
+
+                for line in failure.report.trim_end().lines() {
+                    let _ = writeln!(buf, "    {line}");
+                }
+
+                buf.push_str("  ...\n");
+            }
+        }
+    }
+
+    buf
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 doesn't allow any other control characters, even escaped
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            ch if ch.is_control() => {}
+            ch => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,79 @@
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bumpalo::Bump;
+use roc_build::program::handle_loading_problem;
+use roc_load::deps::{root_module_path, walk_packages};
+use roc_load::LoadingProblem;
+use roc_packaging::cache::{self, RocCacheDir};
+use roc_reporting::report::{to_https_problem_report_string, ANSI_STYLE_CODES};
+
+use crate::package_cache::paint;
+
+/// The package cache, along with the vendor dir next to a main module if `roc vendor` made one
+pub struct PackageDirs {
+    cache_dir: PathBuf,
+    vendor_dir: Option<PathBuf>,
+}
+
+impl PackageDirs {
+    pub fn new(main_module: &Path) -> Self {
+        Self {
+            cache_dir: cache::roc_cache_packages_dir(),
+            vendor_dir: cache::find_vendor_dir(main_module),
+        }
+    }
+
+    pub fn roc_cache_dir(&self) -> RocCacheDir<'_> {
+        RocCacheDir::with_vendor_dir(&self.cache_dir, self.vendor_dir.as_deref())
+    }
+}
+
+/// Copies the tarball of every URL package the given module depends on into the vendor dir next to
+/// it, including the ones those packages depend on, and the ones depended on by packages given as
+/// local paths.
+pub fn vendor(main_module: &Path) -> io::Result<i32> {
+    let arena = Bump::new();
+    let cache_dir = cache::roc_cache_packages_dir();
+    let vendor_dir = match main_module.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.join(cache::VENDOR_DIR_NAME),
+        _ => PathBuf::from(cache::VENDOR_DIR_NAME),
+    };
+
+    let mut vendored = BTreeSet::new();
+
+    let walked = walk_packages(&arena, main_module, |package, importer| {
+        if !vendored.insert(package.url) {
+            return Ok(None);
+        }
+
+        match cache::vendor_package(&cache_dir, &vendor_dir, package.url) {
+            Ok((package_dir, opt_root_module)) => {
+                println!("Vendored {}", paint(package.url, ANSI_STYLE_CODES.cyan));
+
+                Ok(Some(root_module_path(&package_dir, opt_root_module)))
+            }
+            Err(problem) => Err(LoadingProblem::FormattedReport(
+                to_https_problem_report_string(package.url, problem, importer.to_path_buf()),
+                None,
+            )),
+        }
+    });
+
+    if let Err(problem) = walked {
+        return handle_loading_problem(problem);
+    }
+
+    if vendored.is_empty() {
+        println!("There are no URL packages to vendor.");
+    } else {
+        println!(
+            "\nVendored {} package(s) into {}",
+            vendored.len(),
+            vendor_dir.display()
+        );
+    }
+
+    Ok(0)
+}
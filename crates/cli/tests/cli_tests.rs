@@ -11,7 +11,7 @@ mod cli_tests {
     use cli_test_utils::exec_cli::ExecCli;
     use cli_test_utils::helpers::{dir_from_root, file_from_root};
     use const_format::concatcp;
-    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_FORMAT, CMD_TEST};
+    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_DEPS, CMD_EXPLAIN, CMD_FORMAT, CMD_TEST, CMD_VENDOR};
 
     #[cfg(all(unix, not(target_os = "macos")))]
     const ALLOW_VALGRIND: bool = true;
@@ -252,6 +252,219 @@ mod cli_tests {
         insta::assert_snapshot!(cli_test_out.normalize_stdout_and_stderr());
     }
 
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn transitive_expects_single_worker() {
+        let cli_test = ExecCli::new(
+            CMD_TEST,
+            file_from_root(
+                "crates/cli/tests/test-projects/expects_transitive",
+                "main.roc",
+            ),
+        )
+        .add_args(["--max-threads", "1"]);
+
+        let cli_test_out = cli_test.run();
+        cli_test_out.assert_clean_success();
+        cli_test_out.assert_stdout_and_stderr_ends_with(
+            "0 failed and 3 passed in <ignored for test> ms.\n",
+        );
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn many_modules_expects_in_stable_order() {
+        let cli_test = ExecCli::new(
+            CMD_TEST,
+            file_from_root(
+                "crates/cli/tests/test-projects/expects_many_modules",
+                "main.roc",
+            ),
+        )
+        .add_args(["--max-threads", "3", "--verbose"]);
+
+        let first_out = cli_test.run();
+        first_out.assert_nonzero_exit();
+
+        let first = first_out.normalize_stdout_and_stderr();
+
+        for module_name in ["Alpha", "Beta", "Gamma"] {
+            assert!(first.contains(module_name), "{first}");
+        }
+
+        // Alpha's expect takes the longest to run, but the results still come out in the same order
+        for _ in 0..3 {
+            let out = cli_test.run();
+            out.assert_nonzero_exit();
+
+            assert_eq!(out.normalize_stdout_and_stderr(), first);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn transitive_expects_filter() {
+        let cli_test = ExecCli::new(
+            CMD_TEST,
+            file_from_root(
+                "crates/cli/tests/test-projects/expects_transitive",
+                "main.roc",
+            ),
+        )
+        .add_args(["--filter", "Transitive"]);
+
+        let cli_test_out = cli_test.run();
+        cli_test_out.assert_clean_success();
+        cli_test_out.assert_stdout_and_stderr_ends_with(
+            "0 failed and 1 passed in <ignored for test> ms.\n",
+        );
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn transitive_expects_list() {
+        let cli_test = ExecCli::new(
+            CMD_TEST,
+            file_from_root(
+                "crates/cli/tests/test-projects/expects_transitive",
+                "main.roc",
+            ),
+        )
+        .arg("--list");
+
+        let expected_out = indoc::indoc!(
+            r#"
+            Direct:10  expect add_and_stringify(1, 2) == "3"
+            Direct:12  expect add_and_stringify(3, 4) == "7"
+            Transitive:7  expect add(1, 2) == 3
+            "#
+        );
+
+        cli_test.run().assert_clean_stdout(expected_out);
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn transitive_expects_tap() {
+        let cli_test = ExecCli::new(
+            CMD_TEST,
+            file_from_root(
+                "crates/cli/tests/test-projects/expects_transitive",
+                "main.roc",
+            ),
+        )
+        .add_args(["--format", "tap"]);
+
+        let expected_out = indoc::indoc!(
+            r#"
+            TAP version 13
+            1..3
+            ok 1 - Direct:10
+            ok 2 - Direct:12
+            ok 3 - Transitive:7
+            "#
+        );
+
+        cli_test.run().assert_clean_stdout(expected_out);
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn transitive_expects_junit() {
+        let cli_test = ExecCli::new(
+            CMD_TEST,
+            file_from_root(
+                "crates/cli/tests/test-projects/expects_transitive",
+                "main.roc",
+            ),
+        )
+        .add_args(["--format", "junit"]);
+
+        let cli_test_out = cli_test.run();
+        cli_test_out.assert_clean_success();
+
+        let stdout = &cli_test_out.stdout;
+        assert!(stdout.starts_with("<?xml"), "{stdout}");
+        assert!(
+            stdout.contains(r#"<testsuite name="Direct" tests="2" failures="0""#),
+            "{stdout}"
+        );
+        assert!(
+            stdout.contains(r#"<testsuite name="Transitive" tests="1" failures="0""#),
+            "{stdout}"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn expects_junit_with_warning() {
+        let cli_test = ExecCli::new(
+            CMD_TEST,
+            file_from_root("crates/cli/tests/test-projects", "expects_with_warning.roc"),
+        )
+        .add_args(["--format", "junit"]);
+
+        let cli_test_out = cli_test.run();
+        cli_test_out.assert_nonzero_exit();
+
+        // stdout has to be nothing but the XML document, so every line is one of its tags
+        let stdout = &cli_test_out.stdout;
+        assert!(stdout.starts_with("<?xml"), "{stdout}");
+        assert!(stdout.ends_with("</testsuites>\n"), "{stdout}");
+        assert!(!stdout.contains('\u{1b}'), "{stdout}");
+
+        let mut open_tags = Vec::new();
+        let mut in_failure = false;
+
+        for line in stdout.lines().skip(1).map(str::trim) {
+            if in_failure {
+                in_failure = !line.ends_with("</failure>");
+                continue;
+            }
+
+            assert!(line.starts_with('<') && line.ends_with('>'), "{line}");
+
+            if let Some(tag) = line.strip_prefix("</") {
+                assert_eq!(open_tags.pop(), Some(tag.trim_end_matches('>')), "{stdout}");
+            } else if line.starts_with("<failure") {
+                in_failure = !line.ends_with("</failure>");
+            } else if !line.ends_with("/>") {
+                let name = line[1..].split([' ', '>']).next().unwrap();
+                open_tags.push(name);
+            }
+        }
+
+        assert!(open_tags.is_empty(), "{stdout}");
+        assert!(
+            stdout.contains(r#"<testsuites name="roc test" tests="2" failures="1""#),
+            "{stdout}"
+        );
+
+        // the warning still gets reported, just not where it would break the document
+        let stderr = &cli_test_out.stderr;
+        assert!(stderr.contains("UNUSED DEFINITION"), "{stderr}");
+        assert!(!stderr.contains('\u{1b}'), "{stderr}");
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn expects_timeout() {
+        let cli_test = ExecCli::new(
+            CMD_TEST,
+            file_from_root("crates/cli/tests/test-projects", "expects_timeout.roc"),
+        )
+        .add_args(["--timeout", "1"]);
+
+        let cli_test_out = cli_test.run();
+        cli_test_out.assert_nonzero_exit();
+
+        let output = cli_test_out.normalize_stdout_and_stderr();
+        assert!(output.contains("EXPECT TIMED OUT"), "{output}");
+        cli_test_out.assert_stdout_and_stderr_ends_with(
+            "1 failed and 1 passed in <ignored for test> ms.\n",
+        );
+    }
+
     #[test]
     #[cfg_attr(windows, ignore)]
     fn multiple_exposed() {
@@ -886,6 +1099,35 @@ mod cli_tests {
             );
         }
 
+        #[test]
+        #[cfg_attr(windows, ignore)]
+        fn repl_with_platform_runs_effects() {
+            use cli_test_utils::command::run_command;
+            use cli_test_utils::helpers::path_to_roc_binary;
+            use std::process::Command;
+
+            build_platform_host();
+
+            let mut cmd = Command::new(path_to_roc_binary());
+            cmd.arg(roc_cli::CMD_REPL)
+                .arg("--no-header")
+                .arg("--no-color")
+                .arg("--platform")
+                .arg(file_from_root(
+                    "crates/cli/tests/test-projects/test-platform-effects-zig/",
+                    "main.roc",
+                ));
+
+            let stdin =
+                "import pf.Effect\nEffect.put_line!(\"Hello from the repl\")\n:type main!\n";
+            let cmd_out = run_command(cmd, Some(stdin));
+
+            assert!(cmd_out.status.success(), "{cmd_out}");
+            assert!(cmd_out.stdout.contains("Hello from the repl"), "{cmd_out}");
+            // the platform's required def is in scope, with the type the platform asks for
+            assert!(cmd_out.stdout.contains("main! : {} => {}"), "{cmd_out}");
+        }
+
         #[test]
         #[cfg_attr(windows, ignore)]
         fn inspect_logging() {
@@ -1483,6 +1725,509 @@ mod cli_tests {
         insta::assert_snapshot!(cli_check_out.normalize_stdout_and_stderr());
     }
 
+    #[test]
+    fn unused_import_json() {
+        let cli_check = ExecCli::new(
+            CMD_CHECK,
+            file_from_root(
+                "crates/cli/tests/test-projects/known_bad",
+                "UnusedImport.roc",
+            ),
+        )
+        .add_args(["--format", "json"]);
+
+        let cli_check_out = cli_check.run();
+        cli_check_out.assert_nonzero_exit();
+
+        let reports: Vec<serde_json::Value> = cli_check_out
+            .stdout
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(reports.len(), 1);
+
+        let report = &reports[0];
+
+        assert_eq!(report["title"], "UNUSED IMPORT");
+        assert_eq!(report["code"], "E2076");
+        assert_eq!(report["severity"], "warning");
+        assert!(report["file"]
+            .as_str()
+            .unwrap()
+            .ends_with("UnusedImport.roc"));
+        assert_eq!(
+            report["region"],
+            serde_json::json!({
+                "start": { "line": 3, "column": 1 },
+                "end": { "line": 3, "column": 31 },
+            })
+        );
+        assert!(report["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Symbol is imported but not used."));
+    }
+
+    #[test]
+    fn unused_import_fix() {
+        let dir = tempfile::tempdir().unwrap();
+
+        for file_name in ["UnusedImport.roc", "Symbol.roc"] {
+            std::fs::copy(
+                file_from_root("crates/cli/tests/test-projects/known_bad", file_name),
+                dir.path().join(file_name),
+            )
+            .unwrap();
+        }
+
+        let roc_file = dir.path().join("UnusedImport.roc");
+
+        let cli_check_out = ExecCli::new(CMD_CHECK, roc_file.clone()).arg("--fix").run();
+        cli_check_out.assert_nonzero_exit();
+        assert!(cli_check_out.stdout.contains("Applied 1 fix to 1 file."));
+
+        let fixed = std::fs::read_to_string(&roc_file).unwrap();
+        assert!(!fixed.contains("import Symbol"));
+
+        // Now there's nothing left to warn about
+        ExecCli::new(CMD_CHECK, roc_file)
+            .run()
+            .assert_clean_success();
+    }
+
+    #[test]
+    fn deny_unused_import() {
+        let cli_check_out = ExecCli::new(
+            CMD_CHECK,
+            file_from_root(
+                "crates/cli/tests/test-projects/known_bad",
+                "UnusedImport.roc",
+            ),
+        )
+        .add_args(["--deny", "unused-import"])
+        .run();
+
+        // 1 means errors, where the warning alone would have been 2
+        assert_eq!(cli_check_out.status.code(), Some(1), "{cli_check_out}");
+        assert!(cli_check_out.stdout.contains("UNUSED IMPORT"));
+    }
+
+    #[test]
+    fn allow_unused_import() {
+        ExecCli::new(
+            CMD_CHECK,
+            file_from_root(
+                "crates/cli/tests/test-projects/known_bad",
+                "UnusedImport.roc",
+            ),
+        )
+        .add_args(["--allow", "E2076"])
+        .run()
+        .assert_clean_success();
+    }
+
+    #[test]
+    fn allow_unused_import_directive() {
+        let dir = tempfile::tempdir().unwrap();
+
+        for file_name in ["UnusedImport.roc", "Symbol.roc"] {
+            std::fs::copy(
+                file_from_root("crates/cli/tests/test-projects/known_bad", file_name),
+                dir.path().join(file_name),
+            )
+            .unwrap();
+        }
+
+        let roc_file = dir.path().join("UnusedImport.roc");
+        let src = std::fs::read_to_string(&roc_file).unwrap();
+        std::fs::write(&roc_file, format!("{src}\n# roc: allow unused-import\n")).unwrap();
+
+        ExecCli::new(CMD_CHECK, roc_file.clone())
+            .run()
+            .assert_clean_success();
+
+        // The module's directive wins over the command line
+        ExecCli::new(CMD_CHECK, roc_file)
+            .add_args(["--deny", "unused-import"])
+            .run()
+            .assert_clean_success();
+    }
+
+    #[test]
+    fn explain_code() {
+        let explain_out = ExecCli::new(CMD_EXPLAIN, std::path::PathBuf::from("e2076")).run();
+        explain_out.assert_clean_success();
+
+        assert!(explain_out.stdout.starts_with("UNUSED IMPORT [E2076]\n\n"));
+        assert!(explain_out
+            .stdout
+            .contains("import Symbol exposing [Ident]"));
+    }
+
+    #[test]
+    fn explain_code_without_explanation() {
+        let explain_out = ExecCli::new(CMD_EXPLAIN, std::path::PathBuf::from("E4007")).run();
+        explain_out.assert_clean_success();
+
+        assert_eq!(
+            explain_out.stdout,
+            "IMPORT CYCLE [E4007]\n\nNo explanation is available for E4007 (import-cycle) yet. The report itself should say what went wrong.\n"
+        );
+    }
+
+    #[test]
+    fn explain_unknown_code() {
+        let explain_out = ExecCli::new(CMD_EXPLAIN, std::path::PathBuf::from("E9999")).run();
+        explain_out.assert_nonzero_exit();
+
+        assert!(explain_out.stderr.contains("I don't know the code E9999."));
+    }
+
+    #[test]
+    fn vendor_without_url_packages() {
+        let vendor_out = ExecCli::new(
+            CMD_VENDOR,
+            file_from_root("crates/cli/tests/test-projects/module_params", "app.roc"),
+        )
+        .run();
+        vendor_out.assert_clean_success();
+
+        assert!(vendor_out
+            .stdout
+            .contains("There are no URL packages to vendor."));
+        assert!(
+            !dir_from_root("crates/cli/tests/test-projects/module_params")
+                .join("vendor")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn deps_without_url_packages() {
+        let deps_out = ExecCli::new(
+            CMD_DEPS,
+            file_from_root("crates/cli/tests/test-projects/module_params", "app.roc"),
+        )
+        .add_args(["--format", "json"])
+        .run();
+        deps_out.assert_clean_success();
+
+        let report: serde_json::Value = serde_json::from_str(&deps_out.stdout).unwrap();
+
+        assert_eq!(report["packages"], serde_json::json!([]));
+        assert_eq!(report["multiple_versions"], serde_json::json!([]));
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn deps_with_file_url_packages() {
+        use cli_test_utils::command::run_command;
+        use cli_test_utils::helpers::path_to_roc_binary;
+        use roc_packaging::tarball::{self, Compression};
+        use std::process::Command;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = std::fs::canonicalize(tmp.path()).unwrap();
+
+        // Two versions of the same package, so `multiple_versions` has something in it
+        let urls: Vec<String> = ["1.0.0", "2.0.0"]
+            .iter()
+            .map(|version| {
+                let package_dir = dir.join("csv").join(version);
+
+                std::fs::create_dir_all(&package_dir).unwrap();
+                std::fs::write(package_dir.join("main.roc"), "package [Csv] {}\n").unwrap();
+                std::fs::write(
+                    package_dir.join("Csv.roc"),
+                    format!("module [version]\n\nversion = \"{version}\"\n"),
+                )
+                .unwrap();
+
+                let bundle =
+                    tarball::build(&package_dir.join("main.roc"), Compression::Brotli).unwrap();
+
+                format!("file://{}", package_dir.join(bundle.filename).display())
+            })
+            .collect();
+
+        let main_file = dir.join("main.roc");
+        std::fs::write(
+            &main_file,
+            format!(
+                "package [] {{ csv: \"{}\", csv2: \"{}\" }}\n",
+                urls[0], urls[1]
+            ),
+        )
+        .unwrap();
+
+        let cache_home = dir.join("cache");
+        let run_deps = |args: &[&str]| {
+            let mut cmd = Command::new(path_to_roc_binary());
+            cmd.arg(CMD_DEPS)
+                .args(args)
+                .arg(&main_file)
+                .env("XDG_CACHE_HOME", &cache_home);
+
+            let cmd_out = run_command(cmd, None);
+            assert!(cmd_out.status.success(), "{cmd_out}");
+
+            cmd_out.stdout
+        };
+
+        // stdout isn't a terminal here, so there are no colors even without --no-color
+        let text = run_deps(&[]);
+        let host_path = format!("{}/csv", dir.display());
+
+        assert!(!text.contains('\u{001b}'), "{text}");
+        assert_eq!(text, run_deps(&["--no-color"]));
+        assert!(
+            text.starts_with(&format!("{}\n    package, as csv\n", urls[0])),
+            "{text}"
+        );
+        assert!(
+            text.contains(&format!("{}\n    package, as csv2\n", urls[1])),
+            "{text}"
+        );
+        assert!(
+            text.contains(&format!("    imported by {}\n", main_file.display())),
+            "{text}"
+        );
+        assert!(
+            text.contains(&format!(
+                "2 versions of {host_path} are in use:\n    {}\n    {}\n",
+                urls[0], urls[1]
+            )),
+            "{text}"
+        );
+        assert!(
+            text.ends_with("\n2 URL package(s) and platform(s)\n"),
+            "{text}"
+        );
+
+        let report: serde_json::Value =
+            serde_json::from_str(&run_deps(&["--format", "json"])).unwrap();
+        let packages = report["packages"].as_array().unwrap();
+
+        assert_eq!(packages.len(), 2);
+
+        for (package, (url, shorthand)) in packages.iter().zip(urls.iter().zip(["csv", "csv2"])) {
+            assert_eq!(package["url"], serde_json::json!(url));
+            assert_eq!(package["kind"], serde_json::json!("package"));
+            assert_eq!(package["shorthands"], serde_json::json!([shorthand]));
+            assert_eq!(package["importers"], serde_json::json!([main_file]));
+            assert_eq!(package["host_path"], serde_json::json!(host_path));
+            assert!(package["size"].as_u64().unwrap() > 0);
+            assert!(std::path::Path::new(package["dir"].as_str().unwrap()).starts_with(&cache_home));
+        }
+
+        assert_eq!(
+            report["multiple_versions"],
+            serde_json::json!([{ "host_path": host_path, "urls": urls }])
+        );
+    }
+
+    #[test]
+    fn cache_prune_keeps_used_packages() {
+        use cli_test_utils::command::run_command;
+        use cli_test_utils::helpers::path_to_roc_binary;
+        use std::process::Command;
+
+        const USED_HASH: &str = "jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE";
+        const UNUSED_HASH: &str = "tE4xS_zLdmmxmHwHih9kHWQ7fsXtJr7W7h3425-eZFk";
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache_home = dir.path().join("cache");
+        // file:// packages are all cached in the same subdir, since their hash tells them apart
+        let packages_dir = cache_home.join("roc").join("packages").join("file");
+
+        for hash in [USED_HASH, UNUSED_HASH] {
+            std::fs::create_dir_all(packages_dir.join(hash)).unwrap();
+            std::fs::write(packages_dir.join(hash).join("main.roc"), "package [] {}\n").unwrap();
+        }
+
+        // Pruning never downloads anything, so the URL doesn't need to exist
+        let main_file = dir.path().join("main.roc");
+        std::fs::write(
+            &main_file,
+            format!("package [] {{ csv: \"file:///nowhere/{USED_HASH}.tar.br\" }}\n"),
+        )
+        .unwrap();
+
+        let mut cmd = Command::new(path_to_roc_binary());
+        cmd.arg(roc_cli::CMD_CACHE)
+            .arg(roc_cli::CMD_CACHE_PRUNE)
+            .arg(&main_file)
+            .env("XDG_CACHE_HOME", &cache_home);
+
+        let cmd_out = run_command(cmd, None);
+
+        cmd_out.assert_clean_success();
+        assert!(
+            cmd_out
+                .stdout
+                .contains(&format!("Removed file/{UNUSED_HASH}\n")),
+            "{cmd_out}"
+        );
+        assert!(cmd_out.stdout.contains("Removed 1 package(s)"), "{cmd_out}");
+        assert!(packages_dir.join(USED_HASH).join("main.roc").is_file());
+        assert!(!packages_dir.join(UNUSED_HASH).exists());
+    }
+
+    #[test]
+    fn missing_import_fix() {
+        let dir = tempfile::tempdir().unwrap();
+
+        std::fs::copy(
+            file_from_root("crates/cli/tests/test-projects/known_bad", "Symbol.roc"),
+            dir.path().join("Symbol.roc"),
+        )
+        .unwrap();
+
+        // Main imports Symbol, so it's known to exist when Label refers to it without importing it
+        std::fs::write(
+            dir.path().join("Main.roc"),
+            indoc::indoc!(
+                r#"
+                module [label]
+
+                import Symbol exposing [Ident]
+                import Label
+
+                label : Ident
+                label = Label.label
+                "#
+            ),
+        )
+        .unwrap();
+
+        let label_file = dir.path().join("Label.roc");
+        std::fs::write(
+            &label_file,
+            indoc::indoc!(
+                r#"
+                module [label]
+
+                label : Symbol.Ident
+                label = "label"
+                "#
+            ),
+        )
+        .unwrap();
+
+        let main_file = dir.path().join("Main.roc");
+
+        let cli_check_out = ExecCli::new(CMD_CHECK, main_file.clone())
+            .arg("--fix")
+            .run();
+        cli_check_out.assert_nonzero_exit();
+        assert!(cli_check_out.stdout.contains("Applied 1 fix to 1 file."));
+
+        let fixed = std::fs::read_to_string(&label_file).unwrap();
+        assert!(
+            fixed.starts_with("module [label]\n\nimport Symbol\n\nlabel : Symbol.Ident\n"),
+            "{fixed}"
+        );
+
+        ExecCli::new(CMD_CHECK, main_file)
+            .run()
+            .assert_clean_success();
+    }
+
+    #[test]
+    fn missing_import_fix_after_multiline_import() {
+        let dir = tempfile::tempdir().unwrap();
+
+        std::fs::copy(
+            file_from_root("crates/cli/tests/test-projects/known_bad", "Symbol.roc"),
+            dir.path().join("Symbol.roc"),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("Main.roc"),
+            "module [label]\n\nimport Symbol exposing [Ident]\nimport Label\n\nlabel : Ident\nlabel = Label.label\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("Other.roc"),
+            "module [other]\n\nother = \"label\"\n",
+        )
+        .unwrap();
+
+        let label_file = dir.path().join("Label.roc");
+        std::fs::write(
+            &label_file,
+            indoc::indoc!(
+                r#"
+                module [label]
+
+                import Other exposing [
+                    other,
+                ]
+
+                label : Symbol.Ident
+                label = other
+                "#
+            ),
+        )
+        .unwrap();
+
+        let main_file = dir.path().join("Main.roc");
+
+        let cli_check_out = ExecCli::new(CMD_CHECK, main_file.clone())
+            .arg("--fix")
+            .run();
+        cli_check_out.assert_nonzero_exit();
+        assert!(cli_check_out.stdout.contains("Applied 1 fix to 1 file."));
+
+        // The new import goes after the whole of the last one, not inside its exposing list
+        let fixed = std::fs::read_to_string(&label_file).unwrap();
+        assert!(
+            fixed.starts_with(
+                "module [label]\n\nimport Other exposing [\n    other,\n]\nimport Symbol\n\nlabel : Symbol.Ident\n"
+            ),
+            "{fixed}"
+        );
+
+        ExecCli::new(CMD_CHECK, main_file)
+            .run()
+            .assert_clean_success();
+    }
+
+    #[test]
+    fn record_field_typo_fix_only_for_close_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let roc_file = dir.path().join("Person.roc");
+
+        // `name` is the only field, but nothing like `height`, so there's nothing to change it to
+        std::fs::write(
+            &roc_file,
+            "module [height]\n\nperson = { name: \"Ana\" }\n\nheight = person.height\n",
+        )
+        .unwrap();
+
+        let cli_check_out = ExecCli::new(CMD_CHECK, roc_file.clone()).arg("--fix").run();
+        cli_check_out.assert_nonzero_exit();
+        assert!(cli_check_out.stdout.contains("Applied 0 fixes to 0 files."));
+        assert!(std::fs::read_to_string(&roc_file)
+            .unwrap()
+            .contains("person.height"));
+
+        // One letter out of place is close enough
+        std::fs::write(
+            &roc_file,
+            "module [name]\n\nperson = { name: \"Ana\" }\n\nname = person.nmae\n",
+        )
+        .unwrap();
+
+        let cli_check_out = ExecCli::new(CMD_CHECK, roc_file.clone()).arg("--fix").run();
+        cli_check_out.assert_nonzero_exit();
+        assert!(cli_check_out.stdout.contains("Applied 1 fix to 1 file."));
+        assert!(std::fs::read_to_string(&roc_file)
+            .unwrap()
+            .contains("person.name\n"));
+    }
+
     #[test]
     fn format_check_good() {
         ExecCli::new(
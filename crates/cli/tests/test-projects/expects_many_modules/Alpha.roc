@@ -0,0 +1,12 @@
+module [
+    count_down,
+]
+
+# slow enough that the modules after this one usually finish first
+count_down = \n, acc ->
+    if n == 0 then
+        acc
+    else
+        count_down(n - 1, acc + 1)
+
+expect count_down(5_000_000, 0) == 0
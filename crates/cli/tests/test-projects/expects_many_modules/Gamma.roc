@@ -0,0 +1,7 @@
+module [
+    greet,
+]
+
+greet = \name -> Str.concat("Hello, ", name)
+
+expect greet("Gamma") == "Hi, Gamma"
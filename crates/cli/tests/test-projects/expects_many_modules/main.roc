@@ -0,0 +1,5 @@
+package [
+    Alpha,
+    Beta,
+    Gamma,
+] {}
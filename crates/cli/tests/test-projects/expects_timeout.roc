@@ -0,0 +1,11 @@
+module []
+
+count_up = \n ->
+    if n == 0 then
+        n
+    else
+        count_up(n + 1)
+
+expect count_up(1) == 0
+
+expect 1 + 1 == 2
@@ -0,0 +1,9 @@
+module [double]
+
+double = \n -> n * 2
+
+unused = 42
+
+expect double(2) == 4
+
+expect double(3) == 5
@@ -1249,6 +1249,26 @@ pub fn llvm_module_to_dylib(
     module: &inkwell::module::Module,
     target: Target,
     opt_level: OptLevel,
+) -> Result<Library, Error> {
+    llvm_module_to_dylib_help(module, target, opt_level, None)
+}
+
+/// Like [llvm_module_to_dylib], but also links in a platform's prebuilt host, so the module
+/// can call the effects the host provides.
+pub fn llvm_module_to_dylib_with_host(
+    module: &inkwell::module::Module,
+    target: Target,
+    opt_level: OptLevel,
+    host_path: &Path,
+) -> Result<Library, Error> {
+    llvm_module_to_dylib_help(module, target, opt_level, Some(host_path))
+}
+
+fn llvm_module_to_dylib_help(
+    module: &inkwell::module::Module,
+    target: Target,
+    opt_level: OptLevel,
+    opt_host_path: Option<&Path>,
 ) -> Result<Library, Error> {
     use crate::target::{self, convert_opt_level};
     use inkwell::targets::{FileType, RelocMode};
@@ -1269,14 +1289,15 @@ pub fn llvm_module_to_dylib(
         .write_to_file(module, FileType::Object, &app_o_file)
         .expect("Writing .o file failed");
 
+    let mut inputs = vec![app_o_file.to_str().unwrap()];
+
+    if let Some(host_path) = opt_host_path {
+        inputs.push(host_path.to_str().unwrap());
+    }
+
     // Link app.o into a dylib - e.g. app.so or app.dylib
-    let (mut child, dylib_path) = link(
-        target,
-        app_o_file.clone(),
-        &[app_o_file.to_str().unwrap()],
-        LinkType::Dylib,
-    )
-    .unwrap();
+    let (mut child, dylib_path) =
+        link(target, app_o_file.clone(), &inputs, LinkType::Dylib).unwrap();
 
     let exit_status = child.wait().unwrap();
 
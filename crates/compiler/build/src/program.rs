@@ -7,13 +7,14 @@ use roc_gen_dev::AssemblyBackendMode;
 use roc_gen_llvm::llvm::build::{module_from_builtins, LlvmBackendMode};
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_load::{
-    EntryPoint, ExecutionMode, ExpectMetadata, FunctionKind, LoadConfig, LoadMonomorphizedError,
-    LoadedModule, LoadingProblem, MonomorphizedModule, Threading,
+    report_loading_problem, EntryPoint, ExecutionMode, ExpectMetadata, FunctionKind, LoadConfig,
+    LoadMonomorphizedError, LoadedModule, LoadingProblem, MonomorphizedModule, Threading,
 };
+use roc_module::symbol::ModuleIds;
 use roc_mono::ir::{OptLevel, SingleEntryPoint};
 use roc_packaging::cache::RocCacheDir;
 use roc_reporting::{
-    cli::{report_problems, Problems},
+    cli::{report_problems, report_problems_with_fixes, FileFix, Problems, WarningLevels},
     report::{RenderTarget, DEFAULT_PALETTE},
 };
 use roc_target::{Architecture, Target};
@@ -36,21 +37,49 @@ pub struct CodeGenTiming {
     pub total: Duration,
 }
 
-pub fn report_problems_monomorphized(loaded: &mut MonomorphizedModule) -> Problems {
+pub fn report_problems_monomorphized(
+    loaded: &mut MonomorphizedModule,
+    render: RenderTarget,
+    warning_levels: &WarningLevels,
+) -> Problems {
     report_problems(
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        render,
+        warning_levels,
     )
 }
 
-pub fn report_problems_typechecked(loaded: &mut LoadedModule) -> Problems {
+pub fn report_problems_typechecked(
+    loaded: &mut LoadedModule,
+    render: RenderTarget,
+    warning_levels: &WarningLevels,
+) -> Problems {
     report_problems(
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        render,
+        warning_levels,
+    )
+}
+
+/// Like `report_problems_typechecked`, also returning the fixes `report_problems_with_fixes` does.
+pub fn report_problems_typechecked_with_fixes(
+    loaded: &mut LoadedModule,
+    render: RenderTarget,
+    warning_levels: &WarningLevels,
+) -> (Problems, Vec<FileFix>) {
+    report_problems_with_fixes(
+        &loaded.sources,
+        &loaded.interns,
+        &mut loaded.can_problems,
+        &mut loaded.type_problems,
+        render,
+        warning_levels,
     )
 }
 
@@ -650,12 +679,17 @@ impl<'a> BuildFileError<'a> {
 pub fn handle_error_module(
     mut module: roc_load::LoadedModule,
     total_time: std::time::Duration,
+    render: RenderTarget,
+    warning_levels: &WarningLevels,
 ) -> std::io::Result<i32> {
     debug_assert!(module.total_problems() > 0);
 
-    let problems = report_problems_typechecked(&mut module);
+    let problems = report_problems_typechecked(&mut module, render, warning_levels);
 
-    problems.print_error_warning_count(total_time);
+    // Keep stdout to one JSON report per line, or free for whatever a Generic run prints there
+    if !matches!(render, RenderTarget::Json | RenderTarget::Generic) {
+        problems.print_error_warning_count(total_time);
+    }
 
     Ok(problems.exit_code())
 }
@@ -666,6 +700,18 @@ pub fn handle_loading_problem(problem: LoadingProblem) -> std::io::Result<i32> {
             print!("{report}");
             Ok(1)
         }
+        LoadingProblem::FileProblem { .. } | LoadingProblem::ParsingFailed(_) => {
+            // These can come from just reading headers, like `roc deps` does, before any module
+            // has an id.
+            let report = report_loading_problem(
+                problem,
+                ModuleIds::default(),
+                RenderTarget::ColorTerminal,
+                DEFAULT_PALETTE,
+            );
+            print!("{report}");
+            Ok(1)
+        }
         _ => {
             // TODO: tighten up the types here, we should always end up with a
             // formatted report from load.
@@ -711,8 +757,10 @@ pub fn build_file<'a>(
     load_config: LoadConfig,
     out_path: Option<&Path>,
     verbose: bool,
+    warning_levels: &WarningLevels,
 ) -> Result<BuiltFile<'a>, BuildFileError<'a>> {
     let compilation_start = Instant::now();
+    let render = load_config.render;
 
     let loaded = roc_load::load_and_monomorphize(
         arena,
@@ -738,6 +786,8 @@ pub fn build_file<'a>(
         compilation_start,
         out_path,
         verbose,
+        render,
+        warning_levels,
     )
 }
 
@@ -815,6 +865,8 @@ fn build_loaded_file<'a>(
     compilation_start: Instant,
     out_path: Option<&Path>,
     verbose: bool,
+    render: RenderTarget,
+    warning_levels: &WarningLevels,
 ) -> Result<BuiltFile<'a>, BuildFileError<'a>> {
     // get the platform path from the app header
     let platform_main_roc_path = match &loaded.entry_point {
@@ -890,7 +942,7 @@ fn build_loaded_file<'a>(
     // This only needs to be mutable for report_problems. This can't be done
     // inside a nested scope without causing a borrow error!
     let mut loaded = loaded;
-    let problems = report_problems_monomorphized(&mut loaded);
+    let problems = report_problems_monomorphized(&mut loaded, render, warning_levels);
     let loaded = loaded;
 
     let (roc_app_bytes, code_gen_timing, expect_metadata) = gen_from_mono_module(
@@ -1335,7 +1387,9 @@ pub fn check_file<'a>(
     emit_timings: bool,
     roc_cache_dir: RocCacheDir<'_>,
     threading: Threading,
-) -> Result<(Problems, Duration), LoadingProblem<'a>> {
+    render: RenderTarget,
+    warning_levels: &WarningLevels,
+) -> Result<(Problems, Vec<FileFix>, Duration), LoadingProblem<'a>> {
     let compilation_start = Instant::now();
 
     // only used for generating errors. We don't do code generation, so hardcoding should be fine
@@ -1347,8 +1401,7 @@ pub fn check_file<'a>(
     let load_config = LoadConfig {
         target,
         function_kind: FunctionKind::from_env(),
-        // TODO: expose this from CLI?
-        render: RenderTarget::ColorTerminal,
+        render,
         palette: DEFAULT_PALETTE,
         threading,
         exec_mode: ExecutionMode::Check,
@@ -1403,7 +1456,10 @@ pub fn check_file<'a>(
         println!("Finished checking in {} ms\n", compilation_end.as_millis(),);
     }
 
-    Ok((report_problems_typechecked(&mut loaded), compilation_end))
+    let (problems, fixes) =
+        report_problems_typechecked_with_fixes(&mut loaded, render, warning_levels);
+
+    Ok((problems, fixes, compilation_end))
 }
 
 pub fn build_str_test<'a>(
@@ -1465,6 +1521,8 @@ pub fn build_str_test<'a>(
         compilation_start,
         None,
         false,
+        RenderTarget::ColorTerminal,
+        &WarningLevels::default(),
     )
 }
 
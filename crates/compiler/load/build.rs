@@ -61,7 +61,7 @@ fn write_types_for_module_real(module_id: ModuleId, filename: &str, output_path:
     use roc_can::module::TypeState;
     use roc_load_internal::file::{LoadingProblem, Threading};
     use roc_packaging::cache::RocCacheDir;
-    use roc_reporting::cli::report_problems;
+    use roc_reporting::cli::{report_problems, WarningLevels};
 
     let arena = Bump::new();
     let cwd = std::env::current_dir().unwrap();
@@ -100,6 +100,8 @@ fn write_types_for_module_real(module_id: ModuleId, filename: &str, output_path:
         &module.interns,
         &mut module.can_problems,
         &mut module.type_problems,
+        roc_reporting::report::RenderTarget::ColorTerminal,
+        &WarningLevels::default(),
     );
 
     if problems.errors + problems.warnings > 0 {
@@ -16,10 +16,12 @@ const SKIP_SUBS_CACHE: bool = {
     }
 };
 
+#[cfg(not(target_family = "wasm"))]
+pub use roc_load_internal::deps;
 pub use roc_load_internal::docs;
 pub use roc_load_internal::file::{
-    ExecutionMode, ExpectMetadata, LoadConfig, LoadResult, LoadStart, LoadingProblem, Phase,
-    Threading,
+    report_loading_problem, ExecutionMode, ExpectMetadata, LoadConfig, LoadResult, LoadStart,
+    LoadingProblem, Phase, Threading,
 };
 pub use roc_load_internal::module::{
     CheckedModule, EntryPoint, Expectations, ExposedToHost, LoadedModule, MonomorphizedModule,
@@ -120,6 +122,29 @@ pub fn load_and_monomorphize_from_str<'a>(
     }
 }
 
+/// Load from a `LoadStart` the caller set up, e.g. with in-memory modules. The modules in
+/// `reused_types` get those types instead of being solved again, so they must come from an
+/// earlier load of the very same sources.
+pub fn load_reusing<'a>(
+    arena: &'a Bump,
+    load_start: LoadStart<'a>,
+    roc_cache_dir: RocCacheDir<'_>,
+    load_config: LoadConfig,
+    reused_types: MutMap<ModuleId, TypeState>,
+) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
+    let mut cached_types = read_cached_types();
+    cached_types.extend(reused_types);
+
+    roc_load_internal::file::load(
+        arena,
+        load_start,
+        ExposedByModule::default(),
+        cached_types,
+        roc_cache_dir,
+        load_config,
+    )
+}
+
 pub fn load_and_monomorphize<'a>(
     arena: &'a Bump,
     filename: PathBuf,
@@ -184,6 +209,44 @@ pub fn load_and_typecheck_str<'a>(
     render: RenderTarget,
     roc_cache_dir: RocCacheDir<'_>,
     palette: Palette,
+) -> Result<LoadedModule, LoadingProblem<'a>> {
+    load_and_typecheck_str_reusing(
+        arena,
+        filename,
+        source,
+        src_dir,
+        opt_main_path,
+        target,
+        function_kind,
+        render,
+        roc_cache_dir,
+        palette,
+        MutMap::default(),
+    )
+}
+
+/// Like [load_and_typecheck_str], but takes the solved types of modules from a previous load
+/// instead of solving them again.
+///
+/// Only solving is skipped: the reused modules are still parsed, canonicalized and constrained,
+/// because their canonical declarations and the problems found along the way come from those
+/// steps.
+///
+/// The reused modules are looked up by [ModuleId], so they must come from a load of the same
+/// root, and neither their sources nor those of anything they import may have changed since.
+#[allow(clippy::too_many_arguments)]
+pub fn load_and_typecheck_str_reusing<'a>(
+    arena: &'a Bump,
+    filename: PathBuf,
+    source: &'a str,
+    src_dir: PathBuf,
+    opt_main_path: Option<PathBuf>,
+    target: Target,
+    function_kind: FunctionKind,
+    render: RenderTarget,
+    roc_cache_dir: RocCacheDir<'_>,
+    palette: Palette,
+    reused_types: MutMap<ModuleId, TypeState>,
 ) -> Result<LoadedModule, LoadingProblem<'a>> {
     use LoadResult::*;
 
@@ -196,18 +259,23 @@ pub fn load_and_typecheck_str<'a>(
         src_dir,
     )?;
 
+    let mut cached_types = read_cached_types();
+    cached_types.extend(reused_types);
+
     // NOTE: this function is meant for tests, and so we use single-threaded
     // solving so we don't use too many threads per-test. That gives higher
     // throughput for the test run overall
-    match load_single_threaded(
+    match roc_load_internal::file::load_single_threaded(
         arena,
         load_start,
+        ExposedByModule::default(),
         target,
         function_kind,
+        cached_types,
         render,
         palette,
-        roc_cache_dir,
         ExecutionMode::Check,
+        roc_cache_dir,
     )? {
         Monomorphized(_) => unreachable!(""),
         TypeChecked(module) => Ok(module),
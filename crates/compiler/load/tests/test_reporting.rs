@@ -21,6 +21,7 @@ mod test_reporting {
     use roc_parse::test_helpers::parse_expr_with;
     use roc_problem::Severity;
     use roc_region::all::LineInfo;
+    use roc_reporting::error::codes::ERROR_CODES;
     use roc_reporting::report::{
         can_problem, parse_problem, type_problem, RenderTarget, Report, ANSI_STYLE_CODES,
         DEFAULT_PALETTE,
@@ -42,9 +43,73 @@ mod test_reporting {
     fn to_simple_report(doc: RocDocBuilder) -> Report {
         Report {
             title: "".to_string(),
+            code: None,
             doc,
             filename: filename_from_string(r"/code/proj/Main.roc"),
             severity: Severity::RuntimeError,
+            fixes: Vec::new(),
+        }
+    }
+
+    /// Every problem report should say which kind of problem it is, so `roc explain`, `--allow`
+    /// and editors can refer to it
+    fn assert_has_code(report: &Report) {
+        let code = report
+            .code
+            .unwrap_or_else(|| panic!("The {} report has no code", report.title));
+
+        assert_eq!(
+            code.title, report.title,
+            "The {} report has the code {}, which is for {}",
+            report.title, code.code, code.title
+        );
+    }
+
+    #[test]
+    fn error_codes_are_unique() {
+        let mut codes = std::collections::HashSet::new();
+        let mut kinds = std::collections::HashSet::new();
+
+        for error_code in ERROR_CODES {
+            assert!(
+                codes.insert(error_code.code),
+                "{} is used more than once",
+                error_code.code
+            );
+            assert!(
+                kinds.insert(error_code.kind),
+                "{} is used more than once",
+                error_code.kind
+            );
+            assert!(
+                !error_code.title.is_empty(),
+                "{} has no title",
+                error_code.code
+            );
+        }
+    }
+
+    /// Parse, canonicalize and type problems, and every warning `--allow` and `--deny` can change,
+    /// should all be explained by `roc explain`
+    #[test]
+    fn error_codes_are_explained() {
+        for error_code in ERROR_CODES {
+            let must_be_explained = ["E1", "E2", "E3"]
+                .iter()
+                .any(|section| error_code.code.starts_with(section))
+                || error_code.code == "E6001";
+
+            if must_be_explained {
+                let explanation = error_code
+                    .explanation()
+                    .unwrap_or_else(|| panic!("{} has no explanation", error_code.code));
+
+                assert!(
+                    explanation.contains("\n    "),
+                    "The explanation of {} has no example",
+                    error_code.code
+                );
+            }
         }
     }
 
@@ -197,6 +262,7 @@ mod test_reporting {
 
                 for problem in can_problems {
                     let report = can_problem(&alloc, &lines, filename.clone(), problem.clone());
+                    assert_has_code(&report);
                     reports.push(report);
                 }
 
@@ -204,6 +270,7 @@ mod test_reporting {
                     if let Some(report) =
                         type_problem(&alloc, &lines, filename.clone(), problem.clone())
                     {
+                        assert_has_code(&report);
                         reports.push(report);
                     }
                 }
@@ -311,6 +378,7 @@ mod test_reporting {
 
                 let problem = fail.into_file_error(filename.clone());
                 let doc = parse_problem(&alloc, &lines, filename, 0, problem);
+                assert_has_code(&doc);
 
                 callback(doc.pretty(&alloc).append(alloc.line()), buf)
             }
@@ -321,6 +389,7 @@ mod test_reporting {
 
                 for problem in can_problems {
                     let report = can_problem(&alloc, &lines, filename.clone(), problem.clone());
+                    assert_has_code(&report);
                     reports.push(report);
                 }
 
@@ -328,6 +397,7 @@ mod test_reporting {
                     if let Some(report) =
                         type_problem(&alloc, &lines, filename.clone(), problem.clone())
                     {
+                        assert_has_code(&report);
                         reports.push(report);
                     }
                 }
@@ -371,6 +441,7 @@ mod test_reporting {
                     .map_problem(SyntaxError::Header)
                     .into_file_error(filename.clone());
                 let doc = parse_problem(&alloc, &lines, filename, 0, problem);
+                assert_has_code(&doc);
 
                 callback(doc.pretty(&alloc).append(alloc.line()), buf)
             }
@@ -480,7 +551,7 @@ mod test_reporting {
         "
         ),
         @r"
-    ── NOT EXPOSED in /code/proj/Main.roc ──────────────────────────────────────────
+    ── NOT EXPOSED [E2043] in /code/proj/Main.roc ──────────────────────────────────
 
     The List module does not expose `isempty`:
 
@@ -507,7 +578,7 @@ mod test_reporting {
         "
         ),
         @r"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
     `y` is not used anywhere in your code.
 
@@ -532,7 +603,7 @@ mod test_reporting {
        "
         ),
         @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [E2017] in /code/proj/Main.roc ───────────────────────────────
 
     The `i` name is first defined here:
 
@@ -564,7 +635,7 @@ mod test_reporting {
        "
         ),
         @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [E2017] in /code/proj/Main.roc ───────────────────────────────
 
     The `Booly` name is first defined here:
 
@@ -596,7 +667,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E1044] in /code/proj/Main.roc ───────────────────────────────
 
     Using != and == together requires parentheses, to clarify how they
     should be grouped.
@@ -625,7 +696,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-        ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+        ── UNRECOGNIZED NAME [E2073] in /code/proj/Main.roc ────────────────────────────
 
         Nothing is named `bar` in this scope.
 
@@ -649,7 +720,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [E2073] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `true` in this scope.
 
@@ -681,7 +752,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E1044] in /code/proj/Main.roc ───────────────────────────────
 
     Using more than one == like this requires parentheses, to clarify how
     things should be grouped.
@@ -707,7 +778,7 @@ mod test_reporting {
          "#
         ),
         @r#"
-    ── UNUSED ARGUMENT in /code/proj/Main.roc ──────────────────────────────────────
+    ── UNUSED ARGUMENT [E2074] in /code/proj/Main.roc ──────────────────────────────
 
     `box` doesn't use `html_children`.
 
@@ -720,7 +791,7 @@ mod test_reporting {
     at the start of a variable name is a way of saying that the variable
     is not used.
 
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
     `y` is not used anywhere in your code.
 
@@ -807,7 +878,7 @@ mod test_reporting {
             ),
             indoc!(
                 r"
-                <cyan>── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────<reset>
+                <cyan>── UNRECOGNIZED NAME [E2073] in /code/proj/Main.roc ────────────────────────────<reset>
 
                 Nothing is named `the_admin` in this scope.
 
@@ -833,7 +904,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` condition needs to be a Bool:
 
@@ -859,7 +930,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` guard condition needs to be a Bool:
 
@@ -884,7 +955,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -911,7 +982,7 @@ mod test_reporting {
              "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     The 3rd branch of this `if` does not match all the previous branches:
 
@@ -941,7 +1012,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     The 2nd branch of this `when` does not match all the previous branches:
 
@@ -979,7 +1050,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -1023,7 +1094,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This list contains elements with different types:
 
@@ -1050,7 +1121,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This list contains elements with different types:
 
@@ -1083,7 +1154,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     I cannot update the `.foo` field like this:
 
@@ -1113,7 +1184,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1138,7 +1209,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1164,7 +1235,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1177,7 +1248,7 @@ mod test_reporting {
 
         List ∞ -> *
 
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `g`:
 
@@ -1204,7 +1275,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3027] in /code/proj/Main.roc ────────────────────────────────
 
     This returns something that's incompatible with the return type of the
     enclosing function:
@@ -1241,7 +1312,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3027] in /code/proj/Main.roc ────────────────────────────────
 
     This returns something that's incompatible with the return type of the
     enclosing function:
@@ -1276,7 +1347,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1302,7 +1373,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1340,7 +1411,7 @@ mod test_reporting {
         // against that extra variable, rather than possibly having to translate a `Type`
         // again.
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1353,7 +1424,7 @@ mod test_reporting {
 
         List ∞ -> List *
 
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `g`:
 
@@ -1380,7 +1451,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1393,7 +1464,7 @@ mod test_reporting {
 
         List ∞ -> List *
 
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `g`:
 
@@ -1421,7 +1492,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1455,7 +1526,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1492,7 +1563,7 @@ mod test_reporting {
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1529,7 +1600,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the `then` branch of this `if` expression:
 
@@ -1563,7 +1634,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -1596,7 +1667,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -1628,7 +1699,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [E3015] in /code/proj/Main.roc ────────────────────────────────
 
     The `x` value is not a function, but it was given 1 argument:
 
@@ -1650,7 +1721,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [E3015] in /code/proj/Main.roc ────────────────────────────────
 
     The `f` function expects 1 argument, but it got 2 instead:
 
@@ -1672,7 +1743,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO FEW ARGS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── TOO FEW ARGS [E3014] in /code/proj/Main.roc ─────────────────────────────────
 
     The `f` function expects 2 arguments, but it got only 1:
 
@@ -1693,7 +1764,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -1722,7 +1793,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3028] in /code/proj/Main.roc ────────────────────────────────
 
     The 2nd pattern in this `when` does not match the previous ones:
 
@@ -1748,7 +1819,7 @@ mod test_reporting {
              "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -1776,7 +1847,7 @@ mod test_reporting {
              "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -1805,7 +1876,7 @@ mod test_reporting {
              "
         ),
         @r"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [E2073] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `foo` in this scope.
 
@@ -1861,7 +1932,7 @@ mod test_reporting {
         ),
         // Just putting this here. We should probably handle or-patterns better
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3028] in /code/proj/Main.roc ────────────────────────────────
 
     The 2nd pattern in this branch does not match the previous ones:
 
@@ -1889,7 +1960,7 @@ mod test_reporting {
         ),
         // Maybe this should specifically say the pattern doesn't work?
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -1917,7 +1988,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of this definition:
 
@@ -1948,7 +2019,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2082] in /code/proj/Main.roc ───────────────────────────────
 
     This integer pattern is malformed:
 
@@ -1969,7 +2040,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2082] in /code/proj/Main.roc ───────────────────────────────
 
     This float pattern is malformed:
 
@@ -1990,7 +2061,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2082] in /code/proj/Main.roc ───────────────────────────────
 
     This hex integer pattern is malformed:
 
@@ -2011,7 +2082,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2082] in /code/proj/Main.roc ───────────────────────────────
 
     This octal integer pattern is malformed:
 
@@ -2032,7 +2103,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2082] in /code/proj/Main.roc ───────────────────────────────
 
     This binary integer pattern is malformed:
 
@@ -2054,7 +2125,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -2104,7 +2175,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the `else` branch of this `if` expression:
 
@@ -2138,7 +2209,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2173,7 +2244,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2209,7 +2280,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [E2073] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `ok` in this scope.
 
@@ -2240,7 +2311,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
     `ok` is not used anywhere in your code.
 
@@ -2250,7 +2321,7 @@ mod test_reporting {
     If you didn't intend on using `ok` then remove it so future readers of
     your code don't wonder why it is there.
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2282,7 +2353,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR DEFINITION in /code/proj/Main.roc ──────────────────────────────────
+    ── CIRCULAR DEFINITION [E2009] in /code/proj/Main.roc ──────────────────────────
 
     `f` is defined directly in terms of itself:
 
@@ -2309,7 +2380,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR DEFINITION in /code/proj/Main.roc ──────────────────────────────────
+    ── CIRCULAR DEFINITION [E2009] in /code/proj/Main.roc ──────────────────────────
 
     The `foo` definition is causing a very tricky infinite loop:
 
@@ -2337,7 +2408,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3029] in /code/proj/Main.roc ────────────────────────────────
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2359,7 +2430,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3029] in /code/proj/Main.roc ────────────────────────────────
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2392,7 +2463,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3029] in /code/proj/Main.roc ────────────────────────────────
 
     This `r` record doesn’t have a `foo` field:
 
@@ -2420,7 +2491,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3029] in /code/proj/Main.roc ────────────────────────────────
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2450,7 +2521,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -2475,7 +2546,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -2503,7 +2574,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -2531,7 +2602,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2565,7 +2636,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2608,7 +2679,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This pattern does not cover all the possibilities:
 
@@ -2623,7 +2694,7 @@ mod test_reporting {
     matching in function arguments, put a `when` in the function body to
     account for all possibilities.
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2660,7 +2731,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -2691,7 +2762,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2718,7 +2789,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2746,7 +2817,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2775,7 +2846,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2804,7 +2875,7 @@ mod test_reporting {
         ),
         // Tip: Looks like a record field guard is not exhaustive. Learn more about record pattern matches at TODO.
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2833,7 +2904,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2859,7 +2930,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2886,7 +2957,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [E3013] in /code/proj/Main.roc ────────────────────────────
 
     The 2nd pattern is redundant:
 
@@ -2914,7 +2985,7 @@ mod test_reporting {
         ),
         // de-aliases the alias to give a better error message
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `f` has an unexpected type:
 
@@ -2952,7 +3023,7 @@ mod test_reporting {
         ),
         // should not report Bar as unused!
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [E3002] in /code/proj/Main.roc ─────────────────────────────────
 
     The `Foo` alias is self-recursive in an invalid way:
 
@@ -2978,7 +3049,7 @@ mod test_reporting {
         ),
         // should not report Bar as unused!
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [E3002] in /code/proj/Main.roc ─────────────────────────────────
 
     The `Foo` alias is self-recursive in an invalid way:
 
@@ -2998,7 +3069,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── DUPLICATE FIELD NAME in /code/proj/Main.roc ─────────────────────────────────
+    ── DUPLICATE FIELD NAME [E2015] in /code/proj/Main.roc ─────────────────────────
 
     This record defines the `.x` field twice!
 
@@ -3022,7 +3093,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── DUPLICATE FIELD NAME in /code/proj/Main.roc ─────────────────────────────────
+    ── DUPLICATE FIELD NAME [E2015] in /code/proj/Main.roc ─────────────────────────
 
     This record defines the `.x` field twice!
 
@@ -3050,7 +3121,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── DUPLICATE FIELD NAME in /code/proj/Main.roc ─────────────────────────────────
+    ── DUPLICATE FIELD NAME [E2015] in /code/proj/Main.roc ─────────────────────────
 
     This record defines the `.x` field twice!
 
@@ -3085,7 +3156,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── DUPLICATE FIELD NAME in /code/proj/Main.roc ─────────────────────────────────
+    ── DUPLICATE FIELD NAME [E2015] in /code/proj/Main.roc ─────────────────────────
 
     This record defines the `.x` field twice!
 
@@ -3118,7 +3189,7 @@ mod test_reporting {
             "#
         ),
         @r"
-    ── DUPLICATE FIELD NAME in /code/proj/Main.roc ─────────────────────────────────
+    ── DUPLICATE FIELD NAME [E2015] in /code/proj/Main.roc ─────────────────────────
 
     This record type defines the `.foo` field twice!
 
@@ -3146,7 +3217,7 @@ mod test_reporting {
             "#
         ),
         @r"
-    ── DUPLICATE TAG NAME in /code/proj/Main.roc ───────────────────────────────────
+    ── DUPLICATE TAG NAME [E2018] in /code/proj/Main.roc ───────────────────────────
 
     This tag union type defines the `Foo` tag twice!
 
@@ -3175,7 +3246,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── NAMING PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── NAMING PROBLEM [E2093] in /code/proj/Main.roc ───────────────────────────────
 
     This annotation does not match the definition immediately following
     it:
@@ -3211,7 +3282,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2080] in /code/proj/Main.roc ───────────────────────────────
 
     This definition of `MyAlias` has an unexpected pattern:
 
@@ -3220,7 +3291,7 @@ mod test_reporting {
 
     Only type variables like `a` or `value` can occur in this position.
 
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
     `MyAlias` is not used anywhere in your code.
 
@@ -3243,7 +3314,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2080] in /code/proj/Main.roc ───────────────────────────────
 
     This definition of `Age` has an unexpected pattern:
 
@@ -3265,7 +3336,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO MANY TYPE ARGUMENTS in /code/proj/Main.roc ──────────────────────────────
+    ── TOO MANY TYPE ARGUMENTS [E2059] in /code/proj/Main.roc ──────────────────────
 
     The `Num` opaque expects 1 type argument, but it got 2 instead:
 
@@ -3287,7 +3358,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO MANY TYPE ARGUMENTS in /code/proj/Main.roc ──────────────────────────────
+    ── TOO MANY TYPE ARGUMENTS [E2059] in /code/proj/Main.roc ──────────────────────
 
     The `Num` opaque expects 1 type argument, but it got 2 instead:
 
@@ -3311,7 +3382,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO FEW TYPE ARGUMENTS in /code/proj/Main.roc ───────────────────────────────
+    ── TOO FEW TYPE ARGUMENTS [E2058] in /code/proj/Main.roc ───────────────────────
 
     The `Pair` alias expects 2 type arguments, but it got 1 instead:
 
@@ -3335,7 +3406,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO MANY TYPE ARGUMENTS in /code/proj/Main.roc ──────────────────────────────
+    ── TOO MANY TYPE ARGUMENTS [E2059] in /code/proj/Main.roc ──────────────────────
 
     The `Pair` alias expects 2 type arguments, but it got 3 instead:
 
@@ -3358,7 +3429,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── UNUSED TYPE ALIAS PARAMETER in /code/proj/Main.roc ──────────────────────────
+    ── UNUSED TYPE ALIAS PARAMETER [E2077] in /code/proj/Main.roc ──────────────────
 
     The `a` type parameter is not used in the `Foo` alias definition:
 
@@ -3380,7 +3451,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── ARGUMENTS BEFORE EQUALS in tmp/elm_function_syntax/Test.roc ─────────────────
+    ── ARGUMENTS BEFORE EQUALS [E1001] in tmp/elm_function_syntax/Test.roc ─────────
 
     I am partway through parsing a definition, but I got stuck here:
 
@@ -3408,7 +3479,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -3457,7 +3528,7 @@ mod test_reporting {
         // TODO render tag unions across multiple lines
         // TODO do not show recursion var if the recursion var does not render on the surface of a type
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -3511,7 +3582,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2087] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal is too big:
 
@@ -3523,7 +3594,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2087] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal is too small:
 
@@ -3535,7 +3606,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2087] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal is too big:
 
@@ -3547,7 +3618,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2087] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal is too small:
 
@@ -3559,7 +3630,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -3588,7 +3659,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2085] in /code/proj/Main.roc ───────────────────────────────
 
     This float literal is too big:
 
@@ -3600,7 +3671,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2085] in /code/proj/Main.roc ───────────────────────────────
 
     This float literal is too small:
 
@@ -3633,7 +3704,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2086] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal contains an invalid digit:
 
@@ -3645,7 +3716,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2086] in /code/proj/Main.roc ───────────────────────────────
 
     This hex integer literal contains an invalid digit:
 
@@ -3657,7 +3728,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2086] in /code/proj/Main.roc ───────────────────────────────
 
     This octal integer literal contains an invalid digit:
 
@@ -3669,7 +3740,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2086] in /code/proj/Main.roc ───────────────────────────────
 
     This binary integer literal contains an invalid digit:
 
@@ -3699,7 +3770,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2086] in /code/proj/Main.roc ───────────────────────────────
 
     This hex integer literal contains no digits:
 
@@ -3711,7 +3782,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2086] in /code/proj/Main.roc ───────────────────────────────
 
     This octal integer literal contains no digits:
 
@@ -3723,7 +3794,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2086] in /code/proj/Main.roc ───────────────────────────────
 
     This binary integer literal contains no digits:
 
@@ -3747,7 +3818,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2085] in /code/proj/Main.roc ───────────────────────────────
 
     This float literal contains an invalid digit:
 
@@ -3778,7 +3849,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2090] in /code/proj/Main.roc ───────────────────────────────
 
     This expression cannot be updated:
 
@@ -3787,7 +3858,7 @@ mod test_reporting {
 
     Only variables can be updated with record update syntax.
 
-    ── MODULE NOT IMPORTED in /code/proj/Main.roc ──────────────────────────────────
+    ── MODULE NOT IMPORTED [E2034] in /code/proj/Main.roc ──────────────────────────
 
     The `Test` module is not imported:
 
@@ -3802,7 +3873,7 @@ mod test_reporting {
         Dict
         Hash
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2090] in /code/proj/Main.roc ───────────────────────────────
 
     This expression cannot be updated:
 
@@ -3821,7 +3892,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── MODULE NOT IMPORTED in /code/proj/Main.roc ──────────────────────────────────
+    ── MODULE NOT IMPORTED [E2034] in /code/proj/Main.roc ──────────────────────────
 
     The `Foo` module is not imported:
 
@@ -3846,7 +3917,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -3874,7 +3945,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3025] in /code/proj/Main.roc ────────────────────────────────
 
     The 1st argument to `f` is weird:
 
@@ -3903,7 +3974,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of this definition:
 
@@ -3934,7 +4005,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3025] in /code/proj/Main.roc ────────────────────────────────
 
     The 1st argument to `f` is weird:
 
@@ -3967,7 +4038,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -4000,7 +4071,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -4031,7 +4102,7 @@ mod test_reporting {
                 "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to this function has an unexpected type:
 
@@ -4065,7 +4136,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -4099,7 +4170,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -4127,7 +4198,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── BAD OPTIONAL VALUE in /code/proj/Main.roc ───────────────────────────────────
+    ── BAD OPTIONAL VALUE [E2008] in /code/proj/Main.roc ───────────────────────────
 
     This record uses an optional value for the `.y` field in an incorrect
     context!
@@ -4164,7 +4235,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [E3013] in /code/proj/Main.roc ────────────────────────────
 
     The 3rd pattern is redundant:
 
@@ -4208,7 +4279,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── UNUSED ARGUMENT in /code/proj/Main.roc ──────────────────────────────────────
+    ── UNUSED ARGUMENT [E2074] in /code/proj/Main.roc ──────────────────────────────
 
     `f` doesn't use `foo`.
 
@@ -4230,7 +4301,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2083] in /code/proj/Main.roc ───────────────────────────────
 
     I am trying to parse a qualified name here:
 
@@ -4251,7 +4322,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2083] in /code/proj/Main.roc ───────────────────────────────
 
     I am trying to parse a qualified name here:
 
@@ -4271,7 +4342,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2083] in /code/proj/Main.roc ───────────────────────────────
 
     I am trying to parse a record field access here:
 
@@ -4293,7 +4364,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── UNKNOWN OPERATOR in tmp/type_annotation_double_colon/Test.roc ───────────────
+    ── UNKNOWN OPERATOR [E1068] in tmp/type_annotation_double_colon/Test.roc ───────
 
     This looks like an operator, but it's not one I recognize!
 
@@ -4326,7 +4397,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── IGNORED RESULT in /code/proj/Main.roc ───────────────────────────────────────
+    ── IGNORED RESULT [E3005] in /code/proj/Main.roc ───────────────────────────────
 
     The result of this expression is ignored:
 
@@ -4342,7 +4413,7 @@ mod test_reporting {
 
         _ = File.delete!("data.json")?
 
-    ── LEFTOVER STATEMENT in /code/proj/Main.roc ───────────────────────────────────
+    ── LEFTOVER STATEMENT [E3010] in /code/proj/Main.roc ───────────────────────────
 
     This statement does not produce any effects:
 
@@ -4364,7 +4435,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED TAG UNION TYPE in tmp/tag_union_open/Test.roc ────────────────────
+    ── UNFINISHED TAG UNION TYPE [E1063] in tmp/tag_union_open/Test.roc ────────────
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4386,7 +4457,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED TAG UNION TYPE in tmp/tag_union_end/Test.roc ─────────────────────
+    ── UNFINISHED TAG UNION TYPE [E1063] in tmp/tag_union_end/Test.roc ─────────────
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4408,7 +4479,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── WEIRD TAG NAME in tmp/tag_union_lowercase_tag_name/Test.roc ─────────────────
+    ── WEIRD TAG NAME [E1082] in tmp/tag_union_lowercase_tag_name/Test.roc ─────────
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4429,7 +4500,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── WEIRD TAG NAME in tmp/tag_union_second_lowercase_tag_name/Test.roc ──────────
+    ── WEIRD TAG NAME [E1082] in tmp/tag_union_second_lowercase_tag_name/Test.roc ──
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4450,7 +4521,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED RECORD TYPE in tmp/record_type_open/Test.roc ─────────────────────
+    ── UNFINISHED RECORD TYPE [E1062] in tmp/record_type_open/Test.roc ─────────────
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4473,7 +4544,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED RECORD TYPE in tmp/record_type_open_indent/Test.roc ──────────────
+    ── UNFINISHED RECORD TYPE [E1062] in tmp/record_type_open_indent/Test.roc ──────
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4496,7 +4567,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED RECORD TYPE in tmp/record_type_end/Test.roc ──────────────────────
+    ── UNFINISHED RECORD TYPE [E1062] in tmp/record_type_end/Test.roc ──────────────
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4518,7 +4589,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED RECORD TYPE in tmp/record_type_keyword_field_name/Test.roc ───────
+    ── UNFINISHED RECORD TYPE [E1062] in ...ecord_type_keyword_field_name/Test.roc ─
 
     I just started parsing a record type, but I got stuck on this field
     name:
@@ -4540,7 +4611,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED RECORD TYPE in tmp/record_type_missing_comma/Test.roc ────────────
+    ── UNFINISHED RECORD TYPE [E1062] in tmp/record_type_missing_comma/Test.roc ────
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4557,7 +4628,7 @@ mod test_reporting {
         record_type_tab,
         "f : { foo \t }",
         @r###"
-    ── TAB CHARACTER in tmp/record_type_tab/Test.roc ───────────────────────────────
+    ── TAB CHARACTER [E1045] in tmp/record_type_tab/Test.roc ───────────────────────
 
     I encountered a tab character:
 
@@ -4572,7 +4643,7 @@ mod test_reporting {
         comment_with_tab,
         "# comment with a \t char\n4",
         @r###"
-    ── TAB CHARACTER in tmp/comment_with_tab/Test.roc ──────────────────────────────
+    ── TAB CHARACTER [E1045] in tmp/comment_with_tab/Test.roc ──────────────────────
 
     I encountered a tab character:
 
@@ -4587,7 +4658,7 @@ mod test_reporting {
         comment_with_control_character,
         "# comment with a \x07 char\n",
         @r###"
-    ── ASCII CONTROL CHARACTER in tmp/comment_with_control_character/Test.roc ──────
+    ── ASCII CONTROL CHARACTER [E1002] in ...mment_with_control_character/Test.roc ─
 
     I encountered an ASCII control character:
 
@@ -4602,7 +4673,7 @@ mod test_reporting {
         record_type_carriage_return,
         "f : { \r foo }",
         @r"
-    ── MISPLACED CARRIAGE RETURN in tmp/record_type_carriage_return/Test.roc ───────
+    ── MISPLACED CARRIAGE RETURN [E1027] in ...ecord_type_carriage_return/Test.roc ─
 
     I encountered a stray carriage return (\r):
 
@@ -4622,7 +4693,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/type_in_parens_start/Test.roc ─────────────────
+    ── UNFINISHED PARENTHESES [E1059] in tmp/type_in_parens_start/Test.roc ─────────
 
     I am partway through parsing a type in parentheses, but I got stuck
     here:
@@ -4645,7 +4716,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/type_in_parens_end/Test.roc ───────────────────
+    ── UNFINISHED PARENTHESES [E1059] in tmp/type_in_parens_end/Test.roc ───────────
 
     I am partway through parsing a type in parentheses, but I got stuck
     here:
@@ -4670,7 +4741,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2084] in /code/proj/Main.roc ───────────────────────────────
 
     I am confused by this type name:
 
@@ -4681,7 +4752,7 @@ mod test_reporting {
     qualified by a module name, like Bool or Http.Request.Request.
     "
     );
-    //                ── DOUBLE DOT ──────────────────────────────────────────────────────────────────
+    //                ── DOUBLE DOT [E1007] ──────────────────────────────────────────────────────────
     //
     //                I encountered two dots in a row:
     //
@@ -4700,7 +4771,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2084] in /code/proj/Main.roc ───────────────────────────────
 
     I am confused by this type name:
 
@@ -4711,7 +4782,7 @@ mod test_reporting {
     qualified by a module name, like Bool or Http.Request.Request.
     "
     );
-    //                ── TRAILING DOT ────────────────────────────────────────────────────────────────
+    //                ── TRAILING DOT [E1046] ────────────────────────────────────────────────────────
     //
     //                I encountered a dot with nothing after it:
     //
@@ -4729,7 +4800,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED TYPE in tmp/type_apply_stray_dot/Test.roc ────────────────────────
+    ── UNFINISHED TYPE [E1064] in tmp/type_apply_stray_dot/Test.roc ────────────────
 
     I just started parsing a type, but I got stuck here:
 
@@ -4750,7 +4821,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2084] in /code/proj/Main.roc ───────────────────────────────
 
     I am confused by this type name:
 
@@ -4761,7 +4832,7 @@ mod test_reporting {
     qualified by a module name, like Bool or Http.Request.Request.
     "
     );
-    //                ── WEIRD QUALIFIED NAME ────────────────────────────────────────────────────────
+    //                ── WEIRD QUALIFIED NAME [E1081] ────────────────────────────────────────────────
     //
     //                I encountered a number at the start of a qualified name segment:
     //
@@ -4781,7 +4852,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2084] in /code/proj/Main.roc ───────────────────────────────
 
     I am confused by this type name:
 
@@ -4806,7 +4877,7 @@ mod test_reporting {
     //         "
     //     ),
     //     @r#"
-    // ── MISSING FINAL EXPRESSION in tmp/def_missing_final_expression/Test.roc ───────
+    // ── MISSING FINAL EXPRESSION [E1030] in ...ef_missing_final_expression/Test.roc ─
 
     // I am partway through parsing a definition, but I got stuck here:
 
@@ -4834,7 +4905,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── INDENT ENDS AFTER EXPRESSION in tmp/expression_indentation_end/Test.roc ─────
+    ── INDENT ENDS AFTER EXPRESSION [E1018] in ...ression_indentation_end/Test.roc ─
 
     I am partway through parsing an expression, but I got stuck here:
 
@@ -4860,7 +4931,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED INLINE ALIAS in tmp/type_inline_alias/Test.roc ───────────────────
+    ── UNFINISHED INLINE ALIAS [E1056] in tmp/type_inline_alias/Test.roc ───────────
 
     I just started parsing an inline type alias, but I got stuck here:
 
@@ -4882,7 +4953,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── DOUBLE COMMA in tmp/type_double_comma/Test.roc ──────────────────────────────
+    ── DOUBLE COMMA [E1006] in tmp/type_double_comma/Test.roc ──────────────────────
 
     I just started parsing a function argument type, but I encountered two
     commas in a row:
@@ -4905,7 +4976,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED TYPE in tmp/type_argument_no_arrow/Test.roc ──────────────────────
+    ── UNFINISHED TYPE [E1064] in tmp/type_argument_no_arrow/Test.roc ──────────────
 
     I am partway through parsing a type, but I got stuck here:
 
@@ -4928,7 +4999,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED TYPE in tmp/type_argument_arrow_then_nothing/Test.roc ────────────
+    ── UNFINISHED TYPE [E1064] in tmp/type_argument_arrow_then_nothing/Test.roc ────
 
     I just started parsing a type, but I got stuck here:
 
@@ -4947,7 +5018,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── UNFINISHED IMPORT in tmp/unfinished_import/Test.roc ─────────────────────────
+    ── UNFINISHED IMPORT [E1055] in tmp/unfinished_import/Test.roc ─────────────────
 
     I was partway through parsing an `import`, but I got stuck here:
 
@@ -4975,7 +5046,7 @@ mod test_reporting {
             import Menu { x = 4 }
             "
         ),@r###"
-    ── RECORD PARSE PROBLEM in tmp/weird_import_params_record/Test.roc ─────────────
+    ── RECORD PARSE PROBLEM [E1042] in tmp/weird_import_params_record/Test.roc ─────
 
     I am partway through parsing a record, but I got stuck here:
 
@@ -4993,7 +5064,7 @@ mod test_reporting {
             import Menu { my_params & echo: echo_fn }
             "
         ),@r"
-    ── RECORD UPDATE IN MODULE PARAMS in ...ecord_update_in_module_params/Test.roc ─
+    ── RECORD UPDATE IN MODULE PARAMS [E1043] in ...date_in_module_params/Test.roc ─
 
     I was partway through parsing module params, but I got stuck here:
 
@@ -5013,7 +5084,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── UNFINISHED IMPORT in tmp/unfinished_import_as_or_exposing/Test.roc ──────────
+    ── UNFINISHED IMPORT [E1055] in tmp/unfinished_import_as_or_exposing/Test.roc ──
 
     I was partway through parsing an `import`, but I got stuck here:
 
@@ -5042,7 +5113,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── UNFINISHED IMPORT in tmp/unfinished_import_alias/Test.roc ───────────────────
+    ── UNFINISHED IMPORT [E1055] in tmp/unfinished_import_alias/Test.roc ───────────
 
     I was partway through parsing an `import`, but I got stuck here:
 
@@ -5061,7 +5132,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── LOWERCASE ALIAS in tmp/lowercase_import_alias/Test.roc ──────────────────────
+    ── LOWERCASE ALIAS [E1025] in tmp/lowercase_import_alias/Test.roc ──────────────
 
     This import is using a lowercase alias:
 
@@ -5080,7 +5151,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── UNFINISHED IMPORT in tmp/unfinished_import_exposing/Test.roc ────────────────
+    ── UNFINISHED IMPORT [E1055] in tmp/unfinished_import_exposing/Test.roc ────────
 
     I was partway through parsing an `import`, but I got stuck here:
 
@@ -5098,7 +5169,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── WEIRD EXPOSING in tmp/unfinished_import_exposing_name/Test.roc ──────────────
+    ── WEIRD EXPOSING [E1074] in tmp/unfinished_import_exposing_name/Test.roc ──────
 
     I'm partway through parsing an exposing list, but I got stuck here:
 
@@ -5118,7 +5189,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED IMPORT in tmp/unfinished_ingested_file_name/Test.roc ─────────────
+    ── UNFINISHED IMPORT [E1055] in tmp/unfinished_ingested_file_name/Test.roc ─────
 
     I was partway through parsing an `import`, but I got stuck here:
 
@@ -5139,7 +5210,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED TYPE in tmp/ingested_file_import_ann_syntax_err/Test.roc ─────────
+    ── UNFINISHED TYPE [E1064] in tmp/ingested_file_import_ann_syntax_err/Test.roc ─
 
     I am partway through parsing a type, but I got stuck here:
 
@@ -5164,7 +5235,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `my_dict` definition:
 
@@ -5199,7 +5270,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `my_dict` definition:
 
@@ -5231,7 +5302,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── IF GUARD NO CONDITION in tmp/if_guard_without_condition/Test.roc ────────────
+    ── IF GUARD NO CONDITION [E1014] in tmp/if_guard_without_condition/Test.roc ────
 
     I just started parsing an if guard, but there is no guard condition:
 
@@ -5256,7 +5327,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED PATTERN in tmp/empty_or_pattern/Test.roc ─────────────────────────
+    ── UNFINISHED PATTERN [E1060] in tmp/empty_or_pattern/Test.roc ─────────────────
 
     I just started parsing a pattern, but I got stuck here:
 
@@ -5281,7 +5352,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── MISSING ARROW in tmp/pattern_binds_keyword/Test.roc ─────────────────────────
+    ── MISSING ARROW [E1028] in tmp/pattern_binds_keyword/Test.roc ─────────────────
 
     I am partway through parsing a `when` expression, but got stuck here:
 
@@ -5317,7 +5388,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED WHEN in tmp/when_missing_arrow/Test.roc ──────────────────────────
+    ── UNFINISHED WHEN [E1065] in tmp/when_missing_arrow/Test.roc ──────────────────
 
     I was partway through parsing a `when` expression, but I got stuck here:
 
@@ -5350,7 +5421,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED ARGUMENT LIST in tmp/lambda_double_comma/Test.roc ────────────────
+    ── UNFINISHED ARGUMENT LIST [E1052] in tmp/lambda_double_comma/Test.roc ────────
 
     I am partway through parsing a function argument list, but I got stuck
     at this comma:
@@ -5371,7 +5442,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED ARGUMENT LIST in tmp/lambda_leading_comma/Test.roc ───────────────
+    ── UNFINISHED ARGUMENT LIST [E1052] in tmp/lambda_leading_comma/Test.roc ───────
 
     I am partway through parsing a function argument list, but I got stuck
     at this comma:
@@ -5387,7 +5458,7 @@ mod test_reporting {
     // this should get better with time
     // TODO this formerly gave
     //
-    //                ── UNFINISHED WHEN ─────────────────────────────────────────────────────────────
+    //                ── UNFINISHED WHEN [E1065] ─────────────────────────────────────────────────────
     //
     //                I was partway through parsing a `when` expression, but I got stuck here:
     //
@@ -5418,7 +5489,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── UNKNOWN OPERATOR in tmp/when_outdented_branch/Test.roc ──────────────────────
+    ── UNKNOWN OPERATOR [E1068] in tmp/when_outdented_branch/Test.roc ──────────────
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5448,7 +5519,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── UNEXPECTED ARROW in tmp/when_over_indented_underscore/Test.roc ──────────────
+    ── UNEXPECTED ARROW [E1049] in tmp/when_over_indented_underscore/Test.roc ──────
 
     I am parsing a `when` expression right now, but this arrow is confusing
     me:
@@ -5486,7 +5557,7 @@ mod test_reporting {
             "
         ),
         @r###"
-    ── UNEXPECTED ARROW in tmp/when_over_indented_int/Test.roc ─────────────────────
+    ── UNEXPECTED ARROW [E1049] in tmp/when_over_indented_int/Test.roc ─────────────
 
     I am parsing a `when` expression right now, but this arrow is confusing
     me:
@@ -5527,7 +5598,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED IF in tmp/if_outdented_then/Test.roc ─────────────────────────────
+    ── UNFINISHED IF [E1054] in tmp/if_outdented_then/Test.roc ─────────────────────
 
     I was partway through parsing an `if` expression, but I got stuck here:
 
@@ -5547,7 +5618,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED IF in tmp/if_missing_else/Test.roc ───────────────────────────────
+    ── UNFINISHED IF [E1054] in tmp/if_missing_else/Test.roc ───────────────────────
 
     I was partway through parsing an `if` expression, but I got stuck here:
 
@@ -5566,7 +5637,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED LIST in tmp/list_double_comma/Test.roc ───────────────────────────
+    ── UNFINISHED LIST [E1057] in tmp/list_double_comma/Test.roc ───────────────────
 
     I am partway through started parsing a list, but I got stuck here:
 
@@ -5586,7 +5657,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED LIST in tmp/list_without_end/Test.roc ────────────────────────────
+    ── UNFINISHED LIST [E1057] in tmp/list_without_end/Test.roc ────────────────────
 
     I am partway through started parsing a list, but I got stuck here:
 
@@ -5612,7 +5683,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── MISSING EXPRESSION in tmp/missing_return_expression/Test.roc ────────────────
+    ── MISSING EXPRESSION [E1029] in tmp/missing_return_expression/Test.roc ────────
 
     I am partway through parsing a return statement, but I got stuck here:
 
@@ -5632,7 +5703,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── MISSING EXPRESSION in tmp/return_as_def_name/Test.roc ───────────────────────
+    ── MISSING EXPRESSION [E1029] in tmp/return_as_def_name/Test.roc ───────────────
 
     I am partway through parsing a return statement, but I got stuck here:
 
@@ -5647,7 +5718,7 @@ mod test_reporting {
         return_space_problem,
         "return \t",
         @r###"
-    ── TAB CHARACTER in tmp/return_space_problem/Test.roc ──────────────────────────
+    ── TAB CHARACTER [E1045] in tmp/return_space_problem/Test.roc ──────────────────
 
     I encountered a tab character:
 
@@ -5666,7 +5737,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2085] in /code/proj/Main.roc ───────────────────────────────
 
     This float literal contains an invalid digit:
 
@@ -5684,7 +5755,7 @@ mod test_reporting {
         unicode_not_hex,
         r#""abc\u(zzzz)def""#,
         @r#"
-    ── WEIRD CODE POINT in tmp/unicode_not_hex/Test.roc ────────────────────────────
+    ── WEIRD CODE POINT [E1071] in tmp/unicode_not_hex/Test.roc ────────────────────
 
     I am partway through parsing a unicode code point, but I got stuck
     here:
@@ -5702,7 +5773,7 @@ mod test_reporting {
         unicode_too_large,
         r#""abc\u(110000)def""#,
         @r#"
-    ── INVALID UNICODE in /code/proj/Main.roc ──────────────────────────────────────
+    ── INVALID UNICODE [E2030] in /code/proj/Main.roc ──────────────────────────────
 
     This unicode code point is invalid:
 
@@ -5717,7 +5788,7 @@ mod test_reporting {
         weird_escape,
         r#""abc\qdef""#,
         @r#"
-    ── WEIRD ESCAPE in tmp/weird_escape/Test.roc ───────────────────────────────────
+    ── WEIRD ESCAPE [E1072] in tmp/weird_escape/Test.roc ───────────────────────────
 
     I was partway through parsing a  string literal, but I got stuck here:
 
@@ -5740,7 +5811,7 @@ mod test_reporting {
         single_quote_too_long,
         r"'abcdef'",
         @r#"
-    ── INVALID SCALAR in tmp/single_quote_too_long/Test.roc ────────────────────────
+    ── INVALID SCALAR [E1023] in tmp/single_quote_too_long/Test.roc ────────────────
 
     I am part way through parsing this scalar literal (character literal),
     but it's too long to fit in a U32 so it's not a valid scalar.
@@ -5757,7 +5828,7 @@ mod test_reporting {
         single_no_end,
         r#""there is no end"#,
         @r#"
-    ── ENDLESS STRING in tmp/single_no_end/Test.roc ────────────────────────────────
+    ── ENDLESS STRING [E1012] in tmp/single_no_end/Test.roc ────────────────────────
 
     I cannot find the end of this string:
 
@@ -5773,7 +5844,7 @@ mod test_reporting {
         multi_no_end,
         r#""""there is no end"#,
         @r#"
-    ── ENDLESS STRING in tmp/multi_no_end/Test.roc ─────────────────────────────────
+    ── ENDLESS STRING [E1012] in tmp/multi_no_end/Test.roc ─────────────────────────
 
     I cannot find the end of this block string:
 
@@ -5789,7 +5860,7 @@ mod test_reporting {
         multi_insufficient_indent,
         "    \"\"\"\n  testing\n    \"\"\"", // 4 space indent on the start, 2 space on the `testing` line
         @r#"
-    ── INSUFFICIENT INDENT IN MULTI-LINE STRING in ...insufficient_indent/Test.roc ─
+    ── INSUFFICIENT INDENT IN MULTI-LINE STRING [E1019] in ...ient_indent/Test.roc ─
 
     This multiline string is not sufficiently indented:
 
@@ -5811,7 +5882,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── INDENT ENDS AFTER EXPRESSION in ...expect_without_final_expression/Test.roc ─
+    ── INDENT ENDS AFTER EXPRESSION [E1018] in ...ithout_final_expression/Test.roc ─
 
     I am partway through parsing an expect statement, but I got stuck
     here:
@@ -5836,7 +5907,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── UNHANDLED PARSE ERROR in tmp/unhandled_parse_error/Test.roc ─────────────────
+    ── UNHANDLED PARSE ERROR [E1066] in tmp/unhandled_parse_error/Test.roc ─────────
 
     I got stuck while parsing this:
 
@@ -5867,7 +5938,7 @@ mod test_reporting {
             "#,
             ),
             @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -5893,7 +5964,7 @@ mod test_reporting {
                 $name,
                 &format!(r#"if Bool.true then "abc" else 1 {} 2"#, $op),
                 |golden| assert_eq!(golden, format!(
-r#"── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+r#"── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
 This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -5936,7 +6007,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3029] in /code/proj/Main.roc ────────────────────────────────
 
     This `foo` record doesn’t have a `if` field:
 
@@ -5955,7 +6026,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── NOT EXPOSED in /code/proj/Main.roc ──────────────────────────────────────────
+    ── NOT EXPOSED [E2043] in /code/proj/Main.roc ──────────────────────────────────
 
     The Num module does not expose `if`:
 
@@ -5979,7 +6050,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2083] in /code/proj/Main.roc ───────────────────────────────
 
     I am trying to parse a record field access here:
 
@@ -5998,7 +6069,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2083] in /code/proj/Main.roc ───────────────────────────────
 
     I am very confused by this field access:
 
@@ -6017,7 +6088,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2083] in /code/proj/Main.roc ───────────────────────────────
 
     I am very confused by this field access
 
@@ -6053,7 +6124,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r#"
-    ── UNKNOWN OPERATOR in tmp/case_of/Test.roc ────────────────────────────────────
+    ── UNKNOWN OPERATOR [E1068] in tmp/case_of/Test.roc ────────────────────────────
 
     This looks like an operator, but it's not one I recognize!
 
@@ -6086,7 +6157,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [E2073] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `bar` in this scope.
 
@@ -6111,7 +6182,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r#"
-    ── UNKNOWN OPERATOR in tmp/invalid_operator/Test.roc ───────────────────────────
+    ── UNKNOWN OPERATOR [E1068] in tmp/invalid_operator/Test.roc ───────────────────
 
     This looks like an operator, but it's not one I recognize!
 
@@ -6137,7 +6208,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r#"
-    ── UNKNOWN OPERATOR in tmp/double_plus/Test.roc ────────────────────────────────
+    ── UNKNOWN OPERATOR [E1068] in tmp/double_plus/Test.roc ────────────────────────
 
     This looks like an operator, but it's not one I recognize!
 
@@ -6164,7 +6235,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r#"
-    ── UNKNOWN OPERATOR in tmp/inline_hastype/Test.roc ─────────────────────────────
+    ── UNKNOWN OPERATOR [E1068] in tmp/inline_hastype/Test.roc ─────────────────────
 
     This looks like an operator, but it's not one I recognize!
 
@@ -6193,7 +6264,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── SYNTAX PROBLEM in tmp/wild_case_arrow/Test.roc ──────────────────────────────
+    ── SYNTAX PROBLEM [E1083] in tmp/wild_case_arrow/Test.roc ──────────────────────
 
     I got stuck here:
 
@@ -6222,7 +6293,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r"
-                ── WEIRD PROVIDES in /code/proj/Main.roc ───────────────────────────────────────
+                ── WEIRD PROVIDES [E1080] in /code/proj/Main.roc ───────────────────────────────
 
                 I am partway through parsing a provides list, but I got stuck here:
 
@@ -6257,7 +6328,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r"
-                ── WEIRD PROVIDES in /code/proj/Main.roc ───────────────────────────────────────
+                ── WEIRD PROVIDES [E1080] in /code/proj/Main.roc ───────────────────────────────
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -6283,7 +6354,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD PROVIDES in /code/proj/Main.roc ───────────────────────────────────────
+                ── WEIRD PROVIDES [E1080] in /code/proj/Main.roc ───────────────────────────────
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -6310,7 +6381,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD PROVIDES in /code/proj/Main.roc ───────────────────────────────────────
+                ── WEIRD PROVIDES [E1080] in /code/proj/Main.roc ───────────────────────────────
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -6336,7 +6407,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD MODULE PARAMS in /code/proj/Main.roc ──────────────────────────────────
+                ── WEIRD MODULE PARAMS [E1078] in /code/proj/Main.roc ──────────────────────────
 
                 I am partway through parsing a module header, but I got stuck here:
 
@@ -6373,7 +6444,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── BAD REQUIRES in /code/proj/Main.roc ─────────────────────────────────────────
+                ── BAD REQUIRES [E1003] in /code/proj/Main.roc ─────────────────────────────────
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -6401,7 +6472,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r"
-                ── WEIRD IMPORTS in /code/proj/Main.roc ────────────────────────────────────────
+                ── WEIRD IMPORTS [E1076] in /code/proj/Main.roc ────────────────────────────────
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -6426,7 +6497,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r"
-                ── WEIRD EXPOSES in /code/proj/Main.roc ────────────────────────────────────────
+                ── WEIRD EXPOSES [E1073] in /code/proj/Main.roc ────────────────────────────────
 
                 I am partway through parsing an `exposes` list, but I got stuck here:
 
@@ -6451,7 +6522,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD EXPOSES in /code/proj/Main.roc ────────────────────────────────────────
+                ── WEIRD EXPOSES [E1073] in /code/proj/Main.roc ────────────────────────────────
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -6476,7 +6547,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD EXPOSES in /code/proj/Main.roc ────────────────────────────────────────
+                ── WEIRD EXPOSES [E1073] in /code/proj/Main.roc ────────────────────────────────
 
                 I am partway through parsing an `exposes` list, but I got stuck here:
 
@@ -6501,7 +6572,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD EXPOSES in /code/proj/Main.roc ────────────────────────────────────────
+                ── WEIRD EXPOSES [E1073] in /code/proj/Main.roc ────────────────────────────────
 
                 I am partway through parsing an `exposes` list, but I got stuck here:
 
@@ -6529,7 +6600,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD APP NAME in /code/proj/Main.roc ───────────────────────────────────────
+                ── WEIRD APP NAME [E1069] in /code/proj/Main.roc ───────────────────────────────
 
                 I am partway through parsing a header, but got stuck here:
 
@@ -6553,7 +6624,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [E3015] in /code/proj/Main.roc ────────────────────────────────
 
     This value is not a function, but it was given 2 arguments:
 
@@ -6574,7 +6645,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [E3015] in /code/proj/Main.roc ────────────────────────────────
 
     This value is not a function, but it was given 2 arguments:
 
@@ -6596,7 +6667,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -6622,7 +6693,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/pattern_in_parens_open/Test.roc ───────────────
+    ── UNFINISHED PARENTHESES [E1059] in tmp/pattern_in_parens_open/Test.roc ───────
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6645,7 +6716,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/pattern_in_parens_end_comma/Test.roc ──────────
+    ── UNFINISHED PARENTHESES [E1059] in tmp/pattern_in_parens_end_comma/Test.roc ──
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6668,7 +6739,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/pattern_in_parens_end/Test.roc ────────────────
+    ── UNFINISHED PARENTHESES [E1059] in tmp/pattern_in_parens_end/Test.roc ────────
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6692,7 +6763,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── MISSING ARROW in tmp/unfinished_closure_pattern_in_parens/Test.roc ──────────
+    ── MISSING ARROW [E1028] in tmp/unfinished_closure_pattern_in_parens/Test.roc ──
 
     I am partway through parsing a function argument list, but I got stuck
     here:
@@ -6715,7 +6786,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/pattern_in_parens_indent_open/Test.roc ────────
+    ── UNFINISHED PARENTHESES [E1059] in ...pattern_in_parens_indent_open/Test.roc ─
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6740,7 +6811,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This `expect` condition needs to be a Bool:
 
@@ -6767,7 +6838,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to * has an unexpected type:
 
@@ -6782,7 +6853,7 @@ All branches in an `if` must have the same type!
 
         Num *
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `mult` definition:
 
@@ -6811,7 +6882,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to * has an unexpected type:
 
@@ -6826,7 +6897,7 @@ All branches in an `if` must have the same type!
 
         Num a
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `mult` definition:
 
@@ -6861,7 +6932,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [E2017] in /code/proj/Main.roc ───────────────────────────────
 
     This alias has the same name as a builtin:
 
@@ -6871,7 +6942,7 @@ All branches in an `if` must have the same type!
     All builtin aliases are in scope by default, so I need this alias to
     have a different name!
 
-    ── TOO FEW TYPE ARGUMENTS in /code/proj/Main.roc ───────────────────────────────
+    ── TOO FEW TYPE ARGUMENTS [E2058] in /code/proj/Main.roc ───────────────────────
 
     The `Result` alias expects 2 type arguments, but it got 1 instead:
 
@@ -6899,7 +6970,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [E2017] in /code/proj/Main.roc ───────────────────────────────
 
     This alias has the same name as a builtin:
 
@@ -6909,7 +6980,7 @@ All branches in an `if` must have the same type!
     All builtin aliases are in scope by default, so I need this alias to
     have a different name!
 
-    ── TOO MANY TYPE ARGUMENTS in /code/proj/Main.roc ──────────────────────────────
+    ── TOO MANY TYPE ARGUMENTS [E2059] in /code/proj/Main.roc ──────────────────────
 
     The `Result` alias expects 2 type arguments, but it got 3 instead:
 
@@ -6931,7 +7002,7 @@ All branches in an `if` must have the same type!
         ),
         // TODO: We should tell the user that we inferred `_` as `a`
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -6965,7 +7036,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -6997,7 +7068,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -7037,7 +7108,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `inner` definition:
 
@@ -7066,7 +7137,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── NOT AN INLINE ALIAS in tmp/error_inline_alias_not_an_alias/Test.roc ─────────
+    ── NOT AN INLINE ALIAS [E1035] in tmp/error_inline_alias_not_an_alias/Test.roc ─
 
     The inline type after this `as` is not a type alias:
 
@@ -7086,7 +7157,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── QUALIFIED ALIAS NAME in tmp/error_inline_alias_qualified/Test.roc ───────────
+    ── QUALIFIED ALIAS NAME [E1040] in tmp/error_inline_alias_qualified/Test.roc ───
 
     This type alias has a qualified name:
 
@@ -7106,7 +7177,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE ARGUMENT NOT LOWERCASE in ..._inline_alias_argument_uppercase/Test.roc ─
+    ── TYPE ARGUMENT NOT LOWERCASE [E1048] in ...alias_argument_uppercase/Test.roc ─
 
     This alias type argument is not lowercase:
 
@@ -7130,7 +7201,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `is_empty` has an unexpected type:
 
@@ -7166,7 +7237,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `c` has an unexpected type:
 
@@ -7199,7 +7270,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [E3002] in /code/proj/Main.roc ─────────────────────────────────
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -7222,7 +7293,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [E3002] in /code/proj/Main.roc ─────────────────────────────────
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -7244,7 +7315,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [E3002] in /code/proj/Main.roc ─────────────────────────────────
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -7269,7 +7340,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3025] in /code/proj/Main.roc ────────────────────────────────
 
     The 1st argument to `job` is weird:
 
@@ -7304,7 +7375,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `job` definition:
 
@@ -7335,7 +7406,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── NESTED DATATYPE in /code/proj/Main.roc ──────────────────────────────────────
+    ── NESTED DATATYPE [E2038] in /code/proj/Main.roc ──────────────────────────────
 
     `Nested` is a nested datatype. Here is one recursive usage of it:
 
@@ -7363,7 +7434,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── NESTED DATATYPE in /code/proj/Main.roc ──────────────────────────────────────
+    ── NESTED DATATYPE [E2038] in /code/proj/Main.roc ──────────────────────────────
 
     `Nested` is a nested datatype. Here is one recursive usage of it:
 
@@ -7411,7 +7482,7 @@ All branches in an `if` must have the same type!
 
                     let real = format!(indoc!(
                         r"
-                        ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+                        ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
                         This 1st argument to `use` has an unexpected type:
 
@@ -7477,7 +7548,7 @@ All branches in an `if` must have the same type!
 
                     let real = format!(indoc!(
                         r"
-                        ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+                        ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
                         The branches of this `when` expression don't match the condition:
 
@@ -7528,7 +7599,7 @@ All branches in an `if` must have the same type!
         ),
         // TODO: link to number suffixes
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2086] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal contains an invalid digit:
 
@@ -7551,7 +7622,7 @@ All branches in an `if` must have the same type!
         ),
         // TODO: link to number suffixes
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2086] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal contains an invalid digit:
 
@@ -7573,7 +7644,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── CONFLICTING NUMBER SUFFIX in /code/proj/Main.roc ────────────────────────────
+    ── CONFLICTING NUMBER SUFFIX [E2010] in /code/proj/Main.roc ────────────────────
 
     This number literal is an integer, but it has a float suffix:
 
@@ -7590,7 +7661,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── CONFLICTING NUMBER SUFFIX in /code/proj/Main.roc ────────────────────────────
+    ── CONFLICTING NUMBER SUFFIX [E2010] in /code/proj/Main.roc ────────────────────
 
     This number literal is a float, but it has an integer suffix:
 
@@ -7603,7 +7674,7 @@ All branches in an `if` must have the same type!
         u8_overflow,
         "256u8",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [E2044] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7619,7 +7690,7 @@ All branches in an `if` must have the same type!
         negative_u8,
         "-1u8",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [E2045] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7635,7 +7706,7 @@ All branches in an `if` must have the same type!
         u16_overflow,
         "65536u16",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [E2044] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7651,7 +7722,7 @@ All branches in an `if` must have the same type!
         negative_u16,
         "-1u16",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [E2045] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7667,7 +7738,7 @@ All branches in an `if` must have the same type!
         u32_overflow,
         "4_294_967_296u32",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [E2044] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7683,7 +7754,7 @@ All branches in an `if` must have the same type!
         negative_u32,
         "-1u32",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [E2045] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7699,7 +7770,7 @@ All branches in an `if` must have the same type!
         u64_overflow,
         "18_446_744_073_709_551_616u64",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [E2044] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7715,7 +7786,7 @@ All branches in an `if` must have the same type!
         negative_u64,
         "-1u64",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [E2045] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7731,7 +7802,7 @@ All branches in an `if` must have the same type!
         negative_u128,
         "-1u128",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [E2045] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7747,7 +7818,7 @@ All branches in an `if` must have the same type!
         i8_overflow,
         "128i8",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [E2044] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7763,7 +7834,7 @@ All branches in an `if` must have the same type!
         i8_underflow,
         "-129i8",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [E2045] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7779,7 +7850,7 @@ All branches in an `if` must have the same type!
         i16_overflow,
         "32768i16",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [E2044] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7795,7 +7866,7 @@ All branches in an `if` must have the same type!
         i16_underflow,
         "-32769i16",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [E2045] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7811,7 +7882,7 @@ All branches in an `if` must have the same type!
         i32_overflow,
         "2_147_483_648i32",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [E2044] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7827,7 +7898,7 @@ All branches in an `if` must have the same type!
         i32_underflow,
         "-2_147_483_649i32",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [E2045] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7843,7 +7914,7 @@ All branches in an `if` must have the same type!
         i64_overflow,
         "9_223_372_036_854_775_808i64",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [E2044] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7859,7 +7930,7 @@ All branches in an `if` must have the same type!
         i64_underflow,
         "-9_223_372_036_854_775_809i64",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [E2045] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7875,7 +7946,7 @@ All branches in an `if` must have the same type!
         i128_overflow,
         "170_141_183_460_469_231_731_687_303_715_884_105_728i128",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [E2044] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7897,7 +7968,7 @@ All branches in an `if` must have the same type!
         // TODO: this error message could be improved, e.g. something like "This argument can
         // be used as ... because of its literal value"
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7923,7 +7994,7 @@ All branches in an `if` must have the same type!
              "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7950,7 +8021,7 @@ All branches in an `if` must have the same type!
              "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7977,7 +8048,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -8008,7 +8079,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [E3002] in /code/proj/Main.roc ─────────────────────────────────
 
     The `R` alias is self-recursive in an invalid way:
 
@@ -8031,7 +8102,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [E3002] in /code/proj/Main.roc ─────────────────────────────────
 
     The `R` alias is self-recursive in an invalid way:
 
@@ -8055,7 +8126,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [E3002] in /code/proj/Main.roc ─────────────────────────────────
 
     The `Foo` alias is recursive in an invalid way:
 
@@ -8090,7 +8161,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [E2017] in /code/proj/Main.roc ───────────────────────────────
 
     This alias has the same name as a builtin:
 
@@ -8110,7 +8181,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── OPAQUE TYPE NOT DEFINED in /code/proj/Main.roc ──────────────────────────────
+    ── OPAQUE TYPE NOT DEFINED [E2049] in /code/proj/Main.roc ──────────────────────
 
     The opaque type Age referenced here is not defined:
 
@@ -8131,7 +8202,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── OPAQUE TYPE NOT DEFINED in /code/proj/Main.roc ──────────────────────────────
+    ── OPAQUE TYPE NOT DEFINED [E2049] in /code/proj/Main.roc ──────────────────────
 
     The opaque type Age referenced here is not defined:
 
@@ -8145,7 +8216,7 @@ All branches in an `if` must have the same type!
 
     Note: It looks like there are no opaque types declared in this scope yet!
 
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
     `Age` is not used anywhere in your code.
 
@@ -8168,7 +8239,7 @@ All branches in an `if` must have the same type!
         // and checking it during can. The reason the error appears is because it is parsed as
         // Apply(Error(OtherModule), [@Age, 21])
         @r"
-    ── OPAQUE TYPE NOT DEFINED in /code/proj/Main.roc ──────────────────────────────
+    ── OPAQUE TYPE NOT DEFINED [E2049] in /code/proj/Main.roc ──────────────────────
 
     The opaque type Age referenced here is not defined:
 
@@ -8177,7 +8248,7 @@ All branches in an `if` must have the same type!
 
     Note: It looks like there are no opaque types declared in this scope yet!
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [E2083] in /code/proj/Main.roc ───────────────────────────────
 
     I am trying to parse a qualified name here:
 
@@ -8204,7 +8275,7 @@ All branches in an `if` must have the same type!
         // `@Age` can be linked to the declaration of `Age` inside `age`, and a suggestion to
         // raise that declaration to the outer scope.
         @r"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
     `Age` is not used anywhere in your code.
 
@@ -8214,7 +8285,7 @@ All branches in an `if` must have the same type!
     If you didn't intend on using `Age` then remove it so future readers of
     your code don't wonder why it is there.
 
-    ── OPAQUE TYPE NOT DEFINED in /code/proj/Main.roc ──────────────────────────────
+    ── OPAQUE TYPE NOT DEFINED [E2049] in /code/proj/Main.roc ──────────────────────
 
     The opaque type Age referenced here is not defined:
 
@@ -8235,7 +8306,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── MODULE NOT IMPORTED in /code/proj/Main.roc ──────────────────────────────────
+    ── MODULE NOT IMPORTED [E2034] in /code/proj/Main.roc ──────────────────────────
 
     The `Unimported` module is not imported:
 
@@ -8267,7 +8338,7 @@ All branches in an `if` must have the same type!
         // TODO(opaques): error could be improved by saying that the opaque definition demands
         // that the argument be a U8, and linking to the definition!
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -8296,7 +8367,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -8326,7 +8397,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `v` definition:
 
@@ -8362,7 +8433,7 @@ All branches in an `if` must have the same type!
         // TODO(opaques): error could be improved by saying that the user-provided pattern
         // probably wants to change "Age" to "@Age"!
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3025] in /code/proj/Main.roc ────────────────────────────────
 
     The 1st argument to `f` is weird:
 
@@ -8395,7 +8466,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3028] in /code/proj/Main.roc ────────────────────────────────
 
     The 2nd pattern in this `when` does not match the previous ones:
 
@@ -8426,7 +8497,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -8464,7 +8535,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -8497,7 +8568,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `y` has an unexpected type:
 
@@ -8530,7 +8601,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -8554,7 +8625,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── INVALID_EXTENSION_TYPE in /code/proj/Main.roc ───────────────────────────────
+    ── INVALID_EXTENSION_TYPE [E2031] in /code/proj/Main.roc ───────────────────────
 
     This record extension type is invalid:
 
@@ -8575,7 +8646,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── INVALID_EXTENSION_TYPE in /code/proj/Main.roc ───────────────────────────────
+    ── INVALID_EXTENSION_TYPE [E2031] in /code/proj/Main.roc ───────────────────────
 
     This tag union extension type is invalid:
 
@@ -8602,7 +8673,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [E2073] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `UnknownType` in this scope.
 
@@ -8616,7 +8687,7 @@ All branches in an `if` must have the same type!
         Unsigned16
         Unsigned64
 
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [E2073] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `UnknownType` in this scope.
 
@@ -8643,7 +8714,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── UNFINISHED ABILITY in tmp/ability_first_demand_not_indented_enough/Test.roc ─
+    ── UNFINISHED ABILITY [E1051] in ..._first_demand_not_indented_enough/Test.roc ─
 
     I was partway through parsing an ability definition, but I got stuck
     here:
@@ -8668,7 +8739,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-        ── UNFINISHED ABILITY in tmp/ability_demands_not_indented_with_first/Test.roc ──
+        ── UNFINISHED ABILITY [E1051] in ...y_demands_not_indented_with_first/Test.roc ─
 
         I was partway through parsing an ability definition, but I got stuck
         here:
@@ -8691,7 +8762,7 @@ All branches in an `if` must have the same type!
                 "
         ),
         @r"
-        ── UNFINISHED ABILITY in tmp/ability_demand_value_has_args/Test.roc ────────────
+        ── UNFINISHED ABILITY [E1051] in tmp/ability_demand_value_has_args/Test.roc ────
 
         I was partway through parsing an ability definition, but I got stuck
         here:
@@ -8715,7 +8786,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── UNFINISHED ABILITY in tmp/ability_non_signature_expression/Test.roc ─────────
+    ── UNFINISHED ABILITY [E1051] in tmp/ability_non_signature_expression/Test.roc ─
 
     I was partway through parsing an ability definition, but I got stuck
     here:
@@ -8738,7 +8809,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── WILDCARD NOT ALLOWED HERE in /code/proj/Main.roc ────────────────────────────
+    ── WILDCARD NOT ALLOWED HERE [E2078] in /code/proj/Main.roc ────────────────────
 
     The definition of `I` includes a wildcard (`*`) type variable:
 
@@ -8760,7 +8831,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── UNDERSCORE NOT ALLOWED HERE in /code/proj/Main.roc ──────────────────────────
+    ── UNDERSCORE NOT ALLOWED HERE [E2066] in /code/proj/Main.roc ──────────────────
 
     The definition of `I` includes an inferred (`_`) type:
 
@@ -8781,7 +8852,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── WILDCARD NOT ALLOWED HERE in /code/proj/Main.roc ────────────────────────────
+    ── WILDCARD NOT ALLOWED HERE [E2078] in /code/proj/Main.roc ────────────────────
 
     The definition of `I` includes a wildcard (`*`) type variable:
 
@@ -8803,7 +8874,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── WILDCARD NOT ALLOWED HERE in /code/proj/Main.roc ────────────────────────────
+    ── WILDCARD NOT ALLOWED HERE [E2078] in /code/proj/Main.roc ────────────────────
 
     The definition of `I` includes 2 wildcard (`*`) type variables. Here is
     one of them:
@@ -8826,7 +8897,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── UNDERSCORE NOT ALLOWED HERE in /code/proj/Main.roc ──────────────────────────
+    ── UNDERSCORE NOT ALLOWED HERE [E2066] in /code/proj/Main.roc ──────────────────
 
     The definition of `I` includes an inferred (`_`) type:
 
@@ -8847,7 +8918,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── UNDECLARED TYPE VARIABLE in /code/proj/Main.roc ─────────────────────────────
+    ── UNDECLARED TYPE VARIABLE [E2064] in /code/proj/Main.roc ─────────────────────
 
     The definition of `I` includes an undeclared type variable:
 
@@ -8872,7 +8943,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── ABILITY HAS TYPE VARIABLES in /code/proj/Main.roc ───────────────────────────
+    ── ABILITY HAS TYPE VARIABLES [E2001] in /code/proj/Main.roc ───────────────────
 
     The definition of the `MHash` ability includes type variables:
 
@@ -8882,7 +8953,7 @@ All branches in an `if` must have the same type!
     Abilities cannot depend on type variables, but their member values
     can!
 
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
     `MHash` is not used anywhere in your code.
 
@@ -8904,7 +8975,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── IMPLEMENTS CLAUSE IS NOT AN ABILITY in /code/proj/Main.roc ──────────────────
+    ── IMPLEMENTS CLAUSE IS NOT AN ABILITY [E2025] in /code/proj/Main.roc ──────────
 
     The type referenced in this "implements" clause is not an ability:
 
@@ -8923,7 +8994,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-        ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+        ── DUPLICATE NAME [E2017] in /code/proj/Main.roc ───────────────────────────────
 
         The `a` name is first defined here:
 
@@ -8952,7 +9023,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-        ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+        ── DUPLICATE NAME [E2017] in /code/proj/Main.roc ───────────────────────────────
 
         The `Ability` name is first defined here:
 
@@ -8979,7 +9050,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-        ── ABILITY MEMBER MISSING IMPLEMENTS CLAUSE in /code/proj/Main.roc ─────────────
+        ── ABILITY MEMBER MISSING IMPLEMENTS CLAUSE [E2004] in /code/proj/Main.roc ─────
 
         The definition of the ability member `ab` does not include an `implements`
         clause binding a type variable to the ability `Ability`:
@@ -8994,7 +9065,7 @@ All branches in an `if` must have the same type!
 
         Otherwise, the function does not need to be part of the ability!
 
-        ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+        ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
         `Ability` is not used anywhere in your code.
 
@@ -9016,7 +9087,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-        ── ABILITY MEMBER BINDS MULTIPLE VARIABLES in /code/proj/Main.roc ──────────────
+        ── ABILITY MEMBER BINDS MULTIPLE VARIABLES [E2003] in /code/proj/Main.roc ──────
 
         The definition of the ability member `eq` includes multiple variables
         bound to the `MEq`` ability:`
@@ -9044,7 +9115,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── ILLEGAL IMPLEMENTS CLAUSE in /code/proj/Main.roc ────────────────────────────
+    ── ILLEGAL IMPLEMENTS CLAUSE [E2023] in /code/proj/Main.roc ────────────────────
 
     An `implements` clause is not allowed here:
 
@@ -9054,7 +9125,7 @@ All branches in an `if` must have the same type!
     `implements` clauses can only be specified on the top-level type
     annotations.
 
-    ── ABILITY MEMBER MISSING IMPLEMENTS CLAUSE in /code/proj/Main.roc ─────────────
+    ── ABILITY MEMBER MISSING IMPLEMENTS CLAUSE [E2004] in /code/proj/Main.roc ─────
 
     The definition of the ability member `hash` does not include an
     `implements` clause binding a type variable to the ability `MHash`:
@@ -9085,7 +9156,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-        ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+        ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
         Something is off with this specialization of `hash`:
 
@@ -9118,7 +9189,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -9144,7 +9215,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
     `hash` is not used anywhere in your code.
 
@@ -9173,7 +9244,7 @@ All branches in an `if` must have the same type!
         ),
         // TODO: the error message here could be seriously improved!
         @r"
-    ── OVERLOADED SPECIALIZATION in /code/proj/Main.roc ────────────────────────────
+    ── OVERLOADED SPECIALIZATION [E2054] in /code/proj/Main.roc ────────────────────
 
     This ability member specialization is already claimed to specialize
     another opaque type:
@@ -9186,7 +9257,7 @@ All branches in an `if` must have the same type!
     Ability specializations can only provide implementations for one
     opaque type, since all opaque types are different!
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This specialization of `hash` is overly general:
 
@@ -9226,7 +9297,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── OVERLOADED SPECIALIZATION in /code/proj/Main.roc ────────────────────────────
+    ── OVERLOADED SPECIALIZATION [E2054] in /code/proj/Main.roc ────────────────────
 
     This ability member specialization is already claimed to specialize
     another opaque type:
@@ -9257,7 +9328,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with this specialization of `eq`:
 
@@ -9292,7 +9363,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `hash` definition:
 
@@ -9333,7 +9404,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -9346,7 +9417,7 @@ All branches in an `if` must have the same type!
 
     Only builtin abilities can have generated implementations!
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -9371,7 +9442,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-        ── ABILITY NOT ON TOP-LEVEL in /code/proj/Main.roc ─────────────────────────────
+        ── ABILITY NOT ON TOP-LEVEL [E2005] in /code/proj/Main.roc ─────────────────────
 
         This ability definition is not on the top-level of a module:
 
@@ -9399,7 +9470,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `hashable` definition:
 
@@ -9445,7 +9516,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── ABILITY USED AS TYPE in /code/proj/Main.roc ─────────────────────────────────
+    ── ABILITY USED AS TYPE [E2006] in /code/proj/Main.roc ─────────────────────────
 
     You are attempting to use the ability `MHash` as a type directly:
 
@@ -9459,7 +9530,7 @@ All branches in an `if` must have the same type!
 
         a implements MHash
 
-    ── ABILITY USED AS TYPE in /code/proj/Main.roc ─────────────────────────────────
+    ── ABILITY USED AS TYPE [E2006] in /code/proj/Main.roc ─────────────────────────
 
     You are attempting to use the ability `MHash` as a type directly:
 
@@ -9489,7 +9560,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -9542,7 +9613,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── WEIRD IMPORTS in tmp/imports_missing_comma/Test.roc ─────────────────────────
+        ── WEIRD IMPORTS [E1076] in tmp/imports_missing_comma/Test.roc ─────────────────
 
         I am partway through parsing a imports list, but I got stuck here:
 
@@ -9567,7 +9638,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+        ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
         This `when` does not cover all the possibilities:
 
@@ -9614,7 +9685,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-        ── SPECIALIZATION NOT ON TOP-LEVEL in /code/proj/Main.roc ──────────────────────
+        ── SPECIALIZATION NOT ON TOP-LEVEL [E2057] in /code/proj/Main.roc ──────────────
 
         This specialization of the `default` ability member is in a nested
         scope:
@@ -9639,7 +9710,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to == has an unexpected type:
 
@@ -9675,7 +9746,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `remove` has an unexpected type:
 
@@ -9696,7 +9767,7 @@ All branches in an `if` must have the same type!
     change the type annotation to be more specific? Maybe change the code
     to be more general?
 
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `new`:
 
@@ -9709,7 +9780,7 @@ All branches in an `if` must have the same type!
 
         { set : Set ∞ }
 
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [E3001] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `goal`:
 
@@ -9739,7 +9810,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-        ── CIRCULAR DEFINITION in /code/proj/Main.roc ──────────────────────────────────
+        ── CIRCULAR DEFINITION [E2009] in /code/proj/Main.roc ──────────────────────────
 
         The `t1` definition is causing a very tricky infinite loop:
 
@@ -9767,7 +9838,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -9795,7 +9866,7 @@ All branches in an `if` must have the same type!
         // TODO: this error message is quite unfortunate. We should remove the duplication, and
         // also support regions that point to things in other modules. See also https://github.com/roc-lang/roc/issues/3056.
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -9832,7 +9903,7 @@ All branches in an `if` must have the same type!
                 "#
         ),
         @r"
-            ── CIRCULAR DEFINITION in /code/proj/Main.roc ──────────────────────────────────
+            ── CIRCULAR DEFINITION [E2009] in /code/proj/Main.roc ──────────────────────────
 
             The `t1` definition is causing a very tricky infinite loop:
 
@@ -9862,7 +9933,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── IMPLEMENTATION NOT FOUND in /code/proj/Main.roc ─────────────────────────────
+    ── IMPLEMENTATION NOT FOUND [E2024] in /code/proj/Main.roc ─────────────────────
 
     An implementation of `eq` could not be found in this scope:
 
@@ -9873,7 +9944,7 @@ All branches in an `if` must have the same type!
     another variable that implements this ability member, like
     { eq: myeq }
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -9900,7 +9971,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [E2073] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `a_m_eq` in this scope.
 
@@ -9914,7 +9985,7 @@ All branches in an `if` must have the same type!
         Eq
         Num
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -9941,7 +10012,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── OPTIONAL ABILITY IMPLEMENTATION in /code/proj/Main.roc ──────────────────────
+    ── OPTIONAL ABILITY IMPLEMENTATION [E2050] in /code/proj/Main.roc ──────────────
 
     Ability implementations cannot be optional:
 
@@ -9952,7 +10023,7 @@ All branches in an `if` must have the same type!
 
 
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -9979,7 +10050,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── OPTIONAL ABILITY IMPLEMENTATION in /code/proj/Main.roc ──────────────────────
+    ── OPTIONAL ABILITY IMPLEMENTATION [E2050] in /code/proj/Main.roc ──────────────
 
     Ability implementations cannot be optional:
 
@@ -9992,7 +10063,7 @@ All branches in an `if` must have the same type!
     record of implementations. For example,    implements [Encoding] will
     attempt to derive `Encoding`
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `Encoding` ability:
 
@@ -10017,7 +10088,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── QUALIFIED ABILITY IMPLEMENTATION in /code/proj/Main.roc ─────────────────────
+    ── QUALIFIED ABILITY IMPLEMENTATION [E2055] in /code/proj/Main.roc ─────────────
 
     This ability implementation is qualified:
 
@@ -10027,7 +10098,7 @@ All branches in an `if` must have the same type!
     Custom implementations must be defined in the local scope, and
     unqualified.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -10052,7 +10123,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── ABILITY IMPLEMENTATION NOT IDENTIFIER in /code/proj/Main.roc ────────────────
+    ── ABILITY IMPLEMENTATION NOT IDENTIFIER [E2002] in /code/proj/Main.roc ────────
 
     This ability implementation is not an identifier:
 
@@ -10064,7 +10135,7 @@ All branches in an `if` must have the same type!
 
     Tip: consider defining this expression as a variable.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -10091,7 +10162,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── DUPLICATE IMPLEMENTATION in /code/proj/Main.roc ─────────────────────────────
+    ── DUPLICATE IMPLEMENTATION [E2016] in /code/proj/Main.roc ─────────────────────
 
     This ability member implementation is duplicate:
 
@@ -10119,7 +10190,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── NOT AN ABILITY in /code/proj/Main.roc ───────────────────────────────────────
+    ── NOT AN ABILITY [E2040] in /code/proj/Main.roc ───────────────────────────────
 
     This identifier is not an ability in scope:
 
@@ -10142,7 +10213,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── ILLEGAL DERIVE in /code/proj/Main.roc ───────────────────────────────────────
+    ── ILLEGAL DERIVE [E2022] in /code/proj/Main.roc ───────────────────────────────
 
     This ability cannot be derived:
 
@@ -10165,7 +10236,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Encoding` ability for `A`:
 
@@ -10190,7 +10261,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Encoding` ability for `A`:
 
@@ -10242,7 +10313,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [E2017] in /code/proj/Main.roc ───────────────────────────────
 
     The `main` name is first defined here:
 
@@ -10257,7 +10328,7 @@ All branches in an `if` must have the same type!
     Since these variables have the same name, it's easy to use the wrong
     one by accident. Give one of them a new name.
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [E2067] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10291,7 +10362,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-        ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+        ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
         Something is off with the body of the `with_open` definition:
 
@@ -10327,7 +10398,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3027] in /code/proj/Main.roc ────────────────────────────────
 
     This returns something that's incompatible with the return type of the
     enclosing function:
@@ -10375,7 +10446,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `foo` has an unexpected type:
 
@@ -10420,7 +10491,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to == has an unexpected type:
 
@@ -10445,7 +10516,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to == has an unexpected type:
 
@@ -10482,7 +10553,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the 2nd branch of this `when` expression:
 
@@ -10518,7 +10589,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `map` has an unexpected type:
 
@@ -10545,7 +10616,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── NAME NOT BOUND IN ALL PATTERNS in /code/proj/Main.roc ───────────────────────
+        ── NAME NOT BOUND IN ALL PATTERNS [E2036] in /code/proj/Main.roc ───────────────
 
         `x` is not bound in all patterns of this `when` branch
 
@@ -10556,7 +10627,7 @@ All branches in an `if` must have the same type!
         of the branch. Otherwise, the program would crash when it tries to use
         an identifier that wasn't bound!
 
-        ── NAME NOT BOUND IN ALL PATTERNS in /code/proj/Main.roc ───────────────────────
+        ── NAME NOT BOUND IN ALL PATTERNS [E2036] in /code/proj/Main.roc ───────────────
 
         `y` is not bound in all patterns of this `when` branch
 
@@ -10567,7 +10638,7 @@ All branches in an `if` must have the same type!
         of the branch. Otherwise, the program would crash when it tries to use
         an identifier that wasn't bound!
 
-        ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+        ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
         `y` is not used in this `when` branch.
 
@@ -10588,7 +10659,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-        ── NAME NOT BOUND IN ALL PATTERNS in /code/proj/Main.roc ───────────────────────
+        ── NAME NOT BOUND IN ALL PATTERNS [E2036] in /code/proj/Main.roc ───────────────
 
         `x` is not bound in all patterns of this `when` branch
 
@@ -10611,7 +10682,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-        ── NAME NOT BOUND IN ALL PATTERNS in /code/proj/Main.roc ───────────────────────
+        ── NAME NOT BOUND IN ALL PATTERNS [E2036] in /code/proj/Main.roc ───────────────
 
         `rest` is not bound in all patterns of this `when` branch
 
@@ -10634,7 +10705,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -10660,7 +10731,7 @@ All branches in an `if` must have the same type!
         |golden| pretty_assertions::assert_eq!(
             golden,
             indoc!(
-                r"── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+                r"── UNRECOGNIZED NAME [E2073] in /code/proj/Main.roc ────────────────────────────
 
                 Nothing is named `foo` in this scope.
 
@@ -10697,7 +10768,7 @@ All branches in an `if` must have the same type!
         |golden| pretty_assertions::assert_eq!(
             golden,
             indoc!(
-                r"── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+                r"── SYNTAX PROBLEM [E2083] in /code/proj/Main.roc ───────────────────────────────
 
                 An underscore is being used as a variable here:
 
@@ -10723,7 +10794,7 @@ All branches in an `if` must have the same type!
         |golden| pretty_assertions::assert_eq!(
             golden,
             indoc!(
-                r"── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+                r"── SYNTAX PROBLEM [E2083] in /code/proj/Main.roc ───────────────────────────────
 
                 This variable's name starts with an underscore:
 
@@ -10756,7 +10827,7 @@ All branches in an `if` must have the same type!
             golden,
             indoc!(
                 r"
-                ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+                ── SYNTAX PROBLEM [E2083] in /code/proj/Main.roc ───────────────────────────────
 
                 This variable's name starts with an underscore:
 
@@ -10782,7 +10853,7 @@ All branches in an `if` must have the same type!
         |golden| pretty_assertions::assert_eq!(
             golden,
             indoc!(
-                r"── NAMING PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+                r"── NAMING PROBLEM [E2037] in /code/proj/Main.roc ───────────────────────────────
 
                 I am trying to parse an identifier here:
 
@@ -10806,7 +10877,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── EMPTY RECORD BUILDER in /code/proj/Main.roc ─────────────────────────────────
+    ── EMPTY RECORD BUILDER [E2019] in /code/proj/Main.roc ─────────────────────────
 
     This record builder has no fields:
 
@@ -10827,7 +10898,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── NOT ENOUGH FIELDS IN RECORD BUILDER in /code/proj/Main.roc ──────────────────
+    ── NOT ENOUGH FIELDS IN RECORD BUILDER [E2042] in /code/proj/Main.roc ──────────
 
     This record builder only has one field:
 
@@ -10850,7 +10921,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── OPTIONAL FIELD IN RECORD BUILDER in /code/proj/Main.roc ─────────────────────
+    ── OPTIONAL FIELD IN RECORD BUILDER [E2051] in /code/proj/Main.roc ─────────────
 
     Optional fields are not allowed to be used in record builders.
 
@@ -10887,7 +10958,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [E3015] in /code/proj/Main.roc ────────────────────────────────
 
     The `xyz` value is not a function, but it was given 3 arguments:
 
@@ -10915,7 +10986,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [E2067] in /code/proj/Main.roc ───────────────────────
 
     This assignment doesn't introduce any new variables:
 
@@ -10926,7 +10997,7 @@ All branches in an `if` must have the same type!
     affect the program's behavior. If you don't need to use the value on
     the right-hand side, consider removing the assignment.
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [E2067] in /code/proj/Main.roc ───────────────────────
 
     This assignment doesn't introduce any new variables:
 
@@ -10937,7 +11008,7 @@ All branches in an `if` must have the same type!
     affect the program's behavior. If you don't need to use the value on
     the right-hand side, consider removing the assignment.
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [E2067] in /code/proj/Main.roc ───────────────────────
 
     This assignment doesn't introduce any new variables:
 
@@ -10948,7 +11019,7 @@ All branches in an `if` must have the same type!
     affect the program's behavior. If you don't need to use the value on
     the right-hand side, consider removing the assignment.
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [E2067] in /code/proj/Main.roc ───────────────────────
 
     This assignment doesn't introduce any new variables:
 
@@ -10977,7 +11048,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [E2067] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10989,7 +11060,7 @@ All branches in an `if` must have the same type!
     allowed at the top-level, assignments that don't introduce variables
     cannot affect a program's behavior
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [E2067] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -11001,7 +11072,7 @@ All branches in an `if` must have the same type!
     allowed at the top-level, assignments that don't introduce variables
     cannot affect a program's behavior
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [E2067] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -11013,7 +11084,7 @@ All branches in an `if` must have the same type!
     allowed at the top-level, assignments that don't introduce variables
     cannot affect a program's behavior
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [E2067] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -11041,7 +11112,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
     `hash` is not used anywhere in your code.
 
@@ -11068,7 +11139,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── WRONG SPECIALIZATION TYPE in /code/proj/Main.roc ────────────────────────────
+    ── WRONG SPECIALIZATION TYPE [E3021] in /code/proj/Main.roc ────────────────────
 
     This specialization of `hash` is not for the expected type:
 
@@ -11091,7 +11162,7 @@ All branches in an `if` must have the same type!
                 "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -11121,7 +11192,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [E3002] in /code/proj/Main.roc ─────────────────────────────────
 
     The `Recursive` opaque is self-recursive in an invalid way:
 
@@ -11143,7 +11214,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Decoding` ability for `A`:
 
@@ -11168,7 +11239,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Decoding` ability for `A`:
 
@@ -11225,7 +11296,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -11258,7 +11329,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -11288,7 +11359,7 @@ All branches in an `if` must have the same type!
             "
         ),
     @r#"
-    ── UNUSED ARGUMENT in /code/proj/Main.roc ──────────────────────────────────────
+    ── UNUSED ARGUMENT [E2074] in /code/proj/Main.roc ──────────────────────────────
 
     This function doesn't use `x`.
 
@@ -11320,7 +11391,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -11353,7 +11424,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -11390,7 +11461,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the `then` branch of this `if` expression:
 
@@ -11422,7 +11493,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [E2075] in /code/proj/Main.roc ────────────────────────────
 
     `foo` is not used in this `when` branch.
 
@@ -11490,7 +11561,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -11520,7 +11591,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -11586,7 +11657,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -11614,7 +11685,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -11641,7 +11712,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNMATCHABLE PATTERN in /code/proj/Main.roc ──────────────────────────────────
+    ── UNMATCHABLE PATTERN [E3019] in /code/proj/Main.roc ──────────────────────────
 
     The 2nd pattern will never be matched:
 
@@ -11668,7 +11739,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNMATCHABLE PATTERN in /code/proj/Main.roc ──────────────────────────────────
+    ── UNMATCHABLE PATTERN [E3019] in /code/proj/Main.roc ──────────────────────────
 
     The 2nd pattern will never be matched:
 
@@ -11680,7 +11751,7 @@ All branches in an `if` must have the same type!
     It's impossible to create a value of this shape, so this pattern can
     be safely removed!
 
-    ── UNMATCHABLE PATTERN in /code/proj/Main.roc ──────────────────────────────────
+    ── UNMATCHABLE PATTERN [E3019] in /code/proj/Main.roc ──────────────────────────
 
     The 3rd pattern will never be matched:
 
@@ -11705,7 +11776,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [E2017] in /code/proj/Main.roc ───────────────────────────────
 
     This opaque type has the same name as a builtin:
 
@@ -11729,7 +11800,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r###"
-    ── UNUSED IMPORT in /code/proj/Main.roc ────────────────────────────────────────
+    ── UNUSED IMPORT [E2076] in /code/proj/Main.roc ────────────────────────────────
 
     List is imported but not used.
 
@@ -11782,7 +11853,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNRECOGNIZED PACKAGE in tmp/unknown_shorthand_no_deps/Test.roc ──────────────
+    ── UNRECOGNIZED PACKAGE [E4024] in tmp/unknown_shorthand_no_deps/Test.roc ──────
 
     This module is trying to import from `foo`:
 
@@ -11807,7 +11878,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNRECOGNIZED PACKAGE in tmp/unknown_shorthand_in_app/Test.roc ───────────────
+    ── UNRECOGNIZED PACKAGE [E4024] in tmp/unknown_shorthand_in_app/Test.roc ───────
 
     This module is trying to import from `foo`:
 
@@ -11834,7 +11905,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    [1;36m── FILE NOT FOUND in tmp/import_qualified_builtin/../../tests/Bool.roc ─────────[0m
+    [1;36m── FILE NOT FOUND [E4001] in tmp/import_qualified_builtin/../../tests/Bool.roc ─[0m
 
     I am looking for this file, but it's not there:
 
@@ -11856,7 +11927,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── CIRCULAR DEFINITION in /code/proj/Main.roc ──────────────────────────────────
+    ── CIRCULAR DEFINITION [E2009] in /code/proj/Main.roc ──────────────────────────
 
     `main` is defined directly in terms of itself:
 
@@ -11878,7 +11949,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` condition needs to be a Bool:
 
@@ -11904,7 +11975,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` condition needs to be a Bool:
 
@@ -11932,7 +12003,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Hash` ability for `A`:
 
@@ -11957,7 +12028,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Hash` ability for `A`:
 
@@ -12040,7 +12111,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -12067,7 +12138,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -12113,7 +12184,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -12149,7 +12220,7 @@ All branches in an `if` must have the same type!
             "
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `shift_right_zf_by` has an unexpected type:
 
@@ -12164,7 +12235,7 @@ All branches in an `if` must have the same type!
 
         U8
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `shift_right_by` has an unexpected type:
 
@@ -12179,7 +12250,7 @@ All branches in an `if` must have the same type!
 
         U8
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `shift_left_by` has an unexpected type:
 
@@ -12207,7 +12278,7 @@ All branches in an `if` must have the same type!
             "
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `contains` has an unexpected type:
 
@@ -12234,7 +12305,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -12259,7 +12330,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -12289,7 +12360,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -12315,7 +12386,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -12343,7 +12414,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -12426,7 +12497,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -12453,7 +12524,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -12482,7 +12553,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -12505,7 +12576,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -12545,7 +12616,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -12583,7 +12654,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── NOT EXPOSED in /code/proj/Main.roc ──────────────────────────────────────────
+    ── NOT EXPOSED [E2043] in /code/proj/Main.roc ──────────────────────────────────
 
     The Bool module does not expose `structural_eq`:
 
@@ -12597,7 +12668,7 @@ All branches in an `if` must have the same type!
         Bool.false
         Bool.is_eq
 
-    ── NOT EXPOSED in /code/proj/Main.roc ──────────────────────────────────────────
+    ── NOT EXPOSED [E2043] in /code/proj/Main.roc ──────────────────────────────────
 
     The Bool module does not expose `structural_not_eq`:
 
@@ -12625,7 +12696,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -12650,7 +12721,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3025] in /code/proj/Main.roc ────────────────────────────────
 
     The 1st argument to `foo` is weird:
 
@@ -12679,7 +12750,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Hash` ability for `F`:
 
@@ -12690,7 +12761,7 @@ All branches in an `if` must have the same type!
 
     Tip: You can define a custom implementation of `Hash` for `F`.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Eq` ability for `F`:
 
@@ -12701,7 +12772,7 @@ All branches in an `if` must have the same type!
 
     Tip: You can define a custom implementation of `Eq` for `F`.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E2027] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Encoding` ability for `F`:
 
@@ -12724,7 +12795,7 @@ All branches in an `if` must have the same type!
             "
         ),
     @r"
-    ── DUPLICATE BOUND ABILITY in /code/proj/Main.roc ──────────────────────────────
+    ── DUPLICATE BOUND ABILITY [E2014] in /code/proj/Main.roc ──────────────────────
 
     I already saw that this type variable is bound to the `Hash` ability
     once before:
@@ -12750,7 +12821,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `g` has an unexpected type:
 
@@ -12787,7 +12858,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `g` has an unexpected type:
 
@@ -12825,7 +12896,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `g` has an unexpected type:
 
@@ -12858,7 +12929,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNFINISHED LIST PATTERN in tmp/list_pattern_not_terminated/Test.roc ─────────
+    ── UNFINISHED LIST PATTERN [E1058] in tmp/list_pattern_not_terminated/Test.roc ─
 
     I am partway through parsing a list pattern, but I got stuck here:
 
@@ -12879,7 +12950,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── INCORRECT REST PATTERN in tmp/list_pattern_weird_rest_pattern/Test.roc ──────
+    ── INCORRECT REST PATTERN [E1017] in ...st_pattern_weird_rest_pattern/Test.roc ─
 
     It looks like you may trying to write a list rest pattern, but it's
     not the form I expect:
@@ -12901,7 +12972,7 @@ All branches in an `if` must have the same type!
             "
         ),
     @r"
-    ── UNNECESSARY WILDCARD in /code/proj/Main.roc ─────────────────────────────────
+    ── UNNECESSARY WILDCARD [E2071] in /code/proj/Main.roc ─────────────────────────
 
     This type annotation has a wildcard type variable (`*`) that isn't
     needed.
@@ -12928,7 +12999,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── MULTIPLE LIST REST PATTERNS in /code/proj/Main.roc ──────────────────────────
+    ── MULTIPLE LIST REST PATTERNS [E2035] in /code/proj/Main.roc ──────────────────
 
     This list pattern match has multiple rest patterns:
 
@@ -12938,7 +13009,7 @@ All branches in an `if` must have the same type!
     I only support compiling list patterns with one .. pattern! Can you
     remove this additional one?
 
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12962,7 +13033,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── MULTIPLE LIST REST PATTERNS in /code/proj/Main.roc ──────────────────────────
+    ── MULTIPLE LIST REST PATTERNS [E2035] in /code/proj/Main.roc ──────────────────
 
     This list pattern match has multiple rest patterns:
 
@@ -12972,7 +13043,7 @@ All branches in an `if` must have the same type!
     I only support compiling list patterns with one .. pattern! Can you
     remove this additional one?
 
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12996,7 +13067,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3028] in /code/proj/Main.roc ────────────────────────────────
 
     This list element doesn't match the types of other elements in the
     pattern:
@@ -13023,7 +13094,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3024] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -13056,7 +13127,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13096,7 +13167,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13140,7 +13211,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [E3013] in /code/proj/Main.roc ────────────────────────────
 
     The 2nd pattern is redundant:
 
@@ -13206,7 +13277,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13233,7 +13304,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13276,7 +13347,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13303,7 +13374,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13352,7 +13423,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13383,7 +13454,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13415,7 +13486,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13464,7 +13535,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13498,7 +13569,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [E3013] in /code/proj/Main.roc ────────────────────────────
 
     The 3rd pattern is redundant:
 
@@ -13526,7 +13597,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [E3013] in /code/proj/Main.roc ────────────────────────────
 
     The 3rd pattern is redundant:
 
@@ -13554,7 +13625,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [E3013] in /code/proj/Main.roc ────────────────────────────
 
     The 3rd pattern is redundant:
 
@@ -13582,7 +13653,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [E3013] in /code/proj/Main.roc ────────────────────────────
 
     The 2nd pattern is redundant:
 
@@ -13621,7 +13692,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -13652,7 +13723,7 @@ All branches in an `if` must have the same type!
             "
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3026] in /code/proj/Main.roc ────────────────────────────────
 
     This value passed to `crash` is not a string:
 
@@ -13677,7 +13748,7 @@ All branches in an `if` must have the same type!
             "
         ),
     @r"
-    ── UNAPPLIED CRASH in /code/proj/Main.roc ──────────────────────────────────────
+    ── UNAPPLIED CRASH [E2061] in /code/proj/Main.roc ──────────────────────────────
 
     This `crash` doesn't have a message given to it:
 
@@ -13698,7 +13769,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── OVERAPPLIED CRASH in /code/proj/Main.roc ────────────────────────────────────
+    ── OVERAPPLIED CRASH [E2052] in /code/proj/Main.roc ────────────────────────────
 
     This `crash` has too many values given to it:
 
@@ -13734,7 +13805,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -13776,7 +13847,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── DEFINITION ONLY USED IN RECURSION in /code/proj/Main.roc ────────────────────
+    ── DEFINITION ONLY USED IN RECURSION [E2011] in /code/proj/Main.roc ────────────
 
     This definition is only used in recursion with itself:
 
@@ -13809,7 +13880,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── DEFINITIONS ONLY USED IN RECURSION in /code/proj/Main.roc ───────────────────
+    ── DEFINITIONS ONLY USED IN RECURSION [E2012] in /code/proj/Main.roc ───────────
 
     These 2 definitions are only used in mutual recursion with themselves:
 
@@ -13846,7 +13917,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── DEFINITION ONLY USED IN RECURSION in /code/proj/Main.roc ────────────────────
+    ── DEFINITION ONLY USED IN RECURSION [E2011] in /code/proj/Main.roc ────────────
 
     This definition is only used in recursion with itself:
 
@@ -13882,7 +13953,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── DEFINITIONS ONLY USED IN RECURSION in /code/proj/Main.roc ───────────────────
+    ── DEFINITIONS ONLY USED IN RECURSION [E2012] in /code/proj/Main.roc ───────────
 
     These 2 definitions are only used in mutual recursion with themselves:
 
@@ -13922,7 +13993,7 @@ All branches in an `if` must have the same type!
             {one, str}
         "#),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `concat` has an unexpected type:
 
@@ -13953,7 +14024,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the `else` branch of this `if` expression:
 
@@ -13990,7 +14061,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the `else` branch of this `if` expression:
 
@@ -14026,7 +14097,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `main` definition:
 
@@ -14064,7 +14135,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `main` definition:
 
@@ -14132,7 +14203,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -14172,7 +14243,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3023] in /code/proj/Main.roc ────────────────────────────────
 
     The expression below has a type that does not implement the required abilities.
 
@@ -14212,7 +14283,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [E3020] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -14260,7 +14331,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [E3015] in /code/proj/Main.roc ────────────────────────────────
 
     The `parser` value is an opaque type, so it cannot be called with an
     argument:
@@ -14284,7 +14355,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -14315,7 +14386,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -14349,7 +14420,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -14383,7 +14454,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -14411,7 +14482,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TOO FEW ARGS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── TOO FEW ARGS [E3014] in /code/proj/Main.roc ─────────────────────────────────
 
     The `sub` function expects 2 arguments, but it got only 1:
 
@@ -14431,7 +14502,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TOO FEW ARGS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── TOO FEW ARGS [E3014] in /code/proj/Main.roc ─────────────────────────────────
 
     The `sub` function expects 2 arguments, but it got only 1:
 
@@ -14451,7 +14522,7 @@ All branches in an `if` must have the same type!
             "
         ),
     @r"
-    ── UNAPPLIED DBG in /code/proj/Main.roc ────────────────────────────────────────
+    ── UNAPPLIED DBG [E2062] in /code/proj/Main.roc ────────────────────────────────
 
     This `dbg` doesn't have a value given to it:
 
@@ -14462,7 +14533,7 @@ All branches in an `if` must have the same type!
     can't be used as a value that's passed around, like functions can be -
     it must be applied immediately!
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -14487,7 +14558,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r#"
-    ── OVERAPPLIED DBG in /code/proj/Main.roc ──────────────────────────────────────
+    ── OVERAPPLIED DBG [E2053] in /code/proj/Main.roc ──────────────────────────────
 
     This `dbg` has too many values given to it:
 
@@ -14496,7 +14567,7 @@ All branches in an `if` must have the same type!
 
     `dbg` must be given exactly one value to print.
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -14521,7 +14592,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3029] in /code/proj/Main.roc ────────────────────────────────
 
     This record doesn’t have a `abcde` field:
 
@@ -14540,7 +14611,7 @@ All branches in an `if` must have the same type!
               "#
         ),
         @r###"
-      ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+      ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
       This expression is used in an unexpected way:
 
@@ -14568,7 +14639,7 @@ All branches in an `if` must have the same type!
               "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -14600,7 +14671,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-        ── RETURN OUTSIDE OF FUNCTION in /code/proj/Main.roc ───────────────────────────
+        ── RETURN OUTSIDE OF FUNCTION [E2056] in /code/proj/Main.roc ───────────────────
 
         This `return` doesn't belong to a function:
 
@@ -14628,7 +14699,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-        ── UNREACHABLE CODE in /code/proj/Main.roc ─────────────────────────────────────
+        ── UNREACHABLE CODE [E2072] in /code/proj/Main.roc ─────────────────────────────
 
         This code won't run because it follows a `return` statement:
 
@@ -14655,7 +14726,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-        ── UNNECESSARY RETURN in /code/proj/Main.roc ───────────────────────────────────
+        ── UNNECESSARY RETURN [E2070] in /code/proj/Main.roc ───────────────────────────
 
         This `return` keyword is redundant:
 
@@ -14681,7 +14752,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3027] in /code/proj/Main.roc ────────────────────────────────
 
     This returns something that's incompatible with the return type of the
     enclosing function:
@@ -14729,7 +14800,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── IGNORED RESULT in /code/proj/Main.roc ───────────────────────────────────────
+    ── IGNORED RESULT [E3005] in /code/proj/Main.roc ───────────────────────────────
 
     The result of this expression is ignored:
 
@@ -14775,7 +14846,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── IGNORED RESULT in /code/proj/Main.roc ───────────────────────────────────────
+    ── IGNORED RESULT [E3005] in /code/proj/Main.roc ───────────────────────────────
 
     The result of this expression is ignored:
 
@@ -14809,7 +14880,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3027] in /code/proj/Main.roc ────────────────────────────────
 
     This returns something that's incompatible with the return type of the
     enclosing function:
@@ -14852,7 +14923,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── IGNORED RESULT in /code/proj/Main.roc ───────────────────────────────────────
+    ── IGNORED RESULT [E3005] in /code/proj/Main.roc ───────────────────────────────
 
     The result of this expression is ignored:
 
@@ -14905,7 +14976,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── IGNORED RESULT in /code/proj/Main.roc ───────────────────────────────────────
+    ── IGNORED RESULT [E3005] in /code/proj/Main.roc ───────────────────────────────
 
     The result of this expression is ignored:
 
@@ -14942,7 +15013,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── IGNORED RESULT in /code/proj/Main.roc ───────────────────────────────────────
+    ── IGNORED RESULT [E3005] in /code/proj/Main.roc ───────────────────────────────
 
     The result of this call to `Num.to_str` is ignored:
 
@@ -14958,7 +15029,7 @@ All branches in an `if` must have the same type!
 
         _ = File.delete!("data.json")?
 
-    ── LEFTOVER STATEMENT in /code/proj/Main.roc ───────────────────────────────────
+    ── LEFTOVER STATEMENT [E3010] in /code/proj/Main.roc ───────────────────────────
 
     This statement does not produce any effects:
 
@@ -14989,7 +15060,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [E2067] in /code/proj/Main.roc ───────────────────────
 
     This assignment doesn't introduce any new variables:
 
@@ -15017,7 +15088,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-        ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+        ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
         
         Something is off with the body of the `my_function` definition:
         
@@ -15071,7 +15142,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── INVALID TRY TARGET in /code/proj/Main.roc ───────────────────────────────────
+    ── INVALID TRY TARGET [E3007] in /code/proj/Main.roc ───────────────────────────
 
     This expression cannot be used as a `try` target:
 
@@ -15100,7 +15171,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── INVALID TRY TARGET in /code/proj/Main.roc ───────────────────────────────────
+    ── INVALID TRY TARGET [E3007] in /code/proj/Main.roc ───────────────────────────
 
     This expression cannot be tried with the `?` operator:
 
@@ -15130,7 +15201,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3027] in /code/proj/Main.roc ────────────────────────────────
 
     This returns something that's incompatible with the return type of the
     enclosing function:
@@ -15169,7 +15240,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to this function has an unexpected type:
 
@@ -15205,7 +15276,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to |> has an unexpected type:
 
@@ -15240,7 +15311,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to this function has an unexpected type:
 
@@ -15274,7 +15345,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── LEFTOVER STATEMENT in /code/proj/Main.roc ───────────────────────────────────
+    ── LEFTOVER STATEMENT [E3010] in /code/proj/Main.roc ───────────────────────────
 
     This statement does not produce any effects:
 
@@ -15307,7 +15378,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── EFFECT IN PURE FUNCTION in /code/proj/Main.roc ──────────────────────────────
+    ── EFFECT IN PURE FUNCTION [E3003] in /code/proj/Main.roc ──────────────────────
 
     This call to `Effect.get_line!` might produce an effect:
 
@@ -15344,7 +15415,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── EFFECT IN PURE FUNCTION in /code/proj/Main.roc ──────────────────────────────
+    ── EFFECT IN PURE FUNCTION [E3003] in /code/proj/Main.roc ──────────────────────
 
     This call to `Effect.put_line!` might produce an effect:
 
@@ -15379,7 +15450,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── MISSING EXCLAMATION in /code/proj/Main.roc ──────────────────────────────────
+    ── MISSING EXCLAMATION [E2033] in /code/proj/Main.roc ──────────────────────────
 
     This function is effectful, but its name does not indicate so:
 
@@ -15408,7 +15479,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── IGNORED RESULT in /code/proj/Main.roc ───────────────────────────────────────
+    ── IGNORED RESULT [E3005] in /code/proj/Main.roc ───────────────────────────────
 
     The result of this call to `Effect.get_line!` is ignored:
 
@@ -15440,7 +15511,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── IGNORED RESULT in /code/proj/Main.roc ───────────────────────────────────────
+    ── IGNORED RESULT [E3005] in /code/proj/Main.roc ───────────────────────────────
 
     The result of this expression is ignored:
 
@@ -15454,7 +15525,7 @@ All branches in an `if` must have the same type!
 
     Hint: Did you forget to call the function?
 
-    ── LEFTOVER STATEMENT in /code/proj/Main.roc ───────────────────────────────────
+    ── LEFTOVER STATEMENT [E3010] in /code/proj/Main.roc ───────────────────────────
 
     This statement does not produce any effects:
 
@@ -15484,7 +15555,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNNECESSARY EXCLAMATION in /code/proj/Main.roc ──────────────────────────────
+    ── UNNECESSARY EXCLAMATION [E2068] in /code/proj/Main.roc ──────────────────────
 
     This function is pure, but its name suggests otherwise:
 
@@ -15514,7 +15585,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── EFFECT IN TOP-LEVEL in /code/proj/Main.roc ──────────────────────────────────
+    ── EFFECT IN TOP-LEVEL [E3004] in /code/proj/Main.roc ──────────────────────────
 
     This call to `Effect.put_line!` might produce an effect:
 
@@ -15550,7 +15621,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── MISSING EXCLAMATION in /code/proj/Main.roc ──────────────────────────────────
+    ── MISSING EXCLAMATION [E2033] in /code/proj/Main.roc ──────────────────────────
 
     This function is effectful, but its name does not indicate so:
 
@@ -15581,7 +15652,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── MISSING EXCLAMATION in /code/proj/Main.roc ──────────────────────────────────
+    ── MISSING EXCLAMATION [E2033] in /code/proj/Main.roc ──────────────────────────
 
     This field's value is an effectful function, but its name does not
     indicate so:
@@ -15609,7 +15680,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── MISSING EXCLAMATION in /code/proj/Main.roc ──────────────────────────────────
+    ── MISSING EXCLAMATION [E2033] in /code/proj/Main.roc ──────────────────────────
 
     The type of this record field is an effectful function, but its name
     does not indicate so:
@@ -15637,7 +15708,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── UNNECESSARY EXCLAMATION in /code/proj/Main.roc ──────────────────────────────
+    ── UNNECESSARY EXCLAMATION [E2068] in /code/proj/Main.roc ──────────────────────
 
     The type of this record field is a pure function, but its name
     suggests otherwise:
@@ -15673,7 +15744,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── MISSING EXCLAMATION in /code/proj/Main.roc ──────────────────────────────────
+    ── MISSING EXCLAMATION [E2033] in /code/proj/Main.roc ──────────────────────────
 
     This function is effectful, but its name does not indicate so:
 
@@ -15710,7 +15781,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r"
-    ── UNNECESSARY EXCLAMATION in /code/proj/Main.roc ──────────────────────────────
+    ── UNNECESSARY EXCLAMATION [E2068] in /code/proj/Main.roc ──────────────────────
 
     This function is pure, but its name suggests otherwise:
 
@@ -15739,7 +15810,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── MISSING EXCLAMATION in /code/proj/Main.roc ──────────────────────────────────
+    ── MISSING EXCLAMATION [E2033] in /code/proj/Main.roc ──────────────────────────
 
     This function is effectful, but its name does not indicate so:
 
@@ -15752,7 +15823,7 @@ All branches in an `if` must have the same type!
 
     This will help readers identify it as a source of effects.
 
-    ── MISSING EXCLAMATION in /code/proj/Main.roc ──────────────────────────────────
+    ── MISSING EXCLAMATION [E2033] in /code/proj/Main.roc ──────────────────────────
 
     This function is effectful, but its name does not indicate so:
 
@@ -15782,7 +15853,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNNECESSARY EXCLAMATION in /code/proj/Main.roc ──────────────────────────────
+    ── UNNECESSARY EXCLAMATION [E2068] in /code/proj/Main.roc ──────────────────────
 
     This function is pure, but its name suggests otherwise:
 
@@ -15811,7 +15882,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── MISSING EXCLAMATION in /code/proj/Main.roc ──────────────────────────────────
+    ── MISSING EXCLAMATION [E2033] in /code/proj/Main.roc ──────────────────────────
 
     This function is effectful, but its name does not indicate so:
 
@@ -15824,7 +15895,7 @@ All branches in an `if` must have the same type!
 
     This will help readers identify it as a source of effects.
 
-    ── MISSING EXCLAMATION in /code/proj/Main.roc ──────────────────────────────────
+    ── MISSING EXCLAMATION [E2033] in /code/proj/Main.roc ──────────────────────────
 
     This function is effectful, but its name does not indicate so:
 
@@ -15854,7 +15925,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNNECESSARY EXCLAMATION in /code/proj/Main.roc ──────────────────────────────
+    ── UNNECESSARY EXCLAMATION [E2068] in /code/proj/Main.roc ──────────────────────
 
     This function is pure, but its name suggests otherwise:
 
@@ -15884,7 +15955,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── MISSING EXCLAMATION in /code/proj/Main.roc ──────────────────────────────────
+    ── MISSING EXCLAMATION [E2033] in /code/proj/Main.roc ──────────────────────────
 
     This function is effectful, but its name does not indicate so:
 
@@ -15916,7 +15987,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNNECESSARY EXCLAMATION in /code/proj/Main.roc ──────────────────────────────
+    ── UNNECESSARY EXCLAMATION [E2068] in /code/proj/Main.roc ──────────────────────
 
     This function is pure, but its name suggests otherwise:
 
@@ -15944,7 +16015,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `pure_higher_order` has an unexpected type:
 
@@ -15977,7 +16048,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3016] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `pure_higher_order` has an unexpected type:
 
@@ -16005,7 +16076,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [E3022] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `v` definition:
 
@@ -16040,7 +16111,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE VARIABLE IS NOT GENERIC in /code/proj/Main.roc ─────────────────────────
+    ── TYPE VARIABLE IS NOT GENERIC [E3017] in /code/proj/Main.roc ─────────────────
 
     This type variable has a single type:
 
@@ -0,0 +1,186 @@
+//! The packages and platforms an app, package, or platform depends on by URL, found by following
+//! module headers alone. This is much faster than a full load, since nothing gets canonicalized.
+use crate::file::{LoadingProblem, DEFAULT_MAIN_NAME};
+use bumpalo::Bump;
+use roc_packaging::cache::{self, RocCacheDir};
+use roc_parse::ast::{ExtractSpaces, Header};
+use roc_parse::header::{parse_header, AppHeader, PackageHeader, PlatformHeader};
+use roc_parse::parser::SyntaxError;
+use roc_reporting::report::to_https_problem_report_string;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// A package or platform that some module's header refers to by URL
+#[derive(Debug)]
+pub struct UrlDependency {
+    pub url: String,
+    /// Whether a header lists it as its platform, rather than as a package
+    pub is_platform: bool,
+    /// Every shorthand the headers give it, e.g. `pf`
+    pub shorthands: BTreeSet<String>,
+    /// The modules whose headers list it
+    pub importers: BTreeSet<PathBuf>,
+    /// Where its files are in the cache
+    pub package_dir: PathBuf,
+    /// The total size of its files, in bytes
+    pub size: u64,
+}
+
+impl UrlDependency {
+    /// The URL's host and path, minus the tarball name and any segments that look like versions,
+    /// e.g. `github.com/roc-lang/basic-cli/releases/download` for basic-cli 0.10.0. Different
+    /// URLs with the same host path are most likely different versions of the same package.
+    pub fn host_path(&self) -> String {
+        let without_fragment = self.url.split('#').next().unwrap_or_default();
+        let without_protocol = without_fragment
+            .split_once("://")
+            .map_or(without_fragment, |(_, rest)| rest);
+        let without_tarball = without_protocol
+            .rsplit_once('/')
+            .map_or(without_protocol, |(dir, _)| dir);
+
+        without_tarball
+            .split('/')
+            .filter(|segment| !is_version(segment))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// e.g. `0.10.0` or `v1.2`
+fn is_version(segment: &str) -> bool {
+    let digits = segment.strip_prefix('v').unwrap_or(segment);
+
+    digits.starts_with(|ch: char| ch.is_ascii_digit())
+        && digits.chars().all(|ch| ch.is_ascii_digit() || ch == '.')
+}
+
+/// A package or platform that a module's header lists by URL
+#[derive(Debug, Clone, Copy)]
+pub struct UrlPackage<'a> {
+    pub url: &'a str,
+    /// The shorthand the header gives it, e.g. `pf`
+    pub shorthand: &'a str,
+    /// Whether the header lists it as its platform, rather than as a package
+    pub is_platform: bool,
+}
+
+/// Every URL package and platform the given module depends on, directly or through other
+/// packages, sorted by URL. Packages that aren't in the cache yet get downloaded, since their
+/// headers are needed to find what they depend on in turn.
+pub fn load_url_dependencies<'a>(
+    arena: &'a Bump,
+    main_module: &Path,
+    roc_cache_dir: RocCacheDir<'_>,
+) -> Result<Vec<UrlDependency>, LoadingProblem<'a>> {
+    let mut dependencies: BTreeMap<String, UrlDependency> = BTreeMap::new();
+
+    walk_packages(arena, main_module, |package, importer| {
+        if let Some(dependency) = dependencies.get_mut(package.url) {
+            dependency.is_platform |= package.is_platform;
+            dependency.shorthands.insert(package.shorthand.to_string());
+            dependency.importers.insert(importer.to_path_buf());
+
+            return Ok(None);
+        }
+
+        let (package_dir, opt_root_module) = cache::install_package(roc_cache_dir, package.url)
+            .map_err(|problem| {
+                LoadingProblem::FormattedReport(
+                    to_https_problem_report_string(package.url, problem, importer.to_path_buf()),
+                    None,
+                )
+            })?;
+        let size = cache::dir_size(&package_dir).map_err(|err| LoadingProblem::FileProblem {
+            filename: package_dir.clone(),
+            error: err.kind(),
+        })?;
+        let root_module = root_module_path(&package_dir, opt_root_module);
+
+        dependencies.insert(
+            package.url.to_string(),
+            UrlDependency {
+                url: package.url.to_string(),
+                is_platform: package.is_platform,
+                shorthands: BTreeSet::from([package.shorthand.to_string()]),
+                importers: BTreeSet::from([importer.to_path_buf()]),
+                package_dir,
+                size,
+            },
+        );
+
+        Ok(Some(root_module))
+    })?;
+
+    Ok(dependencies.into_values().collect())
+}
+
+/// Visits the header of the given module, and those of every package it depends on, transitively.
+/// `on_url` gets each URL package a header lists, along with the module whose header it is, and
+/// returns the package's root module to visit that too, or None not to. A URL comes up once for
+/// every header that lists it. Packages given as local paths are always visited.
+///
+/// This is how `roc deps`, `roc vendor` and `roc cache prune` find the packages a project uses.
+pub fn walk_packages<'a>(
+    arena: &'a Bump,
+    main_module: &Path,
+    mut on_url: impl FnMut(UrlPackage<'a>, &Path) -> Result<Option<PathBuf>, LoadingProblem<'a>>,
+) -> Result<(), LoadingProblem<'a>> {
+    let mut to_visit = vec![main_module.to_path_buf()];
+    let mut visited = BTreeSet::new();
+
+    while let Some(filename) = to_visit.pop() {
+        if !visited.insert(filename.clone()) {
+            continue;
+        }
+
+        let src_bytes = std::fs::read(&filename).map_err(|err| LoadingProblem::FileProblem {
+            filename: filename.clone(),
+            error: err.kind(),
+        })?;
+        let parse_state = roc_parse::state::State::new(arena.alloc(src_bytes));
+        let (parsed_header, _) = parse_header(arena, parse_state).map_err(|fail| {
+            LoadingProblem::ParsingFailed(
+                fail.map_problem(SyntaxError::Header)
+                    .into_file_error(filename.clone()),
+            )
+        })?;
+
+        let packages = match parsed_header.item {
+            Header::App(AppHeader { packages, .. })
+            | Header::Package(PackageHeader { packages, .. }) => packages.value.items,
+            Header::Platform(PlatformHeader { packages, .. }) => packages.item.items,
+            Header::Module(_) | Header::Hosted(_) => &[],
+        };
+
+        let module_dir = filename.parent().unwrap_or(Path::new("."));
+
+        for entry in packages.iter() {
+            let entry = entry.value.extract_spaces().item;
+            let package_name = entry.package_name.value.to_str();
+
+            if !cache::is_url(package_name) {
+                to_visit.push(module_dir.join(package_name));
+                continue;
+            }
+
+            let package = UrlPackage {
+                url: package_name,
+                shorthand: entry.shorthand,
+                is_platform: entry.platform_marker.is_some(),
+            };
+
+            if let Some(root_module) = on_url(package, &filename)? {
+                to_visit.push(root_module);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The root module of a package whose files are in `package_dir`: the one its URL names, or
+/// `main.roc` if the URL doesn't name one.
+pub fn root_module_path(package_dir: &Path, opt_root_module: Option<&str>) -> PathBuf {
+    package_dir.join(opt_root_module.unwrap_or(DEFAULT_MAIN_NAME))
+}
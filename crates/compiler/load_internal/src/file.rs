@@ -60,6 +60,7 @@ use roc_parse::header::{
 use roc_parse::parser::{FileError, SourceError, SyntaxError};
 use roc_problem::Severity;
 use roc_region::all::{LineInfo, Loc, Region};
+use roc_reporting::error::codes;
 use roc_reporting::error::r#type::suggest;
 #[cfg(not(target_family = "wasm"))]
 use roc_reporting::report::to_https_problem_report_string;
@@ -100,7 +101,7 @@ const ROC_FILE_EXTENSION: &str = "roc";
 const MODULE_SEPARATOR: char = '.';
 
 /// Default name for the main module
-const DEFAULT_MAIN_NAME: &str = "main.roc";
+pub(crate) const DEFAULT_MAIN_NAME: &str = "main.roc";
 
 const EXPANDED_STACK_SIZE: usize = 8 * 1024 * 1024;
 
@@ -191,6 +192,7 @@ fn start_phase<'a>(
                             module_ids: Arc::clone(&state.arc_modules),
                             shorthands: Arc::clone(&state.arc_shorthands),
                             ident_ids_by_module: Arc::clone(&state.ident_ids_by_module),
+                            in_memory_modules: Arc::clone(&state.in_memory_modules),
                         }
                     }
                 }
@@ -779,11 +781,22 @@ struct State<'a> {
     // cached types (used for builtin modules, could include packages in the future too)
     cached_types: CachedTypeState,
 
+    /// Modules whose source the caller gave us, instead of us reading them from disk
+    in_memory_modules: InMemoryModules<'a>,
+    /// The solved types of the in-memory modules, so the caller can reuse them
+    in_memory_types: MutMap<ModuleId, TypeState>,
+    /// The one value of the root module to expose to the host, instead of what the platform
+    /// provides
+    root_exposed_to_host: Option<&'a str>,
+
     layout_interner: GlobalLayoutInterner<'a>,
 }
 
 type CachedTypeState = Arc<Mutex<MutMap<ModuleId, TypeState>>>;
 
+/// Sources of modules that aren't on disk, by module name, with the path to report them at
+type InMemoryModules<'a> = Arc<MutMap<&'a str, (PathBuf, &'a str)>>;
+
 impl<'a> State<'a> {
     fn goal_phase(&self) -> Phase {
         self.exec_mode.goal_phase()
@@ -801,12 +814,14 @@ impl<'a> State<'a> {
         ident_ids_by_module: SharedIdentIdsByModule,
         arc_shorthands: Arc<Mutex<MutMap<&'a str, ShorthandPath>>>,
         cached_types: MutMap<ModuleId, TypeState>,
+        in_memory_modules: MutMap<&'a str, (PathBuf, &'a str)>,
+        root_exposed_to_host: Option<&'a str>,
         render: RenderTarget,
         palette: Palette,
         number_of_workers: usize,
         exec_mode: ExecutionMode,
+        cache_dir: PathBuf,
     ) -> Self {
-        let cache_dir = roc_packaging::cache::roc_cache_packages_dir();
         let dependencies = Dependencies::new(exec_mode.goal_phase());
 
         Self {
@@ -838,6 +853,9 @@ impl<'a> State<'a> {
             timings: MutMap::default(),
             layout_caches: std::vec::Vec::with_capacity(number_of_workers),
             cached_types: Arc::new(Mutex::new(cached_types)),
+            in_memory_modules: Arc::new(in_memory_modules),
+            in_memory_types: MutMap::default(),
+            root_exposed_to_host,
             render,
             palette,
             exec_mode,
@@ -846,6 +864,16 @@ impl<'a> State<'a> {
             layout_interner: GlobalLayoutInterner::with_capacity(128, target),
         }
     }
+
+    fn is_in_memory_module(&self, module_id: ModuleId) -> bool {
+        match self.module_cache.sources.get(&module_id) {
+            Some((path, _)) => self
+                .in_memory_modules
+                .values()
+                .any(|(in_memory_path, _)| in_memory_path == path),
+            None => false,
+        }
+    }
 }
 
 fn report_timing(
@@ -901,6 +929,7 @@ enum BuildTask<'a> {
         module_ids: Arc<Mutex<PackageModuleIds<'a>>>,
         shorthands: Arc<Mutex<MutMap<&'a str, ShorthandPath>>>,
         ident_ids_by_module: SharedIdentIdsByModule,
+        in_memory_modules: InMemoryModules<'a>,
     },
     Parse {
         header: ModuleHeader<'a>,
@@ -1175,6 +1204,8 @@ pub struct LoadStart<'a> {
     root_type: RootType,
     opt_platform_shorthand: Option<&'a str>,
     src_dir: PathBuf,
+    in_memory_modules: MutMap<&'a str, (PathBuf, &'a str)>,
+    root_exposed_to_host: Option<&'a str>,
 }
 
 #[derive(Debug, Clone)]
@@ -1255,6 +1286,8 @@ impl<'a> LoadStart<'a> {
             root_msg: header_output.msg,
             root_type,
             opt_platform_shorthand: header_output.opt_platform_shorthand,
+            in_memory_modules: MutMap::default(),
+            root_exposed_to_host: None,
         })
     }
 
@@ -1311,8 +1344,35 @@ impl<'a> LoadStart<'a> {
             root_msg,
             root_type,
             opt_platform_shorthand: opt_platform_id,
+            in_memory_modules: MutMap::default(),
+            root_exposed_to_host: None,
         })
     }
+
+    /// Use `src` as the source of the module `module_name` when something imports it, rather
+    /// than reading it from disk. Its solved types end up in `in_memory_types` of the result.
+    pub fn with_in_memory_module(mut self, module_name: &'a str, src: &'a str) -> Self {
+        let mut filename = self.src_dir.clone();
+
+        for part in module_name.split(MODULE_SEPARATOR) {
+            filename.push(part);
+        }
+
+        filename.set_extension(ROC_FILE_EXTENSION);
+
+        self.in_memory_modules.insert(module_name, (filename, src));
+
+        self
+    }
+
+    /// Expose the value `name` of the root module to the host, instead of what the platform
+    /// provides. This lets the REPL evaluate an expr that uses a platform's effects, without
+    /// going through the platform's own entry point.
+    pub fn exposing_to_host(mut self, name: &'a str) -> Self {
+        self.root_exposed_to_host = Some(name);
+
+        self
+    }
 }
 
 fn handle_root_type<'a>(
@@ -1358,6 +1418,7 @@ fn handle_root_type<'a>(
                         Arc::clone(&ident_ids_by_module),
                         Arc::clone(&arc_shorthands),
                         cache_dir,
+                        roc_cache_dir.vendor_dir(),
                     )?;
 
                     header_output.msg = Msg::Many(messages);
@@ -1404,6 +1465,7 @@ fn load_packages_from_main<'a>(
     ident_ids_by_module: SharedIdentIdsByModule,
     arc_shorthands: Arc<Mutex<MutMap<&'a str, ShorthandPath>>>,
     cache_dir: &Path,
+    vendor_dir: Option<&Path>,
 ) -> Result<(), LoadingProblem<'a>> {
     let src_bytes = fs::read(&filename).map_err(|err| LoadingProblem::FileProblem {
         filename: filename.clone(),
@@ -1433,7 +1495,7 @@ fn load_packages_from_main<'a>(
     load_packages(
         packages,
         messages,
-        RocCacheDir::Persistent(cache_dir),
+        RocCacheDir::with_vendor_dir(cache_dir, vendor_dir),
         src_dir.clone(),
         arena,
         None,
@@ -1604,7 +1666,8 @@ pub fn load_single_threaded<'a>(
         root_type,
         src_dir,
         opt_platform_shorthand,
-        ..
+        in_memory_modules,
+        root_exposed_to_host,
     } = load_start;
 
     let (msg_tx, msg_rx) = bounded(1024);
@@ -1626,10 +1689,16 @@ pub fn load_single_threaded<'a>(
         ident_ids_by_module,
         arc_shorthands,
         cached_types,
+        in_memory_modules,
+        root_exposed_to_host,
         render,
         palette,
         number_of_workers,
         exec_mode,
+        roc_cache_dir.as_persistent_path().map_or_else(
+            roc_packaging::cache::roc_cache_packages_dir,
+            Path::to_path_buf,
+        ),
     );
 
     // We'll add tasks to this, and then worker threads will take tasks from it.
@@ -1985,7 +2054,8 @@ fn load_multi_threaded<'a>(
         root_type,
         src_dir,
         opt_platform_shorthand,
-        ..
+        in_memory_modules,
+        root_exposed_to_host,
     } = load_start;
 
     let (msg_tx, msg_rx) = bounded(1024);
@@ -2022,10 +2092,16 @@ fn load_multi_threaded<'a>(
         ident_ids_by_module,
         arc_shorthands,
         cached_types,
+        in_memory_modules,
+        root_exposed_to_host,
         render,
         palette,
         num_workers,
         exec_mode,
+        roc_cache_dir.as_persistent_path().map_or_else(
+            roc_packaging::cache::roc_cache_packages_dir,
+            Path::to_path_buf,
+        ),
     );
 
     // an arena for every worker, stored in an arena-allocated bumpalo vec to make the lifetimes work
@@ -2554,9 +2630,12 @@ fn update<'a>(
 
             // if there is a platform, the `platform` module provides host-exposed,
             // otherwise the App module exposes host-exposed
+            let root_exposed_to_host = state.root_exposed_to_host;
             let is_host_exposed = match state.platform_data {
-                None => module_id == state.root_id,
-                Some(ref platform_data) => module_id == platform_data.module_id,
+                Some(ref platform_data) if root_exposed_to_host.is_none() => {
+                    module_id == platform_data.module_id
+                }
+                _ => module_id == state.root_id,
             };
 
             let add_to_host_exposed = is_host_exposed &&
@@ -2569,7 +2648,10 @@ fn update<'a>(
                         .exposed_vars_by_symbol
                         .iter()
                         .filter_map(|(k, v)| {
-                            if abilities_store.is_specialization_name(*k) {
+                            let is_other_value = root_exposed_to_host
+                                .is_some_and(|name| ident_ids.get_name(k.ident_id()) != Some(name));
+
+                            if abilities_store.is_specialization_name(*k) || is_other_value {
                                 None
                             } else {
                                 Some((*k, *v))
@@ -2647,11 +2729,25 @@ fn update<'a>(
                 // the originally requested module, we're all done!
                 return Ok(state);
             } else {
+                let solved_implementations = solved_module.solved_implementations;
+
+                if state.is_in_memory_module(module_id) {
+                    state.in_memory_types.insert(
+                        module_id,
+                        TypeState {
+                            subs: solved_subs.inner().clone(),
+                            exposed_vars_by_symbol: solved_module.exposed_vars_by_symbol.clone(),
+                            abilities: abilities_store.clone(),
+                            solved_implementations: solved_implementations.clone(),
+                        },
+                    );
+                }
+
                 state.exposed_types.insert(
                     module_id,
                     ExposedModuleTypes {
                         exposed_types_storage_subs: solved_module.exposed_types,
-                        resolved_implementations: solved_module.solved_implementations,
+                        resolved_implementations: solved_implementations.clone(),
                     },
                 );
 
@@ -2685,6 +2781,7 @@ fn update<'a>(
                             solved_subs,
                             decls,
                             abilities_store,
+                            solved_implementations,
                         },
                     );
                     state.constrained_ident_ids.insert(module_id, ident_ids);
@@ -3088,16 +3185,18 @@ fn register_package_shorthands<'a>(
 ) -> Result<(), LoadingProblem<'a>> {
     for (shorthand, package_name) in package_entries.iter() {
         let package_str = package_name.as_str();
-        let shorthand_path = if package_str.starts_with("https://") {
+        let shorthand_path = if roc_packaging::cache::is_url(package_str) {
             #[cfg(not(target_family = "wasm"))]
             {
                 let url = package_str;
                 match PackageMetadata::try_from(url) {
                     Ok(url_metadata) => {
                         // This was a valid URL
-                        let root_module_dir = cache_dir
-                            .join(url_metadata.cache_subdir)
-                            .join(url_metadata.content_hash);
+                        let root_module_dir = cache::package_dir(
+                            cache_dir,
+                            url_metadata.cache_subdir,
+                            &url_metadata.content_hash,
+                        );
                         let root_module = root_module_dir.join(
                             url_metadata
                                 .root_module_filename
@@ -3257,7 +3356,12 @@ fn finish_specialization<'a>(
                     }
                 };
 
-                let exposed_symbols_and_layouts = match state.platform_data {
+                let opt_platform_data = match state.root_exposed_to_host {
+                    Some(_) => None,
+                    None => state.platform_data.as_ref(),
+                };
+
+                let exposed_symbols_and_layouts = match opt_platform_data {
                     None => {
                         let src = &state.exposed_to_host.top_level_values;
                         let mut buf = bumpalo::collections::Vec::with_capacity_in(src.len(), arena);
@@ -3271,7 +3375,7 @@ fn finish_specialization<'a>(
 
                         buf.into_bump_slice()
                     }
-                    Some(PlatformData {
+                    Some(&PlatformData {
                         module_id,
                         provides,
                         ..
@@ -3309,6 +3413,7 @@ fn finish_specialization<'a>(
         host_exposed_lambda_sets,
         module_cache,
         platform_data,
+        in_memory_types,
         ..
     } = state;
 
@@ -3349,6 +3454,7 @@ fn finish_specialization<'a>(
         toplevel_expects,
         glue_layouts: GlueLayouts { getters: vec![] },
         needs_prebuilt_host,
+        in_memory_types,
     })
 }
 
@@ -3441,6 +3547,7 @@ fn finish(
         exposed_imports: state.module_cache.exposed_imports,
         imports: state.module_cache.imports,
         exposes: state.module_cache.exposes,
+        in_memory_types: state.in_memory_types,
     }
 }
 
@@ -3702,6 +3809,7 @@ fn load_module<'a>(
     arc_shorthands: Arc<Mutex<MutMap<&'a str, ShorthandPath>>>,
     roc_cache_dir: RocCacheDir<'_>,
     ident_ids_by_module: SharedIdentIdsByModule,
+    in_memory_modules: &MutMap<&'a str, (PathBuf, &'a str)>,
 ) -> Result<HeaderOutput<'a>, LoadingProblem<'a>> {
     let module_start_time = Instant::now();
 
@@ -3749,6 +3857,22 @@ fn load_module<'a>(
         "Inspect", ModuleId::INSPECT
     }
 
+    let opt_in_memory = module_name
+        .unqualified()
+        .and_then(|name| in_memory_modules.get(name.as_str()));
+
+    if let Some((filename, src)) = opt_in_memory {
+        return load_from_str(
+            arena,
+            filename.clone(),
+            src,
+            module_ids,
+            ident_ids_by_module,
+            roc_cache_dir,
+            module_start_time,
+        );
+    }
+
     let (filename, opt_shorthand) = module_name_to_path(src_dir, &module_name, arc_shorthands);
 
     load_filename(
@@ -4203,10 +4327,10 @@ fn load_packages<'a>(
 
         // find the `package` or `platform` module on disk,
         // downloading it into a cache dir first if necessary.
-        let root_module_path = if src.starts_with("https://") {
+        let root_module_path = if roc_packaging::cache::is_url(src) {
             #[cfg(not(target_family = "wasm"))]
             {
-                // If this is a HTTPS package, synchronously download it
+                // If this is a URL package, synchronously download it
                 // to the cache before proceeding.
 
                 // TODO we should do this async; however, with the current
@@ -4923,58 +5047,42 @@ fn run_solve<'a>(
     let has_dbgs = module.has_dbgs;
     let module = module;
 
-    let solve_result = {
-        if module_id.is_builtin() {
-            match cached_types.lock().remove(&module_id) {
-                None => run_solve_solve(
-                    exposed_for_module,
-                    types,
-                    constraints,
-                    constraint,
-                    function_kind,
-                    pending_derives,
-                    var_store,
-                    module,
-                    derived_module,
-                    is_host_exposed,
-                    //
-                    #[cfg(debug_assertions)]
-                    checkmate,
-                ),
-                Some(TypeState {
-                    subs,
-                    exposed_vars_by_symbol,
-                    abilities,
-                    solved_implementations,
-                }) => SolveResult {
-                    solved: Solved(subs),
-                    solved_implementations,
-                    exposed_vars_by_symbol,
-                    problems: vec![],
-                    abilities_store: abilities,
-                    imported_modules_with_params: vec![],
+    // Builtins come with their types already solved, and so can modules the caller solved in
+    // an earlier load of the very same sources.
+    let cached = cached_types.lock().remove(&module_id);
 
-                    #[cfg(debug_assertions)]
-                    checkmate: None,
-                },
-            }
-        } else {
-            run_solve_solve(
-                exposed_for_module,
-                types,
-                constraints,
-                constraint,
-                function_kind,
-                pending_derives,
-                var_store,
-                module,
-                derived_module,
-                is_host_exposed,
-                //
-                #[cfg(debug_assertions)]
-                checkmate,
-            )
-        }
+    let solve_result = match cached {
+        None => run_solve_solve(
+            exposed_for_module,
+            types,
+            constraints,
+            constraint,
+            function_kind,
+            pending_derives,
+            var_store,
+            module,
+            derived_module,
+            is_host_exposed,
+            //
+            #[cfg(debug_assertions)]
+            checkmate,
+        ),
+        Some(TypeState {
+            subs,
+            exposed_vars_by_symbol,
+            abilities,
+            solved_implementations,
+        }) => SolveResult {
+            solved: Solved(subs),
+            solved_implementations,
+            exposed_vars_by_symbol,
+            problems: vec![],
+            abilities_store: abilities,
+            imported_modules_with_params: vec![],
+
+            #[cfg(debug_assertions)]
+            checkmate: None,
+        },
     };
 
     let SolveResult {
@@ -6335,6 +6443,7 @@ fn run_task<'a>(
             module_ids,
             shorthands,
             ident_ids_by_module,
+            in_memory_modules,
         } => load_module(
             arena,
             src_dir,
@@ -6343,6 +6452,7 @@ fn run_task<'a>(
             shorthands,
             roc_cache_dir,
             ident_ids_by_module,
+            &in_memory_modules,
         )
         .map(|HeaderOutput { msg, .. }| msg),
         Parse {
@@ -6577,7 +6687,9 @@ fn to_import_cycle_report(
         filename,
         doc,
         title: "IMPORT CYCLE".to_string(),
+        code: Some(codes::IMPORT_CYCLE),
         severity: Severity::RuntimeError,
+        fixes: Vec::new(),
     };
 
     let mut buf = String::new();
@@ -6628,7 +6740,9 @@ fn to_incorrect_module_name_report<'a>(
         filename,
         doc,
         title: "INCORRECT MODULE NAME".to_string(),
+        code: Some(codes::INCORRECT_MODULE_NAME),
         severity,
+        fixes: Vec::new(),
     };
 
     let mut buf = String::new();
@@ -6676,7 +6790,9 @@ fn to_no_platform_package_report(
         filename,
         doc,
         title: "UNSPECIFIED PLATFORM".to_string(),
+        code: Some(codes::UNSPECIFIED_PLATFORM),
         severity,
+        fixes: Vec::new(),
     };
 
     let mut buf = String::new();
@@ -6720,7 +6836,9 @@ fn to_multiple_platform_packages_report(
         filename,
         doc,
         title: "MULTIPLE PLATFORMS".to_string(),
+        code: Some(codes::MULTIPLE_PLATFORMS),
         severity,
+        fixes: Vec::new(),
     };
 
     let mut buf = String::new();
@@ -6827,7 +6945,9 @@ fn to_unrecognized_package_shorthand_report(
         filename,
         doc,
         title: "UNRECOGNIZED PACKAGE".to_string(),
+        code: Some(codes::UNRECOGNIZED_PACKAGE),
         severity,
+        fixes: Vec::new(),
     };
 
     let mut buf = String::new();
@@ -6864,6 +6984,11 @@ fn to_parse_problem_report<'a>(
     let starting_line = 0;
 
     let lines = LineInfo::new(src);
+    let opt_region = problem
+        .problem
+        .problem
+        .get_region()
+        .map(|region| lines.convert_region(region));
 
     let report = parse_problem(
         &alloc,
@@ -6875,7 +7000,10 @@ fn to_parse_problem_report<'a>(
 
     let mut buf = String::new();
 
-    report.render(render, &mut buf, &alloc, &palette);
+    match render {
+        RenderTarget::Json => report.render_json(&mut buf, &alloc, opt_region),
+        _ => report.render(render, &mut buf, &alloc, &palette),
+    }
 
     buf
 }
@@ -6934,7 +7062,9 @@ fn report_cannot_run(
                     filename,
                     doc,
                     title: "NO PLATFORM".to_string(),
+                    code: Some(codes::NO_PLATFORM),
                     severity: Severity::RuntimeError,
+                    fixes: Vec::new(),
                 }
             }
             RootIsModule => {
@@ -6949,7 +7079,9 @@ fn report_cannot_run(
                     filename,
                     doc,
                     title: "NO PLATFORM".to_string(),
+                    code: Some(codes::ROOT_IS_NOT_AN_APP),
                     severity: Severity::RuntimeError,
+                    fixes: Vec::new(),
                 }
             }
             RootIsHosted => {
@@ -6964,7 +7096,9 @@ fn report_cannot_run(
                     filename,
                     doc,
                     title: "NO PLATFORM".to_string(),
+                    code: Some(codes::ROOT_IS_NOT_AN_APP),
                     severity: Severity::RuntimeError,
+                    fixes: Vec::new(),
                 }
             }
             RootIsPlatformModule => {
@@ -6979,7 +7113,9 @@ fn report_cannot_run(
                     filename,
                     doc,
                     title: "NO PLATFORM".to_string(),
+                    code: Some(codes::ROOT_IS_NOT_AN_APP),
                     severity: Severity::RuntimeError,
+                    fixes: Vec::new(),
                 }
             }
         }
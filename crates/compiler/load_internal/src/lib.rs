@@ -4,6 +4,8 @@
 #![allow(clippy::large_enum_variant)]
 
 use roc_module::symbol::ModuleId;
+#[cfg(not(target_family = "wasm"))]
+pub mod deps;
 pub mod docs;
 pub mod file;
 pub mod module;
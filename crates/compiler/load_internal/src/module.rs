@@ -4,7 +4,7 @@ use roc_can::expr::ExpectLookup;
 use roc_can::{
     abilities::AbilitiesStore,
     expr::{Declarations, PendingDerives},
-    module::{Module, ResolvedImplementations},
+    module::{Module, ResolvedImplementations, TypeState},
 };
 use roc_collections::{MutMap, MutSet, VecMap};
 use roc_module::ident::Ident;
@@ -52,6 +52,8 @@ pub struct LoadedModule {
     pub imports: MutMap<ModuleId, MutSet<ModuleId>>,
     pub exposed_imports: MutMap<ModuleId, MutMap<Symbol, Region>>,
     pub exposes: MutMap<ModuleId, Vec<(Symbol, Variable)>>,
+    /// Solved types of the modules given to `LoadStart::with_in_memory_module`
+    pub in_memory_types: MutMap<ModuleId, TypeState>,
 }
 
 impl LoadedModule {
@@ -142,6 +144,7 @@ pub struct CheckedModule {
     pub solved_subs: Solved<Subs>,
     pub decls: Declarations,
     pub abilities_store: AbilitiesStore,
+    pub solved_implementations: ResolvedImplementations,
 }
 
 #[derive(Debug)]
@@ -187,6 +190,8 @@ pub struct MonomorphizedModule<'a> {
     pub expectations: VecMap<ModuleId, Expectations>,
     pub needs_prebuilt_host: bool,
     pub glue_layouts: GlueLayouts<'a>,
+    /// Solved types of the modules given to `LoadStart::with_in_memory_module`
+    pub in_memory_types: MutMap<ModuleId, TypeState>,
 }
 
 #[derive(Debug, Clone)]
@@ -46,6 +46,24 @@ fn load_and_typecheck(
     target: Target,
     function_kind: FunctionKind,
 ) -> Result<LoadedModule, LoadingProblem> {
+    load_and_typecheck_with_cache_dir(
+        arena,
+        filename,
+        exposed_types,
+        target,
+        function_kind,
+        RocCacheDir::Disallowed,
+    )
+}
+
+fn load_and_typecheck_with_cache_dir<'a>(
+    arena: &'a Bump,
+    filename: PathBuf,
+    exposed_types: ExposedByModule,
+    target: Target,
+    function_kind: FunctionKind,
+    roc_cache_dir: RocCacheDir<'_>,
+) -> Result<LoadedModule, LoadingProblem<'a>> {
     use LoadResult::*;
 
     let load_start = LoadStart::from_path(
@@ -53,7 +71,7 @@ fn load_and_typecheck(
         filename,
         None,
         RenderTarget::Generic,
-        RocCacheDir::Disallowed,
+        roc_cache_dir,
         DEFAULT_PALETTE,
     )?;
     let load_config = LoadConfig {
@@ -70,7 +88,7 @@ fn load_and_typecheck(
         load_start,
         exposed_types,
         Default::default(), // these tests will re-compile the builtins
-        RocCacheDir::Disallowed,
+        roc_cache_dir,
         load_config,
     )? {
         Monomorphized(_) => unreachable!(""),
@@ -754,7 +772,7 @@ fn parse_problem() {
             report,
             indoc!(
                 "
-                    ── UNFINISHED LIST in tmp/parse_problem/Main.roc ───────────────────────────────
+                    ── UNFINISHED LIST [E1057] in tmp/parse_problem/Main.roc ───────────────────────
 
                     I am partway through started parsing a list, but I got stuck here:
 
@@ -919,7 +937,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
         err,
         indoc!(
             r"
-                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE in ...d_outside_defining_module/Main.roc ─
+                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE [E2047] in ...e_defining_module/Main.roc ─
 
                 The unwrapped opaque type Age referenced here:
 
@@ -933,7 +951,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
 
                 Note: Opaque types can only be wrapped and unwrapped in the module they are defined in!
 
-                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE in ...d_outside_defining_module/Main.roc ─
+                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE [E2047] in ...e_defining_module/Main.roc ─
 
                 The unwrapped opaque type Age referenced here:
 
@@ -947,7 +965,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
 
                 Note: Opaque types can only be wrapped and unwrapped in the module they are defined in!
 
-                ── UNUSED IMPORT in ...aque_wrapped_unwrapped_outside_defining_module/Main.roc ─
+                ── UNUSED IMPORT [E2076] in ...pped_unwrapped_outside_defining_module/Main.roc ─
 
                 Age is imported but not used.
 
@@ -1034,7 +1052,7 @@ fn unused_imports() {
         err,
         indoc!(
             r"
-            ── UNUSED IMPORT in tmp/unused_imports/Main.roc ────────────────────────────────
+            ── UNUSED IMPORT [E2076] in tmp/unused_imports/Main.roc ────────────────────────
 
             Dep2 is imported but not used.
 
@@ -1043,7 +1061,7 @@ fn unused_imports() {
 
             Since Dep2 isn't used, you don't need to import it.
 
-            ── UNUSED IMPORT in tmp/unused_imports/Main.roc ────────────────────────────────
+            ── UNUSED IMPORT [E2076] in tmp/unused_imports/Main.roc ────────────────────────
 
             Dep2 is imported but not used.
 
@@ -1052,7 +1070,7 @@ fn unused_imports() {
 
             Since Dep2 isn't used, you don't need to import it.
 
-            ── UNUSED IMPORT in tmp/unused_imports/Main.roc ────────────────────────────────
+            ── UNUSED IMPORT [E2076] in tmp/unused_imports/Main.roc ────────────────────────
 
             Dep2 is imported but not used.
 
@@ -1061,7 +1079,7 @@ fn unused_imports() {
 
             Since Dep2 isn't used, you don't need to import it.
 
-            ── UNUSED IMPORT in tmp/unused_imports/Main.roc ────────────────────────────────
+            ── UNUSED IMPORT [E2076] in tmp/unused_imports/Main.roc ────────────────────────
 
             Dep1 is imported but not used.
 
@@ -1070,7 +1088,7 @@ fn unused_imports() {
 
             Since Dep1 isn't used, you don't need to import it.
 
-            ── UNUSED IMPORT in tmp/unused_imports/Main.roc ────────────────────────────────
+            ── UNUSED IMPORT [E2076] in tmp/unused_imports/Main.roc ────────────────────────
 
             `Dep3.Three` is not used in this module.
 
@@ -1136,7 +1154,7 @@ fn explicit_builtin_import() {
         err,
         indoc!(
             r"
-            ── EXPLICIT BUILTIN IMPORT in tmp/explicit_builtin_import/Main.roc ─────────────
+            ── EXPLICIT BUILTIN IMPORT [E2021] in tmp/explicit_builtin_import/Main.roc ─────
 
             The builtin Bool was imported here:
 
@@ -1171,7 +1189,7 @@ fn explicit_builtin_import_empty_exposing() {
         err,
         indoc!(
             r"
-            ── EXPLICIT BUILTIN IMPORT in tmp/empty_exposing_builtin_import/Main.roc ───────
+            ── EXPLICIT BUILTIN IMPORT [E2021] in ...mpty_exposing_builtin_import/Main.roc ─
 
             The builtin Bool was imported here:
 
@@ -1210,7 +1228,7 @@ fn explicit_builtin_type_import() {
         err,
         indoc!(
             r"
-            ── EXPLICIT BUILTIN IMPORT in tmp/explicit_builtin_type_import/Main.roc ────────
+            ── EXPLICIT BUILTIN IMPORT [E2021] in ...explicit_builtin_type_import/Main.roc ─
 
             `Dict.Dict` was imported here:
 
@@ -1260,7 +1278,7 @@ fn import_shadows_symbol() {
         err,
         indoc!(
             r"
-            ── DUPLICATE NAME in tmp/import_shadows_symbol/Main.roc ────────────────────────
+            ── DUPLICATE NAME [E2017] in tmp/import_shadows_symbol/Main.roc ────────────────
 
             This import exposes `One.one`:
 
@@ -1274,7 +1292,7 @@ fn import_shadows_symbol() {
 
             You can rename it, or use the qualified name: `One.one`
 
-            ── UNUSED IMPORT in tmp/import_shadows_symbol/Main.roc ─────────────────────────
+            ── UNUSED IMPORT [E2076] in tmp/import_shadows_symbol/Main.roc ─────────────────
 
             One is imported but not used.
 
@@ -1308,7 +1326,7 @@ fn ingested_file_import_shadows_symbol() {
         err,
         indoc!(
             r#"
-            ── DUPLICATE NAME in tmp/ingested_import_shadows_symbol/Main.roc ───────────────
+            ── DUPLICATE NAME [E2017] in tmp/ingested_import_shadows_symbol/Main.roc ───────
 
             The `name` name is first defined here:
 
@@ -1401,7 +1419,7 @@ fn duplicate_alias() {
         err,
         indoc!(
             r"
-            ── IMPORT NAME CONFLICT in tmp/duplicate_alias/Main.roc ────────────────────────
+            ── IMPORT NAME CONFLICT [E2026] in tmp/duplicate_alias/Main.roc ────────────────
 
             Two was imported as D:
 
@@ -1466,7 +1484,7 @@ fn alias_using_module_name() {
         err,
         indoc!(
             r"
-            ── IMPORT NAME CONFLICT in tmp/alias_using_module_name/Main.roc ────────────────
+            ── IMPORT NAME CONFLICT [E2026] in tmp/alias_using_module_name/Main.roc ────────
 
             Two was imported as One:
 
@@ -1521,7 +1539,7 @@ fn alias_using_builtin_name() {
         err,
         indoc!(
             r"
-            ── IMPORT NAME CONFLICT in tmp/alias_using_builtin_name/Main.roc ───────────────
+            ── IMPORT NAME CONFLICT [E2026] in tmp/alias_using_builtin_name/Main.roc ───────
 
             BoolExtra was imported as Bool:
 
@@ -1663,7 +1681,7 @@ fn module_params_typecheck_fail() {
         result,
         indoc!(
             r#"
-            ── MODULE PARAMS MISMATCH in tmp/module_params_typecheck_fail/Main.roc ─────────
+            ── MODULE PARAMS MISMATCH [E3012] in tmp/module_params_typecheck_fail/Main.roc ─
 
             Something is off with the params provided by this import:
 
@@ -1714,7 +1732,7 @@ fn module_params_missing_fields() {
         result,
         indoc!(
             r#"
-            ── MODULE PARAMS MISMATCH in tmp/module_params_missing_fields/Main.roc ─────────
+            ── MODULE PARAMS MISMATCH [E3012] in tmp/module_params_missing_fields/Main.roc ─
 
             Something is off with the params provided by this import:
 
@@ -1767,7 +1785,7 @@ fn module_params_extra_fields() {
         result,
         indoc!(
             r#"
-            ── MODULE PARAMS MISMATCH in tmp/module_params_extra_fields/Main.roc ───────────
+            ── MODULE PARAMS MISMATCH [E3012] in tmp/module_params_extra_fields/Main.roc ───
 
             Something is off with the params provided by this import:
 
@@ -1820,7 +1838,7 @@ fn module_params_unexpected() {
         err,
         indoc!(
             r#"
-            ── UNEXPECTED MODULE PARAMS in tmp/module_params_unexpected/Main.roc ───────────
+            ── UNEXPECTED MODULE PARAMS [E3018] in tmp/module_params_unexpected/Main.roc ───
 
             This import specifies module params:
 
@@ -1866,7 +1884,7 @@ fn module_params_missing() {
         err,
         indoc!(
             r#"
-            ── MISSING MODULE PARAMS in tmp/module_params_missing/Main.roc ─────────────────
+            ── MISSING MODULE PARAMS [E3011] in tmp/module_params_missing/Main.roc ─────────
 
             This import specifies no module params:
 
@@ -1928,7 +1946,7 @@ fn issue_2863_module_type_does_not_exist() {
                 report,
                 indoc!(
                     "
-                        ── UNRECOGNIZED NAME in tmp/issue_2863_module_type_does_not_exist/main.roc ─────
+                        ── UNRECOGNIZED NAME [E2073] in ...ue_2863_module_type_does_not_exist/main.roc ─
 
                         Nothing is named `DoesNotExist` in this scope.
 
@@ -2006,7 +2024,7 @@ fn module_cyclic_import_itself() {
         err,
         indoc!(
             r"
-            ── IMPORT CYCLE in tmp/module_cyclic_import_itself/Age.roc ─────────────────────
+            ── IMPORT CYCLE [E4007] in tmp/module_cyclic_import_itself/Age.roc ─────────────
 
             I can't compile Age because it depends on itself through the following
             chain of module imports:
@@ -2055,7 +2073,7 @@ fn module_cyclic_import_transitive() {
         err,
         indoc!(
             r"
-            ── IMPORT CYCLE in tmp/module_cyclic_import_transitive/Age.roc ─────────────────
+            ── IMPORT CYCLE [E4007] in tmp/module_cyclic_import_transitive/Age.roc ─────────
 
             I can't compile Age because it depends on itself through the following
             chain of module imports:
@@ -2090,7 +2108,7 @@ fn non_roc_file_extension() {
 
     let expected = indoc!(
         r"
-        ── NOT A ROC FILE in tmp/non_roc_file_extension/main.md ────────────────────────
+        ── NOT A ROC FILE [E4021] in tmp/non_roc_file_extension/main.md ────────────────
 
         I expected a file with extension `.roc` or without extension.
         Instead I received a file with extension `.md`."
@@ -2120,7 +2138,7 @@ fn roc_file_no_extension() {
 
     let expected = indoc!(
         r"
-        ── NOT A ROC FILE in tmp/roc_file_no_extension/main ────────────────────────────
+        ── NOT A ROC FILE [E4021] in tmp/roc_file_no_extension/main ────────────────────
 
         I expected a file with either:
         - extension `.roc`
@@ -2183,3 +2201,202 @@ fn roc_package_depends_on_other_package() {
 
     assert!(result.is_ok());
 }
+
+#[test]
+fn url_dependencies_across_packages() {
+    use roc_load_internal::deps::load_url_dependencies;
+    use roc_packaging::tarball::{self, Compression};
+    use std::fs;
+
+    let tmp = TmpDir::new("tmp/url_dependencies_across_packages");
+    let dir = fs::canonicalize(tmp.path()).unwrap();
+    let write = |path: &str, src: &str| {
+        let path = dir.join(path);
+
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, src).unwrap();
+    };
+
+    write("json/main.roc", "package [Json] {}\n");
+    write("json/Json.roc", "module [x]\n\nx = 1\n");
+
+    let bundle = tarball::build(&dir.join("json/main.roc"), Compression::Uncompressed).unwrap();
+    let url = format!(
+        "file://{}",
+        dir.join("json").join(bundle.filename).display()
+    );
+
+    write(
+        "main.roc",
+        &format!("package [] {{ json: \"{url}\", other: \"other/main.roc\" }}\n"),
+    );
+    write(
+        "other/main.roc",
+        &format!("package [] {{ j: \"{url}\" }}\n"),
+    );
+
+    let arena = Bump::new();
+    let cache_dir = dir.join("cache");
+    let dependencies = load_url_dependencies(
+        &arena,
+        &dir.join("main.roc"),
+        RocCacheDir::Persistent(&cache_dir),
+    )
+    .unwrap();
+
+    assert_eq!(dependencies.len(), 1);
+
+    let dependency = &dependencies[0];
+
+    assert_eq!(dependency.url, url);
+    assert!(!dependency.is_platform);
+    assert_eq!(
+        dependency.shorthands.iter().collect::<Vec<_>>(),
+        ["j", "json"]
+    );
+    assert_eq!(
+        dependency.importers.iter().collect::<Vec<_>>(),
+        [&dir.join("main.roc"), &dir.join("other/main.roc")]
+    );
+    assert!(dependency.package_dir.starts_with(&cache_dir));
+    assert!(dependency.size > 0);
+}
+
+/// Writes a package that depends on a URL package, vendors that into `vendor`, then deletes the
+/// URL package's tarball, so the vendored copy is the only one left. Returns the vendored tarball.
+fn write_vendored_package(dir: &std::path::Path) -> PathBuf {
+    use roc_packaging::cache;
+    use roc_packaging::tarball::{self, Compression};
+    use std::fs;
+
+    let write = |path: &str, src: &str| {
+        let path = dir.join(path);
+
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, src).unwrap();
+    };
+
+    write("csv/main.roc", "package [Csv] {}\n");
+    write("csv/Csv.roc", "module [x]\n\nx = 1\n");
+
+    let bundle = tarball::build(&dir.join("csv/main.roc"), Compression::Brotli).unwrap();
+    let tarball_path = dir.join("csv").join(bundle.filename);
+    let url = format!("file://{}", tarball_path.display());
+
+    write(
+        "main.roc",
+        &format!("package [Main] {{ csv: \"{url}\" }}\n"),
+    );
+    write(
+        "Main.roc",
+        "module [y]\n\nimport csv.Csv\n\ny = Csv.x + 1\n",
+    );
+
+    let vendor_dir = dir.join(cache::VENDOR_DIR_NAME);
+
+    cache::vendor_package(&dir.join("vendor-cache"), &vendor_dir, &url).unwrap();
+    fs::remove_file(&tarball_path).unwrap();
+
+    let metadata = roc_packaging::https::PackageMetadata::try_from(url.as_str()).unwrap();
+
+    cache::vendored_tarball(&vendor_dir, metadata.cache_subdir, &metadata.content_hash)
+}
+
+#[test]
+fn load_vendored_package() {
+    use std::fs;
+
+    let tmp = TmpDir::new("tmp/load_vendored_package");
+    let dir = fs::canonicalize(tmp.path()).unwrap();
+    let vendored_tarball = write_vendored_package(&dir);
+
+    assert!(vendored_tarball.is_file());
+
+    let arena = Bump::new();
+    let cache_dir = dir.join("cache");
+    let result = load_and_typecheck_with_cache_dir(
+        &arena,
+        dir.join("main.roc"),
+        Default::default(),
+        TARGET,
+        FunctionKind::LambdaSet,
+        RocCacheDir::Vendored {
+            vendor_dir: &dir.join("vendor"),
+            cache_dir: &cache_dir,
+        },
+    );
+
+    assert!(result.is_ok(), "{:?}", result.err());
+    // It was extracted from the vendor dir, since its URL no longer works
+    assert!(fs::read_dir(&cache_dir).unwrap().next().is_some());
+}
+
+#[test]
+fn load_tampered_vendored_package() {
+    use std::fs;
+    use std::io::Write;
+
+    let tmp = TmpDir::new("tmp/load_tampered_vendored_package");
+    let dir = fs::canonicalize(tmp.path()).unwrap();
+    let vendored_tarball = write_vendored_package(&dir);
+
+    fs::OpenOptions::new()
+        .append(true)
+        .open(vendored_tarball)
+        .unwrap()
+        .write_all(b"tampered")
+        .unwrap();
+
+    let arena = Bump::new();
+    let result = load_and_typecheck_with_cache_dir(
+        &arena,
+        dir.join("main.roc"),
+        Default::default(),
+        TARGET,
+        FunctionKind::LambdaSet,
+        RocCacheDir::Vendored {
+            vendor_dir: &dir.join("vendor"),
+            cache_dir: &dir.join("cache"),
+        },
+    );
+
+    match result {
+        Err(LoadingProblem::FormattedReport(report, _)) => {
+            assert!(report.contains("VENDORED PACKAGE CHANGED"), "{report}");
+        }
+        other => panic!("expected a report, got {:?}", other.map(|_| ())),
+    }
+    assert!(!dir.join("cache").exists());
+}
+
+#[test]
+fn url_dependency_host_path_ignores_versions() {
+    use roc_load_internal::deps::UrlDependency;
+
+    let host_path = |url: &str| {
+        UrlDependency {
+            url: url.to_string(),
+            is_platform: true,
+            shorthands: Default::default(),
+            importers: Default::default(),
+            package_dir: PathBuf::new(),
+            size: 0,
+        }
+        .host_path()
+    };
+
+    assert_eq!(
+        host_path("https://github.com/roc-lang/basic-cli/releases/download/0.10.0/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE.tar.br"),
+        "github.com/roc-lang/basic-cli/releases/download"
+    );
+    assert_eq!(
+        host_path("https://github.com/roc-lang/basic-cli/releases/download/v0.9/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE.tar.br#main.roc"),
+        "github.com/roc-lang/basic-cli/releases/download"
+    );
+    assert_eq!(
+        host_path(
+            "https://example.com/roc/1password/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE.tar.br"
+        ),
+        "example.com/roc/1password"
+    );
+}
@@ -1433,6 +1433,7 @@ fn report_markdown_link_problem(
     link_markdown: &str,
     problem: LinkProblem,
 ) {
+    use roc_reporting::error::codes;
     use roc_reporting::report::{Report, RocDocAllocator, DEFAULT_PALETTE};
     use ven_pretty::DocAllocator;
 
@@ -1472,7 +1473,9 @@ fn report_markdown_link_problem(
             filename,
             doc,
             title: "INVALID DOCS LINK".to_string(),
+            code: Some(codes::INVALID_DOCS_LINK),
             severity: Severity::Warning,
+            fixes: Vec::new(),
         }
     };
 
@@ -16,6 +16,7 @@ use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadedModule, LoadingPro
 use roc_mono::ir::{generate_glue_procs, CrashTag, GlueProc, OptLevel};
 use roc_mono::layout::{GlobalLayoutInterner, LayoutCache, LayoutInterner};
 use roc_packaging::cache::{self, RocCacheDir};
+use roc_reporting::cli::WarningLevels;
 use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
 use roc_target::{Architecture, Target, TargetFromTripleError::TripleUnsupported};
 use roc_types::subs::{Subs, Variable};
@@ -93,6 +94,7 @@ pub fn generate(
                     load_config,
                     Some(dylib_dir.path()),
                     false,
+                    &WarningLevels::default(),
                 ),
                 Err(_) => {
                     eprintln!("`roc glue` was unable to create a tempdir.");
@@ -188,9 +190,12 @@ pub fn generate(
 
                     Ok(0)
                 }
-                Err(BuildFileError::ErrorModule { module, total_time }) => {
-                    handle_error_module(module, total_time)
-                }
+                Err(BuildFileError::ErrorModule { module, total_time }) => handle_error_module(
+                    module,
+                    total_time,
+                    RenderTarget::ColorTerminal,
+                    &WarningLevels::default(),
+                ),
                 Err(BuildFileError::LoadingProblem(problem)) => handle_loading_problem(problem),
             };
 
@@ -1,18 +1,21 @@
 use std::{
     collections::HashMap,
+    panic::{catch_unwind, AssertUnwindSafe},
     path::{Path, PathBuf},
     sync::Arc,
 };
 
 use bumpalo::Bump;
 
+use log::debug;
+
 use parking_lot::Mutex;
-use roc_can::{abilities::AbilitiesStore, expr::Declarations};
+use roc_can::{abilities::AbilitiesStore, expr::Declarations, module::TypeState};
 use roc_collections::{MutMap, MutSet, VecMap};
-use roc_load::{docs::ModuleDocumentation, CheckedModule, LoadedModule};
+use roc_load::{docs::ModuleDocumentation, CheckedModule, LoadedModule, LoadingProblem};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_packaging::cache::{self, RocCacheDir};
-use roc_region::all::LineInfo;
+use roc_region::all::{LineInfo, Region};
 use roc_reporting::report::RocDocAllocator;
 use roc_solve_problem::TypeError;
 use roc_types::subs::{Subs, Variable};
@@ -22,15 +25,23 @@ use tower_lsp::lsp_types::{Diagnostic, SemanticTokenType, Url};
 mod analysed_doc;
 mod annotation_visitor;
 mod completion;
+mod missing_branches;
 mod parse_ast;
+mod references;
 mod semantic_tokens;
+mod signature;
+mod solved_modules;
+mod symbols;
 mod tokens;
 mod utils;
 
 use crate::convert::diag::{IntoLspDiagnostic, ProblemFmt};
 
-pub(crate) use self::analysed_doc::{AnalyzedDocument, DocInfo};
 use self::{analysed_doc::ModuleIdToUrl, tokens::Token};
+pub(crate) use self::{
+    analysed_doc::{AnalyzedDocument, DocInfo},
+    solved_modules::SolvedModules,
+};
 
 pub const HIGHLIGHT_TOKENS_LEGEND: &[SemanticTokenType] = Token::LEGEND;
 
@@ -90,6 +101,8 @@ pub(super) struct AnalyzedModule {
     subs: Subs,
     abilities: AbilitiesStore,
     declarations: Declarations,
+    /// The region of each `when` that isn't exhaustive, with the patterns it doesn't cover.
+    incomplete_whens: Vec<(Region, Vec<roc_exhaustive::Pattern>)>,
     modules_info: Arc<ModulesInfo>,
     // ModuleIds are not stable between compilations, so a ModuleId visible to
     // one module may not be true global to the language server.
@@ -101,23 +114,52 @@ pub struct AnalysisResult {
     diagnostics: Vec<Diagnostic>,
 }
 
-pub(crate) fn global_analysis(doc_info: DocInfo) -> Vec<AnalyzedDocument> {
-    let fi = doc_info.url.to_file_path().unwrap();
-    let src_dir = find_src_dir(&fi).to_path_buf();
-
+/// Type checks the module in `doc_info` along with everything it imports.
+///
+/// The modules `previous` solved are reused as long as they haven't changed, so only the changed
+/// ones and those depending on them are solved again. Along with the analyzed documents, this
+/// returns the solved modules to pass to the next analysis of the same document.
+///
+/// The module in `doc_info` and those at `open_paths` are never reused, since their latest
+/// source is the editor's rather than the one on disk.
+pub(crate) fn global_analysis(
+    doc_info: DocInfo,
+    previous: Option<Arc<SolvedModules>>,
+    open_paths: Vec<PathBuf>,
+) -> (Vec<AnalyzedDocument>, Option<SolvedModules>) {
     let arena = Bump::new();
-    let loaded = roc_load::load_and_typecheck_str(
-        &arena,
-        fi,
-        &doc_info.source,
-        src_dir,
-        None,
-        roc_target::Target::LinuxX64,
-        roc_load::FunctionKind::LambdaSet,
-        roc_reporting::report::RenderTarget::LanguageServer,
-        RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
-        roc_reporting::report::DEFAULT_PALETTE,
-    );
+
+    let mut never_reused = open_paths.into_iter().collect::<MutSet<_>>();
+    never_reused.extend(doc_info.url.to_file_path());
+
+    let reused = previous
+        .as_deref()
+        .map(|previous| previous.reusable_types(&never_reused))
+        .unwrap_or_default();
+    let mut reused_ids = reused.keys().copied().collect::<MutSet<_>>();
+
+    let loaded = match previous.as_deref() {
+        Some(previous) if !reused_ids.is_empty() => {
+            let loaded = catch_unwind(AssertUnwindSafe(|| load(&arena, &doc_info, reused)));
+
+            match loaded {
+                Ok(Ok(module))
+                    if previous.ids_match(reused_ids.iter(), &module.interns)
+                        && previous.sources_match(reused_ids.iter(), &module.sources) =>
+                {
+                    debug!("Reused the solved types of {} modules", reused_ids.len());
+                    Ok(module)
+                }
+                Ok(Err(problem)) => Err(problem),
+                _ => {
+                    debug!("Couldn't reuse previously solved types, solving every module again");
+                    reused_ids.clear();
+                    load(&arena, &doc_info, MutMap::default())
+                }
+            }
+        }
+        _ => load(&arena, &doc_info, MutMap::default()),
+    };
 
     let module = match loaded {
         Ok(module) => module,
@@ -135,10 +177,12 @@ pub(crate) fn global_analysis(doc_info: DocInfo) -> Vec<AnalyzedDocument> {
                 },
             };
 
-            return vec![analyzed_document];
+            return (vec![analyzed_document], None);
         }
     };
 
+    let solved_modules = SolvedModules::from_loaded_module(&module);
+
     let mut documents = vec![];
 
     let LoadedModule {
@@ -184,11 +228,39 @@ pub(crate) fn global_analysis(doc_info: DocInfo) -> Vec<AnalyzedDocument> {
     };
 
     for (module_id, (path, source)) in sources {
+        // The documents of reused modules haven't changed since they were last analyzed
+        if reused_ids.contains(&module_id) {
+            continue;
+        }
+
         let doc = builder.build_document(path, source, module_id, doc_info.version);
         documents.push(doc);
     }
 
-    documents
+    (documents, Some(solved_modules))
+}
+
+fn load<'a>(
+    arena: &'a Bump,
+    doc_info: &'a DocInfo,
+    reused_types: MutMap<ModuleId, TypeState>,
+) -> Result<LoadedModule, LoadingProblem<'a>> {
+    let fi = doc_info.url.to_file_path().unwrap();
+    let src_dir = find_src_dir(&fi).to_path_buf();
+
+    roc_load::load_and_typecheck_str_reusing(
+        arena,
+        fi,
+        &doc_info.source,
+        src_dir,
+        None,
+        roc_target::Target::LinuxX64,
+        roc_load::FunctionKind::LambdaSet,
+        roc_reporting::report::RenderTarget::LanguageServer,
+        RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
+        roc_reporting::report::DEFAULT_PALETTE,
+        reused_types,
+    )
 }
 
 /// Take the exposed imports from each module, lookup the symbol within that module's list of
@@ -291,6 +363,8 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
 
         let exposed_imports = self.exposed_imports.remove(&module_id).unwrap_or_default();
 
+        let incomplete_whens = self.incomplete_whens(module_id);
+
         if let Some(m) = self.typechecked.remove(&module_id) {
             subs = m.solved_subs.into_inner();
             abilities = m.abilities_store;
@@ -308,6 +382,7 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
             subs,
             abilities,
             declarations,
+            incomplete_whens,
             module_id,
             modules_info: self.modules_info.clone(),
             interns: self.interns.clone(),
@@ -331,6 +406,26 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
         }
     }
 
+    /// Finds the non-exhaustive `when`s reported by the exhaustiveness checker, so we can offer
+    /// to add the branches they're missing.
+    fn incomplete_whens(&self, module_id: ModuleId) -> Vec<(Region, Vec<roc_exhaustive::Pattern>)> {
+        use roc_exhaustive::{Context, Error};
+
+        let Some(type_problems) = self.type_problems.get(&module_id) else {
+            return vec![];
+        };
+
+        type_problems
+            .iter()
+            .filter_map(|problem| match problem {
+                TypeError::Exhaustive(Error::Incomplete(region, Context::BadCase, missing)) => {
+                    Some((*region, missing.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     ///Gets the exposed symbols, and type info for each imported module
     fn get_symbols_for_imports(
         &mut self,
@@ -2,32 +2,49 @@ use log::{debug, info};
 
 use roc_cli::{annotation_edit, annotation_edits};
 use roc_fmt::MigrationFlags;
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 use bumpalo::Bump;
 
-use roc_module::symbol::{ModuleId, Symbol};
+use roc_can::expr::DeclarationTag;
+use roc_module::{
+    ident::ModuleName,
+    symbol::{ModuleId, Symbol},
+};
 
 use roc_region::all::{LineInfo, Position as RocPosition, Region};
+use roc_reporting::report::new_import_line;
 
 use tower_lsp::lsp_types::{
-    CodeAction, CodeActionKind, CompletionItem, Diagnostic, GotoDefinitionResponse, Hover,
-    HoverContents, LanguageString, Location, MarkedString, Position, Range, SemanticTokens,
-    SemanticTokensResult, TextEdit, Url, WorkspaceEdit,
+    CodeAction, CodeActionKind, CompletionItem, CompletionItemKind, Diagnostic, DocumentSymbol,
+    DocumentSymbolResponse, Documentation, FoldingRange, GotoDefinitionResponse, Hover,
+    HoverContents, InlayHint, InlayHintKind, InlayHintLabel, LanguageString, Location,
+    MarkedString, ParameterInformation, ParameterLabel, Position, Range, SemanticTokens,
+    SemanticTokensResult, SignatureHelp, SignatureInformation, SymbolInformation, SymbolKind,
+    TextDocumentContentChangeEvent, TextEdit, Url, WorkspaceEdit,
 };
 
 use crate::{
     analysis::completion::{
-        field_completion, get_completion_items, get_module_completion_items,
-        get_tag_completion_items,
+        completion_context, expected_types_at, field_completion, find_when_at,
+        get_completion_items, get_expected_tag_completion_items, get_module_completion_items,
+        get_record_field_completion_items, get_tag_completion_items, get_type_completion_items,
+        get_unimported_completion_items, get_when_branch_completion_items, CompletionContext,
     },
-    convert::{ToRange, ToRegion, ToRocPosition},
+    convert::{diag::QuickFix, ToRange, ToRegion, ToRocPosition},
 };
 
 use super::{
-    annotation_visitor::{find_declaration_at, FoundDeclaration, NotFound},
-    parse_ast::Ast,
+    annotation_visitor::{find_declaration_at, find_unannotated_in, FoundDeclaration, NotFound},
+    missing_branches::missing_branches_text,
+    parse_ast::{is_importable_module, Ast},
+    references::{
+        exposed_name_occurrences, find_all_occurrences, find_occurrences, occurrence_at,
+        OccurrenceKind, Reference, Target,
+    },
     semantic_tokens::arrange_semantic_tokens,
+    signature::{find_call_at, parameter_offsets},
+    symbols::OutlineSymbol,
     utils::{format_var_type, is_roc_identifier_char},
     AnalysisResult, AnalyzedModule,
 };
@@ -72,6 +89,28 @@ impl DocInfo {
         );
     }
 
+    /// The document after applying `changes` to it, in order.
+    pub fn apply_changes(
+        &self,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) -> Self {
+        let mut source = self.source.clone();
+
+        for change in changes {
+            match change.range {
+                Some(Range { start, end }) => {
+                    let start = utf16_position_offset(&source, start);
+                    let end = utf16_position_offset(&source, end).max(start);
+                    source.replace_range(start..end, &change.text);
+                }
+                None => source = change.text,
+            }
+        }
+
+        Self::new(self.url.clone(), source, version)
+    }
+
     fn whole_document_range(&self) -> Range {
         let start = Position::new(0, 0);
         let end = Position::new(self.line_info.num_lines(), 0);
@@ -97,6 +136,32 @@ impl DocInfo {
         String::from(symbol)
     }
 
+    /// What's being written at `position`, where `prefix` has been typed so far.
+    pub fn completion_context(&self, position: Position, prefix: &str) -> CompletionContext {
+        let offset = position.to_roc_position(&self.line_info).offset as usize;
+        completion_context(&self.source, offset - prefix.len())
+    }
+
+    /// An edit importing `name` from `module_name`. The import goes below the existing ones, or
+    /// right below the header if there aren't any.
+    pub fn import_edit(&self, module_name: &str, name: &str) -> TextEdit {
+        let (line, has_imports) = new_import_line(&self.source);
+
+        let import = format!("import {module_name} exposing [{name}]\n");
+        let new_text = if has_imports {
+            import
+        } else {
+            // Keep the first import apart from what follows the header
+            import + "\n"
+        };
+
+        let position = Position::new(line as u32, 0);
+        TextEdit {
+            range: Range::new(position, position),
+            new_text,
+        }
+    }
+
     pub fn format(&self) -> Option<Vec<TextEdit>> {
         let source = &self.source;
         let arena = &Bump::new();
@@ -131,6 +196,148 @@ impl DocInfo {
             data,
         }))
     }
+
+    pub fn document_symbols(&self) -> Option<DocumentSymbolResponse> {
+        let arena = &Bump::new();
+        let ast = Ast::parse(arena, &self.source).ok()?;
+
+        let symbols = ast
+            .outline()
+            .into_iter()
+            .map(|symbol| self.document_symbol(symbol))
+            .collect();
+
+        Some(DocumentSymbolResponse::Nested(symbols))
+    }
+
+    #[allow(deprecated)] // `DocumentSymbol::deprecated` has to be filled in
+    fn document_symbol(&self, symbol: OutlineSymbol) -> DocumentSymbol {
+        let children = symbol
+            .children
+            .into_iter()
+            .map(|child| self.document_symbol(child))
+            .collect::<Vec<_>>();
+
+        DocumentSymbol {
+            name: symbol.name.to_string(),
+            detail: None,
+            kind: symbol.kind,
+            tags: None,
+            deprecated: None,
+            range: symbol.region.to_range(&self.line_info),
+            selection_range: symbol.name_region.to_range(&self.line_info),
+            children: (!children.is_empty()).then_some(children),
+        }
+    }
+
+    /// The symbols in this document whose name fuzzily matches `query`, for `workspace/symbol`.
+    pub fn workspace_symbols(&self, query: &str) -> Vec<SymbolInformation> {
+        let arena = &Bump::new();
+        let Ok(ast) = Ast::parse(arena, &self.source) else {
+            return vec![];
+        };
+
+        let module_name = Path::new(self.url.path())
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned());
+
+        let mut symbols = vec![];
+        let mut pending = ast
+            .outline()
+            .into_iter()
+            .map(|symbol| (module_name.clone(), symbol))
+            .collect::<Vec<_>>();
+
+        while let Some((container_name, symbol)) = pending.pop() {
+            pending.extend(
+                symbol
+                    .children
+                    .into_iter()
+                    .map(|child| (Some(symbol.name.to_string()), child)),
+            );
+
+            if !fuzzy_matches(query, symbol.name) {
+                continue;
+            }
+
+            #[allow(deprecated)] // `SymbolInformation::deprecated` has to be filled in
+            symbols.push(SymbolInformation {
+                name: symbol.name.to_string(),
+                kind: symbol.kind,
+                tags: None,
+                deprecated: None,
+                location: Location::new(
+                    self.url.clone(),
+                    symbol.name_region.to_range(&self.line_info),
+                ),
+                container_name,
+            });
+        }
+
+        symbols
+    }
+
+    pub fn folding_ranges(&self) -> Option<Vec<FoldingRange>> {
+        let arena = &Bump::new();
+        let ast = Ast::parse(arena, &self.source).ok()?;
+
+        let ranges = ast
+            .folding_regions()
+            .into_iter()
+            .map(|(region, kind)| {
+                // Don't fold away any blank lines the region ends with
+                let start = region.start().offset as usize;
+                let text = &self.source[start..region.end().offset as usize];
+                let end = RocPosition::new((start + text.trim_end().len()) as u32);
+                let range = Region::new(region.start(), end).to_range(&self.line_info);
+
+                FoldingRange {
+                    start_line: range.start.line,
+                    start_character: None,
+                    end_line: range.end.line,
+                    end_character: None,
+                    kind,
+                }
+            })
+            .filter(|range| range.start_line < range.end_line)
+            .collect();
+
+        Some(ranges)
+    }
+}
+
+/// The byte offset of `position` in `source`. Positions count UTF-16 code units, as that's
+/// what editors send unless told otherwise, and are clamped to the end of their line.
+fn utf16_position_offset(source: &str, position: Position) -> usize {
+    let line_start = source
+        .split_inclusive('\n')
+        .take(position.line as usize)
+        .map(str::len)
+        .sum::<usize>();
+
+    let line = source[line_start..].split('\n').next().unwrap_or("");
+
+    let mut column = 0;
+    let line_offset = line
+        .char_indices()
+        .find(|(_, char)| {
+            let reached = column >= position.character;
+            column += char.len_utf16() as u32;
+            reached
+        })
+        .map_or(line.len(), |(offset, _)| offset);
+
+    line_start + line_offset
+}
+
+/// Whether all the characters of `query` appear in `name` in order, ignoring case.
+fn fuzzy_matches(query: &str, name: &str) -> bool {
+    let mut name_chars = name.chars().flat_map(char::to_lowercase);
+
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|query_char| name_chars.any(|name_char| name_char == query_char))
 }
 
 impl AnalyzedDocument {
@@ -221,6 +428,89 @@ impl AnalyzedDocument {
         })
     }
 
+    pub fn signature_help(&self, position: Position) -> Option<SignatureHelp> {
+        let AnalyzedModule {
+            subs,
+            declarations,
+            module_id,
+            interns,
+            modules_info,
+            ..
+        } = self.module()?;
+
+        let position = position.to_roc_position(self.line_info());
+        let call = find_call_at(position, declarations)?;
+
+        let type_str = format_var_type(call.fn_var, &mut subs.clone(), module_id, interns);
+        let prefix = match call.fn_symbol {
+            Some(symbol) => format!("{} : ", symbol.as_str(interns)),
+            None => String::new(),
+        };
+
+        let prefix_len = prefix.encode_utf16().count() as u32;
+        let parameters = parameter_offsets(&type_str)
+            .into_iter()
+            .map(|[start, end]| ParameterInformation {
+                label: ParameterLabel::LabelOffsets([start + prefix_len, end + prefix_len]),
+                documentation: None,
+            })
+            .collect::<Vec<_>>();
+
+        let documentation = call.fn_symbol.and_then(|symbol| {
+            let docs = modules_info
+                .get_docs(&symbol.module_id())?
+                .get_doc_for_symbol(&symbol)?;
+            Some(Documentation::String(docs))
+        });
+
+        let active_parameter = call.active_argument.min(parameters.len().saturating_sub(1));
+
+        Some(SignatureHelp {
+            signatures: vec![SignatureInformation {
+                label: prefix + &type_str,
+                documentation,
+                parameters: Some(parameters),
+                active_parameter: Some(active_parameter as u32),
+            }],
+            active_signature: Some(0),
+            active_parameter: Some(active_parameter as u32),
+        })
+    }
+
+    /// The inferred types of the un-annotated defs and lambda parameters in `range`.
+    pub fn inlay_hints(&self, range: Range) -> Option<Vec<InlayHint>> {
+        let AnalyzedModule {
+            subs,
+            declarations,
+            module_id,
+            interns,
+            ..
+        } = self.module()?;
+
+        let region = range.to_region(self.line_info());
+        let mut subs = subs.clone();
+
+        let hints = find_unannotated_in(region, declarations)
+            .into_iter()
+            .map(|(name_region, var)| {
+                let type_str = format_var_type(var, &mut subs, module_id, interns);
+
+                InlayHint {
+                    position: name_region.to_range(self.line_info()).end,
+                    label: InlayHintLabel::String(format!(": {type_str}")),
+                    kind: Some(InlayHintKind::TYPE),
+                    text_edits: None,
+                    tooltip: None,
+                    padding_left: Some(true),
+                    padding_right: None,
+                    data: None,
+                }
+            })
+            .collect();
+
+        Some(hints)
+    }
+
     pub fn definition(&self, symbol: Symbol) -> Option<GotoDefinitionResponse> {
         let AnalyzedModule { declarations, .. } = self.module()?;
 
@@ -235,15 +525,251 @@ impl AnalyzedDocument {
         self.module()?.module_id_to_url.get(&module_id).cloned()
     }
 
+    /// Finds the value, tag or record field named at `position`, and the range of that name.
+    pub(crate) fn reference_at(&self, position: Position) -> Option<(Reference, Range)> {
+        let AnalyzedModule {
+            declarations,
+            interns,
+            module_id,
+            ..
+        } = self.module()?;
+
+        let roc_position = position.to_roc_position(self.line_info());
+        let source = &self.doc_info.source;
+
+        if let Some(found) = self.exposed_name_at(roc_position) {
+            return Some(found);
+        }
+
+        let occurrences = find_all_occurrences(declarations, source, interns);
+        let occurrence = self
+            .symbol_at(position)
+            .and_then(|symbol| {
+                occurrences.iter().find(|occurrence| {
+                    occurrence.target == Target::Symbol(symbol)
+                        && occurrence.region.contains_pos(roc_position)
+                })
+            })
+            .or_else(|| occurrence_at(&occurrences, roc_position))?;
+
+        let reference = match &occurrence.target {
+            Target::Symbol(symbol) => match self.top_level_name(*symbol) {
+                Some((module, ident)) => Reference::TopLevel { module, ident },
+                None => Reference::Local {
+                    url: self.url().clone(),
+                    symbol: *symbol,
+                    ident: symbol.as_str(interns).to_string(),
+                },
+            },
+            Target::Tag(name) => Reference::Tag(name.clone()),
+            Target::Field(name) => Reference::Field(name.clone()),
+        };
+
+        debug!(
+            "Found reference {:?} at {:?} in module {:?}",
+            reference, position, module_id
+        );
+
+        Some((reference, occurrence.region.to_range(self.line_info())))
+    }
+
+    /// Finds a name listed in the header's exposes or an `import ... exposing [...]` list.
+    fn exposed_name_at(&self, position: RocPosition) -> Option<(Reference, Range)> {
+        let AnalyzedModule {
+            interns, module_id, ..
+        } = self.module()?;
+
+        let arena = Bump::new();
+        let ast = Ast::parse(&arena, &self.doc_info.source).ok()?;
+
+        let own_module_name = interns.module_name(*module_id);
+        let exposed = ast
+            .exposed_names()
+            .into_iter()
+            .map(|name| (own_module_name.as_str(), name));
+
+        let (module_name, loc_name) = exposed
+            .chain(ast.imported_names())
+            .find(|(_, name)| name.region.contains_pos(position))?;
+
+        let reference = Reference::TopLevel {
+            module: ModuleName::from(module_name),
+            ident: loc_name.value.to_string(),
+        };
+
+        Some((reference, loc_name.region.to_range(self.line_info())))
+    }
+
+    /// The module and name of `symbol`, if it's defined at the top level of a module and so
+    /// may be referenced by other modules.
+    fn top_level_name(&self, symbol: Symbol) -> Option<(ModuleName, String)> {
+        let AnalyzedModule {
+            interns, module_id, ..
+        } = self.module()?;
+
+        let is_top_level = symbol.module_id() != *module_id
+            || self
+                .top_level_symbols()
+                .any(|top_level| top_level == symbol);
+
+        if is_top_level {
+            Some((
+                interns.module_name(symbol.module_id()).clone(),
+                symbol.as_str(interns).to_string(),
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn top_level_symbols(&self) -> impl Iterator<Item = Symbol> + '_ {
+        let declarations = self.module().map(|module| &module.declarations);
+
+        declarations.into_iter().flat_map(|declarations| {
+            declarations
+                .declarations
+                .iter()
+                .zip(&declarations.symbols)
+                .filter(|(tag, _)| {
+                    !matches!(
+                        tag,
+                        DeclarationTag::Expectation
+                            | DeclarationTag::Destructure(_)
+                            | DeclarationTag::MutualRecursion { .. }
+                    )
+                })
+                .map(|(_, loc_symbol)| loc_symbol.value)
+        })
+    }
+
+    /// Looks up the symbol this document uses for the top-level value `ident` of `module`.
+    fn resolve_top_level(&self, module: &ModuleName, ident: &str) -> Option<Symbol> {
+        let AnalyzedModule {
+            interns,
+            module_id,
+            imports_by_module,
+            ..
+        } = self.module()?;
+
+        let defining_module = interns.module_ids.get_id(module)?;
+
+        if defining_module == *module_id {
+            self.top_level_symbols()
+                .find(|symbol| symbol.as_str(interns) == ident)
+        } else {
+            imports_by_module
+                .get(&defining_module)?
+                .iter()
+                .map(|(symbol, _)| *symbol)
+                .find(|symbol| symbol.as_str(interns) == ident)
+        }
+    }
+
+    fn reference_occurrences(&self, reference: &Reference) -> Vec<(Region, OccurrenceKind)> {
+        let Some(AnalyzedModule {
+            declarations,
+            interns,
+            module_id,
+            ..
+        }) = self.module()
+        else {
+            return vec![];
+        };
+
+        let source = &self.doc_info.source;
+        let find = |target: Target| {
+            find_occurrences(&target, declarations, source, interns)
+                .into_iter()
+                .map(|occurrence| (occurrence.region, occurrence.kind))
+                .collect::<Vec<_>>()
+        };
+
+        match reference {
+            Reference::Local { url, symbol, .. } if url == self.url() => {
+                find(Target::Symbol(*symbol))
+            }
+            Reference::Local { .. } => vec![],
+            Reference::TopLevel { module, ident } => {
+                let mut occurrences = self
+                    .resolve_top_level(module, ident)
+                    .map(|symbol| find(Target::Symbol(symbol)))
+                    .unwrap_or_default();
+
+                let arena = Bump::new();
+                if let Ok(ast) = Ast::parse(&arena, source) {
+                    let mut names = ast
+                        .imported_names()
+                        .into_iter()
+                        .filter(|(import_module, _)| *import_module == module.as_str())
+                        .map(|(_, name)| name)
+                        .collect::<Vec<_>>();
+
+                    if interns.module_name(*module_id) == module {
+                        names.extend(ast.exposed_names());
+                    }
+
+                    occurrences.extend(
+                        exposed_name_occurrences(names, ident, source)
+                            .into_iter()
+                            .map(|region| (region, OccurrenceKind::Use)),
+                    );
+                }
+
+                occurrences.sort_by_key(|(region, _)| region.start());
+                occurrences
+            }
+            Reference::Tag(name) => find(Target::Tag(name.clone())),
+            Reference::Field(name) => find(Target::Field(name.clone())),
+        }
+    }
+
+    /// Whether `reference` is defined in this document, rather than in a builtin or a package.
+    pub(crate) fn defines(&self, reference: &Reference) -> bool {
+        match reference {
+            Reference::Local { url, .. } => url == self.url(),
+            Reference::TopLevel { module, ident } => self.module().map_or(false, |m| {
+                m.interns.module_name(m.module_id) == module
+                    && self.resolve_top_level(module, ident).is_some()
+            }),
+            Reference::Tag(_) | Reference::Field(_) => true,
+        }
+    }
+
+    /// All the places `reference` is named in this document.
+    pub(crate) fn references(
+        &self,
+        reference: &Reference,
+        include_declaration: bool,
+    ) -> Vec<Location> {
+        self.reference_occurrences(reference)
+            .into_iter()
+            .filter(|(_, kind)| include_declaration || *kind != OccurrenceKind::Definition)
+            .map(|(region, _)| self.location(region.to_range(self.line_info())))
+            .collect()
+    }
+
+    /// The edits renaming every place `reference` is named in this document to `new_name`.
+    pub(crate) fn rename_edits(&self, reference: &Reference, new_name: &str) -> Vec<TextEdit> {
+        self.reference_occurrences(reference)
+            .into_iter()
+            .map(|(region, kind)| TextEdit {
+                range: region.to_range(self.line_info()),
+                new_text: reference.rename_text(kind, new_name),
+            })
+            .collect()
+    }
+
     pub fn completion_items(
         &self,
         position: Position,
         latest_doc: &DocInfo,
+        project_documents: &[Arc<AnalyzedDocument>],
     ) -> Option<Vec<CompletionItem>> {
         let symbol_prefix = latest_doc.get_prefix_at_position(position);
+        let context = latest_doc.completion_context(position, &symbol_prefix);
         debug!(
-            "Starting to get completion items for prefix: {:?} docVersion:{:?}",
-            symbol_prefix, latest_doc.version
+            "Starting to get completion items for prefix: {:?} context: {:?} docVersion:{:?}",
+            symbol_prefix, context, latest_doc.version
         );
         let len_diff = latest_doc.source.len() as i32 - self.doc_info.source.len() as i32;
 
@@ -299,36 +825,277 @@ impl AnalyzedDocument {
             let is_module_or_type_completion =
                 symbol_prefix.starts_with(|c: char| c.is_uppercase());
 
-            if is_module_or_type_completion {
-                info!("Getting module completion...");
-                let mut completions = get_module_completion_items(
-                    &symbol_prefix,
-                    interns,
-                    imports,
-                    modules_info,
-                    true,
-                );
-                let tag_completions =
-                    get_tag_completion_items(&symbol_prefix, module_id, modules_info);
-                completions.extend(tag_completions);
-                Some(completions)
-            } else {
-                info!("Getting variable completion...");
-                let completions = get_completion_items(
-                    position,
-                    symbol_prefix,
-                    declarations,
-                    &mut subs.clone(),
-                    module_id,
-                    interns,
-                    modules_info.get_docs(module_id),
-                    exposed_imports,
-                );
-                Some(completions)
+            match context {
+                CompletionContext::Type
+                    if is_module_or_type_completion || symbol_prefix.is_empty() =>
+                {
+                    info!("Getting type completion...");
+                    let mut completions = self.type_completion_items(&symbol_prefix)?;
+                    completions.extend(get_module_completion_items(
+                        &symbol_prefix,
+                        interns,
+                        imports,
+                        modules_info,
+                        true,
+                    ));
+                    Some(completions)
+                }
+                // Anything else in an annotation is a type variable, which we can't help with
+                CompletionContext::Type => Some(vec![]),
+                CompletionContext::RecordField(written) => {
+                    info!("Getting record field completion...");
+                    let mut subs = subs.clone();
+                    let expected_types = expected_types_at(position, declarations, &subs);
+                    let mut completions = get_record_field_completion_items(
+                        &symbol_prefix,
+                        &expected_types,
+                        &written,
+                        &mut subs,
+                        module_id,
+                        interns,
+                    );
+
+                    // A field can be punned, as in `{ name }`, so any value in scope will do too
+                    completions.extend(get_completion_items(
+                        position,
+                        symbol_prefix,
+                        declarations,
+                        &mut subs,
+                        module_id,
+                        interns,
+                        modules_info.get_docs(module_id),
+                        exposed_imports,
+                    ));
+                    Some(completions)
+                }
+                CompletionContext::LineStart(column)
+                    if is_module_or_type_completion || symbol_prefix.is_empty() =>
+                {
+                    let found_when = find_when_at(position, declarations).filter(|found| {
+                        self.line_info()
+                            .convert_pos(found.first_pattern.start())
+                            .column
+                            == column
+                    });
+
+                    match found_when {
+                        Some(found) => {
+                            info!("Getting when branch completion...");
+                            Some(get_when_branch_completion_items(
+                                &symbol_prefix,
+                                &found,
+                                &mut subs.clone(),
+                                module_id,
+                                interns,
+                            ))
+                        }
+                        None => self.tag_and_module_completion_items(position, &symbol_prefix),
+                    }
+                }
+                _ if is_module_or_type_completion => {
+                    self.tag_and_module_completion_items(position, &symbol_prefix)
+                }
+                _ => {
+                    info!("Getting variable completion...");
+                    let mut completions = get_completion_items(
+                        position,
+                        symbol_prefix.clone(),
+                        declarations,
+                        &mut subs.clone(),
+                        module_id,
+                        interns,
+                        modules_info.get_docs(module_id),
+                        exposed_imports,
+                    );
+                    completions.extend(self.unimported_completion_items(
+                        &symbol_prefix,
+                        latest_doc,
+                        project_documents,
+                    ));
+                    Some(completions)
+                }
             }
         }
     }
 
+    fn tag_and_module_completion_items(
+        &self,
+        position: RocPosition,
+        prefix: &String,
+    ) -> Option<Vec<CompletionItem>> {
+        info!("Getting module and tag completion...");
+        let AnalyzedModule {
+            module_id,
+            interns,
+            subs,
+            declarations,
+            imports_by_module: imports,
+            modules_info,
+            ..
+        } = self.module()?;
+
+        // The tags of the type expected here come first, then all the others we know of
+        let mut subs = subs.clone();
+        let expected_types = expected_types_at(position, declarations, &subs);
+        let mut completions = get_expected_tag_completion_items(
+            prefix,
+            &expected_types,
+            &mut subs,
+            module_id,
+            interns,
+        );
+
+        completions.extend(get_module_completion_items(
+            prefix,
+            interns,
+            imports,
+            modules_info,
+            true,
+        ));
+
+        let tag_completions = get_tag_completion_items(prefix, module_id, modules_info)
+            .into_iter()
+            .filter(|tag| !completions.iter().any(|item| item.label == tag.label))
+            .collect::<Vec<_>>();
+        completions.extend(tag_completions);
+
+        Some(completions)
+    }
+
+    /// Completions for the types defined in and imported into this module, along with the
+    /// builtin ones.
+    fn type_completion_items(&self, prefix: &str) -> Option<Vec<CompletionItem>> {
+        let AnalyzedModule { interns, .. } = self.module()?;
+
+        let arena = Bump::new();
+        let ast = Ast::parse(&arena, &self.doc_info.source).ok()?;
+
+        let defined_types = ast.outline().into_iter().filter_map(|symbol| {
+            let kind = match symbol.kind {
+                SymbolKind::ENUM => CompletionItemKind::ENUM,
+                SymbolKind::STRUCT => CompletionItemKind::STRUCT,
+                SymbolKind::CLASS => CompletionItemKind::CLASS,
+                SymbolKind::INTERFACE => CompletionItemKind::INTERFACE,
+                _ => return None,
+            };
+            Some((symbol.name, kind))
+        });
+
+        let imported_types = ast
+            .imported_names()
+            .into_iter()
+            .map(|(_, name)| (name.value, CompletionItemKind::CLASS));
+
+        let module_types = defined_types.chain(imported_types).collect();
+
+        Some(get_type_completion_items(prefix, module_types, interns))
+    }
+
+    /// Completions for the values exposed by the project's other modules that this one doesn't
+    /// import yet. Picking one of them adds the import.
+    fn unimported_completion_items(
+        &self,
+        prefix: &str,
+        latest_doc: &DocInfo,
+        project_documents: &[Arc<AnalyzedDocument>],
+    ) -> Vec<CompletionItem> {
+        // Every value in the project would be a lot to wade through
+        if prefix.is_empty() {
+            return vec![];
+        }
+
+        let Some(AnalyzedModule {
+            module_id,
+            interns,
+            imports_by_module,
+            ..
+        }) = self.module()
+        else {
+            return vec![];
+        };
+
+        let imported_modules = imports_by_module
+            .keys()
+            .chain([module_id])
+            .map(|module_id| module_id.to_ident_str(interns).as_str())
+            .collect::<Vec<_>>();
+
+        project_documents
+            .iter()
+            .filter(|document| document.url() != self.url())
+            .filter_map(|document| {
+                let module_name = document.import_name_from(self.url())?;
+                if imported_modules.contains(&module_name.as_str()) {
+                    return None;
+                }
+
+                document.exposed_completion_items(prefix, &module_name, latest_doc)
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// The name a module next to `importer` would import this document's module by, if it can
+    /// be imported at all.
+    fn import_name_from(&self, importer: &Url) -> Option<String> {
+        if !is_importable_module(&self.doc_info.source) {
+            return None;
+        }
+
+        let path = self.url().to_file_path().ok()?;
+        let importer_path = importer.to_file_path().ok()?;
+        let relative = path
+            .strip_prefix(importer_path.parent()?)
+            .ok()?
+            .with_extension("");
+
+        let parts = relative
+            .components()
+            .map(|component| component.as_os_str().to_str())
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(parts.join("."))
+    }
+
+    /// Completions for the values this document's module exposes, for a module that's yet to
+    /// import it as `module_name`.
+    fn exposed_completion_items(
+        &self,
+        prefix: &str,
+        module_name: &str,
+        importer: &DocInfo,
+    ) -> Option<Vec<CompletionItem>> {
+        let AnalyzedModule {
+            module_id,
+            interns,
+            subs,
+            modules_info,
+            ..
+        } = self.module()?;
+
+        let exposed = modules_info
+            .exposed_by_module
+            .get(module_id)?
+            .iter()
+            .filter(|(symbol, _)| symbol.as_str(interns).starts_with(prefix))
+            .copied()
+            .collect::<Vec<_>>();
+
+        if exposed.is_empty() {
+            return None;
+        }
+
+        Some(get_unimported_completion_items(
+            module_name,
+            exposed,
+            &mut subs.clone(),
+            module_id,
+            interns,
+            modules_info.get_docs(module_id),
+            |name| importer.import_edit(module_name, name),
+        ))
+    }
+
     pub fn annotate(&self, range: Range) -> Option<CodeAction> {
         let region = range.to_region(self.line_info());
 
@@ -409,4 +1176,68 @@ impl AnalyzedDocument {
             ..Default::default()
         })
     }
+
+    /// Offers to add a branch for every pattern the innermost non-exhaustive `when` containing
+    /// `range` doesn't cover.
+    pub fn add_missing_branches(&self, range: Range) -> Option<CodeAction> {
+        let region = range.to_region(self.line_info());
+
+        let AnalyzedModule {
+            interns,
+            incomplete_whens,
+            ..
+        } = self.module()?;
+
+        let (when_region, missing) = incomplete_whens
+            .iter()
+            .filter(|(when_region, _)| when_region.contains(&region))
+            .min_by_key(|(when_region, _)| when_region.len())?;
+
+        let new_text =
+            missing_branches_text(&self.doc_info.source, *when_region, missing, interns)?;
+
+        let end = when_region.end();
+        let range = Region::new(end, end).to_range(self.line_info());
+
+        Some(CodeAction {
+            title: "Add missing branches".to_owned(),
+            edit: Some(WorkspaceEdit::new(HashMap::from([(
+                self.url().clone(),
+                vec![TextEdit { range, new_text }],
+            )]))),
+            kind: Some(CodeActionKind::QUICKFIX),
+            ..Default::default()
+        })
+    }
+
+    /// Offers the fixes the compiler suggested for each diagnostic overlapping `range`.
+    pub fn quick_fixes(&self, range: Range) -> Vec<CodeAction> {
+        self.analysis_result
+            .diagnostics
+            .iter()
+            .filter(|diag| diag.range.start <= range.end && range.start <= diag.range.end)
+            .flat_map(|diag| {
+                let quick_fixes: Vec<QuickFix> = diag
+                    .data
+                    .clone()
+                    .and_then(|data| serde_json::from_value(data).ok())
+                    .unwrap_or_default();
+                let is_preferred = quick_fixes.len() == 1;
+
+                quick_fixes
+                    .into_iter()
+                    .map(move |QuickFix { title, edits }| CodeAction {
+                        title,
+                        kind: Some(CodeActionKind::QUICKFIX),
+                        diagnostics: Some(vec![diag.clone()]),
+                        edit: Some(WorkspaceEdit::new(HashMap::from([(
+                            self.url().clone(),
+                            edits,
+                        )]))),
+                        is_preferred: Some(is_preferred),
+                        ..Default::default()
+                    })
+            })
+            .collect()
+    }
 }
@@ -1,9 +1,10 @@
 use roc_can::{
     def::{Def, DefKind},
-    expr::{Declarations, Expr},
+    expr::{AnnotatedMark, Declarations, Expr},
+    pattern::Pattern,
     traverse::{self, DeclarationInfo, Visitor},
 };
-use roc_region::all::Region;
+use roc_region::all::{Loc, Region};
 use roc_types::subs::Variable;
 use std::ops::Range;
 
@@ -88,3 +89,90 @@ pub fn find_declaration_at(
         }
     }
 }
+
+/// Everything in `region` whose type is inferred rather than written down: un-annotated defs
+/// and lambda parameters. Each comes with the region of the name the type belongs to.
+pub fn find_unannotated_in(region: Region, decls: &Declarations) -> Vec<(Region, Variable)> {
+    let mut visitor = Collector {
+        region,
+        annotated_lambdas: vec![],
+        found: vec![],
+    };
+
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Collector {
+        region: Region,
+        /// Lambdas whose parameter types are already written down in the def's annotation.
+        annotated_lambdas: Vec<Region>,
+        found: Vec<(Region, Variable)>,
+    }
+
+    impl Collector {
+        fn push_arguments(&mut self, arguments: &[(Variable, AnnotatedMark, Loc<Pattern>)]) {
+            for (var, _, loc_pattern) in arguments {
+                if let Pattern::Identifier(_) = loc_pattern.value {
+                    self.found.push((loc_pattern.region, *var));
+                }
+            }
+        }
+    }
+
+    impl Visitor for Collector {
+        fn should_visit(&mut self, region: Region) -> bool {
+            self.region.start() <= region.end() && region.start() <= self.region.end()
+        }
+
+        fn visit_decl(&mut self, decl: DeclarationInfo<'_>) {
+            if self.should_visit(decl.region()) {
+                match decl {
+                    DeclarationInfo::Value { loc_expr, .. }
+                        if matches!(loc_expr.value, Expr::ImportParams(..)) => {}
+                    DeclarationInfo::Value {
+                        expr_var: var,
+                        loc_symbol,
+                        annotation: None,
+                        ..
+                    } => self.found.push((loc_symbol.region, var)),
+                    DeclarationInfo::Function {
+                        expr_var: var,
+                        loc_symbol,
+                        function,
+                        annotation: None,
+                        ..
+                    } => {
+                        self.found.push((loc_symbol.region, var));
+                        self.push_arguments(&function.value.arguments);
+                    }
+                    _ => {}
+                }
+                traverse::walk_decl(self, decl)
+            }
+        }
+
+        fn visit_def(&mut self, def: &Def) {
+            if self.should_visit(def.region()) {
+                match (&def.loc_pattern.value, &def.annotation) {
+                    (_, Some(_)) => self.annotated_lambdas.push(def.loc_expr.region),
+                    (Pattern::Identifier(_), None) => {
+                        self.found.push((def.loc_pattern.region, def.expr_var))
+                    }
+                    _ => {}
+                }
+                traverse::walk_def(self, def)
+            }
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if self.should_visit(region) {
+                if let Expr::Closure(closure) = expr {
+                    if !self.annotated_lambdas.contains(&region) {
+                        self.push_arguments(&closure.arguments);
+                    }
+                }
+                traverse::walk_expr(self, expr, var)
+            }
+        }
+    }
+}
@@ -1,21 +1,32 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use log::{debug, warn};
 
 use roc_can::{expr::Declarations, traverse::Visitor};
-use roc_collections::MutMap;
 use roc_load::docs::{DocDef, ModuleDocumentation};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_region::all::Position;
-use roc_types::{
-    subs::{Subs, Variable},
-    types::Alias,
+use roc_types::subs::{Content, FlatType, Subs, Variable};
+use tower_lsp::lsp_types::{
+    self, CompletionItem, CompletionItemKind, CompletionItemLabelDetails, TextEdit,
+};
+
+use self::{
+    expected::{resolve_alias, tag_union_tags},
+    visitor::CompletionVisitor,
 };
-use tower_lsp::lsp_types::{self, CompletionItem, CompletionItemKind};
 
-use self::visitor::CompletionVisitor;
+pub(super) use self::{
+    context::{completion_context, CompletionContext},
+    expected::{expected_types_at, find_when_at, FoundWhen},
+};
 
 use super::{utils::format_var_type, ModulesInfo};
+mod context;
+mod expected;
 mod formatting;
 mod visitor;
 
@@ -201,28 +212,171 @@ fn get_completion_docs(
         .collect()
 }
 
-/// Provides a list of completions for Type aliases within the scope.
-///TODO: Use this when we know we are within a type definition
-fn _alias_completions(
-    aliases: &MutMap<Symbol, (bool, Alias)>,
+/// Completions for the types that can be written in an annotation: the ones `module_types`
+/// lists, followed by the builtin types that are always in scope.
+pub(super) fn get_type_completion_items(
+    prefix: &str,
+    module_types: Vec<(&str, CompletionItemKind)>,
+    interns: &Interns,
+) -> Vec<CompletionItem> {
+    let builtin_types = interns
+        .module_ids
+        .iter()
+        .filter(|(module_id, _)| module_id.is_builtin())
+        .flat_map(|(module_id, _)| Symbol::builtin_types_in_scope(module_id))
+        .map(|(name, _)| (*name, CompletionItemKind::CLASS));
+
+    let mut seen = HashSet::new();
+
+    module_types
+        .into_iter()
+        .chain(builtin_types)
+        .filter(|(name, _)| {
+            name.starts_with(|c: char| c.is_ascii_uppercase())
+                && name.starts_with(prefix)
+                && seen.insert(*name)
+        })
+        .map(|(name, kind)| CompletionItem {
+            label: name.to_string(),
+            kind: Some(kind),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Completions for the tags of the innermost of the `expected_types` that's a tag union.
+pub(super) fn get_expected_tag_completion_items(
+    prefix: &str,
+    expected_types: &[Variable],
+    subs: &mut Subs,
     module_id: &ModuleId,
     interns: &Interns,
 ) -> Vec<CompletionItem> {
-    aliases
+    let Some((expected_var, tags)) = expected_types
         .iter()
-        .filter(|(symbol, (_exposed, _alias))| &symbol.module_id() == module_id)
-        .map(|(symbol, (_exposed, _alias))| {
-            let name = symbol.as_str(interns).to_string();
+        .rev()
+        .map(|var| (*var, tag_union_tags(*var, subs)))
+        .find(|(_, tags)| !tags.is_empty())
+    else {
+        return vec![];
+    };
+
+    let type_str = format_var_type(expected_var, subs, module_id, interns);
+
+    tags.into_iter()
+        .map(|(tag_name, _)| tag_name.as_ident_str().to_string())
+        .filter(|tag_name| tag_name.starts_with(prefix))
+        .map(|tag_name| CompletionItem {
+            label: tag_name,
+            kind: Some(CompletionItemKind::ENUM_MEMBER),
+            detail: Some(type_str.clone()),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Completions for the pattern of a new branch of the `when` in `found`, one for each tag of
+/// the condition's type that none of its branches match yet.
+pub(super) fn get_when_branch_completion_items(
+    prefix: &str,
+    found: &FoundWhen,
+    subs: &mut Subs,
+    module_id: &ModuleId,
+    interns: &Interns,
+) -> Vec<CompletionItem> {
+    let tags = tag_union_tags(found.cond_var, subs);
+    if tags.is_empty() {
+        return vec![];
+    }
+
+    let type_str = format_var_type(found.cond_var, subs, module_id, interns);
+
+    tags.into_iter()
+        .filter(|(tag_name, _)| {
+            !found.matched_tags.contains(tag_name) && tag_name.as_ident_str().starts_with(prefix)
+        })
+        .map(|(tag_name, payload_len)| {
+            let pattern = std::iter::once(tag_name.as_ident_str().to_string())
+                .chain(std::iter::repeat("_".to_string()).take(payload_len))
+                .collect::<Vec<_>>()
+                .join(" ");
+
             CompletionItem {
-                label: name.clone(),
-                detail: Some(name + "we don't know how to print types "),
-                kind: Some(CompletionItemKind::CLASS),
+                insert_text: Some(format!("{pattern} -> ")),
+                label: pattern,
+                kind: Some(CompletionItemKind::ENUM_MEMBER),
+                detail: Some(type_str.clone()),
                 ..Default::default()
             }
         })
         .collect()
 }
 
+/// Completions for the fields of the innermost of the `expected_types` that's a record,
+/// leaving out the ones already `written`.
+pub(super) fn get_record_field_completion_items(
+    prefix: &str,
+    expected_types: &[Variable],
+    written: &[String],
+    subs: &mut Subs,
+    module_id: &ModuleId,
+    interns: &Interns,
+) -> Vec<CompletionItem> {
+    let record_var = expected_types
+        .iter()
+        .rev()
+        .map(|var| resolve_alias(*var, subs))
+        .find(|var| {
+            matches!(
+                subs.get_content_without_compacting(*var),
+                Content::Structure(FlatType::Record(..))
+            )
+        });
+
+    let Some(record_var) = record_var else {
+        return vec![];
+    };
+
+    let fields = find_record_fields(record_var, subs)
+        .into_iter()
+        .filter(|(field, _)| field.starts_with(prefix) && !written.contains(field))
+        .collect();
+
+    make_completion_items_string(subs, module_id, interns, fields)
+        .into_iter()
+        .map(|item| CompletionItem {
+            kind: Some(CompletionItemKind::FIELD),
+            ..item
+        })
+        .collect()
+}
+
+/// Completions for values exposed by `module_name`, which isn't imported yet. Picking one
+/// also applies its `import_edit`, so it's imported along the way.
+pub(super) fn get_unimported_completion_items(
+    module_name: &str,
+    exposed: Vec<(Symbol, Variable)>,
+    subs: &mut Subs,
+    module_id: &ModuleId,
+    interns: &Interns,
+    docs: Option<&ModuleDocumentation>,
+    import_edit: impl Fn(&str) -> TextEdit,
+) -> Vec<CompletionItem> {
+    make_completion_items(subs, module_id, interns, docs, exposed)
+        .into_iter()
+        .map(|item| CompletionItem {
+            label_details: Some(CompletionItemLabelDetails {
+                detail: None,
+                description: Some(format!("import {module_name}")),
+            }),
+            // Rank them below everything that's already in scope
+            sort_text: Some(format!("~{}", item.label)),
+            additional_text_edits: Some(vec![import_edit(&item.label)]),
+            ..item
+        })
+        .collect()
+}
+
 fn make_completion_items(
     subs: &mut Subs,
     module_id: &ModuleId,
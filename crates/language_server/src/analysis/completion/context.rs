@@ -0,0 +1,137 @@
+/// What's being written where completion was requested. The document is usually in the
+/// middle of being edited and won't parse, so this is worked out from the raw text before
+/// the cursor.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CompletionContext {
+    /// A type, in an annotation or a type definition.
+    Type,
+    /// The name of a field of a record being created or destructured, along with the names
+    /// of the fields it already has.
+    RecordField(Vec<String>),
+    /// The first thing on a line, starting at the given column, like the pattern of a new
+    /// `when` branch.
+    LineStart(u32),
+    /// Anything else.
+    Expression,
+}
+
+/// Works out the context of the word being completed, which starts at `prefix_start`.
+pub fn completion_context(source: &str, prefix_start: usize) -> CompletionContext {
+    let before = &source[..prefix_start];
+    let open_brackets = open_brackets(before);
+
+    // Brackets can span lines, so the statement starts on the line of the outermost one
+    let statement_end = open_brackets
+        .first()
+        .map_or(prefix_start, |(offset, _)| *offset);
+    let statement_start = line_start(before, statement_end);
+
+    if is_annotation(&before[statement_start..statement_end]) {
+        return CompletionContext::Type;
+    }
+
+    if let Some((open, '{')) = open_brackets.last() {
+        let inside = &before[open + 1..];
+
+        if inside.trim_end().ends_with([',', '&']) || inside.trim().is_empty() {
+            return CompletionContext::RecordField(written_fields(inside));
+        }
+    }
+
+    let line = &before[line_start(before, prefix_start)..];
+    if line.trim().is_empty() {
+        CompletionContext::LineStart(line.len() as u32)
+    } else {
+        CompletionContext::Expression
+    }
+}
+
+fn line_start(source: &str, offset: usize) -> usize {
+    source[..offset]
+        .rfind('\n')
+        .map_or(0, |newline| newline + 1)
+}
+
+/// The offsets of the brackets in `source` that haven't been closed, outermost first.
+/// Brackets in strings and comments don't count.
+fn open_brackets(source: &str) -> Vec<(usize, char)> {
+    let mut open = vec![];
+    let mut in_string = false;
+    let mut in_comment = false;
+    let mut escaped = false;
+
+    for (offset, char) in source.char_indices() {
+        if in_comment {
+            in_comment = char != '\n';
+            continue;
+        }
+        if in_string {
+            match char {
+                '\\' => escaped = !escaped,
+                '"' if !escaped => in_string = false,
+                _ => escaped = false,
+            }
+            continue;
+        }
+
+        match char {
+            '"' => in_string = true,
+            '#' => in_comment = true,
+            '(' | '[' | '{' => open.push((offset, char)),
+            ')' | ']' | '}' => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+
+    open
+}
+
+/// Whether `statement` is the start of an annotation or type definition, like `name : ` or
+/// `Pair a b := `.
+fn is_annotation(statement: &str) -> bool {
+    let Some((name, _)) = statement.trim_start().split_once(':') else {
+        return false;
+    };
+
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ' ')
+}
+
+/// The names of the fields already written in `fields`, the contents of a record up to the
+/// cursor. For `{ name: "Ayo", age: 42, ` that's `name` and `age`.
+fn written_fields(fields: &str) -> Vec<String> {
+    // In an update like `{ user & name: "Ayo" }`, the fields come after the `&`
+    let fields = fields.rsplit_once('&').map_or(fields, |(_, fields)| fields);
+
+    // Field values can have commas of their own, so only split at the record's
+    let mut depth = 0;
+    let mut segments = vec![];
+    let mut segment_start = 0;
+    for (offset, char) in fields.char_indices() {
+        match char {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                segments.push(&fields[segment_start..offset]);
+                segment_start = offset + 1;
+            }
+            _ => {}
+        }
+    }
+
+    segments
+        .into_iter()
+        .filter_map(|segment| {
+            let segment = segment.trim_start();
+            let name_len = segment
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(segment.len());
+
+            (name_len > 0).then(|| segment[..name_len].to_string())
+        })
+        .collect()
+}
@@ -0,0 +1,198 @@
+use roc_can::{
+    expr::{Declarations, Expr, WhenBranch},
+    pattern::Pattern,
+    traverse::{walk_expr, walk_pattern, Visitor},
+};
+use roc_module::{ident::TagName, symbol::Symbol};
+use roc_region::all::{Position, Region};
+use roc_types::{
+    subs::{Content, FlatType, GetSubsSlice, Subs, Variable},
+    types::AliasKind,
+};
+
+/// Finds the types the code at `position` is expected to have, outermost first: the type
+/// of each expression and pattern around it, along with the parameter type wherever it's an
+/// argument to a function.
+pub fn expected_types_at(position: Position, decls: &Declarations, subs: &Subs) -> Vec<Variable> {
+    let mut visitor = ExpectedTypeVisitor {
+        position,
+        decls,
+        subs,
+        found: vec![],
+    };
+
+    visitor.visit_decls(decls);
+    visitor.found
+}
+
+struct ExpectedTypeVisitor<'a> {
+    position: Position,
+    decls: &'a Declarations,
+    subs: &'a Subs,
+    found: Vec<Variable>,
+}
+
+impl ExpectedTypeVisitor<'_> {
+    /// The type of the `index`th parameter of the function called by `fn_expr`.
+    fn parameter_type(&self, fn_var: Variable, fn_expr: &Expr, index: usize) -> Option<Variable> {
+        // A type mismatch at the call site ruins `fn_var`, but not the function's own type
+        let fn_var = match fn_expr {
+            Expr::Var(symbol, _) => self.declared_type(*symbol).unwrap_or(fn_var),
+            _ => fn_var,
+        };
+
+        match self
+            .subs
+            .get_content_without_compacting(resolve_alias(fn_var, self.subs))
+        {
+            Content::Structure(FlatType::Func(arguments, ..)) => {
+                self.subs.get_subs_slice(*arguments).get(index).copied()
+            }
+            _ => None,
+        }
+    }
+
+    fn declared_type(&self, symbol: Symbol) -> Option<Variable> {
+        let index = self
+            .decls
+            .symbols
+            .iter()
+            .position(|loc_symbol| loc_symbol.value == symbol)?;
+
+        self.decls.variables.get(index).copied()
+    }
+}
+
+impl Visitor for ExpectedTypeVisitor<'_> {
+    fn should_visit(&mut self, region: Region) -> bool {
+        region.contains_pos(self.position)
+    }
+
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        if region.contains_pos(self.position) {
+            self.found.push(var);
+
+            if let Expr::Call(call, args, _) = expr {
+                let (fn_var, fn_expr, ..) = &**call;
+
+                if fn_expr.region.end() < self.position {
+                    // Past the last argument means we're about to write the next one
+                    let index = args
+                        .iter()
+                        .position(|(_, arg)| self.position <= arg.region.end())
+                        .unwrap_or(args.len());
+
+                    let parameter_type = self.parameter_type(*fn_var, &fn_expr.value, index);
+                    self.found.extend(parameter_type);
+                }
+            }
+
+            walk_expr(self, expr, var);
+        }
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern, region: Region, opt_var: Option<Variable>) {
+        if region.contains_pos(self.position) {
+            self.found.extend(pattern.opt_var().or(opt_var));
+
+            walk_pattern(self, pattern);
+        }
+    }
+}
+
+/// A `when` whose branches `position` is among.
+pub struct FoundWhen {
+    pub cond_var: Variable,
+    /// The first pattern of the first branch, which the patterns of new branches line up with.
+    pub first_pattern: Region,
+    /// The tags the branches already match, whatever their payload.
+    pub matched_tags: Vec<TagName>,
+}
+
+/// Finds the innermost `when` whose region includes `position`.
+pub fn find_when_at(position: Position, decls: &Declarations) -> Option<FoundWhen> {
+    let mut visitor = Finder {
+        position,
+        found: None,
+    };
+
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        position: Position,
+        found: Option<FoundWhen>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, region: Region) -> bool {
+            region.contains_pos(self.position)
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if region.contains_pos(self.position) {
+                if let Expr::When {
+                    cond_var, branches, ..
+                } = expr
+                {
+                    if let Some(first_branch) = branches.first() {
+                        let matched_tags = branches
+                            .iter()
+                            .filter(|branch| branch.guard.is_none())
+                            .flat_map(matched_tags)
+                            .collect();
+
+                        self.found = Some(FoundWhen {
+                            cond_var: *cond_var,
+                            first_pattern: first_branch.patterns[0].pattern.region,
+                            matched_tags,
+                        });
+                    }
+                }
+
+                walk_expr(self, expr, var);
+            }
+        }
+    }
+
+    fn matched_tags(branch: &WhenBranch) -> impl Iterator<Item = TagName> + '_ {
+        branch
+            .patterns
+            .iter()
+            .filter_map(|branch_pattern| match &branch_pattern.pattern.value {
+                Pattern::AppliedTag { tag_name, .. } => Some(tag_name.clone()),
+                _ => None,
+            })
+    }
+}
+
+/// Looks through structural aliases, which is as far as the tags of a type are visible.
+pub fn resolve_alias(mut var: Variable, subs: &Subs) -> Variable {
+    loop {
+        match subs.get_content_without_compacting(var) {
+            Content::Alias(_, _, actual, AliasKind::Structural) => var = *actual,
+            Content::RecursionVar { structure, .. } => var = *structure,
+            _ => return var,
+        }
+    }
+}
+
+/// The tags of `var`'s type, with the number of values each carries, if it's a tag union.
+pub fn tag_union_tags(var: Variable, subs: &Subs) -> Vec<(TagName, usize)> {
+    match subs.get_content_without_compacting(resolve_alias(var, subs)) {
+        Content::Structure(
+            FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext),
+        ) => match roc_types::types::gather_tags_unsorted_iter(subs, *tags, *ext) {
+            Ok((tags, _)) => tags
+                .map(|(tag_name, payload)| (tag_name.clone(), payload.len()))
+                .collect(),
+            Err(_) => vec![],
+        },
+        Content::Structure(FlatType::FunctionOrTagUnion(tag_names, _, _)) => subs
+            .get_subs_slice(*tag_names)
+            .iter()
+            .map(|tag_name| (tag_name.clone(), 0))
+            .collect(),
+        _ => vec![],
+    }
+}
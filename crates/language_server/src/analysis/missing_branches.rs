@@ -0,0 +1,167 @@
+use bumpalo::Bump;
+use roc_can::exhaustive::NONEXHAUSIVE_CTOR;
+use roc_exhaustive::{CtorName, ListArity, Literal, Pattern, RenderAs};
+use roc_fmt::{annotation::Formattable, Buf, MigrationFlags};
+use roc_module::{called_via::CalledVia, symbol::Interns};
+use roc_parse::ast::{self, Collection, Expr, StrLiteral, WhenBranch};
+use roc_region::all::{Loc, Region};
+
+/// Renders a branch for each of the `missing` patterns of the `when` at `when_region`, to be
+/// inserted right after its last branch.
+///
+/// The branches are formatted by roc_fmt as part of a stand-in `when`, so they come out exactly
+/// as the formatter would lay them out at the indentation of the existing branches.
+pub(super) fn missing_branches_text(
+    source: &str,
+    when_region: Region,
+    missing: &[Pattern],
+    interns: &Interns,
+) -> Option<String> {
+    let arena = Bump::new();
+
+    let crash = arena.alloc(Loc::at_zero(Expr::Crash));
+    let todo = &*arena.alloc(Loc::at_zero(Expr::Str(StrLiteral::PlainLine("todo"))));
+    let body = Loc::at_zero(Expr::Apply(crash, arena.alloc([todo]), CalledVia::Space));
+
+    let branches = missing
+        .iter()
+        .map(|pattern| {
+            let pattern = to_ast_pattern(&arena, pattern, interns);
+            &*arena.alloc(WhenBranch {
+                patterns: arena.alloc([Loc::at_zero(pattern)]),
+                value: body,
+                guard: None,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let condition = arena.alloc(Loc::at_zero(Expr::Var {
+        module_name: "",
+        ident: "x",
+    }));
+    let when = Expr::When(condition, arena.alloc_slice_copy(&branches));
+
+    let flags = MigrationFlags {
+        snakify: false,
+        parens_and_commas: false,
+    };
+    let mut buf = Buf::new_in(&arena, flags);
+    when.format(&mut buf, when_indent(source, when_region));
+
+    // Drop the stand-in `when x is` line; the branches go into the existing `when`
+    let (_, branches_text) = buf.into_bump_str().split_once('\n')?;
+
+    Some(format!("\n{}", branches_text.trim_end()))
+}
+
+/// The indentation the formatter would have given the `when` keyword, worked out from the
+/// indentation of its existing branches so the new ones line up with them.
+fn when_indent(source: &str, when_region: Region) -> u16 {
+    let start = when_region.start().offset as usize;
+    let end = when_region.end().offset as usize;
+    let indent_of = |line: &str| (line.len() - line.trim_start().len()) as u16;
+
+    let first_branch = source.get(start..end).and_then(|when_source| {
+        when_source
+            .lines()
+            .skip(1)
+            .find(|line| !line.trim().is_empty())
+    });
+
+    match first_branch {
+        Some(line) => indent_of(line).saturating_sub(4),
+        None => {
+            let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+            indent_of(&source[line_start..start])
+        }
+    }
+}
+
+fn to_ast_pattern<'a>(arena: &'a Bump, pattern: &Pattern, interns: &Interns) -> ast::Pattern<'a> {
+    let loc_patterns = |patterns: &mut dyn Iterator<Item = ast::Pattern<'a>>| {
+        let patterns = patterns.map(Loc::at_zero).collect::<Vec<_>>();
+        &*arena.alloc_slice_clone(&patterns)
+    };
+
+    match pattern {
+        Pattern::Anything => ast::Pattern::Underscore(""),
+        Pattern::Literal(literal) => literal_pattern(arena, literal),
+        Pattern::List(arity, patterns) => {
+            let mut items = patterns
+                .iter()
+                .map(|pattern| to_ast_pattern(arena, pattern, interns))
+                .collect::<Vec<_>>();
+
+            if let ListArity::Slice(before, _) = arity {
+                items.insert(*before, ast::Pattern::ListRest(None));
+            }
+
+            ast::Pattern::List(Collection::with_items(loc_patterns(&mut items.into_iter())))
+        }
+        Pattern::Ctor(union, tag_id, args) => {
+            let mut args = args.iter().map(|arg| to_ast_pattern(arena, arg, interns));
+
+            match &union.render_as {
+                // #Guard <fake-condition-tag> <unexhausted-pattern>
+                RenderAs::Guard => args.nth(1).unwrap_or(ast::Pattern::Underscore("")),
+                RenderAs::Record(field_names) => {
+                    let mut fields = field_names.iter().zip(args).map(|(label, arg)| {
+                        let label = arena.alloc_str(label.as_str());
+                        match arg {
+                            ast::Pattern::Underscore(_) => {
+                                ast::Pattern::Identifier { ident: label }
+                            }
+                            _ => ast::Pattern::RequiredField(label, arena.alloc(Loc::at_zero(arg))),
+                        }
+                    });
+
+                    ast::Pattern::RecordDestructure(Collection::with_items(loc_patterns(
+                        &mut fields,
+                    )))
+                }
+                RenderAs::Tuple => {
+                    ast::Pattern::Tuple(Collection::with_items(loc_patterns(&mut args)))
+                }
+                RenderAs::Tag | RenderAs::Opaque => {
+                    let name = match &union.alternatives[tag_id.0 as usize].name {
+                        CtorName::Tag(tag) if tag.0.as_str() == NONEXHAUSIVE_CTOR => {
+                            return ast::Pattern::Underscore("");
+                        }
+                        CtorName::Tag(tag) => ast::Pattern::Tag(arena.alloc_str(tag.0.as_str())),
+                        CtorName::Opaque(opaque) => ast::Pattern::OpaqueRef(
+                            arena.alloc_str(&format!("@{}", opaque.as_str(interns))),
+                        ),
+                    };
+
+                    let args = loc_patterns(&mut args);
+                    if args.is_empty() {
+                        name
+                    } else {
+                        ast::Pattern::Apply(arena.alloc(Loc::at_zero(name)), args)
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn literal_pattern<'a>(arena: &'a Bump, literal: &Literal) -> ast::Pattern<'a> {
+    match literal {
+        Literal::Int(int) => {
+            ast::Pattern::NumLiteral(arena.alloc_str(&i128::from_ne_bytes(*int).to_string()))
+        }
+        Literal::U128(int) => {
+            ast::Pattern::NumLiteral(arena.alloc_str(&u128::from_ne_bytes(*int).to_string()))
+        }
+        Literal::Byte(byte) => ast::Pattern::NumLiteral(arena.alloc_str(&byte.to_string())),
+        Literal::Float(bits) => {
+            ast::Pattern::FloatLiteral(arena.alloc_str(&f64::from_bits(*bits).to_string()))
+        }
+        Literal::Str(string) => {
+            ast::Pattern::StrLiteral(StrLiteral::PlainLine(arena.alloc_str(string)))
+        }
+        // Bools aren't a tag union, and decimals can't be written back exactly,
+        // so there's nothing more specific than a catch-all we can suggest.
+        Literal::Bit(_) | Literal::Decimal(_) => ast::Pattern::Underscore(""),
+    }
+}
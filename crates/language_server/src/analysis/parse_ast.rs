@@ -1,21 +1,26 @@
 use bumpalo::Bump;
 use roc_fmt::{Buf, MigrationFlags};
 use roc_parse::{
-    ast::{Defs, Header, SpacesBefore},
-    header::parse_module_defs,
+    ast::{Collection, Defs, Header, Spaced, SpacesBefore, ValueDef},
+    header::{parse_module_defs, ExposedName},
     parser::SyntaxError,
 };
-use roc_region::all::Loc;
+use roc_region::all::{Loc, Position, Region};
+use tower_lsp::lsp_types::FoldingRangeKind;
 
 use self::format::FormattedAst;
 
-use super::tokens::{IterTokens, Token};
+use super::{
+    symbols::{folding_regions, outline, OutlineSymbol},
+    tokens::{IterTokens, Token},
+};
 
 mod format;
 
 pub struct Ast<'a> {
     arena: &'a Bump,
     module: SpacesBefore<'a, Header<'a>>,
+    header_region: Region,
     defs: Defs<'a>,
 }
 
@@ -26,6 +31,7 @@ impl<'a> Ast<'a> {
         let (module, state) = parse_header(arena, State::new(src.as_bytes()))
             .map_err(|e| SyntaxError::Header(e.problem))?;
 
+        let header_region = Region::new(Position::zero(), state.pos());
         let (header, defs) = module.item.upgrade_header_imports(arena);
 
         let defs = parse_module_defs(arena, state, defs)?;
@@ -35,6 +41,7 @@ impl<'a> Ast<'a> {
                 before: module.before,
                 item: header,
             },
+            header_region,
             defs,
             arena,
         })
@@ -58,4 +65,67 @@ impl<'a> Ast<'a> {
 
         header_tokens.into_iter().chain(body_tokens)
     }
+
+    pub fn outline(&self) -> Vec<OutlineSymbol<'a>> {
+        outline(&self.defs)
+    }
+
+    pub fn folding_regions(&self) -> Vec<(Region, Option<FoldingRangeKind>)> {
+        folding_regions(self.header_region, &self.defs)
+    }
+
+    /// The names this module exposes in its header.
+    pub fn exposed_names(&self) -> Vec<Loc<&'a str>> {
+        let exposes = match &self.module.item {
+            Header::Module(header) => &header.exposes,
+            Header::App(header) => &header.provides,
+            Header::Hosted(header) => &header.exposes,
+            Header::Platform(header) => &header.provides.item,
+            Header::Package(_) => return vec![],
+        };
+
+        exposed_name_locs(exposes).collect()
+    }
+
+    /// The names brought into scope by `import Module exposing [...]`, with the name of the
+    /// module each one was imported from.
+    pub fn imported_names(&self) -> Vec<(&'a str, Loc<&'a str>)> {
+        self.defs
+            .value_defs
+            .iter()
+            .filter_map(|def| match def {
+                ValueDef::ModuleImport(import) => {
+                    let module_name = import.name.value.name.as_str();
+                    let exposed = import.exposed.as_ref()?;
+                    Some(exposed_name_locs(&exposed.item).map(move |name| (module_name, name)))
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+}
+
+/// Whether `src` is a module other modules can import, as opposed to an app or platform.
+pub fn is_importable_module(src: &str) -> bool {
+    with_header(src, |header, _| matches!(header, Header::Module(_))).unwrap_or(false)
+}
+
+/// Parses just the header of `src`, which is a lot quicker than parsing the whole module.
+fn with_header<T>(src: &str, f: impl FnOnce(&Header, Position) -> T) -> Option<T> {
+    use roc_parse::{header::parse_header, state::State};
+
+    let arena = Bump::new();
+    let (module, state) = parse_header(&arena, State::new(src.as_bytes())).ok()?;
+
+    Some(f(&module.item, state.pos()))
+}
+
+fn exposed_name_locs<'a>(
+    collection: &Collection<'a, Loc<Spaced<'a, ExposedName<'a>>>>,
+) -> impl Iterator<Item = Loc<&'a str>> {
+    collection
+        .items
+        .iter()
+        .map(|loc_name| Loc::at(loc_name.region, (*loc_name.value.item()).into()))
 }
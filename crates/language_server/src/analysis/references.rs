@@ -0,0 +1,446 @@
+use roc_can::{
+    def::{Annotation, Def},
+    expr::{Declarations, Expr, Field},
+    pattern::{DestructType, Pattern, RecordDestruct},
+    traverse::{self, DeclarationInfo, Visitor},
+};
+use roc_module::{
+    ident::{Lowercase, ModuleName},
+    symbol::{Interns, Symbol},
+};
+use roc_parse::keyword;
+use roc_region::all::{Loc, Position, Region};
+use roc_types::{subs::Variable, types::IndexOrField};
+use tower_lsp::lsp_types::Url;
+
+use super::utils::is_roc_identifier_char;
+
+/// Words that aren't keywords everywhere, but that a value still can't be named since they are
+/// keywords in headers, imports, or types.
+const RESERVED_NAMES: [&str; 4] = [
+    keyword::EXPOSING,
+    keyword::IMPLEMENTS,
+    keyword::WHERE,
+    keyword::PLATFORM,
+];
+
+/// Something that can be referenced by name in a module.
+/// Values are identified by their symbol; tags and record fields are structural, so they are
+/// identified only by their name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Target {
+    Symbol(Symbol),
+    Tag(String),
+    Field(String),
+}
+
+/// A [Target] that can be looked for in documents from other analyses, whose symbols may not
+/// line up with the ones of the document it was found in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Reference {
+    /// A value that can't be seen outside of the document it's defined in.
+    Local {
+        url: Url,
+        symbol: Symbol,
+        ident: String,
+    },
+    /// A value defined at the top level of `module`, which other modules can import.
+    TopLevel {
+        module: ModuleName,
+        ident: String,
+    },
+    Tag(String),
+    Field(String),
+}
+
+impl Reference {
+    pub fn name(&self) -> &str {
+        match self {
+            Reference::Local { ident, .. } | Reference::TopLevel { ident, .. } => ident,
+            Reference::Tag(name) | Reference::Field(name) => name,
+        }
+    }
+
+    /// The text that should replace an occurrence of `kind` when renaming to `new_name`.
+    pub fn rename_text(&self, kind: OccurrenceKind, new_name: &str) -> String {
+        match (self, kind) {
+            // `{ x }` becomes `{ newName: x }`
+            (Reference::Field(old_name), OccurrenceKind::FieldShorthand) => {
+                format!("{new_name}: {old_name}")
+            }
+            // `{ x }` becomes `{ x: newName }`
+            (_, OccurrenceKind::FieldShorthand) => format!("{}: {new_name}", self.name()),
+            _ => new_name.to_string(),
+        }
+    }
+
+    /// Whether `name` could replace this reference's name without changing what kind of
+    /// identifier it is.
+    pub fn is_valid_new_name(&self, name: &str) -> bool {
+        let Some(first) = name.chars().next() else {
+            return false;
+        };
+
+        let starts_correctly = match self {
+            Reference::Tag(_) => first.is_uppercase(),
+            _ => first.is_lowercase(),
+        };
+
+        let base_name = name.trim_end_matches('!');
+
+        starts_correctly
+            && base_name.chars().all(|c| c.is_alphanumeric() || c == '_')
+            && keyword::is_allowed_identifier(base_name)
+            && !RESERVED_NAMES.contains(&base_name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OccurrenceKind {
+    /// The name is introduced here, e.g. the left-hand side of a def or a pattern binding.
+    Definition,
+    Use,
+    /// A record field written in shorthand, e.g. `{ x }`, which names both a field and a value.
+    FieldShorthand,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Occurrence {
+    pub target: Target,
+    /// The region of just the name, e.g. `bar` in `Foo.bar`.
+    pub region: Region,
+    pub kind: OccurrenceKind,
+}
+
+/// Which end of a region a name is expected to be found at.
+#[derive(Clone, Copy)]
+enum Side {
+    Start,
+    End,
+}
+
+/// Narrows `region` to the bytes spelling `name`, if `name` is really written there.
+/// Desugared expressions can carry regions of unrelated source (e.g. the `+` of a binop),
+/// so checking against the source text is what makes the found regions safe to edit.
+fn name_region(source: &str, region: Region, name: &str, side: Side) -> Option<Region> {
+    let region_start = region.start().offset as usize;
+    let region_end = region.end().offset as usize;
+
+    if name.is_empty() || region_end < region_start + name.len() {
+        return None;
+    }
+
+    let (start, end) = match side {
+        Side::Start => (region_start, region_start + name.len()),
+        Side::End => (region_end - name.len(), region_end),
+    };
+
+    if source.get(start..end)? != name {
+        return None;
+    }
+
+    // Make sure we didn't match the prefix or suffix of a longer identifier
+    let before = source[..start].chars().next_back();
+    let after = source[end..].chars().next();
+    let is_ident_char = |c: char| c != '.' && (is_roc_identifier_char(&c) || c == '_');
+    if before.map_or(false, is_ident_char) || after.map_or(false, is_ident_char) {
+        return None;
+    }
+
+    Some(Region::new(
+        Position::new(start as u32),
+        Position::new(end as u32),
+    ))
+}
+
+/// Like [name_region] with [Side::Start], for names that were parsed rather than canonicalized.
+pub(crate) fn name_region_at_start(source: &str, region: Region, name: &str) -> Option<Region> {
+    name_region(source, region, name, Side::Start)
+}
+
+/// Collects every occurrence of a value, tag or record field name in `decls`.
+pub(crate) fn find_all_occurrences(
+    decls: &Declarations,
+    source: &str,
+    interns: &Interns,
+) -> Vec<Occurrence> {
+    let mut visitor = Collector {
+        source,
+        interns,
+        found: Vec::new(),
+    };
+    visitor.visit_decls(decls);
+
+    let mut found = visitor.found;
+    found.sort_by_key(|occurrence| occurrence.region.start());
+    found.dedup();
+    found
+}
+
+/// Collects every occurrence of `target` in `decls`.
+pub(crate) fn find_occurrences(
+    target: &Target,
+    decls: &Declarations,
+    source: &str,
+    interns: &Interns,
+) -> Vec<Occurrence> {
+    find_all_occurrences(decls, source, interns)
+        .into_iter()
+        .filter(|occurrence| &occurrence.target == target)
+        .collect()
+}
+
+struct Collector<'a> {
+    source: &'a str,
+    interns: &'a Interns,
+    found: Vec<Occurrence>,
+}
+
+impl Collector<'_> {
+    fn push(&mut self, target: Target, region: Region, side: Side, kind: OccurrenceKind) {
+        let name = match &target {
+            Target::Symbol(symbol) => symbol.as_str(self.interns),
+            Target::Tag(name) | Target::Field(name) => name.as_str(),
+        };
+
+        if let Some(region) = name_region(self.source, region, name, side) {
+            self.found.push(Occurrence {
+                target,
+                region,
+                kind,
+            });
+        }
+    }
+
+    fn push_symbol(&mut self, symbol: Symbol, region: Region, side: Side, kind: OccurrenceKind) {
+        self.push(Target::Symbol(symbol), region, side, kind)
+    }
+
+    fn push_field(&mut self, field: &Lowercase, region: Region, side: Side, kind: OccurrenceKind) {
+        self.push(
+            Target::Field(field.as_str().to_string()),
+            region,
+            side,
+            kind,
+        )
+    }
+
+    /// Whether `region` contains nothing but `name`, as in the shorthand `{ x }`.
+    fn is_shorthand(&self, region: Region, name: &str) -> bool {
+        let text = self
+            .source
+            .get(region.start().offset as usize..region.end().offset as usize);
+
+        text.map(str::trim) == Some(name)
+    }
+
+    /// Annotations are kept apart from the def they annotate, and their region only covers the
+    /// type, so the name is found by looking back from the type for `name :`.
+    fn push_annotation(&mut self, symbol: Symbol, annotation: &Annotation) {
+        let type_start = annotation.region.start().offset as usize;
+        let Some(before_type) = self.source.get(..type_start) else {
+            return;
+        };
+
+        let before_colon = before_type.trim_end();
+        let Some(before_colon) = before_colon.strip_suffix(':') else {
+            return;
+        };
+
+        let name_end = before_colon.trim_end().len();
+        let region = Region::new(Position::new(0), Position::new(name_end as u32));
+        self.push_symbol(symbol, region, Side::End, OccurrenceKind::Definition);
+    }
+
+    fn visit_record_fields<'b>(
+        &mut self,
+        fields: impl Iterator<Item = (&'b Lowercase, &'b Field)>,
+    ) {
+        for (label, field) in fields {
+            let Field {
+                var,
+                region,
+                loc_expr,
+            } = field;
+
+            let shorthand = self.is_shorthand(*region, label.as_str());
+
+            match &loc_expr.value {
+                Expr::Var(symbol, _) if shorthand => {
+                    self.push_field(label, *region, Side::Start, OccurrenceKind::FieldShorthand);
+                    self.push_symbol(
+                        *symbol,
+                        *region,
+                        Side::Start,
+                        OccurrenceKind::FieldShorthand,
+                    );
+                }
+                _ => {
+                    self.push_field(label, *region, Side::Start, OccurrenceKind::Use);
+                    self.visit_expr(&loc_expr.value, loc_expr.region, *var);
+                }
+            }
+        }
+    }
+
+    /// The record being updated in `{ rec & x: 1 }` has no region of its own.
+    fn push_record_update_symbol(&mut self, symbol: Symbol, region: Region) {
+        let after_brace = region.start().offset as usize + 1;
+        let Some(rest) = self.source.get(after_brace..) else {
+            return;
+        };
+
+        let start = after_brace + (rest.len() - rest.trim_start().len());
+        let region = Region::new(Position::new(start as u32), region.end());
+        self.push_symbol(symbol, region, Side::Start, OccurrenceKind::Use);
+    }
+}
+
+impl Visitor for Collector<'_> {
+    fn visit_decl(&mut self, decl: DeclarationInfo<'_>) {
+        match &decl {
+            DeclarationInfo::Value {
+                loc_symbol,
+                annotation: Some(annotation),
+                ..
+            }
+            | DeclarationInfo::Function {
+                loc_symbol,
+                annotation: Some(annotation),
+                ..
+            } => self.push_annotation(loc_symbol.value, annotation),
+            _ => {}
+        }
+
+        traverse::walk_decl(self, decl);
+    }
+
+    fn visit_def(&mut self, def: &Def) {
+        if let (Pattern::Identifier(symbol), Some(annotation)) =
+            (&def.loc_pattern.value, &def.annotation)
+        {
+            self.push_annotation(*symbol, annotation);
+        }
+
+        traverse::walk_def(self, def);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+        use OccurrenceKind::*;
+
+        match pattern {
+            Pattern::Identifier(symbol)
+            | Pattern::Shadowed(_, _, symbol)
+            | Pattern::AbilityMemberSpecialization { ident: symbol, .. } => {
+                self.push_symbol(*symbol, region, Side::Start, Definition)
+            }
+            Pattern::As(_, symbol) => self.push_symbol(*symbol, region, Side::End, Definition),
+            Pattern::AppliedTag { tag_name, .. } => self.push(
+                Target::Tag(tag_name.0.as_str().to_string()),
+                region,
+                Side::Start,
+                Use,
+            ),
+            _ => {}
+        }
+
+        traverse::walk_pattern(self, pattern);
+    }
+
+    fn visit_record_destruct(&mut self, destruct: &RecordDestruct, region: Region) {
+        let shorthand = self.is_shorthand(region, destruct.label.as_str());
+
+        if shorthand {
+            self.push_field(
+                &destruct.label,
+                region,
+                Side::Start,
+                OccurrenceKind::FieldShorthand,
+            );
+        } else {
+            self.push_field(&destruct.label, region, Side::Start, OccurrenceKind::Use);
+        }
+
+        if !matches!(destruct.typ, DestructType::Guard(..)) {
+            let kind = if shorthand {
+                OccurrenceKind::FieldShorthand
+            } else {
+                OccurrenceKind::Definition
+            };
+            self.push_symbol(destruct.symbol, region, Side::Start, kind);
+        }
+
+        traverse::walk_record_destruct(self, destruct);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        use OccurrenceKind::*;
+
+        match expr {
+            Expr::Var(symbol, _)
+            | Expr::ParamsVar { symbol, .. }
+            | Expr::AbilityMember(symbol, _, _) => {
+                self.push_symbol(*symbol, region, Side::End, Use);
+            }
+            Expr::Tag { name, .. } | Expr::ZeroArgumentTag { name, .. } => {
+                self.push(
+                    Target::Tag(name.0.as_str().to_string()),
+                    region,
+                    Side::Start,
+                    Use,
+                );
+            }
+            Expr::RecordAccess { field, .. } => self.push_field(field, region, Side::End, Use),
+            Expr::RecordAccessor(data) => {
+                if let IndexOrField::Field(field) = &data.field {
+                    self.push_field(field, region, Side::End, Use);
+                }
+            }
+            Expr::Record { fields, .. } => {
+                self.visit_record_fields(fields.iter());
+                return;
+            }
+            Expr::RecordUpdate {
+                symbol, updates, ..
+            } => {
+                self.push_record_update_symbol(*symbol, region);
+                self.visit_record_fields(updates.iter());
+                return;
+            }
+            _ => {}
+        }
+
+        traverse::walk_expr(self, expr, var);
+    }
+}
+
+/// The occurrence under `position`, preferring values over the fields they're shorthand for.
+pub(crate) fn occurrence_at(occurrences: &[Occurrence], position: Position) -> Option<&Occurrence> {
+    let mut at_position = occurrences
+        .iter()
+        .filter(|occurrence| occurrence.region.contains_pos(position));
+
+    let first = at_position.next()?;
+    match first.target {
+        Target::Symbol(_) => Some(first),
+        _ => Some(
+            at_position
+                .find(|occurrence| matches!(occurrence.target, Target::Symbol(_)))
+                .unwrap_or(first),
+        ),
+    }
+}
+
+/// Names listed in a header's `exposes` and in `import ... exposing [...]` aren't part of the
+/// canonical AST, so they are found in the parsed module instead.
+pub(crate) fn exposed_name_occurrences<'a>(
+    names: impl IntoIterator<Item = Loc<&'a str>>,
+    ident: &str,
+    source: &str,
+) -> Vec<Region> {
+    names
+        .into_iter()
+        .filter(|loc_name| loc_name.value == ident)
+        .filter_map(|loc_name| name_region_at_start(source, loc_name.region, ident))
+        .collect()
+}
@@ -0,0 +1,99 @@
+use roc_can::{
+    expr::{Declarations, Expr},
+    traverse::{self, Visitor},
+};
+use roc_module::symbol::Symbol;
+use roc_region::all::{Position, Region};
+use roc_types::subs::Variable;
+
+pub struct FoundCall {
+    pub fn_var: Variable,
+    /// The function being called, if it's called by name.
+    pub fn_symbol: Option<Symbol>,
+    /// The index of the argument `position` is at.
+    pub active_argument: usize,
+}
+
+/// Finds the innermost function call whose arguments `position` is among.
+pub fn find_call_at(position: Position, decls: &Declarations) -> Option<FoundCall> {
+    let mut visitor = Finder {
+        position,
+        found: None,
+    };
+
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        position: Position,
+        found: Option<FoundCall>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, region: Region) -> bool {
+            region.contains_pos(self.position)
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if self.should_visit(region) {
+                if let Expr::Call(call, args, _) = expr {
+                    let (fn_var, fn_expr, ..) = &**call;
+
+                    if fn_expr.region.end() < self.position {
+                        let fn_symbol = match fn_expr.value {
+                            Expr::Var(symbol, _) => Some(symbol),
+                            _ => None,
+                        };
+
+                        // Past the last argument means we're about to write the next one
+                        let active_argument = args
+                            .iter()
+                            .position(|(_, arg)| self.position <= arg.region.end())
+                            .unwrap_or(args.len());
+
+                        self.found = Some(FoundCall {
+                            fn_var: *fn_var,
+                            fn_symbol,
+                            active_argument,
+                        });
+                    }
+                }
+                traverse::walk_expr(self, expr, var)
+            }
+        }
+    }
+}
+
+/// The ranges of the parameters in a printed function type like `Str, U64 -> Str`,
+/// in UTF-16 code units as LSP's `ParameterLabel::LabelOffsets` expects.
+pub fn parameter_offsets(function_type: &str) -> Vec<[u32; 2]> {
+    let mut offsets = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    let mut utf16_index = 0;
+
+    for (index, char) in function_type.char_indices() {
+        match char {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                offsets.push([start, utf16_index]);
+                start = utf16_index + ", ".len() as u32;
+            }
+            ' ' if depth == 0
+                && [" -> ", " => "]
+                    .iter()
+                    .any(|arrow| function_type[index..].starts_with(arrow)) =>
+            {
+                offsets.push([start, utf16_index]);
+                return offsets;
+            }
+            _ => {}
+        }
+
+        utf16_index += char.len_utf16() as u32;
+    }
+
+    // Not a function after all
+    vec![]
+}
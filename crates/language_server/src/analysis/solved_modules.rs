@@ -0,0 +1,166 @@
+use std::{fs, path::PathBuf};
+
+use roc_can::{
+    abilities::AbilitiesStore,
+    module::{ResolvedImplementations, TypeState},
+};
+use roc_collections::{MutMap, MutSet};
+use roc_load::LoadedModule;
+use roc_module::{
+    ident::ModuleName,
+    symbol::{Interns, ModuleId, Symbol},
+};
+use roc_types::subs::{Subs, Variable};
+
+/// The solved types of the modules a load type checked, kept around so the next load of the
+/// same root only has to solve the modules that changed and the ones depending on them.
+///
+/// The next load still parses and canonicalizes every module; only the solving is skipped.
+#[derive(Debug, Default)]
+pub(crate) struct SolvedModules {
+    modules: MutMap<ModuleId, SolvedModule>,
+}
+
+#[derive(Debug)]
+struct SolvedModule {
+    name: ModuleName,
+    path: PathBuf,
+    source: Box<str>,
+    imports: MutSet<ModuleId>,
+    subs: Subs,
+    exposed_vars_by_symbol: Vec<(Symbol, Variable)>,
+    abilities: AbilitiesStore,
+    solved_implementations: ResolvedImplementations,
+}
+
+impl SolvedModules {
+    pub(super) fn from_loaded_module(loaded: &LoadedModule) -> Self {
+        let modules = loaded
+            .typechecked
+            .iter()
+            .filter(|(module_id, _)| !module_id.is_builtin())
+            .filter_map(|(module_id, checked)| {
+                let (path, source) = loaded.sources.get(module_id)?;
+
+                let solved = SolvedModule {
+                    name: loaded.interns.module_name(*module_id).clone(),
+                    path: path.clone(),
+                    source: source.clone(),
+                    imports: loaded.imports.get(module_id).cloned().unwrap_or_default(),
+                    subs: checked.solved_subs.inner().clone(),
+                    exposed_vars_by_symbol: loaded
+                        .exposes
+                        .get(module_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                    abilities: checked.abilities_store.clone(),
+                    solved_implementations: checked.solved_implementations.clone(),
+                };
+
+                Some((*module_id, solved))
+            })
+            .collect();
+
+        Self { modules }
+    }
+
+    /// The types of the modules we don't need to solve again, because neither they nor anything
+    /// they import has changed on disk since they were solved.
+    ///
+    /// The modules at `never_reused` are always solved again. The loader doesn't read those from
+    /// disk, or the editor may have changed them without saving, so the disk says nothing about
+    /// whether they changed.
+    pub(super) fn reusable_types(
+        &self,
+        never_reused: &MutSet<PathBuf>,
+    ) -> MutMap<ModuleId, TypeState> {
+        let mut reusable = MutMap::default();
+        for module_id in self.modules.keys() {
+            self.is_reusable(*module_id, never_reused, &mut reusable);
+        }
+
+        reusable
+            .into_iter()
+            .filter(|(_, is_reusable)| *is_reusable)
+            .filter_map(|(module_id, _)| {
+                let solved = self.modules.get(&module_id)?;
+
+                let types = TypeState {
+                    subs: solved.subs.clone(),
+                    exposed_vars_by_symbol: solved.exposed_vars_by_symbol.clone(),
+                    abilities: solved.abilities.clone(),
+                    solved_implementations: solved.solved_implementations.clone(),
+                };
+
+                Some((module_id, types))
+            })
+            .collect()
+    }
+
+    fn is_reusable(
+        &self,
+        module_id: ModuleId,
+        never_reused: &MutSet<PathBuf>,
+        known: &mut MutMap<ModuleId, bool>,
+    ) -> bool {
+        if module_id.is_builtin() {
+            return true;
+        }
+        if let Some(is_reusable) = known.get(&module_id) {
+            return *is_reusable;
+        }
+
+        let is_reusable = match self.modules.get(&module_id) {
+            Some(solved) => {
+                let unchanged = !never_reused.contains(&solved.path)
+                    && fs::read_to_string(&solved.path)
+                        .is_ok_and(|source| *source == *solved.source);
+
+                // Import cycles are an error anyway, but they mustn't send us round in circles
+                known.insert(module_id, false);
+
+                // Check every import, so they're all known by the time we're done
+                let imports_reusable = solved.imports.iter().fold(true, |all, import| {
+                    self.is_reusable(*import, never_reused, known) && all
+                });
+
+                unchanged && imports_reusable
+            }
+            None => false,
+        };
+
+        known.insert(module_id, is_reusable);
+        is_reusable
+    }
+
+    /// Whether every module in `reused` got the same id in `interns` as it had when it was
+    /// solved. If a change to the imports shuffled the ids around, the reused types are bogus.
+    pub(super) fn ids_match<'a>(
+        &self,
+        mut reused: impl Iterator<Item = &'a ModuleId>,
+        interns: &Interns,
+    ) -> bool {
+        reused.all(|module_id| {
+            let solved_name = self.modules.get(module_id).map(|solved| &solved.name);
+            solved_name.is_some() && interns.module_ids.get_name(*module_id) == solved_name
+        })
+    }
+
+    /// Whether the loader read the same source for every module in `reused` as the one it was
+    /// solved from. A module can change on disk between checking it and loading it, and then
+    /// only the source the loader actually used tells whether the reused types still fit.
+    pub(super) fn sources_match<'a>(
+        &self,
+        mut reused: impl Iterator<Item = &'a ModuleId>,
+        sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    ) -> bool {
+        reused.all(|module_id| {
+            let solved = self.modules.get(module_id);
+            let loaded = sources.get(module_id);
+
+            solved.zip(loaded).is_some_and(|(solved, (path, source))| {
+                *path == solved.path && *source == solved.source
+            })
+        })
+    }
+}
@@ -0,0 +1,184 @@
+use roc_parse::ast::{
+    AbilityMember, Defs, Expr, ExtractSpaces, Pattern, TypeAnnotation, TypeDef, TypeHeader,
+    ValueDef,
+};
+use roc_region::all::Region;
+use tower_lsp::lsp_types::{FoldingRangeKind, SymbolKind};
+
+/// A named top-level def, as it shows up in an editor's outline.
+#[derive(Debug)]
+pub struct OutlineSymbol<'a> {
+    pub name: &'a str,
+    pub kind: SymbolKind,
+    /// The whole def, including its annotation.
+    pub region: Region,
+    /// Just the name being defined.
+    pub name_region: Region,
+    pub children: Vec<OutlineSymbol<'a>>,
+}
+
+/// The outline of a module: its type aliases, opaques, abilities (with their members) and
+/// top-level values, in source order.
+pub fn outline<'a>(defs: &Defs<'a>) -> Vec<OutlineSymbol<'a>> {
+    let mut symbols: Vec<OutlineSymbol<'a>> = vec![];
+
+    for def in defs.loc_defs() {
+        let symbol = match def {
+            Ok(type_def) => Some(type_symbol(type_def.region, &type_def.value)),
+            Err(value_def) => {
+                let symbol = value_symbol(value_def.region, &value_def.value);
+
+                // An annotation separated from its body by a blank line is parsed as two defs,
+                // but it's still a single thing as far as the outline is concerned
+                if let (Some(symbol), ValueDef::Body(..), Some(previous)) =
+                    (&symbol, value_def.value, symbols.last_mut())
+                {
+                    if previous.name == symbol.name {
+                        previous.region = Region::span_across(&previous.region, &symbol.region);
+                        if symbol.kind == SymbolKind::FUNCTION {
+                            previous.kind = SymbolKind::FUNCTION;
+                        }
+                        continue;
+                    }
+                }
+
+                symbol
+            }
+        };
+
+        symbols.extend(symbol);
+    }
+
+    symbols
+}
+
+fn type_symbol<'a>(region: Region, type_def: &TypeDef<'a>) -> OutlineSymbol<'a> {
+    let type_header_symbol = |header: &TypeHeader<'a>, kind| OutlineSymbol {
+        name: header.name.value,
+        kind,
+        region,
+        name_region: header.name.region,
+        children: vec![],
+    };
+
+    match type_def {
+        TypeDef::Alias { header, ann } => {
+            let kind = match ann.value.extract_spaces().item {
+                TypeAnnotation::TagUnion { .. } => SymbolKind::ENUM,
+                _ => SymbolKind::STRUCT,
+            };
+            type_header_symbol(header, kind)
+        }
+        TypeDef::Opaque { header, .. } => type_header_symbol(header, SymbolKind::CLASS),
+        TypeDef::Ability {
+            header, members, ..
+        } => OutlineSymbol {
+            children: members.iter().map(ability_member_symbol).collect(),
+            ..type_header_symbol(header, SymbolKind::INTERFACE)
+        },
+    }
+}
+
+fn ability_member_symbol<'a>(member: &AbilityMember<'a>) -> OutlineSymbol<'a> {
+    OutlineSymbol {
+        name: *member.name.value.item(),
+        kind: SymbolKind::METHOD,
+        region: member.region(),
+        name_region: member.name.region,
+        children: vec![],
+    }
+}
+
+fn value_symbol<'a>(region: Region, value_def: &ValueDef<'a>) -> Option<OutlineSymbol<'a>> {
+    let (pattern, kind) = match value_def {
+        ValueDef::Annotation(pattern, ann) => (pattern, annotation_kind(&ann.value)),
+        ValueDef::Body(pattern, body) => (*pattern, body_kind(&body.value)),
+        ValueDef::AnnotatedBody {
+            ann_pattern,
+            ann_type,
+            body_expr,
+            ..
+        } => {
+            let kind = match body_kind(&body_expr.value) {
+                SymbolKind::FUNCTION => SymbolKind::FUNCTION,
+                _ => annotation_kind(&ann_type.value),
+            };
+            (*ann_pattern, kind)
+        }
+        ValueDef::IngestedFileImport(import) => {
+            return Some(OutlineSymbol {
+                name: import.name.item.value,
+                kind: SymbolKind::FILE,
+                region,
+                name_region: import.name.item.region,
+                children: vec![],
+            })
+        }
+        ValueDef::Dbg { .. }
+        | ValueDef::Expect { .. }
+        | ValueDef::ModuleImport(_)
+        | ValueDef::Stmt(_)
+        | ValueDef::StmtAfterExpr => return None,
+    };
+
+    // Destructuring at the top level doesn't give us one name to show
+    match pattern.value.extract_spaces().item {
+        Pattern::Identifier { ident } => Some(OutlineSymbol {
+            name: ident,
+            kind,
+            region,
+            name_region: pattern.region,
+            children: vec![],
+        }),
+        _ => None,
+    }
+}
+
+fn annotation_kind(ann: &TypeAnnotation) -> SymbolKind {
+    match ann.extract_spaces().item {
+        TypeAnnotation::Function(..) => SymbolKind::FUNCTION,
+        _ => SymbolKind::CONSTANT,
+    }
+}
+
+fn body_kind(body: &Expr) -> SymbolKind {
+    match body.extract_spaces().item {
+        Expr::Closure(..) => SymbolKind::FUNCTION,
+        _ => SymbolKind::CONSTANT,
+    }
+}
+
+/// The regions an editor can fold: the module header, each run of imports, and every
+/// top-level def. It's up to the caller to drop the ones that fit on a single line.
+pub fn folding_regions(header: Region, defs: &Defs) -> Vec<(Region, Option<FoldingRangeKind>)> {
+    let mut regions = vec![(header, None)];
+    let mut imports: Option<Region> = None;
+
+    for def in defs.loc_defs() {
+        match def {
+            Err(import) if matches!(import.value, ValueDef::ModuleImport(_)) => {
+                imports = Some(match imports {
+                    Some(previous) => Region::span_across(&previous, &import.region),
+                    None => import.region,
+                });
+            }
+            def => {
+                if let Some(imports) = imports.take() {
+                    regions.push((imports, Some(FoldingRangeKind::Imports)));
+                }
+
+                let region = match def {
+                    Ok(type_def) => type_def.region,
+                    Err(value_def) => value_def.region,
+                };
+                regions.push((region, Some(FoldingRangeKind::Region)));
+            }
+        }
+    }
+
+    if let Some(imports) = imports {
+        regions.push((imports, Some(FoldingRangeKind::Imports)));
+    }
+
+    regions
+}
@@ -76,11 +76,43 @@ pub(crate) mod diag {
     use roc_solve_problem::TypeError;
 
     use roc_problem::Severity;
-    use roc_reporting::report::RocDocAllocator;
-    use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+    use roc_reporting::report::{Fix, RocDocAllocator};
+    use serde::{Deserialize, Serialize};
+    use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, TextEdit};
 
     use super::ToRange;
 
+    /// A fix for a diagnostic, which we keep in its `data` so that the client hands it back to us
+    /// when asking for code actions.
+    #[derive(Debug, Serialize, Deserialize)]
+    pub(crate) struct QuickFix {
+        pub title: String,
+        pub edits: Vec<TextEdit>,
+    }
+
+    fn quick_fixes_data(fixes: &[Fix], line_info: &LineInfo) -> Option<serde_json::Value> {
+        if fixes.is_empty() {
+            return None;
+        }
+
+        let quick_fixes: Vec<_> = fixes
+            .iter()
+            .map(|fix| QuickFix {
+                title: fix.title.clone(),
+                edits: fix
+                    .edits
+                    .iter()
+                    .map(|edit| TextEdit {
+                        range: edit.region.to_range(line_info),
+                        new_text: edit.replacement.clone(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        serde_json::to_value(quick_fixes).ok()
+    }
+
     pub trait IntoLspSeverity {
         fn into_lsp_severity(self) -> DiagnosticSeverity;
     }
@@ -204,6 +236,10 @@ pub(crate) mod diag {
                 self,
             );
 
+            let data = quick_fixes_data(&report.fixes, fmt.line_info);
+            let code = report
+                .code()
+                .map(|code| NumberOrString::String(code.to_string()));
             let severity = report.severity.into_lsp_severity();
             let mut msg = String::new();
             report.render_language_server(&mut msg, fmt.alloc);
@@ -211,13 +247,13 @@ pub(crate) mod diag {
             Some(Diagnostic {
                 range,
                 severity: Some(severity),
-                code: None,
+                code,
                 code_description: None,
                 source: None,
                 message: msg,
                 related_information: None,
                 tags: None,
-                data: None,
+                data,
             })
         }
     }
@@ -241,6 +277,10 @@ pub(crate) mod diag {
                 self,
             )?;
 
+            let data = quick_fixes_data(&report.fixes, fmt.line_info);
+            let code = report
+                .code()
+                .map(|code| NumberOrString::String(code.to_string()));
             let severity = report.severity.into_lsp_severity();
 
             let mut msg = String::new();
@@ -249,13 +289,13 @@ pub(crate) mod diag {
             Some(Diagnostic {
                 range,
                 severity: Some(severity),
-                code: None,
+                code,
                 code_description: None,
                 source: None,
                 message: msg,
                 related_information: None,
                 tags: None,
-                data: None,
+                data,
             })
         }
     }
@@ -10,7 +10,9 @@ use tokio::sync::{Mutex, MutexGuard};
 
 use tower_lsp::lsp_types::{
     CodeActionOrCommand, CodeActionResponse, CompletionResponse, Diagnostic,
-    GotoDefinitionResponse, Hover, Position, Range, SemanticTokensResult, TextEdit, Url,
+    DocumentSymbolResponse, FoldingRange, GotoDefinitionResponse, Hover, InlayHint, Location,
+    Position, PrepareRenameResponse, Range, SemanticTokensResult, SignatureHelp, SymbolInformation,
+    TextEdit, Url, WorkspaceEdit,
 };
 
 use crate::analysis::{AnalyzedDocument, DocInfo};
@@ -33,6 +35,14 @@ impl DocumentPair {
             last_good_document,
         }
     }
+
+    /// The most recent document that was successfully type checked.
+    fn checked_document(&self) -> Arc<AnalyzedDocument> {
+        match self.latest_document.get() {
+            Some(latest) if latest.type_checked() => latest.clone(),
+            _ => self.last_good_document.clone(),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -140,6 +150,32 @@ impl Registry {
         }
     }
 
+    /// Applies edits to the latest content of a document, returning its new content.
+    pub async fn apply_content_changes(
+        &self,
+        url: &Url,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) -> Option<DocInfo> {
+        let mut documents_lock = self.documents.lock().await;
+        let doc = documents_lock.get_mut(url)?;
+
+        let info = doc.info.apply_changes(changes, version);
+        debug!(
+            "Applied changes to {:?}, now at version:{:?}",
+            url.as_str(),
+            version
+        );
+
+        *doc = DocumentPair {
+            info: info.clone(),
+            last_good_document: doc.last_good_document.clone(),
+            latest_document: OnceLock::new(),
+        };
+
+        Some(info)
+    }
+
     async fn document_info_by_url(&self, url: &Url) -> Option<DocInfo> {
         self.documents.lock().await.get(url).map(|a| a.info.clone())
     }
@@ -175,6 +211,16 @@ impl Registry {
         self.latest_document_by_url(url).await?.hover(position)
     }
 
+    pub async fn signature_help(&self, url: &Url, position: Position) -> Option<SignatureHelp> {
+        self.latest_document_by_url(url)
+            .await?
+            .signature_help(position)
+    }
+
+    pub async fn inlay_hints(&self, url: &Url, range: Range) -> Option<Vec<InlayHint>> {
+        self.latest_document_by_url(url).await?.inlay_hints(range)
+    }
+
     pub async fn goto_definition(
         &self,
         url: &Url,
@@ -196,6 +242,24 @@ impl Registry {
         let document = self.document_info_by_url(url).await?;
         document.semantic_tokens()
     }
+
+    pub async fn document_symbols(&self, url: &Url) -> Option<DocumentSymbolResponse> {
+        let document = self.document_info_by_url(url).await?;
+        document.document_symbols()
+    }
+
+    pub async fn workspace_symbols(&self, query: &str) -> Vec<SymbolInformation> {
+        let documents = self.documents.lock().await;
+        documents
+            .values()
+            .flat_map(|document| document.info.workspace_symbols(query))
+            .collect()
+    }
+
+    pub async fn folding_ranges(&self, url: &Url) -> Option<Vec<FoldingRange>> {
+        let document = self.document_info_by_url(url).await?;
+        document.folding_ranges()
+    }
     pub async fn completion_items(
         &self,
         url: &Url,
@@ -211,13 +275,103 @@ impl Registry {
             latest_doc_info.version
         );
 
-        let completions = pair
-            .last_good_document
-            .completion_items(position, latest_doc_info)?;
+        // Other modules of the project may expose what's being completed, even if this one
+        // doesn't import them yet
+        let project_documents = lock
+            .values()
+            .map(DocumentPair::checked_document)
+            .collect::<Vec<_>>();
+
+        let completions = pair.last_good_document.completion_items(
+            position,
+            latest_doc_info,
+            &project_documents,
+        )?;
 
         Some(CompletionResponse::Array(completions))
     }
 
+    /// Every document we currently know of, from the most recent analysis that type checked.
+    async fn all_checked_documents(&self) -> Vec<Arc<AnalyzedDocument>> {
+        let documents = self.documents.lock().await;
+        documents
+            .values()
+            .map(DocumentPair::checked_document)
+            .collect()
+    }
+
+    pub async fn references(
+        &self,
+        url: &Url,
+        position: Position,
+        include_declaration: bool,
+    ) -> Option<Vec<Location>> {
+        let document = self.latest_document_by_url(url).await?;
+        let (reference, _) = document.reference_at(position)?;
+
+        let locations = self
+            .all_checked_documents()
+            .await
+            .into_iter()
+            .flat_map(|document| document.references(&reference, include_declaration))
+            .collect();
+
+        Some(locations)
+    }
+
+    pub async fn prepare_rename(
+        &self,
+        url: &Url,
+        position: Position,
+    ) -> Option<PrepareRenameResponse> {
+        let document = self.latest_document_by_url(url).await?;
+        let (reference, range) = document.reference_at(position)?;
+
+        let documents = self.all_checked_documents().await;
+        if !documents
+            .iter()
+            .any(|document| document.defines(&reference))
+        {
+            return None;
+        }
+
+        Some(PrepareRenameResponse::RangeWithPlaceholder {
+            range,
+            placeholder: reference.name().to_string(),
+        })
+    }
+
+    pub async fn rename(
+        &self,
+        url: &Url,
+        position: Position,
+        new_name: &str,
+    ) -> Option<WorkspaceEdit> {
+        let document = self.latest_document_by_url(url).await?;
+        let (reference, _) = document.reference_at(position)?;
+
+        // We can only rename things whose definition we are able to edit too
+        let documents = self.all_checked_documents().await;
+        if !reference.is_valid_new_name(new_name)
+            || !documents
+                .iter()
+                .any(|document| document.defines(&reference))
+        {
+            return None;
+        }
+
+        let changes = documents
+            .into_iter()
+            .map(|document| {
+                let edits = document.rename_edits(&reference, new_name);
+                (document.url().clone(), edits)
+            })
+            .filter(|(_, edits)| !edits.is_empty())
+            .collect::<HashMap<_, _>>();
+
+        Some(WorkspaceEdit::new(changes))
+    }
+
     pub async fn code_actions(&self, url: &Url, range: Range) -> Option<CodeActionResponse> {
         let document = self.latest_document_by_url(url).await?;
 
@@ -225,6 +379,15 @@ impl Registry {
         if let Some(edit) = document.annotate(range) {
             responses.push(CodeActionOrCommand::CodeAction(edit));
         }
+        if let Some(edit) = document.add_missing_branches(range) {
+            responses.push(CodeActionOrCommand::CodeAction(edit));
+        }
+        responses.extend(
+            document
+                .quick_fixes(range)
+                .into_iter()
+                .map(CodeActionOrCommand::CodeAction),
+        );
         Some(responses)
     }
 }
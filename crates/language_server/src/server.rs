@@ -2,15 +2,18 @@ use analysis::HIGHLIGHT_TOKENS_LEGEND;
 
 use log::{debug, trace};
 use registry::{Registry, RegistryConfig};
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Mutex;
 
 use tower_lsp::jsonrpc::{self, Result};
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
-use crate::analysis::{global_analysis, DocInfo};
+use crate::analysis::{global_analysis, DocInfo, SolvedModules};
 
 mod analysis;
 mod convert;
@@ -37,6 +40,10 @@ impl Default for RocServerConfig {
 struct RocServerState {
     registry: Registry,
     config: RocServerConfig,
+    /// The modules solved by the last analysis of each document, for the next one to reuse.
+    solved_modules: Mutex<HashMap<Url, Arc<SolvedModules>>>,
+    /// The documents the editor has open, whose latest source may not be saved to disk.
+    open_documents: Mutex<HashSet<Url>>,
 }
 
 impl std::panic::RefUnwindSafe for RocServer {}
@@ -64,14 +71,11 @@ impl RocServer {
     }
 
     pub fn capabilities() -> ServerCapabilities {
-        let text_document_sync = TextDocumentSyncCapability::Options(
-            // TODO: later on make this incremental
-            TextDocumentSyncOptions {
-                open_close: Some(true),
-                change: Some(TextDocumentSyncKind::FULL),
-                ..TextDocumentSyncOptions::default()
-            },
-        );
+        let text_document_sync = TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+            open_close: Some(true),
+            change: Some(TextDocumentSyncKind::INCREMENTAL),
+            ..TextDocumentSyncOptions::default()
+        });
         let hover_provider = HoverProviderCapability::Simple(true);
         let definition_provider = DefinitionOptions {
             work_done_progress_options: WorkDoneProgressOptions {
@@ -104,6 +108,22 @@ impl RocServer {
             },
         };
         let code_action_provider = CodeActionProviderCapability::Simple(true);
+        let references_provider = OneOf::Left(true);
+        let rename_provider = RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        };
+        let folding_range_provider = FoldingRangeProviderCapability::Simple(true);
+        let signature_help_provider = SignatureHelpOptions {
+            // Arguments are separated by spaces, so that's when the next one starts
+            trigger_characters: Some(vec![" ".to_string()]),
+            retrigger_characters: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        };
         ServerCapabilities {
             text_document_sync: Some(text_document_sync),
             hover_provider: Some(hover_provider),
@@ -112,6 +132,13 @@ impl RocServer {
             semantic_tokens_provider: Some(semantic_tokens_provider),
             completion_provider: Some(completion_provider),
             code_action_provider: Some(code_action_provider),
+            references_provider: Some(references_provider),
+            rename_provider: Some(OneOf::Right(rename_provider)),
+            document_symbol_provider: Some(OneOf::Left(true)),
+            workspace_symbol_provider: Some(OneOf::Left(true)),
+            folding_range_provider: Some(folding_range_provider),
+            signature_help_provider: Some(signature_help_provider),
+            inlay_hint_provider: Some(OneOf::Left(true)),
             ..ServerCapabilities::default()
         }
     }
@@ -119,7 +146,26 @@ impl RocServer {
     /// Records a document content change.
     async fn change(&self, fi: Url, text: String, version: i32) {
         let updating_result = self.state.change(&fi, text, version).await;
+        self.publish_diagnostics(fi, version, updating_result).await
+    }
+
+    /// Records edits to a document's content.
+    async fn change_incrementally(
+        &self,
+        fi: Url,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) {
+        let updating_result = self.state.change_incrementally(&fi, changes, version).await;
+        self.publish_diagnostics(fi, version, updating_result).await
+    }
 
+    async fn publish_diagnostics(
+        &self,
+        fi: Url,
+        version: i32,
+        updating_result: std::result::Result<(), String>,
+    ) {
         //The analysis task can be cancelled by another change coming in which will update the watched variable
         if let Err(e) = updating_result {
             debug!("Cancelled change. Reason:{:?}", e);
@@ -138,10 +184,19 @@ impl RocServer {
 
 impl RocServerState {
     pub fn new(config: RocServerConfig, registry: Registry) -> RocServerState {
-        Self { config, registry }
+        Self {
+            config,
+            registry,
+            solved_modules: Default::default(),
+            open_documents: Default::default(),
+        }
     }
 
-    async fn close(&self, _fi: Url) {}
+    async fn close(&self, fi: Url) {
+        self.open_documents.lock().await.remove(&fi);
+        // Nothing is going to reuse them once the document is closed
+        self.solved_modules.lock().await.remove(&fi);
+    }
 
     pub async fn change(
         &self,
@@ -152,10 +207,34 @@ impl RocServerState {
         debug!("V{:?}:starting change", version);
         let doc_info = DocInfo::new(fi.clone(), text, version);
 
+        self.open_documents.lock().await.insert(fi.clone());
         self.registry
             .apply_doc_info_changes(fi.clone(), doc_info.clone())
             .await;
 
+        self.analyze(fi, doc_info).await
+    }
+
+    pub async fn change_incrementally(
+        &self,
+        fi: &Url,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) -> std::result::Result<(), String> {
+        debug!("V{:?}:starting incremental change", version);
+
+        let doc_info = self
+            .registry
+            .apply_content_changes(fi, changes, version)
+            .await
+            .ok_or_else(|| format!("Got changes to {fi} before it was opened"))?;
+
+        self.analyze(fi, doc_info).await
+    }
+
+    async fn analyze(&self, fi: &Url, doc_info: DocInfo) -> std::result::Result<(), String> {
+        let version = doc_info.version;
+
         debug!(
             "V{:?}:finished updating docinfo, starting analysis ",
             version
@@ -175,9 +254,22 @@ impl RocServerState {
                 return Err("Not latest version skipping analysis".to_string());
             }
 
-            let results = match tokio::time::timeout(
+            let previous = inner_ref.solved_modules.lock().await.get(fi).cloned();
+            let open_paths = inner_ref
+                .open_documents
+                .lock()
+                .await
+                .iter()
+                .filter_map(|url| url.to_file_path().ok())
+                .collect::<Vec<_>>();
+
+            let (results, solved_modules) = match tokio::time::timeout(
                 Duration::from_secs(60),
-                tokio::task::spawn_blocking(|| catch_unwind(|| global_analysis(doc_info))),
+                tokio::task::spawn_blocking(|| {
+                    catch_unwind(AssertUnwindSafe(|| {
+                        global_analysis(doc_info, previous, open_paths)
+                    }))
+                }),
             )
             .await
             {
@@ -211,6 +303,14 @@ impl RocServerState {
                 version
             );
 
+            if let Some(solved_modules) = solved_modules {
+                inner_ref
+                    .solved_modules
+                    .lock()
+                    .await
+                    .insert(fi.clone(), Arc::new(solved_modules));
+            }
+
             inner_ref.registry.apply_changes(results, fi.clone()).await;
             Ok(())
         }
@@ -248,15 +348,8 @@ impl LanguageServer for RocServer {
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
         let VersionedTextDocumentIdentifier { uri, version, .. } = params.text_document;
 
-        // NOTE: We specify that we expect full-content syncs in the server capabilities,
-        // so here we assume the only change passed is a change of the entire document's content.
-        let TextDocumentContentChangeEvent { text, .. } = params
-            .content_changes
-            .into_iter()
-            .last()
-            .expect("textDocument change event had no changes ");
-
-        let _res = unwind_async(self.change(uri, text, version)).await;
+        let _res =
+            unwind_async(self.change_incrementally(uri, params.content_changes, version)).await;
         if let Err(e) = _res {
             self.client.log_message(MessageType::ERROR, e.message).await
         }
@@ -284,6 +377,35 @@ impl LanguageServer for RocServer {
         unwind_async(self.state.registry.hover(&text_document.uri, position)).await
     }
 
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        let SignatureHelpParams {
+            text_document_position_params:
+                TextDocumentPositionParams {
+                    text_document,
+                    position,
+                },
+            context: _,
+            work_done_progress_params: _,
+        } = params;
+
+        unwind_async(
+            self.state
+                .registry
+                .signature_help(&text_document.uri, position),
+        )
+        .await
+    }
+
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        let InlayHintParams {
+            text_document,
+            range,
+            work_done_progress_params: _,
+        } = params;
+
+        unwind_async(self.state.registry.inlay_hints(&text_document.uri, range)).await
+    }
+
     async fn goto_definition(
         &self,
         params: GotoDefinitionParams,
@@ -329,6 +451,42 @@ impl LanguageServer for RocServer {
         unwind_async(self.state.registry.semantic_tokens(&text_document.uri)).await
     }
 
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let DocumentSymbolParams {
+            text_document,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        unwind_async(self.state.registry.document_symbols(&text_document.uri)).await
+    }
+
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        let WorkspaceSymbolParams {
+            query,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        unwind_async(async { Some(self.state.registry.workspace_symbols(&query).await) }).await
+    }
+
+    async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        let FoldingRangeParams {
+            text_document,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        unwind_async(self.state.registry.folding_ranges(&text_document.uri)).await
+    }
+
     async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
         let doc = params.text_document_position;
         trace!("Got completion request.");
@@ -352,6 +510,62 @@ impl LanguageServer for RocServer {
 
         unwind_async(self.state.registry.code_actions(&text_document.uri, range)).await
     }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let ReferenceParams {
+            text_document_position:
+                TextDocumentPositionParams {
+                    text_document,
+                    position,
+                },
+            context,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        unwind_async(self.state.registry.references(
+            &text_document.uri,
+            position,
+            context.include_declaration,
+        ))
+        .await
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let TextDocumentPositionParams {
+            text_document,
+            position,
+        } = params;
+
+        unwind_async(
+            self.state
+                .registry
+                .prepare_rename(&text_document.uri, position),
+        )
+        .await
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let RenameParams {
+            text_document_position:
+                TextDocumentPositionParams {
+                    text_document,
+                    position,
+                },
+            new_name,
+            work_done_progress_params: _,
+        } = params;
+
+        unwind_async(
+            self.state
+                .registry
+                .rename(&text_document.uri, position, &new_name),
+        )
+        .await
+    }
 }
 
 async fn unwind_async<Fut, T>(future: Fut) -> tower_lsp::jsonrpc::Result<T>
@@ -393,6 +607,7 @@ mod tests {
     use log::info;
 
     use super::*;
+    use crate::analysis::AnalyzedDocument;
 
     fn completion_resp_to_strings(
         resp: CompletionResponse,
@@ -659,6 +874,176 @@ mod tests {
         .assert_debug_eq(&actual);
     }
 
+    #[tokio::test]
+    async fn test_completion_expected_tags() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+            Color : [Red, Green, Blue]
+
+            paint : Color -> Str
+            paint = \_ -> "paint"
+
+            main = paint Red
+            "#};
+
+        let (inner, url) = test_setup(doc.clone()).await;
+        let change = doc.replace("paint Red", "paint G");
+        inner.change(&url, change, 1).await.unwrap();
+
+        let completions = match inner
+            .registry
+            .completion_items(&url, Position::new(8, 14))
+            .await
+        {
+            Some(CompletionResponse::Array(completions)) => completions,
+            _ => panic!("No completions"),
+        };
+
+        let first = &completions[0];
+        assert_eq!(
+            (first.label.as_str(), first.kind, first.detail.as_deref()),
+            (
+                "Green",
+                Some(CompletionItemKind::ENUM_MEMBER),
+                Some("Color")
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_completion_when_branches() {
+        let actual = completion_test_labels(
+            indoc! {r#"
+            Color : [Red, Green, Blue]
+
+            name : Color -> Str
+            name = \color ->
+                when color is
+                    Red -> "red"
+            "#},
+            "        G",
+            Position::new(9, 9),
+        )
+        .await;
+
+        expect![[r#"
+            Some(
+                [
+                    "Green",
+                ],
+            )
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_completion_record_fields() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+            Person : { name : Str, age : U64 }
+
+            greet : Person -> Str
+            greet = \person -> person.name
+
+            main = greet {}
+            "#};
+
+        let (inner, url) = test_setup(doc.clone()).await;
+        let change = doc.replace("greet {}", r#"greet { name: "Ayo", a"#);
+        inner.change(&url, change, 1).await.unwrap();
+
+        let actual = comp_labels(
+            get_basic_completion_info(&inner.registry, &url, Position::new(8, 29)).await,
+        );
+
+        expect![[r#"
+            Some(
+                [
+                    "age",
+                ],
+            )
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_completion_types_in_annotation() {
+        let actual = completion_test_labels(
+            indoc! {r"
+            Color : [Red, Green, Blue]
+
+            main = 1
+            "},
+            "x : Co",
+            Position::new(6, 6),
+        )
+        .await;
+
+        expect![[r#"
+            Some(
+                [
+                    "Color",
+                ],
+            )
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_completion_unimported() {
+        let doc = DOC_LIT.to_string() + "main = \"hi\"\n";
+        let (inner, url) = test_setup(doc.clone()).await;
+
+        let other_url = Url::parse("file:/Greeting.roc").unwrap();
+        let other_doc = indoc! {r#"
+            interface Greeting
+              exposes [greeting]
+              imports []
+
+            greeting = "Hello"
+            "#};
+        inner
+            .change(&other_url, other_doc.to_string(), 0)
+            .await
+            .unwrap();
+
+        let change = doc.replace("\"hi\"", "gree");
+        inner.change(&url, change, 1).await.unwrap();
+
+        let completions = match inner
+            .registry
+            .completion_items(&url, Position::new(3, 11))
+            .await
+        {
+            Some(CompletionResponse::Array(completions)) => completions,
+            _ => panic!("No completions"),
+        };
+
+        let import = Position::new(3, 0);
+        let actual = completions
+            .into_iter()
+            .map(|item| {
+                (
+                    item.label,
+                    item.label_details.and_then(|details| details.description),
+                    item.additional_text_edits,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![(
+                "greeting".to_string(),
+                Some("import Greeting".to_string()),
+                Some(vec![TextEdit {
+                    range: Range::new(import, import),
+                    new_text: "import Greeting exposing [greeting]\n\n".to_string(),
+                }]),
+            )]
+        );
+    }
+
     async fn code_action_edits(doc: String, position: Position, name: &str) -> Vec<TextEdit> {
         let (inner, url) = test_setup(doc.clone()).await;
         let registry = &inner.registry;
@@ -801,4 +1186,568 @@ mod tests {
         "#]]
         .assert_debug_eq(&edit);
     }
+
+    #[tokio::test]
+    async fn test_add_missing_branches() {
+        let edit = code_action_edits(
+            DOC_LIT.to_string()
+                + indoc! {r#"
+                Color : [Red, Green, Blue]
+
+                toStr : Color -> Str
+                toStr = \color ->
+                    when color is
+                        Red -> "red"
+            "#},
+            Position::new(7, 6),
+            "Add missing branches",
+        )
+        .await;
+
+        expect![[r#"
+            [
+                TextEdit {
+                    range: Range {
+                        start: Position {
+                            line: 8,
+                            character: 20,
+                        },
+                        end: Position {
+                            line: 8,
+                            character: 20,
+                        },
+                    },
+                    new_text: "\n        Green -> crash \"todo\"\n        Blue -> crash \"todo\"",
+                },
+            ]
+        "#]]
+        .assert_debug_eq(&edit);
+    }
+
+    #[tokio::test]
+    async fn test_quick_fix_typo() {
+        let edit = code_action_edits(
+            DOC_LIT.to_string()
+                + indoc! {r#"
+                greeting = "Hello"
+
+                main = greting
+            "#},
+            Position::new(5, 9),
+            "Change to greeting",
+        )
+        .await;
+
+        expect![[r#"
+            [
+                TextEdit {
+                    range: Range {
+                        start: Position {
+                            line: 5,
+                            character: 7,
+                        },
+                        end: Position {
+                            line: 5,
+                            character: 14,
+                        },
+                    },
+                    new_text: "greeting",
+                },
+            ]
+        "#]]
+        .assert_debug_eq(&edit);
+    }
+
+    /// Summarises a range as `(line, start character, end character)`, assuming it's on one line.
+    fn range_summary(range: Range) -> (u32, u32, u32) {
+        assert_eq!(range.start.line, range.end.line);
+        (range.start.line, range.start.character, range.end.character)
+    }
+
+    #[tokio::test]
+    async fn test_references_top_level() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+                greet : Str -> Str
+                greet = \name -> name
+
+                main = greet (greet "world")
+            "#};
+
+        let (inner, url) = test_setup(doc).await;
+
+        let references = inner
+            .registry
+            .references(&url, Position::new(6, 8), true)
+            .await
+            .unwrap();
+
+        let actual = references
+            .into_iter()
+            .map(|location| range_summary(location.range))
+            .collect::<Vec<_>>();
+
+        expect![[r#"
+            [
+                (
+                    3,
+                    0,
+                    5,
+                ),
+                (
+                    4,
+                    0,
+                    5,
+                ),
+                (
+                    6,
+                    7,
+                    12,
+                ),
+                (
+                    6,
+                    14,
+                    19,
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_rename_local() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+                main =
+                    count = 1
+                    count + count
+            "#};
+
+        let (inner, url) = test_setup(doc).await;
+        let registry = &inner.registry;
+
+        let invalid = registry.rename(&url, Position::new(5, 5), "Total").await;
+        assert_eq!(invalid, None);
+
+        let edit = registry
+            .rename(&url, Position::new(5, 5), "total")
+            .await
+            .unwrap();
+
+        let actual = edit
+            .changes
+            .expect("Edit does not have any changes")
+            .remove(&url)
+            .expect("Edit does not have changes for this file")
+            .into_iter()
+            .map(|edit| (range_summary(edit.range), edit.new_text))
+            .collect::<Vec<_>>();
+
+        expect![[r#"
+            [
+                (
+                    (
+                        4,
+                        4,
+                        9,
+                    ),
+                    "total",
+                ),
+                (
+                    (
+                        5,
+                        4,
+                        9,
+                    ),
+                    "total",
+                ),
+                (
+                    (
+                        5,
+                        12,
+                        17,
+                    ),
+                    "total",
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_rename_rejects_keywords() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+                main =
+                    count = 1
+                    count + count
+            "#};
+
+        let (inner, url) = test_setup(doc).await;
+        let registry = &inner.registry;
+
+        for keyword in [
+            "if",
+            "when",
+            "crash",
+            "where",
+            "implements",
+            "exposing",
+            "dbg!",
+        ] {
+            let edit = registry.rename(&url, Position::new(5, 5), keyword).await;
+            assert_eq!(edit, None, "renaming to {keyword:?} should be rejected");
+        }
+
+        let edit = registry.rename(&url, Position::new(5, 5), "whenever").await;
+        assert!(edit.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_rename_across_modules() {
+        let dir = std::env::temp_dir().join(format!("roc_ls_rename_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let greeting_path = dir.join("Greeting.roc");
+        std::fs::write(
+            &greeting_path,
+            indoc! {r#"
+                interface Greeting
+                    exposes [greet]
+                    imports []
+
+                greet = \name -> name
+            "#},
+        )
+        .unwrap();
+
+        let doc = indoc! {r#"
+            interface Test
+                exposes []
+                imports [Greeting]
+
+            main = Greeting.greet "world"
+        "#};
+
+        let url = Url::from_file_path(dir.join("Test.roc")).unwrap();
+        let greeting_url = Url::from_file_path(&greeting_path).unwrap();
+
+        let inner = RocServerState::new(RocServerConfig::default(), Registry::default());
+        inner.change(&url, doc.to_string(), 0).await.unwrap();
+
+        let edit = inner
+            .registry
+            .rename(&url, Position::new(4, 17), "salute")
+            .await;
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let mut changes = edit
+            .expect("Renaming a value from another module failed")
+            .changes
+            .expect("Edit does not have any changes");
+
+        let summarise = |edits: Vec<TextEdit>| {
+            edits
+                .into_iter()
+                .map(|edit| (range_summary(edit.range), edit.new_text))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            summarise(changes.remove(&url).expect("Test.roc was not edited")),
+            vec![((4, 16, 21), "salute".to_string())]
+        );
+        assert_eq!(
+            summarise(
+                changes
+                    .remove(&greeting_url)
+                    .expect("Greeting.roc was not edited")
+            ),
+            vec![
+                ((1, 13, 18), "salute".to_string()),
+                ((4, 0, 5), "salute".to_string()),
+            ]
+        );
+        assert!(changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_symbols_and_folding() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+                Color : [Red, Green]
+
+                Age := U64
+
+                Describe implements
+                    describe : a -> Str where a implements Describe
+
+                greet : Str -> Str
+                greet = \name ->
+                    Str.concat "Hello, " name
+
+                answer = 42
+            "#};
+
+        let (inner, url) = test_setup(doc).await;
+        let registry = &inner.registry;
+
+        fn summary(symbol: &DocumentSymbol) -> (String, SymbolKind, u32, u32, usize) {
+            let children = symbol.children.as_ref().map_or(0, Vec::len);
+            let Range { start, end } = symbol.range;
+            (
+                symbol.name.clone(),
+                symbol.kind,
+                start.line,
+                end.line,
+                children,
+            )
+        }
+
+        let Some(DocumentSymbolResponse::Nested(symbols)) = registry.document_symbols(&url).await
+        else {
+            panic!("Expected nested document symbols");
+        };
+
+        let actual = symbols.iter().map(summary).collect::<Vec<_>>();
+        let expected = vec![
+            ("Color".to_string(), SymbolKind::ENUM, 3, 3, 0),
+            ("Age".to_string(), SymbolKind::CLASS, 5, 5, 0),
+            ("Describe".to_string(), SymbolKind::INTERFACE, 7, 8, 1),
+            ("greet".to_string(), SymbolKind::FUNCTION, 10, 12, 0),
+            ("answer".to_string(), SymbolKind::CONSTANT, 14, 14, 0),
+        ];
+        assert_eq!(actual, expected);
+
+        let member = &symbols[2].children.as_ref().unwrap()[0];
+        assert_eq!(
+            summary(member),
+            ("describe".to_string(), SymbolKind::METHOD, 8, 8, 0)
+        );
+
+        let found = registry
+            .workspace_symbols("GRT")
+            .await
+            .into_iter()
+            .map(|symbol| (symbol.name, symbol.container_name))
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec![("greet".to_string(), Some("Test".to_string()))]);
+
+        let folds = registry
+            .folding_ranges(&url)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|range| (range.start_line, range.end_line, range.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            folds,
+            vec![
+                (0, 2, None),
+                (7, 8, Some(FoldingRangeKind::Region)),
+                (10, 12, Some(FoldingRangeKind::Region)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signature_help_and_inlay_hints() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+                greet = \greeting, name ->
+                    Str.concat greeting name
+
+                main = greet "Hello" "world"
+            "#};
+
+        let (inner, url) = test_setup(doc).await;
+        let registry = &inner.registry;
+
+        let help = registry
+            .signature_help(&url, Position::new(6, 22))
+            .await
+            .unwrap();
+        let signature = &help.signatures[0];
+
+        assert_eq!(signature.label, "greet : Str, Str -> Str");
+        assert_eq!(
+            signature.parameters,
+            Some(vec![
+                ParameterInformation {
+                    label: ParameterLabel::LabelOffsets([8, 11]),
+                    documentation: None,
+                },
+                ParameterInformation {
+                    label: ParameterLabel::LabelOffsets([13, 16]),
+                    documentation: None,
+                },
+            ])
+        );
+        assert_eq!(help.active_parameter, Some(1));
+
+        let whole_document = Range::new(Position::new(0, 0), Position::new(7, 0));
+        let hints = registry
+            .inlay_hints(&url, whole_document)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|hint| match hint.label {
+                InlayHintLabel::String(label) => {
+                    (hint.position.line, hint.position.character, label)
+                }
+                InlayHintLabel::LabelParts(_) => panic!("Expected a plain label"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            hints,
+            vec![
+                (3, 5, ": Str, Str -> Str".to_string()),
+                (3, 17, ": Str".to_string()),
+                (3, 23, ": Str".to_string()),
+                (6, 4, ": Str".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signature_help_offsets_are_utf16() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+                grüßen = \greeting, name ->
+                    Str.concat greeting name
+
+                main = grüßen "Hello" "world"
+            "#};
+
+        let (inner, url) = test_setup(doc).await;
+
+        let help = inner
+            .registry
+            .signature_help(&url, Position::new(6, 23))
+            .await
+            .unwrap();
+        let signature = &help.signatures[0];
+
+        // `ü` and `ß` are two bytes each in UTF-8 but one UTF-16 code unit
+        assert_eq!(signature.label, "grüßen : Str, Str -> Str");
+        assert_eq!(
+            signature.parameters,
+            Some(vec![
+                ParameterInformation {
+                    label: ParameterLabel::LabelOffsets([9, 12]),
+                    documentation: None,
+                },
+                ParameterInformation {
+                    label: ParameterLabel::LabelOffsets([14, 17]),
+                    documentation: None,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_reuses_unchanged_modules() {
+        let dir = std::env::temp_dir().join(format!("roc_ls_reuse_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let write_dep = |value: &str| {
+            let source = format!("module [value]\n\nvalue = {value}\n");
+            std::fs::write(dir.join("Dep.roc"), source).unwrap();
+        };
+
+        write_dep("1");
+        std::fs::write(
+            dir.join("Other.roc"),
+            indoc! {r#"
+                module [other]
+
+                other = "other"
+            "#},
+        )
+        .unwrap();
+
+        let doc = indoc! {r#"
+            module [main]
+
+            import Dep
+            import Other
+
+            main = (Dep.value, Other.other)
+        "#};
+        let url = Url::from_file_path(dir.join("Test.roc")).unwrap();
+        let doc_info = DocInfo::new(url, doc.to_string(), 0);
+
+        let analyzed = |documents: Vec<AnalyzedDocument>| {
+            let mut names = documents
+                .iter()
+                .filter_map(|document| document.url().to_file_path().ok())
+                .filter(|path| path.starts_with(&dir))
+                .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+
+        let (documents, solved) = global_analysis(doc_info.clone(), None, vec![]);
+        assert_eq!(analyzed(documents), ["Dep.roc", "Other.roc", "Test.roc"]);
+
+        // Only the changed dependency and the root, which imports it, get solved again
+        write_dep("\"changed\"");
+        let (documents, solved) = global_analysis(doc_info.clone(), solved.map(Arc::new), vec![]);
+        assert_eq!(analyzed(documents), ["Dep.roc", "Test.roc"]);
+
+        // Open documents may have unsaved changes, so they are solved again as well
+        let open_paths = vec![dir.join("Other.roc")];
+        let (documents, _) = global_analysis(doc_info, solved.map(Arc::new), open_paths);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(analyzed(documents), ["Other.roc", "Test.roc"]);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_change() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r#"
+                # 🦅 soaring
+                main = 1
+            "#};
+
+        let (inner, url) = test_setup(doc).await;
+
+        let edit = |line, start, end, text: &str| TextDocumentContentChangeEvent {
+            range: Some(Range::new(
+                Position::new(line, start),
+                Position::new(line, end),
+            )),
+            range_length: None,
+            text: text.to_string(),
+        };
+
+        // The comment's emoji is two UTF-16 code units, but four bytes
+        let changes = vec![edit(3, 5, 12, "diving"), edit(4, 7, 8, "\"hi\"")];
+        inner.change_incrementally(&url, changes, 1).await.unwrap();
+
+        let hover = inner
+            .registry
+            .hover(&url, Position::new(4, 0))
+            .await
+            .unwrap();
+
+        let HoverContents::Array(contents) = hover.contents else {
+            panic!("Expected the hover to have an array of contents");
+        };
+        assert_eq!(
+            contents[0],
+            MarkedString::LanguageString(LanguageString {
+                language: "roc".to_string(),
+                value: "Str".to_string(),
+            })
+        );
+
+        let latest_version = inner.registry.get_latest_version(&url).await;
+        assert_eq!(latest_version, Some(1));
+    }
 }
@@ -1,8 +1,9 @@
 #[cfg(not(target_family = "wasm"))]
 use {
-    crate::https::{self, PackageMetadata, Problem},
+    crate::https::{self, Destination, Mirrors, PackageMetadata, Problem},
+    crate::tarball,
     roc_error_macros::internal_error,
-    std::fs,
+    std::{fs, io},
 };
 #[cfg(not(target_family = "wasm"))]
 const MAX_DOWNLOAD_BYTES: u64 = 32 * 1_000_000_000; // GB
@@ -13,6 +14,12 @@ use std::path::{Path, PathBuf};
 pub enum RocCacheDir<'a> {
     /// Normal scenario: reading from the user's cache dir on disk
     Persistent(&'a Path),
+    /// Like Persistent, except that packages `roc vendor` copied into the project's vendor dir
+    /// are extracted from there into the cache, without needing the network.
+    Vendored {
+        vendor_dir: &'a Path,
+        cache_dir: &'a Path,
+    },
     /// For build.rs and tests where we never want to be downloading anything - yell loudly if we try!
     Disallowed,
     /// For tests only; we don't want to write to the real cache during a test!
@@ -20,15 +27,66 @@ pub enum RocCacheDir<'a> {
     Temp(&'a tempfile::TempDir),
 }
 
-impl RocCacheDir<'_> {
+impl<'a> RocCacheDir<'a> {
+    /// Persistent, or Vendored if there's a vendor dir.
+    pub fn with_vendor_dir(cache_dir: &'a Path, vendor_dir: Option<&'a Path>) -> Self {
+        match vendor_dir {
+            Some(vendor_dir) => RocCacheDir::Vendored {
+                vendor_dir,
+                cache_dir,
+            },
+            None => RocCacheDir::Persistent(cache_dir),
+        }
+    }
+
     pub fn as_persistent_path(&self) -> Option<&Path> {
         match self {
             RocCacheDir::Persistent(path) => Some(path),
+            RocCacheDir::Vendored { cache_dir, .. } => Some(cache_dir),
             RocCacheDir::Disallowed => None,
             #[cfg(test)]
             RocCacheDir::Temp(_) => None,
         }
     }
+
+    pub fn vendor_dir(&self) -> Option<&Path> {
+        match self {
+            RocCacheDir::Vendored { vendor_dir, .. } => Some(vendor_dir),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the package or platform is given as a URL, rather than as a path to its main module.
+pub fn is_url(package_name: &str) -> bool {
+    package_name.starts_with("https://") || package_name.starts_with("file://")
+}
+
+/// The name of the dir `roc vendor` copies packages into, next to the app or package's main module
+pub const VENDOR_DIR_NAME: &str = "vendor";
+
+/// The vendor dir next to the given main module, if there is one.
+pub fn find_vendor_dir(main_module: &Path) -> Option<PathBuf> {
+    let vendor_dir = match main_module.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.join(VENDOR_DIR_NAME),
+        _ => PathBuf::from(VENDOR_DIR_NAME),
+    };
+
+    vendor_dir.is_dir().then_some(vendor_dir)
+}
+
+/// Where the package with the given cache subdir and content hash is in the cache dir (whether or
+/// not it's been downloaded yet). Vendored packages are extracted there too.
+pub fn package_dir(cache_dir: &Path, cache_subdir: &str, content_hash: &str) -> PathBuf {
+    cache_dir.join(cache_subdir).join(content_hash)
+}
+
+/// Where `roc vendor` puts the tarball of the package with the given cache subdir and content
+/// hash. It's stored uncompressed, so its hash is the one in the package's URL.
+pub fn vendored_tarball(vendor_dir: &Path, cache_subdir: &str, content_hash: &str) -> PathBuf {
+    vendor_dir
+        .join(cache_subdir)
+        .join(format!("{content_hash}.tar"))
 }
 
 // Errors in case NixOS users try to use a dynamically linked platform
@@ -80,13 +138,12 @@ pub fn install_package<'a>(
     roc_cache_dir: RocCacheDir<'_>,
     url: &'a str,
 ) -> Result<(PathBuf, Option<&'a str>), Problem> {
-    use std::io::ErrorKind;
-
     let PackageMetadata {
         cache_subdir,
         content_hash,
         root_module_filename,
     } = PackageMetadata::try_from(url).map_err(Problem::InvalidUrl)?;
+    let content_hash: &str = &content_hash;
 
     match roc_cache_dir {
         RocCacheDir::Persistent(cache_dir) => {
@@ -98,77 +155,33 @@ pub fn install_package<'a>(
             if dest_dir.exists() {
                 // If the cache dir exists already, we assume it has the correct contents
                 // (it's a cache, after all!) and return without downloading anything.
+                // `roc cache verify` is for when that assumption is in doubt.
                 //
                 #[cfg(target_os = "linux")]
                 {
                     nixos_error_if_dynamic(url, &dest_dir);
                 }
 
+                mark_used(&dest_dir);
+
                 Ok((dest_dir, root_module_filename))
             } else {
                 // Download into a tempdir; only move it to dest_dir if hash verification passes.
-                println!(
+                eprintln!(
                     "Downloading \u{001b}[36m{url}\u{001b}[0m\n    into {}\n",
                     cache_dir.display()
                 );
                 let tempdir = tempfile::tempdir().map_err(Problem::IoErr)?;
                 let tempdir_path = tempdir.path();
-                let downloaded_hash =
-                    https::download_and_hash(url, tempdir_path, MAX_DOWNLOAD_BYTES)?;
+                let downloaded_hash = download_and_hash(url, Destination::Dir(tempdir_path))?;
 
                 // Download the tarball into memory and verify it.
                 // The tarball name is the hash of its contents.
                 if downloaded_hash == content_hash {
-                    // Now that we've verified the hash, rename the tempdir to the real dir.
-
-                    // Create the destination dir's parent dir, since it may not exist yet.
-                    fs::create_dir_all(parent_dir).or_else(|err| match err.kind() {
-                        // It's fine if the destination dir's parent already exists
-                        ErrorKind::AlreadyExists => Ok(()),
-                        _ => Err(Problem::IoErr(err)),
-                    })?;
-
-                    // This rename should be super cheap if it succeeds - just an inode change.
-                    let rename_err_kind = fs::rename(tempdir_path, &dest_dir)
-                        .err()
-                        .map(|err| err.kind());
-
-                    // It's okay if the rename failed because the destination already existed.
-                    // This could be a race condition between multiple downloads happening concurrently.
-                    // (This has happened in our test suite, for example!) Both downloads should have
-                    // the same content, so the rename failing for that reason should be no problem.
-                    if rename_err_kind.is_some()
-                        && rename_err_kind != Some(ErrorKind::AlreadyExists)
-                    {
-                        // If the rename failed, try a recursive copy -
-                        // it could have failed due to std::io::ErrorKind::CrossesDevices
-                        // (e.g. if the source an destination directories are on different disks)
-                        // which as of this implementation is nightly-only
-                        // https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.CrossesDevices                       match io_err.kind() {
-                        // but if that's what happened, this should work!
-
-                        // fs_extra::dir::copy needs the destination directory to exist already.
-                        fs::create_dir(&dest_dir).or_else(|err| match err.kind() {
-                            // It's fine if the destination dir already exists
-                            ErrorKind::AlreadyExists => Ok(()),
-                            _ => Err(Problem::IoErr(err)),
-                        })?;
-
-                        fs_extra::dir::copy(
-                            tempdir_path,
-                            &dest_dir,
-                            &fs_extra::dir::CopyOptions {
-                                content_only: true,
-                                ..Default::default()
-                            },
-                        )
-                        .or_else(|err| match err.kind {
-                            // It's fine if the destination file already exists; this could be the same
-                            // as the rename race condition mentioned above.
-                            fs_extra::error::ErrorKind::AlreadyExists => Ok(0),
-                            _ => Err(Problem::FsExtraErr(err)),
-                        })?;
-                    }
+                    tarball::verify_manifest(tempdir_path).map_err(Problem::InvalidManifest)?;
+
+                    move_into_cache(tempdir_path, &parent_dir, &dest_dir)?;
+                    mark_used(&dest_dir);
 
                     #[cfg(target_os = "linux")]
                     {
@@ -185,6 +198,47 @@ pub fn install_package<'a>(
                 }
             }
         }
+        RocCacheDir::Vendored {
+            vendor_dir,
+            cache_dir,
+        } => {
+            let tarball_path = vendored_tarball(vendor_dir, cache_subdir, content_hash);
+
+            if !tarball_path.exists() {
+                return install_package(RocCacheDir::Persistent(cache_dir), url);
+            }
+
+            // Unlike the cache, the vendor dir lives in the project, where anyone with commit
+            // access could change it, so its tarball gets checked against the URL's hash every
+            // time it's used, even if it was already extracted into the cache.
+            let parent_dir = cache_dir.join(cache_subdir);
+            let dest_dir = parent_dir.join(content_hash);
+
+            if dest_dir.exists() {
+                if https::hash_tarball(&tarball_path)? != content_hash {
+                    return Err(Problem::VendoredPackageChanged(tarball_path));
+                }
+            } else {
+                let tempdir = tempfile::tempdir().map_err(Problem::IoErr)?;
+                let tempdir_path = tempdir.path();
+
+                if https::unpack_and_hash(&tarball_path, tempdir_path)? != content_hash {
+                    return Err(Problem::VendoredPackageChanged(tarball_path));
+                }
+
+                tarball::verify_manifest(tempdir_path).map_err(Problem::InvalidManifest)?;
+                move_into_cache(tempdir_path, &parent_dir, &dest_dir)?;
+            }
+
+            mark_used(&dest_dir);
+
+            #[cfg(target_os = "linux")]
+            {
+                nixos_error_if_dynamic(url, &dest_dir);
+            }
+
+            Ok((dest_dir, root_module_filename))
+        }
         RocCacheDir::Disallowed => {
             internal_error!(
                 "Tried to download a package ({:?}) via RocCacheDir::Disallowed - which was explicitly used in order to disallow downloading packages in the current context!",
@@ -196,6 +250,357 @@ pub fn install_package<'a>(
     }
 }
 
+/// Moves a package that was extracted into a tempdir, and whose hash has been verified, into its
+/// dir in the cache.
+#[cfg(not(target_family = "wasm"))]
+fn move_into_cache(tempdir_path: &Path, parent_dir: &Path, dest_dir: &Path) -> Result<(), Problem> {
+    use std::io::ErrorKind;
+
+    // Now that we've verified the hash, rename the tempdir to the real dir.
+
+    // Create the destination dir's parent dir, since it may not exist yet.
+    fs::create_dir_all(parent_dir).or_else(|err| match err.kind() {
+        // It's fine if the destination dir's parent already exists
+        ErrorKind::AlreadyExists => Ok(()),
+        _ => Err(Problem::IoErr(err)),
+    })?;
+
+    // This rename should be super cheap if it succeeds - just an inode change.
+    let rename_err_kind = fs::rename(tempdir_path, dest_dir)
+        .err()
+        .map(|err| err.kind());
+
+    // It's okay if the rename failed because the destination already existed.
+    // This could be a race condition between multiple downloads happening concurrently.
+    // (This has happened in our test suite, for example!) Both downloads should have
+    // the same content, so the rename failing for that reason should be no problem.
+    if rename_err_kind.is_some() && rename_err_kind != Some(ErrorKind::AlreadyExists) {
+        // If the rename failed, try a recursive copy -
+        // it could have failed due to std::io::ErrorKind::CrossesDevices
+        // (e.g. if the source an destination directories are on different disks)
+        // which as of this implementation is nightly-only
+        // https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.CrossesDevices                       match io_err.kind() {
+        // but if that's what happened, this should work!
+
+        // fs_extra::dir::copy needs the destination directory to exist already.
+        fs::create_dir(dest_dir).or_else(|err| match err.kind() {
+            // It's fine if the destination dir already exists
+            ErrorKind::AlreadyExists => Ok(()),
+            _ => Err(Problem::IoErr(err)),
+        })?;
+
+        fs_extra::dir::copy(
+            tempdir_path,
+            dest_dir,
+            &fs_extra::dir::CopyOptions {
+                content_only: true,
+                ..Default::default()
+            },
+        )
+        .or_else(|err| match err.kind {
+            // It's fine if the destination file already exists; this could be the same
+            // as the rename race condition mentioned above.
+            fs_extra::error::ErrorKind::AlreadyExists => Ok(0),
+            _ => Err(Problem::FsExtraErr(err)),
+        })?;
+    }
+
+    record_contents_hash(dest_dir).map_err(Problem::IoErr)
+}
+
+/// Downloads from the package's mirror if ROC_PACKAGE_MIRRORS has one, and otherwise from its URL.
+#[cfg(not(target_family = "wasm"))]
+fn download_and_hash(url: &str, dest: Destination) -> Result<String, Problem> {
+    let download_url = Mirrors::from_env().apply(url);
+
+    if download_url != url {
+        eprintln!("    (from the mirror \u{001b}[36m{download_url}\u{001b}[0m)\n");
+    }
+
+    https::download_and_hash(&download_url, dest, MAX_DOWNLOAD_BYTES)
+}
+
+/// Downloads the package's tarball into the vendor dir, where the loader extracts it into the
+/// cache from (see `RocCacheDir::Vendored`). It's stored uncompressed, so that checking it against
+/// the hash in the URL doesn't need the network, or the compression the URL's server used.
+///
+/// Returns the path to the package in the cache, as well as the requested root module filename.
+#[cfg(not(target_family = "wasm"))]
+pub fn vendor_package<'a>(
+    cache_dir: &Path,
+    vendor_dir: &Path,
+    url: &'a str,
+) -> Result<(PathBuf, Option<&'a str>), Problem> {
+    let PackageMetadata {
+        cache_subdir,
+        content_hash,
+        ..
+    } = PackageMetadata::try_from(url).map_err(Problem::InvalidUrl)?;
+    let content_hash: &str = &content_hash;
+
+    let roc_cache_dir = RocCacheDir::Vendored {
+        vendor_dir,
+        cache_dir,
+    };
+    let tarball_path = vendored_tarball(vendor_dir, cache_subdir, content_hash);
+
+    if tarball_path.exists() {
+        match install_package(roc_cache_dir, url) {
+            Err(Problem::VendoredPackageChanged(_)) => {
+                fs::remove_file(&tarball_path).map_err(Problem::IoErr)?;
+            }
+            result => return result,
+        }
+    }
+
+    let parent_dir = vendor_dir.join(cache_subdir);
+
+    fs::create_dir_all(&parent_dir).map_err(Problem::IoErr)?;
+
+    eprintln!(
+        "Downloading \u{001b}[36m{url}\u{001b}[0m\n    into {}\n",
+        vendor_dir.display()
+    );
+
+    // Download next to where the tarball goes, and only move it there if its hash is right
+    let tempfile = tempfile::NamedTempFile::new_in(&parent_dir).map_err(Problem::IoErr)?;
+    let downloaded_hash = download_and_hash(url, Destination::Tarball(tempfile.path()))?;
+
+    if downloaded_hash != content_hash {
+        return Err(Problem::InvalidContentHash {
+            expected: content_hash.to_string(),
+            actual: downloaded_hash,
+        });
+    }
+
+    tempfile
+        .persist(&tarball_path)
+        .map_err(|err| Problem::IoErr(err.error))?;
+
+    install_package(roc_cache_dir, url)
+}
+
+/// The hash in a package's URL is of its tarball, which can't be recomputed from the files
+/// extracted from it. So after extracting a package we also record a hash of its files, in a file
+/// next to it with this extension, which lets us check later that they haven't changed.
+#[cfg(not(target_family = "wasm"))]
+const CONTENTS_HASH_EXT: &str = "contents";
+
+#[cfg(not(target_family = "wasm"))]
+fn contents_hash_path(package_dir: &Path) -> PathBuf {
+    package_dir.with_extension(CONTENTS_HASH_EXT)
+}
+
+/// The extension of an empty file next to each package dir in the cache, whose modification time
+/// is when a build last used the package. `roc cache prune` uses it to find stale packages.
+#[cfg(not(target_family = "wasm"))]
+const LAST_USED_EXT: &str = "last-used";
+
+#[cfg(not(target_family = "wasm"))]
+fn mark_used(package_dir: &Path) {
+    // This is only for pruning, so it's not worth failing a build over, e.g. if the cache is
+    // read-only
+    let _ = fs::write(package_dir.with_extension(LAST_USED_EXT), "");
+}
+
+/// A package in the cache
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug)]
+pub struct CacheEntry {
+    /// e.g. ~/.cache/roc/packages/example.com/roc-packages/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE
+    pub path: PathBuf,
+    /// The path relative to the cache dir, e.g.
+    /// example.com/roc-packages/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE
+    pub name: String,
+    /// The total size of its files, in bytes
+    pub size: u64,
+    /// When a build last used it. For packages cached before we kept track, this is when they
+    /// were downloaded.
+    pub last_used: Option<std::time::SystemTime>,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl CacheEntry {
+    fn new(cache_dir: &Path, path: PathBuf) -> io::Result<Self> {
+        let name = path
+            .strip_prefix(cache_dir)
+            .unwrap_or(&path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let size = dir_size(&path)?;
+        let last_used = fs::metadata(path.with_extension(LAST_USED_EXT))
+            .or_else(|_| fs::metadata(&path))
+            .and_then(|metadata| metadata.modified())
+            .ok();
+
+        Ok(Self {
+            path,
+            name,
+            size,
+            last_used,
+        })
+    }
+
+    /// The content hash from the package's URL, which is also its dir name
+    pub fn content_hash(&self) -> &str {
+        self.name.rsplit('/').next().unwrap_or(&self.name)
+    }
+
+    /// Checks that the package's files still match the hash recorded when they were extracted.
+    pub fn verify(&self) -> Result<(), Problem> {
+        verify_contents(&self.path)
+    }
+
+    /// Deletes the package from the cache. If a later build needs it, it gets downloaded again.
+    pub fn remove(self) -> io::Result<()> {
+        for record in [
+            self.path.with_extension(CONTENTS_HASH_EXT),
+            self.path.with_extension(LAST_USED_EXT),
+        ] {
+            match fs::remove_file(record) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            }
+        }
+
+        fs::remove_dir_all(&self.path)
+    }
+}
+
+/// The total size of the files in the given dir, in bytes
+#[cfg(not(target_family = "wasm"))]
+pub fn dir_size(dir: &Path) -> io::Result<u64> {
+    let mut size = 0;
+
+    for entry in walkdir::WalkDir::new(dir) {
+        let entry = entry?;
+
+        if entry.file_type().is_file() {
+            size += entry.metadata()?.len();
+        }
+    }
+
+    Ok(size)
+}
+
+/// Every package in the cache dir, sorted by name.
+#[cfg(not(target_family = "wasm"))]
+pub fn cache_entries(cache_dir: &Path) -> io::Result<Vec<CacheEntry>> {
+    let mut entries = Vec::new();
+
+    if cache_dir.is_dir() {
+        add_cache_entries(cache_dir, cache_dir, &mut entries)?;
+    }
+
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(entries)
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn add_cache_entries(
+    cache_dir: &Path,
+    dir: &Path,
+    entries: &mut Vec<CacheEntry>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        let path = entry.path();
+
+        if is_package_dir(&path) {
+            entries.push(CacheEntry::new(cache_dir, path)?);
+        } else {
+            add_cache_entries(cache_dir, &path, entries)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Package dirs are named after their content hash. Ones we've extracted since we started
+/// recording contents hashes also have that record next to them.
+#[cfg(not(target_family = "wasm"))]
+fn is_package_dir(path: &Path) -> bool {
+    // A base64url-encoded BLAKE3 hash is always 43 characters
+    const HASH_LEN: usize = 43;
+
+    let is_hash = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            name.len() == HASH_LEN
+                && name
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        });
+
+    is_hash || contents_hash_path(path).is_file()
+}
+
+/// The base64url-encoded BLAKE3 hash of the paths (relative to the given dir) and contents of
+/// every file in the given dir.
+#[cfg(not(target_family = "wasm"))]
+pub fn hash_contents(dir: &Path) -> io::Result<String> {
+    let mut hasher = blake3::Hasher::new();
+
+    for entry in walkdir::WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry?;
+
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        // Join with `/` on every OS, so the same files always get the same hash
+        let relative_path = entry
+            .path()
+            .strip_prefix(dir)
+            .unwrap_or(entry.path())
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let contents = fs::read(entry.path())?;
+
+        hasher.update(relative_path.as_bytes());
+        hasher.update(&[0]);
+        hasher.update(&(contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+
+    Ok(base64_url::encode(hasher.finalize().as_bytes()))
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn record_contents_hash(package_dir: &Path) -> io::Result<()> {
+    fs::write(contents_hash_path(package_dir), hash_contents(package_dir)?)
+}
+
+/// Checks that the package's files still match the hash recorded when they were extracted.
+#[cfg(not(target_family = "wasm"))]
+pub fn verify_contents(package_dir: &Path) -> Result<(), Problem> {
+    let recorded = match fs::read_to_string(contents_hash_path(package_dir)) {
+        Ok(recorded) => recorded,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(Problem::ContentsHashMissing(package_dir.to_path_buf()));
+        }
+        Err(err) => return Err(Problem::IoErr(err)),
+    };
+
+    if hash_contents(package_dir).map_err(Problem::IoErr)? == recorded.trim() {
+        Ok(())
+    } else {
+        Err(Problem::ContentsChanged(package_dir.to_path_buf()))
+    }
+}
+
 #[cfg(windows)]
 // e.g. the "Roc" in %APPDATA%\\Roc
 const ROC_CACHE_DIR_NAME: &str = "Roc";
@@ -267,3 +672,226 @@ pub fn roc_cache_packages_dir() -> PathBuf {
     const PACKAGES_DIR_NAME: &str = "packages";
     roc_cache_dir().join(PACKAGES_DIR_NAME)
 }
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn verify_contents_detects_changes() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let package_dir = tempdir
+        .path()
+        .join("jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE");
+
+    fs::create_dir_all(package_dir.join("Json")).unwrap();
+    fs::write(package_dir.join("main.roc"), "package [Json] {}\n").unwrap();
+    fs::write(package_dir.join("Json").join("Decode.roc"), "module []\n").unwrap();
+
+    record_contents_hash(&package_dir).unwrap();
+    assert!(verify_contents(&package_dir).is_ok());
+
+    fs::write(package_dir.join("Json").join("Decode.roc"), "module [x]\n").unwrap();
+    assert!(matches!(
+        verify_contents(&package_dir),
+        Err(Problem::ContentsChanged(_))
+    ));
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn verify_contents_without_record() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    assert!(matches!(
+        verify_contents(tempdir.path()),
+        Err(Problem::ContentsHashMissing(_))
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn install_package_from_file_url() {
+    use crate::tarball::Compression;
+
+    let package = tempfile::tempdir().unwrap();
+    let main_path = package.path().join("main.roc");
+
+    fs::write(&main_path, "package [Csv] {}\n").unwrap();
+    fs::write(package.path().join("Csv.roc"), "module [x]\n\nx = 1\n").unwrap();
+
+    let filename = tarball::build(&main_path, Compression::Brotli)
+        .unwrap()
+        .filename;
+    let tarball_path = package.path().join(filename);
+    let cache = tempfile::tempdir().unwrap();
+
+    let url = format!("file://{}#main.roc", tarball_path.display());
+    let (package_dir, root_module) =
+        install_package(RocCacheDir::Persistent(cache.path()), &url).unwrap();
+
+    assert_eq!(root_module, Some("main.roc"));
+    assert!(package_dir.join("Csv.roc").is_file());
+    assert!(verify_contents(&package_dir).is_ok());
+
+    // The hash is still checked, so a file:// URL can't serve a different package
+    let wrong_path = package
+        .path()
+        .join("jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE.tar.br");
+    fs::copy(&tarball_path, &wrong_path).unwrap();
+
+    let wrong_url = format!("file://{}", wrong_path.display());
+
+    assert!(matches!(
+        install_package(RocCacheDir::Persistent(cache.path()), &wrong_url),
+        Err(Problem::InvalidContentHash { .. })
+    ));
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn install_package_from_file_url_with_space() {
+    use crate::tarball::Compression;
+
+    let tempdir = tempfile::tempdir().unwrap();
+    let package = tempdir.path().join("roc packages");
+    let main_path = package.join("main.roc");
+
+    fs::create_dir_all(&package).unwrap();
+    fs::write(&main_path, "package [Csv] {}\n").unwrap();
+    fs::write(package.join("Csv.roc"), "module [x]\n\nx = 1\n").unwrap();
+
+    let filename = tarball::build(&main_path, Compression::Zstd)
+        .unwrap()
+        .filename;
+
+    // Like the URLs that editors and browsers make for files: forward slashes, an extra one
+    // before a Windows drive letter, and spaces escaped
+    let path = package
+        .join(filename)
+        .display()
+        .to_string()
+        .replace('\\', "/");
+    let path = if path.starts_with('/') {
+        path
+    } else {
+        format!("/{path}")
+    };
+    let url = format!("file://{}", path.replace(' ', "%20"));
+    let cache = tempfile::tempdir().unwrap();
+
+    let (package_dir, _) = install_package(RocCacheDir::Persistent(cache.path()), &url).unwrap();
+
+    assert!(package_dir.join("Csv.roc").is_file());
+}
+
+#[cfg(unix)]
+#[test]
+fn vendor_package_restores_changed_tarball() {
+    use crate::tarball::Compression;
+    use std::io::Write;
+
+    let package = tempfile::tempdir().unwrap();
+    let main_path = package.path().join("main.roc");
+
+    fs::write(&main_path, "package [Csv] {}\n").unwrap();
+    fs::write(package.path().join("Csv.roc"), "module [x]\n\nx = 1\n").unwrap();
+
+    let filename = tarball::build(&main_path, Compression::Gzip)
+        .unwrap()
+        .filename;
+    let url = format!("file://{}", package.path().join(filename).display());
+    let vendor = tempfile::tempdir().unwrap();
+    let cache = tempfile::tempdir().unwrap();
+
+    let (package_dir, _) = vendor_package(cache.path(), vendor.path(), &url).unwrap();
+
+    assert!(package_dir.starts_with(cache.path()));
+    assert!(package_dir.join("Csv.roc").is_file());
+
+    let PackageMetadata {
+        cache_subdir,
+        content_hash,
+        ..
+    } = PackageMetadata::try_from(url.as_str()).unwrap();
+    let tarball_path = vendored_tarball(vendor.path(), cache_subdir, &content_hash);
+
+    // It's stored uncompressed, so its own hash is the one in the URL
+    assert_eq!(https::hash_tarball(&tarball_path).unwrap(), content_hash);
+
+    fs::OpenOptions::new()
+        .append(true)
+        .open(&tarball_path)
+        .unwrap()
+        .write_all(b"tampered")
+        .unwrap();
+
+    // Even though it was already extracted into the cache
+    let roc_cache_dir = RocCacheDir::Vendored {
+        vendor_dir: vendor.path(),
+        cache_dir: cache.path(),
+    };
+
+    assert!(matches!(
+        install_package(roc_cache_dir, &url),
+        Err(Problem::VendoredPackageChanged(_))
+    ));
+
+    vendor_package(cache.path(), vendor.path(), &url).unwrap();
+
+    assert!(install_package(roc_cache_dir, &url).is_ok());
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn cache_entries_finds_packages() {
+    let cache = tempfile::tempdir().unwrap();
+    let downloaded = cache
+        .path()
+        .join("example.com")
+        .join("roc-packages")
+        .join("jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE");
+    let from_file = cache.path().join("file").join("package");
+
+    fs::create_dir_all(&downloaded).unwrap();
+    fs::write(downloaded.join("main.roc"), "package [] {}\n").unwrap();
+    fs::create_dir_all(&from_file).unwrap();
+    fs::write(from_file.join("main.roc"), "package [] {}\n").unwrap();
+    record_contents_hash(&from_file).unwrap();
+    mark_used(&from_file);
+
+    // Not a package dir: neither named after a hash nor recorded
+    fs::create_dir_all(cache.path().join("example.com").join("partial")).unwrap();
+
+    let entries = cache_entries(cache.path()).unwrap();
+    let names: Vec<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        [
+            "example.com/roc-packages/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE",
+            "file/package"
+        ]
+    );
+    assert_eq!(entries[0].size, 14);
+    assert_eq!(
+        entries[0].content_hash(),
+        "jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE"
+    );
+    assert!(entries[1].last_used.is_some());
+
+    for entry in entries {
+        entry.remove().unwrap();
+    }
+
+    assert!(cache_entries(cache.path()).unwrap().is_empty());
+    assert!(!contents_hash_path(&from_file).exists());
+    assert!(!from_file.with_extension(LAST_USED_EXT).exists());
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn cache_entries_without_cache_dir() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    assert!(cache_entries(&tempdir.path().join("packages"))
+        .unwrap()
+        .is_empty());
+}
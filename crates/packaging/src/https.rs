@@ -1,12 +1,14 @@
 use std::{
+    borrow::Cow,
     io::{self, Read, Write},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
-use crate::tarball::Compression;
+use crate::tarball::{Compression, ManifestProblem};
 
 // gzip should be the most widely supported, and brotli offers the highest compression.
 // flate2 gets us both gzip and deflate, so there's no harm in offering deflate too.
+// zstd is there for .tar.zst bundles, which decompress much faster than brotli.
 //
 // Here are all the officially supported options: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Accept-Encoding
 // We can consider supporting more, but that would bloat the `roc` binary more, so
@@ -16,19 +18,27 @@ const BROTLI_BUFFER_BYTES: usize = 8 * 1_000_000; // MB
 #[derive(Debug, PartialEq, Eq)]
 pub struct PackageMetadata<'a> {
     /// The BLAKE3 hash of the tarball's contents. Also the .tar filename on disk.
-    pub content_hash: &'a str,
-    /// On disk, this will be the subfolder inside the cache dir where the package lives
+    /// Only a file:// URL's `%XX` escapes need decoding, so this is almost always borrowed.
+    pub content_hash: Cow<'a, str>,
+    /// On disk, this will be the subfolder inside the cache dir where the package lives.
+    /// For file:// URLs this is always FILE_CACHE_SUBDIR, since the content hash is enough to
+    /// tell packages apart, and absolute paths can't be joined onto the cache dir.
     pub cache_subdir: &'a str,
     /// Other code will default this to main.roc, but this module isn't concerned with that default.
     pub root_module_filename: Option<&'a str>,
 }
 
+pub const FILE_PROTOCOL: &str = "file://";
+
+const FILE_CACHE_SUBDIR: &str = "file";
+
 /// Valid URLs must end in one of these:
 ///
 /// - .tar
 /// - .tar.gz
 /// - .tar.br
-const VALID_EXTENSION_SUFFIXES: [&str; 2] = [".gz", ".br"];
+/// - .tar.zst
+const VALID_EXTENSION_SUFFIXES: [&str; 3] = [".gz", ".br", ".zst"];
 
 /// Since the TLD (top level domain) `.zip` is now available, there is a new attack
 /// vector where malicous URLs can be used to confuse the reader.
@@ -65,12 +75,16 @@ impl<'a> TryFrom<&'a str> for PackageMetadata<'a> {
 
 impl<'a> PackageMetadata<'a> {
     fn new(url: &'a str) -> Result<Self, UrlProblem> {
-        // First, verify that the URL starts with https://
-        let without_protocol = match url.split_once("https://") {
-            Some((_, without_protocol)) => without_protocol,
-            None => {
-                return Err(UrlProblem::MissingHttps);
-            }
+        // First, verify that the URL starts with https:// - or file://, which is for serving
+        // packages from a local directory, like a mirror or a test fixture.
+        let (without_protocol, is_file) = match url.strip_prefix(FILE_PROTOCOL) {
+            Some(without_protocol) => (without_protocol, true),
+            None => match url.split_once("https://") {
+                Some((_, without_protocol)) => (without_protocol, false),
+                None => {
+                    return Err(UrlProblem::MissingHttps);
+                }
+            },
         };
 
         // Next, check if there are misleading characters in the URL
@@ -119,14 +133,87 @@ impl<'a> PackageMetadata<'a> {
             }
         };
 
+        // A file:// URL's path gets decoded before the tarball is read, so its name does too
+        let content_hash = if is_file {
+            percent_decode(tarball_name)
+        } else {
+            Cow::Borrowed(tarball_name)
+        };
+
+        // The hash becomes a dir name in the cache, so anything like `..` or `a%2Fb` that isn't
+        // a plain base64url token could point outside it
+        if !content_hash
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        {
+            return Err(UrlProblem::MissingHash);
+        }
+
         Ok(PackageMetadata {
-            cache_subdir: path,
-            content_hash: tarball_name,
+            cache_subdir: if is_file { FILE_CACHE_SUBDIR } else { path },
+            content_hash,
             root_module_filename: fragment,
         })
     }
 }
 
+/// The path of the tarball a file:// URL refers to. Its `%XX` escapes are decoded, and on Windows
+/// the `/` before a drive letter, as in `file:///C:/roc-packages/...`, is dropped.
+pub fn file_url_path(url: &str) -> Option<PathBuf> {
+    let path = url.strip_prefix(FILE_PROTOCOL)?;
+    // The fragment is for the loader, not part of the path
+    let path = path.rsplit_once('#').map_or(path, |(path, _)| path);
+    let path = percent_decode(path);
+
+    if cfg!(windows) {
+        Some(PathBuf::from(without_drive_letter_slash(&path)))
+    } else {
+        Some(PathBuf::from(path.as_ref()))
+    }
+}
+
+/// e.g. `/C:/roc-packages` becomes `C:/roc-packages`
+fn without_drive_letter_slash(path: &str) -> &str {
+    match path.as_bytes() {
+        [b'/', letter, b':', ..] if letter.is_ascii_alphabetic() => &path[1..],
+        _ => path,
+    }
+}
+
+/// Decodes the `%XX` escapes in a URL's path, like `%20` for a space. Anything that isn't a valid
+/// escape is kept as it is, like browsers do.
+fn percent_decode(text: &str) -> Cow<'_, str> {
+    if !text.contains('%') {
+        return Cow::Borrowed(text);
+    }
+
+    let hex_digit = |byte: u8| (byte as char).to_digit(16).map(|digit| digit as u8);
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+
+    while let Some((&byte, after)) = rest.split_first() {
+        let decoded = match after {
+            [high, low, ..] if byte == b'%' => hex_digit(*high)
+                .zip(hex_digit(*low))
+                .map(|(high, low)| high * 16 + low),
+            _ => None,
+        };
+
+        match decoded {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &after[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = after;
+            }
+        }
+    }
+
+    Cow::Owned(String::from_utf8_lossy(&bytes).into_owned())
+}
+
 #[test]
 fn url_problem_missing_https() {
     let expected = Err(UrlProblem::MissingHttps);
@@ -204,7 +291,7 @@ fn url_problem_missing_hash() {
 fn url_without_fragment() {
     let expected = Ok(PackageMetadata {
         cache_subdir: "example.com/path",
-        content_hash: "hash",
+        content_hash: "hash".into(),
         root_module_filename: None,
     });
     assert_eq!(
@@ -217,7 +304,7 @@ fn url_without_fragment() {
 fn url_with_fragment() {
     let expected = Ok(PackageMetadata {
         cache_subdir: "example.com/path",
-        content_hash: "hash",
+        content_hash: "hash".into(),
         root_module_filename: Some("filename.roc"),
     });
     assert_eq!(
@@ -226,6 +313,145 @@ fn url_with_fragment() {
     );
 }
 
+#[test]
+fn file_url() {
+    let expected = Ok(PackageMetadata {
+        cache_subdir: "file",
+        content_hash: "hash".into(),
+        root_module_filename: Some("filename.roc"),
+    });
+    assert_eq!(
+        PackageMetadata::try_from("file:///srv/roc-packages/hash.tar.br#filename.roc"),
+        expected
+    );
+}
+
+#[test]
+fn file_url_with_escapes() {
+    let url = "file:///srv/roc%20packages/h%61sh.tar.br#main.roc";
+
+    assert_eq!(
+        PackageMetadata::try_from(url).map(|metadata| metadata.content_hash),
+        Ok("hash".into())
+    );
+    assert_eq!(
+        file_url_path(url),
+        Some(PathBuf::from("/srv/roc packages/hash.tar.br"))
+    );
+    // Not valid escapes, so they're kept
+    assert_eq!(percent_decode("100%.tar%2"), "100%.tar%2");
+}
+
+#[test]
+fn url_problem_file_url_hash_outside_cache() {
+    for url in [
+        "file:///srv/roc-packages/..%2F..%2Fhash.tar.br",
+        "file:///srv/roc-packages/%2E%2E.tar.br",
+        "file:///srv/roc-packages/a%5Cb.tar.br",
+    ] {
+        assert_eq!(PackageMetadata::try_from(url), Err(UrlProblem::MissingHash));
+    }
+}
+
+#[test]
+fn file_url_drive_letter() {
+    assert_eq!(
+        without_drive_letter_slash("/C:/roc-packages/hash.tar"),
+        "C:/roc-packages/hash.tar"
+    );
+    assert_eq!(
+        without_drive_letter_slash("/srv/roc-packages/hash.tar"),
+        "/srv/roc-packages/hash.tar"
+    );
+}
+
+#[test]
+fn url_problem_file_url_missing_hash() {
+    let expected = Err(UrlProblem::MissingHash);
+    assert_eq!(
+        PackageMetadata::try_from("file:///srv/roc-packages/.tar.br"),
+        expected
+    );
+}
+
+/// The environment variable that lists mirrors to download packages from instead of where their
+/// URLs say, as comma-separated `<prefix>=<replacement>` pairs, for example:
+///
+/// ```text
+/// ROC_PACKAGE_MIRRORS=https://github.com/=https://artifacts.example.com/github/,https://example.com/=file:///srv/roc-packages/
+/// ```
+///
+/// The URL in the source still decides where the package goes in the cache, and its hash is still
+/// checked, so a mirror can only serve the same package.
+pub const MIRRORS_ENV_VAR: &str = "ROC_PACKAGE_MIRRORS";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Mirrors {
+    rewrites: Vec<(String, String)>,
+}
+
+impl Mirrors {
+    /// The mirrors in ROC_PACKAGE_MIRRORS, if it's set.
+    pub fn from_env() -> Self {
+        match std::env::var(MIRRORS_ENV_VAR) {
+            Ok(spec) => Self::parse(&spec),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Entries without an `=`, or whose replacement isn't an https:// or file:// URL, are ignored.
+    pub fn parse(spec: &str) -> Self {
+        let rewrites = spec
+            .split(',')
+            .filter_map(|entry| entry.trim().split_once('='))
+            .filter(|(prefix, replacement)| {
+                !prefix.is_empty()
+                    && (replacement.starts_with("https://")
+                        || replacement.starts_with(FILE_PROTOCOL))
+            })
+            .map(|(prefix, replacement)| (prefix.to_string(), replacement.to_string()))
+            .collect();
+
+        Self { rewrites }
+    }
+
+    /// The URL to download the package from. When more than one prefix matches, the longest wins.
+    pub fn apply<'u>(&self, url: &'u str) -> Cow<'u, str> {
+        let longest_match = self
+            .rewrites
+            .iter()
+            .filter(|(prefix, _)| url.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len());
+
+        match longest_match {
+            Some((prefix, replacement)) => {
+                Cow::Owned(format!("{replacement}{}", &url[prefix.len()..]))
+            }
+            None => Cow::Borrowed(url),
+        }
+    }
+}
+
+#[test]
+fn mirrors_rewrite_longest_prefix() {
+    let mirrors = Mirrors::parse(
+        "https://github.com/=https://mirror.example.com/github/, https://github.com/roc-lang/=file:///srv/roc/,invalid,https://example.com/=http://example.org/",
+    );
+
+    assert_eq!(
+        mirrors.apply("https://github.com/roc-lang/basic-cli/releases/download/0.1.0/hash.tar.br"),
+        "file:///srv/roc/basic-cli/releases/download/0.1.0/hash.tar.br"
+    );
+    assert_eq!(
+        mirrors.apply("https://github.com/someone/pkg/hash.tar.br"),
+        "https://mirror.example.com/github/someone/pkg/hash.tar.br"
+    );
+    assert_eq!(
+        mirrors.apply("https://example.com/hash.tar.br"),
+        "https://example.com/hash.tar.br"
+    );
+}
+
 #[derive(Debug)]
 pub enum Problem {
     UnsupportedEncoding(String),
@@ -241,13 +467,35 @@ pub enum Problem {
     /// The Content-Length header of the response exceeded max_download_bytes
     DownloadTooBig(u64),
     NotFound,
+    /// The files in this package dir aren't the ones that were extracted into it
+    ContentsChanged(PathBuf),
+    /// There's no record of what files were extracted into this package dir, so it can't be checked
+    ContentsHashMissing(PathBuf),
+    /// The extracted files don't match the manifest in the bundle
+    InvalidManifest(ManifestProblem),
+    /// The tarball in the vendor dir doesn't have the hash in the package's URL
+    VendoredPackageChanged(PathBuf),
+}
+
+/// Where a tarball's bytes go as they're hashed
+#[derive(Debug, Clone, Copy)]
+pub enum Destination<'a> {
+    /// Extract its files into this dir
+    Dir(&'a Path),
+    /// Write the tarball itself, uncompressed, to this file. Its hash is the one in the URL, so
+    /// it can be checked again later, e.g. when it's been vendored.
+    Tarball(&'a Path),
 }
 
 pub fn download_and_hash(
     url: &str,
-    dest_dir: &Path,
+    dest: Destination,
     max_download_bytes: u64,
 ) -> Result<String, Problem> {
+    if let Some(path) = file_url_path(url) {
+        return read_and_hash(url, &path, dest, max_download_bytes);
+    }
+
     // TODO apparently it really improves performance to construct a Client once and then reuse it,
     // instead of making a new Client for every request.
     // Per https://github.com/seanmonstar/reqwest/issues/1454#issuecomment-1026076701
@@ -285,7 +533,49 @@ pub fn download_and_hash(
     // Use .take to prevent a malicious server from sending back bytes
     // until system resources are exhausted!
     let resp = ProgressReporter::new(resp.take(max_download_bytes), content_length);
-    decompress_into(dest_dir, encoding, resp)
+    decompress_into(dest, encoding, resp)
+}
+
+/// Like download_and_hash, but for the tarball at the path in a file:// URL
+fn read_and_hash(
+    url: &str,
+    path: &Path,
+    dest: Destination,
+    max_download_bytes: u64,
+) -> Result<String, Problem> {
+    let file = std::fs::File::open(path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => Problem::NotFound,
+        _ => Problem::IoErr(err),
+    })?;
+
+    let len = file.metadata().map_err(Problem::IoErr)?.len();
+
+    if len > max_download_bytes {
+        return Err(Problem::DownloadTooBig(len));
+    }
+
+    // There's no Content-Encoding header, so this goes by the file extension
+    let encoding = Encoding::new("", url)?;
+
+    decompress_into(dest, encoding, io::BufReader::new(file))
+}
+
+/// Extracts the files from an uncompressed tarball written by `Destination::Tarball`, and returns
+/// the tarball's hash, which is the one in its URL if it hasn't been changed since.
+pub fn unpack_and_hash(tarball: &Path, dest_dir: &Path) -> Result<String, Problem> {
+    let file = std::fs::File::open(tarball).map_err(Problem::IoErr)?;
+
+    hash_into(Destination::Dir(dest_dir), io::BufReader::new(file))
+}
+
+/// The hash of an uncompressed tarball written by `Destination::Tarball`, without extracting it
+pub fn hash_tarball(tarball: &Path) -> Result<String, Problem> {
+    let file = std::fs::File::open(tarball).map_err(Problem::IoErr)?;
+    let mut hash_reader = HashReader::new(io::BufReader::new(file));
+
+    io::copy(&mut hash_reader, &mut io::sink()).map_err(Problem::IoErr)?;
+
+    Ok(base64_url::encode(hash_reader.finalize().as_bytes()))
 }
 
 /// The content encodings we support
@@ -294,6 +584,7 @@ enum Encoding {
     Gzip,
     Brotli,
     Deflate,
+    Zstd,
     Uncompressed,
 }
 
@@ -306,6 +597,7 @@ impl Encoding {
             "br" => Ok(Brotli),
             "gzip" => Ok(Gzip),
             "deflate" => Ok(Deflate),
+            "zstd" => Ok(Zstd),
             "" => {
                 // There was no Content-Encoding header, but we can infer the encoding
                 // from the file extension in the URL.
@@ -316,6 +608,7 @@ impl Encoding {
                     Some((_, after_dot)) => match Compression::from_file_ext(after_dot) {
                         Some(Compression::Brotli) => Ok(Self::Brotli),
                         Some(Compression::Gzip) => Ok(Self::Gzip),
+                        Some(Compression::Zstd) => Ok(Self::Zstd),
                         Some(Compression::Uncompressed) | None => Ok(Self::Uncompressed),
                     },
                     None => Ok(Uncompressed),
@@ -335,6 +628,17 @@ impl Encoding {
     }
 }
 
+#[test]
+fn encoding_from_tar_zst() {
+    let actual = Encoding::new(
+        "",
+        "https://example.com/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE.tar.zst#main.roc",
+    )
+    .unwrap();
+
+    assert_eq!(Encoding::Zstd, actual);
+}
+
 #[test]
 fn encoding_from_tar_br() {
     let actual = Encoding::new(
@@ -346,42 +650,53 @@ fn encoding_from_tar_br() {
     assert_eq!(Encoding::Brotli, actual);
 }
 
-fn hash_and_unpack(dest_dir: &Path, reader: impl Read) -> Result<String, Problem> {
+fn hash_into(dest: Destination, reader: impl Read) -> Result<String, Problem> {
     let mut hash_reader = HashReader::new(reader);
 
-    tar::Archive::new(&mut hash_reader)
-        .unpack(dest_dir)
-        .map_err(Problem::IoErr)?;
+    match dest {
+        Destination::Dir(dest_dir) => {
+            tar::Archive::new(&mut hash_reader)
+                .unpack(dest_dir)
+                .map_err(Problem::IoErr)?;
 
-    let mut buf = Vec::with_capacity(1024);
+            let mut buf = Vec::with_capacity(1024);
+
+            // Archive::new() doesn't always read all the bytes, but we need to read them all
+            // in order to get the correct hash!
+            hash_reader.read_to_end(&mut buf).map_err(Problem::IoErr)?;
+        }
+        Destination::Tarball(path) => {
+            let mut file = std::fs::File::create(path).map_err(Problem::IoErr)?;
 
-    // Archive::new() doesn't always read all the bytes, but we need to read them all
-    // in order to get the correct hash!
-    hash_reader.read_to_end(&mut buf).map_err(Problem::IoErr)?;
+            io::copy(&mut hash_reader, &mut file).map_err(Problem::IoErr)?;
+        }
+    }
 
     Ok(base64_url::encode(hash_reader.finalize().as_bytes()))
 }
 
 /// Read from the given reader, decompress the bytes using the given Content-Encoding string,
-/// write them to the given writer, and return the base64url-encoded BLAKE3 hash of what was written.
+/// write them to the given destination, and return the base64url-encoded BLAKE3 hash of what was
+/// written.
 /// This both writes and hashes incrementally as it reads, so the only extra work that's done
 /// at the end is base64url-encoding the final hash.
 fn decompress_into(
-    dest_dir: &Path,
+    dest: Destination,
     encoding: Encoding,
     reader: impl Read,
 ) -> Result<String, Problem> {
     match encoding {
-        Encoding::Brotli => hash_and_unpack(
-            dest_dir,
-            brotli::Decompressor::new(reader, BROTLI_BUFFER_BYTES),
-        ),
+        Encoding::Brotli => hash_into(dest, brotli::Decompressor::new(reader, BROTLI_BUFFER_BYTES)),
         Encoding::Gzip => {
             // Note: GzDecoder::new immediately parses the gzip header (so, calls read())
-            hash_and_unpack(dest_dir, flate2::read::GzDecoder::new(reader))
+            hash_into(dest, flate2::read::GzDecoder::new(reader))
         }
-        Encoding::Deflate => hash_and_unpack(dest_dir, flate2::read::DeflateDecoder::new(reader)),
-        Encoding::Uncompressed => hash_and_unpack(dest_dir, reader),
+        Encoding::Deflate => hash_into(dest, flate2::read::DeflateDecoder::new(reader)),
+        Encoding::Zstd => hash_into(
+            dest,
+            zstd::stream::read::Decoder::new(reader).map_err(Problem::IoErr)?,
+        ),
+        Encoding::Uncompressed => hash_into(dest, reader),
     }
 }
 
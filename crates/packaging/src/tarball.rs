@@ -7,10 +7,11 @@ use roc_parse::ast::{
 use roc_parse::header::PlatformHeader;
 use roc_parse::header::{parse_header, parse_module_defs};
 use roc_parse::state::State;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsStr;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, Read, Write};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use tar;
 use walkdir::WalkDir;
 
@@ -18,6 +19,7 @@ use walkdir::WalkDir;
 pub enum Compression {
     Brotli,
     Gzip,
+    Zstd,
     Uncompressed,
 }
 
@@ -26,6 +28,7 @@ impl Compression {
         match self {
             Compression::Brotli => ".tar.br",
             Compression::Gzip => ".tar.gz",
+            Compression::Zstd => ".tar.zst",
             Compression::Uncompressed => ".tar",
         }
     }
@@ -35,6 +38,7 @@ impl Compression {
             "tar" => Some(Self::Uncompressed),
             "gz" => Some(Self::Gzip),
             "br" => Some(Self::Brotli),
+            "zst" => Some(Self::Zstd),
             _ => None,
         }
     }
@@ -48,6 +52,8 @@ impl<'a> TryFrom<&'a str> for Compression {
             Ok(Compression::Brotli)
         } else if extension.ends_with(".gz") {
             Ok(Compression::Gzip)
+        } else if extension.ends_with(".zst") {
+            Ok(Compression::Zstd)
         } else if extension.ends_with(".tar") {
             Ok(Compression::Uncompressed)
         } else {
@@ -56,6 +62,82 @@ impl<'a> TryFrom<&'a str> for Compression {
     }
 }
 
+/// The file at the root of every bundle that lists the other files in it, along with their hashes.
+/// When installing a package, the extracted files are checked against it.
+pub const MANIFEST_FILENAME: &str = "roc-manifest.txt";
+
+/// A file in a bundle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// The base64url-encoded BLAKE3 hash of the file's contents
+    pub hash: String,
+    pub size: u64,
+    /// Relative to the root of the bundle, with / as the separator
+    pub path: String,
+}
+
+/// The files in a bundle, sorted by path. Each is on its own line, as its hash, size and path
+/// separated by spaces.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    pub entries: Vec<ManifestEntry>,
+}
+
+#[derive(Debug)]
+pub enum ManifestProblem {
+    IoErr(io::Error),
+    /// The line with this (1-based) number isn't a hash, size and path
+    Malformed(usize),
+    /// This file is in the manifest, but it's missing or has different contents
+    Mismatch(String),
+    /// This file isn't in the manifest
+    Unlisted(String),
+}
+
+impl Manifest {
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        for ManifestEntry { hash, size, path } in self.entries.iter() {
+            text.push_str(&format!("{hash} {size} {path}\n"));
+        }
+
+        text
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ManifestProblem> {
+        let mut entries = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let mut parts = line.splitn(3, ' ');
+
+            match (parts.next(), parts.next().map(str::parse), parts.next()) {
+                // Paths are always relative and normalized, so they can't point outside the bundle
+                (Some(hash), Some(Ok(size)), Some(path))
+                    if archive_path(Path::new(path)) == path =>
+                {
+                    entries.push(ManifestEntry {
+                        hash: hash.to_string(),
+                        size,
+                        path: path.to_string(),
+                    })
+                }
+                _ => return Err(ManifestProblem::Malformed(index + 1)),
+            }
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+/// The result of bundling a package or platform
+#[derive(Debug)]
+pub struct Bundle {
+    /// The archive's filename, which is the hash of its contents plus the extension
+    pub filename: String,
+    pub manifest: Manifest,
+}
+
 /// Given a path to a .roc file, write a .tar file to disk.
 ///
 /// The .tar file will be in the same directory, and its filename
@@ -63,10 +145,13 @@ impl<'a> TryFrom<&'a str> for Compression {
 /// the name of that filename (including the .tar extension),
 /// so the caller can obtain the path to the file by calling
 /// Path::with_file_name(returned_string) on the Path argument it provided.
-pub fn build(path_to_main: &Path, compression: Compression) -> io::Result<String> {
+///
+/// The archive only depends on the contents of the files it includes, not on where or when it's
+/// built, so bundling the same files always gives the same hash.
+pub fn build(path_to_main: &Path, compression: Compression) -> io::Result<Bundle> {
     let mut archive_bytes = Vec::new();
 
-    write_archive(path_to_main, &mut archive_bytes)?;
+    let manifest = write_archive(path_to_main, &mut archive_bytes)?;
 
     // Now that we have our compressed archive, get its BLAKE3 hash
     // and base64url encode it. Use base64url encoding because:
@@ -105,15 +190,30 @@ pub fn build(path_to_main: &Path, compression: Compression) -> io::Result<String
                 encoder.write_all(&archive_bytes)?;
                 encoder.finish()?;
             }
+            #[cfg(not(target_family = "wasm"))]
+            Compression::Zstd => {
+                zstd::stream::copy_encode(archive_bytes.as_slice(), &mut file, ZSTD_LEVEL)?;
+            }
+            #[cfg(target_family = "wasm")]
+            Compression::Zstd => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "zstd compression is not available on wasm",
+                ));
+            }
             Compression::Uncompressed => file.write_all(&archive_bytes)?,
         };
     }
 
-    Ok(filename)
+    Ok(Bundle { filename, manifest })
 }
 
-/// Write an uncompressed tar archive to the given writer.
-fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
+/// The highest level that doesn't need a lot more memory to decompress
+#[cfg(not(target_family = "wasm"))]
+const ZSTD_LEVEL: i32 = 19;
+
+/// Write an uncompressed tar archive to the given writer, and return its manifest.
+fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<Manifest> {
     let root_dir = if let Some(parent) = path.parent() {
         parent
     } else {
@@ -123,7 +223,9 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
         );
         std::process::exit(1);
     };
-    let mut builder = tar::Builder::new(writer);
+    // The files to include, by their path in the archive. Sorting them by path (rather than
+    // adding them in whatever order the filesystem lists them) keeps the archive reproducible.
+    let mut files = BTreeMap::new();
     let arena = Bump::new();
     let mut buf = Vec::new();
 
@@ -143,7 +245,7 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
             // TODO report error
         }
         Header::Package(_) => {
-            add_source_files(&arena, root_dir, &mut builder)?;
+            add_source_files(&arena, root_dir, &mut files)?;
         }
         Header::Platform(PlatformHeader { imports: _, .. }) => {
             // Add all the prebuilt host files to the archive.
@@ -168,17 +270,17 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
                 ]
                 .contains(&path.extension().and_then(OsStr::to_str))
                 {
-                    builder.append_path_with_name(
-                        &path,
+                    files.insert(
                         // Store it without the root path, so that (for example) we don't store
                         // `examples/platform-switching/zig-platform/main.roc` and therefore end up with the root of the tarball
                         // being an `examples/platform-switching/zig-platform/` dir instead of having `main.roc` in the root.
-                        path.strip_prefix(root_dir).unwrap(),
-                    )?;
+                        archive_path(path.strip_prefix(root_dir).unwrap()),
+                        path,
+                    );
                 }
             }
 
-            add_source_files(&arena, root_dir, &mut builder)?;
+            add_source_files(&arena, root_dir, &mut files)?;
         }
     };
 
@@ -220,13 +322,127 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
     //     }
     // }
 
-    builder.finish()
+    if files.contains_key(MANIFEST_FILENAME) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{MANIFEST_FILENAME} is reserved for the list of files in the bundle, so a bundle can't include a file by that name."),
+        ));
+    }
+
+    let mut builder = tar::Builder::new(writer);
+    let mut manifest = Manifest::default();
+    let mut contents = Vec::with_capacity(files.len());
+
+    for (archive_path, path) in files {
+        let bytes = fs::read(&path)?;
+
+        manifest.entries.push(ManifestEntry {
+            hash: base64_url::encode(blake3::hash(&bytes).as_bytes()),
+            size: bytes.len() as u64,
+            path: archive_path.clone(),
+        });
+        contents.push((archive_path, bytes));
+    }
+
+    append_file(
+        &mut builder,
+        MANIFEST_FILENAME,
+        manifest.to_text().as_bytes(),
+    )?;
+
+    for (archive_path, bytes) in contents {
+        append_file(&mut builder, &archive_path, &bytes)?;
+    }
+
+    builder.finish()?;
+
+    Ok(manifest)
+}
+
+/// Adds a file whose header only depends on its path and contents, not on the filesystem it
+/// came from: no timestamps, owners, or permissions (which Windows doesn't have anyway).
+fn append_file<W: Write>(
+    builder: &mut tar::Builder<W>,
+    archive_path: &str,
+    bytes: &[u8],
+) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(0);
+    header.set_uid(0);
+    header.set_gid(0);
+
+    builder.append_data(&mut header, archive_path, bytes)
+}
+
+/// The path in the archive of the file at this path relative to the root dir, with / as the
+/// separator on every OS.
+fn archive_path(relative_path: &Path) -> String {
+    relative_path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Checks the files extracted into the given dir against the bundle's manifest, if it has one.
+/// (Bundles made before manifests were added don't.)
+pub fn verify_manifest(dir: &Path) -> Result<(), ManifestProblem> {
+    let text = match fs::read_to_string(dir.join(MANIFEST_FILENAME)) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(ManifestProblem::IoErr(err)),
+    };
+    let manifest = Manifest::parse(&text)?;
+
+    for ManifestEntry { hash, size, path } in manifest.entries.iter() {
+        let matches = match fs::read(dir.join(path)) {
+            Ok(bytes) => {
+                bytes.len() as u64 == *size
+                    && base64_url::encode(blake3::hash(&bytes).as_bytes()) == *hash
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+            Err(err) => return Err(ManifestProblem::IoErr(err)),
+        };
+
+        if !matches {
+            return Err(ManifestProblem::Mismatch(path.clone()));
+        }
+    }
+
+    let listed: BTreeSet<&str> = manifest
+        .entries
+        .iter()
+        .map(|entry| entry.path.as_str())
+        .collect();
+
+    for entry in WalkDir::new(dir) {
+        let entry = entry.map_err(|err| ManifestProblem::IoErr(err.into()))?;
+
+        if entry.file_type().is_dir() {
+            continue;
+        }
+
+        let path = archive_path(entry.path().strip_prefix(dir).unwrap());
+
+        if path != MANIFEST_FILENAME && !listed.contains(path.as_str()) {
+            return Err(ManifestProblem::Unlisted(path));
+        }
+    }
+
+    Ok(())
 }
 
-fn add_source_files<W: Write>(
+fn add_source_files(
     arena: &Bump,
     root_dir: &Path,
-    builder: &mut tar::Builder<W>,
+    files: &mut BTreeMap<String, PathBuf>,
 ) -> Result<(), io::Error> {
     for entry in WalkDir::new(root_dir).into_iter().filter_entry(|entry| {
         let path = entry.path();
@@ -242,15 +458,15 @@ fn add_source_files<W: Write>(
         // added based on the paths of the files inside anyway. (In fact, if we don't
         // filter out directories in this step, then empty ones can sometimes be added!)
         if path.is_file() {
-            add_ingested_files(arena, root_dir, path, builder)?;
+            add_ingested_files(arena, root_dir, path, files)?;
 
-            builder.append_path_with_name(
-                path,
+            files.insert(
                 // Store it without the root path, so that (for example) we don't store
                 // `examples/platform-switching/zig-platform/main.roc` and therefore end up with the root of the tarball
                 // being an `examples/platform-switching/zig-platform/` dir instead of having `main.roc` in the root.
-                path.strip_prefix(root_dir).unwrap(),
-            )?;
+                archive_path(path.strip_prefix(root_dir).unwrap()),
+                path.to_path_buf(),
+            );
         }
     }
 
@@ -280,11 +496,11 @@ fn read_header<'a>(
     })
 }
 
-fn add_ingested_files<W: Write>(
+fn add_ingested_files(
     arena: &Bump,
     root_dir: &Path,
     dot_roc_path: &Path,
-    builder: &mut tar::Builder<W>,
+    files: &mut BTreeMap<String, PathBuf>,
 ) -> io::Result<()> {
     let mut buf = Vec::new();
     let (header, state) = read_header(arena, &mut buf, dot_roc_path)?;
@@ -308,7 +524,9 @@ fn add_ingested_files<W: Write>(
                     );
                 }
 
-                builder.append_path_with_name(root_dir.join(&relative_path), relative_path.display().to_string())
+                files.insert(archive_path(&relative_path), root_dir.join(&relative_path));
+
+                Ok(())
 
             } else {
                 unreachable!()
@@ -318,3 +536,83 @@ fn add_ingested_files<W: Write>(
         }
     })
 }
+
+#[test]
+fn build_is_reproducible() {
+    let write_package = |files: &[(&str, &str)]| {
+        let dir = tempfile::tempdir().unwrap();
+
+        for (path, contents) in files {
+            let path = dir.path().join(path);
+
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        dir
+    };
+
+    let main = ("main.roc", "package [Csv, Csv.Decode] {}\n");
+    let csv = ("Csv.roc", "module [x]\n\nx = 1\n");
+    let decode = ("Csv/Decode.roc", "module [y]\n\ny = 2\n");
+
+    // Same files, written in a different order at a different time
+    let first = write_package(&[main, csv, decode]);
+    let second = write_package(&[decode, csv, main]);
+
+    let first_bundle = build(&first.path().join("main.roc"), Compression::Uncompressed).unwrap();
+    let second_bundle = build(&second.path().join("main.roc"), Compression::Uncompressed).unwrap();
+
+    assert_eq!(first_bundle.filename, second_bundle.filename);
+    assert_eq!(
+        fs::read(first.path().join(&first_bundle.filename)).unwrap(),
+        fs::read(second.path().join(&second_bundle.filename)).unwrap()
+    );
+
+    let paths: Vec<&str> = first_bundle
+        .manifest
+        .entries
+        .iter()
+        .map(|entry| entry.path.as_str())
+        .collect();
+
+    assert_eq!(paths, ["Csv.roc", "Csv/Decode.roc", "main.roc"]);
+}
+
+#[test]
+fn verify_manifest_detects_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let contents = "module [x]\n\nx = 1\n";
+    let manifest = Manifest {
+        entries: vec![ManifestEntry {
+            hash: base64_url::encode(blake3::hash(contents.as_bytes()).as_bytes()),
+            size: contents.len() as u64,
+            path: "Csv.roc".to_string(),
+        }],
+    };
+
+    fs::write(dir.path().join(MANIFEST_FILENAME), manifest.to_text()).unwrap();
+    fs::write(dir.path().join("Csv.roc"), contents).unwrap();
+    assert!(verify_manifest(dir.path()).is_ok());
+
+    fs::write(dir.path().join("Extra.roc"), "module []\n").unwrap();
+    assert!(matches!(
+        verify_manifest(dir.path()),
+        Err(ManifestProblem::Unlisted(path)) if path == "Extra.roc"
+    ));
+
+    fs::remove_file(dir.path().join("Extra.roc")).unwrap();
+    fs::write(dir.path().join("Csv.roc"), "module [x]\n\nx = 2\n").unwrap();
+    assert!(matches!(
+        verify_manifest(dir.path()),
+        Err(ManifestProblem::Mismatch(path)) if path == "Csv.roc"
+    ));
+}
+
+#[test]
+fn manifest_paths_stay_inside_the_bundle() {
+    assert!(matches!(
+        Manifest::parse("jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE 3 ../secret.txt\n"),
+        Err(ManifestProblem::Malformed(1))
+    ));
+}
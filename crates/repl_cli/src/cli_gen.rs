@@ -1,7 +1,7 @@
 use bumpalo::Bump;
 use inkwell::context::Context;
 use libloading::Library;
-use roc_build::link::llvm_module_to_dylib;
+use roc_build::link::{llvm_module_to_dylib, llvm_module_to_dylib_with_host};
 use roc_collections::all::MutSet;
 
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
@@ -18,11 +18,13 @@ use roc_std::RocStr;
 use roc_target::Target;
 use roc_types::pretty_print::{name_and_print_var, DebugPrint};
 use roc_types::subs::Subs;
+use std::path::Path;
 
 pub fn eval_llvm(
     mut loaded: MonomorphizedModule<'_>,
     target: Target,
     opt_level: OptLevel,
+    opt_host_path: Option<&Path>,
 ) -> Option<ReplOutput> {
     let arena = Bump::new();
 
@@ -52,21 +54,20 @@ pub fn eval_llvm(
 
     let interns = loaded.interns.clone();
 
-    #[cfg(not(all(
+    let use_dev_backend = cfg!(all(
         any(target_os = "linux", target_os = "macos"),
         any(target_arch = "x86_64", target_arch = "aarch64")
-    )))]
-    let (lib, main_fn_name, subs, layout_interner) =
-        mono_module_to_dylib_llvm(&arena, target, loaded, opt_level)
-            .expect("we produce a valid Dylib");
+    ))
+        // The dev backend defines its own roc_alloc and friends, which would clash with the host's
+        && opt_host_path.is_none();
 
-    #[cfg(all(
-        any(target_os = "linux", target_os = "macos"),
-        any(target_arch = "x86_64", target_arch = "aarch64")
-    ))]
-    let (lib, main_fn_name, subs, layout_interner) =
+    let (lib, main_fn_name, subs, layout_interner) = if use_dev_backend {
         mono_module_to_dylib_asm(&arena, target, loaded, opt_level)
-            .expect("We failed to produce a valid Dylib.\nTIP: if you're on macos, try this:\n\t<https://github.com/roc-lang/roc/issues/5797#issuecomment-1786105269>");
+            .expect("We failed to produce a valid Dylib.\nTIP: if you're on macos, try this:\n\t<https://github.com/roc-lang/roc/issues/5797#issuecomment-1786105269>")
+    } else {
+        mono_module_to_dylib_llvm(&arena, target, loaded, opt_level, opt_host_path)
+            .expect("we produce a valid Dylib")
+    };
 
     let mut app = CliApp { lib };
 
@@ -169,18 +170,12 @@ impl ReplAppMemory for CliMemory {
     }
 }
 
-#[cfg_attr(
-    all(
-        any(target_os = "linux", target_os = "macos"),
-        any(target_arch = "x86_64", target_arch = "aarch64")
-    ),
-    allow(unused)
-)]
 fn mono_module_to_dylib_llvm<'a>(
     arena: &'a Bump,
     target: Target,
     loaded: MonomorphizedModule<'a>,
     opt_level: OptLevel,
+    opt_host_path: Option<&Path>,
 ) -> Result<(libloading::Library, &'a str, Subs, STLayoutInterner<'a>), libloading::Error> {
     let MonomorphizedModule {
         procedures,
@@ -218,7 +213,8 @@ fn mono_module_to_dylib_llvm<'a>(
     };
 
     // Add roc_alloc, roc_realloc, and roc_dealloc, since the repl has no
-    // platform to provide them.
+    // platform to provide them. Even with a host linked in, these stay internal
+    // to the module, so they don't clash with the host's.
     add_default_roc_externs(&env);
 
     let entry_point = match entry_point {
@@ -260,17 +256,14 @@ fn mono_module_to_dylib_llvm<'a>(
         &ll_file_path,
     );
 
-    llvm_module_to_dylib(env.module, target, opt_level)
-        .map(|lib| (lib, main_fn_name, subs, layout_interner))
+    let lib = match opt_host_path {
+        Some(host_path) => llvm_module_to_dylib_with_host(env.module, target, opt_level, host_path),
+        None => llvm_module_to_dylib(env.module, target, opt_level),
+    };
+
+    lib.map(|lib| (lib, main_fn_name, subs, layout_interner))
 }
 
-#[cfg_attr(
-    not(all(
-        any(target_os = "linux", target_os = "macos"),
-        any(target_arch = "x86_64", target_arch = "aarch64")
-    )),
-    allow(unused)
-)]
 fn mono_module_to_dylib_asm<'a>(
     arena: &'a Bump,
     target: Target,
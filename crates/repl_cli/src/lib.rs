@@ -5,12 +5,14 @@ use bumpalo::Bump;
 use const_format::concatcp;
 use roc_load::MonomorphizedModule;
 use roc_mono::ir::OptLevel;
-use roc_repl_eval::gen::Problems;
+use roc_packaging::cache::{self, RocCacheDir};
+use roc_repl_eval::gen::{Problems, ReplPlatform};
 use roc_repl_ui::colors::{CYAN, END_COL};
 use roc_repl_ui::repl_state::{ReplAction, ReplState};
 use roc_repl_ui::{format_output, is_incomplete, CONT_PROMPT, PROMPT, SHORT_INSTRUCTIONS, TIPS};
 use roc_reporting::report::{
-    strip_colors, to_file_problem_report_string, ANSI_STYLE_CODES, DEFAULT_PALETTE,
+    strip_colors, to_file_problem_report_string, to_https_problem_report_string, ANSI_STYLE_CODES,
+    DEFAULT_PALETTE,
 };
 use roc_target::Target;
 use rustyline::highlight::{Highlighter, PromptInfo};
@@ -20,7 +22,9 @@ use rustyline_derive::{Completer, Helper, Hinter};
 use std::any::Any;
 use std::backtrace::Backtrace;
 use std::borrow::Cow;
+use std::fs;
 use std::panic::{AssertUnwindSafe, PanicInfo};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, OnceLock};
 use target_lexicon::Triple;
 
@@ -55,10 +59,24 @@ fn panic_hook(_: &PanicInfo) {
     }
 }
 
-pub fn main(has_color: bool, has_header: bool) -> i32 {
+/// `opt_platform` is the path or URL of a platform whose effects the REPL's exprs can use
+pub fn main(has_color: bool, has_header: bool, opt_platform: Option<&str>) -> i32 {
     use rustyline::error::ReadlineError;
     use rustyline::Editor;
 
+    let target = Triple::host().into();
+
+    let (repl_state, opt_host_path) = match opt_platform {
+        Some(source) => match load_platform(source, target) {
+            Ok((platform, host_path)) => (ReplState::with_platform(platform), Some(host_path)),
+            Err(message) => {
+                eprintln!("{message}");
+                return 1;
+            }
+        },
+        None => (ReplState::new(), None),
+    };
+
     init_backtrace_storage();
     std::panic::set_hook(Box::new(panic_hook));
 
@@ -88,9 +106,11 @@ pub fn main(has_color: bool, has_header: bool) -> i32 {
     };
     let mut editor =
         Editor::<ReplHelper>::with_config(Config::builder().color_mode(editor_color_mode).build());
-    let repl_helper = ReplHelper::default();
+    let repl_helper = ReplHelper {
+        validator: InputValidator::default(),
+        state: repl_state,
+    };
     editor.set_helper(Some(repl_helper));
-    let target = Triple::host().into();
     let mut arena = Bump::new();
 
     loop {
@@ -114,13 +134,28 @@ pub fn main(has_color: bool, has_header: bool) -> i32 {
 
                 match action {
                     ReplAction::Eval { opt_mono, problems } => {
-                        let output = evaluate(opt_mono, problems, target);
+                        let output = match &opt_host_path {
+                            Some(host_path) => {
+                                evaluate_with_host(opt_mono, problems, target, host_path)
+                            }
+                            None => evaluate(opt_mono, problems, target),
+                        };
                         // If there was no output, don't print a blank line!
                         // (This happens for something like a type annotation.)
                         if !output.is_empty() {
                             println!("{}", strip_colors_if_necessary(&output));
                         }
                     }
+                    ReplAction::Type {
+                        opt_output,
+                        problems,
+                    } => {
+                        let output = format_output(ANSI_STYLE_CODES, opt_output, problems);
+                        println!("{}", strip_colors_if_necessary(&output));
+                    }
+                    ReplAction::Message(message) => {
+                        println!("{message}");
+                    }
                     ReplAction::Exit => {
                         return 0;
                     }
@@ -192,10 +227,57 @@ pub fn evaluate(
     problems: Problems,
     target: Target,
 ) -> String {
-    let opt_output = opt_mono.and_then(|mono| eval_llvm(mono, target, OptLevel::Normal));
+    let opt_output = opt_mono.and_then(|mono| eval_llvm(mono, target, OptLevel::Normal, None));
     format_output(ANSI_STYLE_CODES, opt_output, problems)
 }
 
+/// Like `evaluate`, but links in a platform's host so the expr can call its effects
+pub fn evaluate_with_host(
+    opt_mono: Option<MonomorphizedModule<'_>>,
+    problems: Problems,
+    target: Target,
+    host_path: &Path,
+) -> String {
+    let opt_output =
+        opt_mono.and_then(|mono| eval_llvm(mono, target, OptLevel::Normal, Some(host_path)));
+    format_output(ANSI_STYLE_CODES, opt_output, problems)
+}
+
+/// Find a platform's main module and prebuilt host, downloading the platform first if it's
+/// a URL, the same way an app header's platform would be.
+fn load_platform(source: &str, target: Target) -> Result<(ReplPlatform, PathBuf), String> {
+    let main_path = if cache::is_url(source) {
+        let cache_dir = cache::roc_cache_packages_dir();
+
+        match cache::install_package(RocCacheDir::Persistent(cache_dir.as_path()), source) {
+            // The URL can name the root module in its fragment, e.g. #foo.roc
+            Ok((package_dir, opt_root_module)) => {
+                package_dir.join(opt_root_module.unwrap_or("main.roc"))
+            }
+            Err(problem) => {
+                return Err(to_https_problem_report_string(
+                    source,
+                    problem,
+                    PathBuf::from(source),
+                ));
+            }
+        }
+    } else {
+        PathBuf::from(source)
+    };
+
+    let platform_src = fs::read_to_string(&main_path).map_err(|err| {
+        format!(
+            "I couldn't read the platform at {}: {err}",
+            main_path.display()
+        )
+    })?;
+    let platform = ReplPlatform::new(source, &main_path, &platform_src)?;
+    let host_path = target.find_legacy_host(&main_path)?;
+
+    Ok((platform, host_path))
+}
+
 #[derive(Default)]
 struct InputValidator {}
 
@@ -1,18 +1,36 @@
 use bumpalo::Bump;
-use roc_load::{ExecutionMode, LoadConfig, LoadMonomorphizedError, Threading};
+use const_format::concatcp;
+use roc_load::{ExecutionMode, LoadConfig, LoadResult, LoadStart, Threading};
 use roc_packaging::cache::{self, RocCacheDir};
 use roc_problem::Severity;
 use roc_reporting::report::Palette;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
+use roc_can::module::TypeState;
+use roc_collections::MutMap;
 use roc_fmt::annotation::Formattable;
 use roc_fmt::annotation::{Newlines, Parens};
-use roc_load::{LoadingProblem, MonomorphizedModule};
-use roc_parse::ast::Expr;
+use roc_load::{report_loading_problem, LoadingProblem, MonomorphizedModule};
+use roc_module::symbol::{Interns, ModuleId, ModuleIds};
+use roc_parse::ast::{Expr, ExtractSpaces, Header, SpacesBefore, TypeAnnotation};
+use roc_parse::header::parse_header;
+use roc_parse::state::State;
 use roc_region::all::LineInfo;
-use roc_reporting::report::{can_problem, type_problem, RocDocAllocator};
+use roc_reporting::report::{can_problem, type_problem, RenderTarget, RocDocAllocator};
 use roc_solve::FunctionKind;
+use roc_solve_problem::TypeError;
 use roc_target::Target;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+
+/// The name of the def that the expr being evaluated gets wrapped in
+const REPL_OUTPUT_NAME: &str = "repl_output";
+
+/// The name of the module that the defs from earlier in the session go in
+const REPL_DEFS_MODULE_NAME: &str = "ReplDefs";
+
+/// The shorthand the REPL module uses for its platform, if it has one
+pub const REPL_PLATFORM_SHORTHAND: &str = "pf";
 
 #[derive(Debug)]
 pub struct ReplOutput {
@@ -39,6 +57,222 @@ pub fn format_answer<'a>(arena: &'a Bump, answer: Expr<'_>) -> &'a str {
     }
 }
 
+/// The defs and imports from earlier in a REPL session, which the expr being evaluated can use
+#[derive(Debug)]
+pub struct PastDefs<'a> {
+    /// The source of each top-level def, and the name it defines
+    pub defs: Vec<(&'a str, &'a str)>,
+    pub imports: Vec<&'a str>,
+    /// The dir the imports are resolved in
+    pub src_dir: &'a Path,
+    /// A name the expr defines again, so it mustn't also be imported from the past defs
+    pub redefined: Option<&'a str>,
+}
+
+/// The solved types of the module the past defs go in. As long as the defs and the modules they
+/// import don't change, later lines reuse these types rather than solving all the defs again.
+///
+/// The loader reuses the types of whole modules only, so this helps lines that don't touch the
+/// defs, like exprs and `:type`. A line that adds or redefines a def changes the module, and
+/// then every def in it gets solved again.
+#[derive(Debug, Default)]
+pub struct SolvedDefs {
+    cached: Option<CachedDefs>,
+}
+
+#[derive(Debug)]
+struct CachedDefs {
+    /// The source of the defs module the types were solved from
+    module_src: String,
+    module_id: ModuleId,
+    types: TypeState,
+    /// The other modules the defs depend on, with the sources they had when the defs were solved
+    dependencies: Vec<(PathBuf, Box<str>)>,
+}
+
+impl SolvedDefs {
+    /// The types to reuse for a defs module with this source, if they're still good. That takes
+    /// the very same source, byte for byte, as the types were solved from.
+    fn reusable(&self, module_src: &str) -> MutMap<ModuleId, TypeState> {
+        let mut reusable = MutMap::default();
+
+        if let Some(cached) = &self.cached {
+            let unchanged = cached.module_src == module_src
+                && cached.dependencies.iter().all(|(path, src)| {
+                    fs::read_to_string(path).is_ok_and(|current| *current == **src)
+                });
+
+            if unchanged {
+                let TypeState {
+                    subs,
+                    exposed_vars_by_symbol,
+                    abilities,
+                    solved_implementations,
+                } = &cached.types;
+
+                reusable.insert(
+                    cached.module_id,
+                    TypeState {
+                        subs: subs.clone(),
+                        exposed_vars_by_symbol: exposed_vars_by_symbol.clone(),
+                        abilities: abilities.clone(),
+                        solved_implementations: solved_implementations.clone(),
+                    },
+                );
+            }
+        }
+
+        reusable
+    }
+
+    /// Keep the types a load solved for the defs module, for the next line to reuse
+    fn update(
+        &mut self,
+        opt_module_src: Option<&str>,
+        root_id: ModuleId,
+        sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+        in_memory_types: &mut MutMap<ModuleId, TypeState>,
+    ) {
+        // The defs module is the only one we load from memory
+        self.cached = opt_module_src.zip(in_memory_types.drain().next()).map(
+            |(module_src, (module_id, types))| {
+                let dependencies = sources
+                    .iter()
+                    .filter(|(id, _)| !id.is_builtin() && **id != root_id && **id != module_id)
+                    .map(|(_, (path, src))| (path.clone(), src.clone()))
+                    .collect();
+
+                CachedDefs {
+                    module_src: module_src.to_string(),
+                    module_id,
+                    types,
+                    dependencies,
+                }
+            },
+        );
+    }
+}
+
+/// A platform for the REPL module to use, so exprs can call the effects it provides
+#[derive(Debug, Clone)]
+pub struct ReplPlatform {
+    /// The platform's path or URL, as it goes in an app header
+    source: String,
+    /// The names of the modules the platform exposes
+    exposes: Vec<String>,
+    /// The directory of the platform's main module, where its other modules are
+    dir: PathBuf,
+    /// The names of the types and values the platform requires of an app
+    required: Vec<String>,
+    /// Stand-ins for the required types and values. The REPL never runs the platform's own
+    /// entry point, so all they need is the types the platform asks for.
+    required_defs: String,
+}
+
+impl ReplPlatform {
+    /// `platform_src` is the source of the platform's main module, which `source` points to and
+    /// which is at `main_path` on disk
+    pub fn new(source: &str, main_path: &Path, platform_src: &str) -> Result<Self, String> {
+        let arena = Bump::new();
+
+        let header = match parse_header(&arena, State::new(platform_src.as_bytes())) {
+            Ok((
+                SpacesBefore {
+                    item: Header::Platform(header),
+                    ..
+                },
+                _,
+            )) => header,
+            Ok(_) => return Err(format!("{source} is a module, but not a platform.")),
+            Err(_) => return Err(format!("I couldn't parse the header of {source}.")),
+        };
+
+        let mut required = Vec::new();
+        let mut required_defs = String::new();
+
+        for loc_rigid in header.requires.item.rigids.items.iter() {
+            let name: &str = loc_rigid.value.item().into();
+
+            required_defs.push_str(&format!("{name} : {{}}\n\n"));
+            required.push(name.to_string());
+        }
+
+        for loc_signature in header.requires.item.signatures.items.iter() {
+            let signature = loc_signature.value.item();
+            let name = signature.ident.value;
+            let ann_src = &platform_src[signature.ann.byte_range()];
+            let crash = format!("crash \"The repl doesn't run {name}\"");
+
+            let body = match signature.ann.value.extract_spaces().item {
+                TypeAnnotation::Function(args, _, _) => {
+                    format!("\\{} -> {crash}", vec!["_"; args.len()].join(", "))
+                }
+                _ => crash,
+            };
+
+            required_defs.push_str(&format!("{name} : {ann_src}\n{name} = {body}\n\n"));
+            required.push(name.to_string());
+        }
+
+        let exposes = header
+            .exposes
+            .item
+            .items
+            .iter()
+            .map(|loc_name| loc_name.value.item().as_str().to_string())
+            .collect();
+
+        Ok(Self {
+            source: source.to_string(),
+            exposes,
+            dir: main_path.parent().unwrap_or(Path::new(".")).to_path_buf(),
+            required,
+            required_defs,
+        })
+    }
+
+    /// The platform's path or URL, for the header of an app whose imports are resolved in
+    /// `src_dir`. A relative path is relative to the current dir, which is only where the app's
+    /// paths are resolved as long as that's also where its imports are.
+    fn header_source(&self, src_dir: &Path) -> String {
+        if src_dir == Path::new(".") || cache::is_url(&self.source) {
+            return self.source.clone();
+        }
+
+        match std::env::current_dir() {
+            // Roc strings treat backslashes as escapes, and Windows takes forward slashes too
+            Ok(current_dir) => current_dir
+                .join(&self.source)
+                .to_string_lossy()
+                .replace('\\', "/"),
+            Err(_) => self.source.clone(),
+        }
+    }
+
+    /// Whether an expr can import the module `module_name` from this platform. Apps can import
+    /// a platform's hosted modules without the platform exposing them, so any module the
+    /// platform has will do.
+    pub fn exposes_module(&self, module_name: &str) -> bool {
+        if self.exposes.iter().any(|exposed| exposed == module_name) {
+            return true;
+        }
+
+        let mut path = self.dir.clone();
+        path.extend(module_name.split('.'));
+        path.set_extension("roc");
+
+        path.is_file()
+    }
+}
+
+/// The solved types of a REPL session, from type checking it without generating any code
+#[derive(Debug)]
+pub struct ReplTypes {
+    pub expr_type: String,
+    /// The types of the session's top-level values, in the order they were defined
+    pub def_types: Vec<(String, String)>,
+}
+
 #[derive(Default, Debug)]
 pub struct Problems {
     pub errors: Vec<String>,
@@ -51,69 +285,289 @@ impl Problems {
     }
 }
 
-pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
+pub fn compile_to_mono<'a>(
     arena: &'a Bump,
-    defs: I,
+    past_defs: &PastDefs,
+    solved_defs: &mut SolvedDefs,
+    opt_platform: Option<&ReplPlatform>,
     expr: &str,
     target: Target,
     palette: Palette,
 ) -> (Option<MonomorphizedModule<'a>>, Problems) {
-    let filename = PathBuf::from("replfile.roc");
-    let src_dir = PathBuf::from(".");
-    let (bytes_before_expr, module_src) = promote_expr_to_module(arena, defs, expr);
-    let loaded = roc_load::load_and_monomorphize_from_str(
+    let (opt_defs_src, bytes_before_expr, module_src) =
+        promote_expr_to_module(arena, past_defs, opt_platform, expr);
+    let loaded = load_repl_module(
         arena,
-        filename,
+        opt_defs_src,
         module_src,
-        src_dir,
-        None,
-        RocCacheDir::Persistent(cache::roc_cache_packages_dir().as_path()),
-        LoadConfig {
-            target,
-            function_kind: FunctionKind::LambdaSet,
-            render: roc_reporting::report::RenderTarget::ColorTerminal,
-            palette,
-            threading: Threading::Single,
-            exec_mode: ExecutionMode::Executable,
-        },
+        past_defs.src_dir,
+        solved_defs,
+        target,
+        palette,
+        ExecutionMode::Executable,
     );
 
     let mut loaded = match loaded {
-        Ok(v) => v,
-        Err(LoadMonomorphizedError::ErrorModule(m)) => {
-            todo!(
-                "error while loading module: {:?}",
-                (m.can_problems, m.type_problems)
+        Ok(LoadResult::Monomorphized(module)) => module,
+        Ok(LoadResult::TypeChecked(mut module)) => {
+            // Loading stops after type checking when there's nothing it could run
+            let problems = report_problems(
+                &module.interns,
+                &module.sources,
+                &mut module.can_problems,
+                &mut module.type_problems,
+                module_src,
+                bytes_before_expr,
+                palette,
             );
+
+            return (None, problems);
         }
-        Err(LoadMonomorphizedError::LoadingProblem(LoadingProblem::FormattedReport(report, _))) => {
-            return (
-                None,
-                Problems {
-                    errors: vec![report],
-                    warnings: Vec::new(),
-                },
-            );
-        }
-        Err(e) => {
-            todo!("error while loading module: {:?}", e)
-        }
+        Err(problem) => return (None, loading_problems(problem, palette)),
     };
 
     let MonomorphizedModule {
+        module_id,
         interns,
         sources,
         can_problems,
         type_problems,
+        in_memory_types,
         ..
     } = &mut loaded;
 
+    solved_defs.update(opt_defs_src, *module_id, sources, in_memory_types);
+
+    let problems = report_problems(
+        interns,
+        sources,
+        can_problems,
+        type_problems,
+        module_src,
+        bytes_before_expr,
+        palette,
+    );
+
+    (Some(loaded), problems)
+}
+
+/// Like `compile_to_mono`, but stops after type checking. This is enough to tell the user what
+/// type something has, and doesn't need to evaluate anything.
+pub fn type_check(
+    arena: &Bump,
+    past_defs: &PastDefs,
+    solved_defs: &mut SolvedDefs,
+    opt_platform: Option<&ReplPlatform>,
+    expr: &str,
+    target: Target,
+    palette: Palette,
+) -> (Option<ReplTypes>, Problems) {
+    let (opt_defs_src, bytes_before_expr, module_src) =
+        promote_expr_to_module(arena, past_defs, opt_platform, expr);
+    let loaded = load_repl_module(
+        arena,
+        opt_defs_src,
+        module_src,
+        past_defs.src_dir,
+        solved_defs,
+        target,
+        palette,
+        ExecutionMode::Check,
+    );
+
+    let mut loaded = match loaded {
+        Ok(LoadResult::TypeChecked(module)) => module,
+        Ok(LoadResult::Monomorphized(_)) => unreachable!(),
+        Err(problem) => return (None, loading_problems(problem, palette)),
+    };
+
+    let problems = report_problems(
+        &loaded.interns,
+        &loaded.sources,
+        &mut loaded.can_problems,
+        &mut loaded.type_problems,
+        module_src,
+        bytes_before_expr,
+        palette,
+    );
+
+    let home = loaded.module_id;
+    let mut expr_type = String::new();
+
+    if let Some(declarations) = loaded.declarations_by_id.get(&home) {
+        let subs = loaded.solved.inner_mut();
+
+        for (symbol, var) in declarations
+            .symbols
+            .iter()
+            .zip(declarations.variables.iter())
+        {
+            if symbol.value.as_str(&loaded.interns) == REPL_OUTPUT_NAME {
+                expr_type =
+                    name_and_print_var(*var, subs, home, &loaded.interns, DebugPrint::NOTHING);
+            }
+        }
+    }
+
+    // The past defs are all in the one module we load from memory
+    let mut def_types = Vec::new();
+    let opt_defs_id = loaded.in_memory_types.keys().next().copied();
+
+    if let Some(checked) = opt_defs_id.and_then(|id| loaded.typechecked.get_mut(&id)) {
+        let defs_id = opt_defs_id.unwrap();
+        let subs = checked.solved_subs.inner_mut();
+
+        for (symbol, var) in checked
+            .decls
+            .symbols
+            .iter()
+            .zip(checked.decls.variables.iter())
+        {
+            let name = symbol.value.as_str(&loaded.interns).to_string();
+            let type_str =
+                name_and_print_var(*var, subs, defs_id, &loaded.interns, DebugPrint::NOTHING);
+
+            def_types.push((name, type_str));
+        }
+    }
+
+    solved_defs.update(
+        opt_defs_src,
+        home,
+        &loaded.sources,
+        &mut loaded.in_memory_types,
+    );
+
+    (
+        Some(ReplTypes {
+            expr_type,
+            def_types,
+        }),
+        problems,
+    )
+}
+
+/// Load the module for the expr, which imports the past defs from a module of their own. If
+/// the past defs haven't changed since the last line, their types don't get solved again.
+///
+/// Only solving is skipped. The loader can't take a canonicalized module from an earlier load,
+/// so the past defs still get parsed and canonicalized on every line; their problems are
+/// filtered out in [report_problems] rather than not being found at all. Nothing after solving
+/// is kept between lines either, so the past defs an expr uses get specialized and generated
+/// again each time.
+#[allow(clippy::too_many_arguments)]
+fn load_repl_module<'a>(
+    arena: &'a Bump,
+    opt_defs_src: Option<&'a str>,
+    module_src: &'a str,
+    src_dir: &Path,
+    solved_defs: &SolvedDefs,
+    target: Target,
+    palette: Palette,
+    exec_mode: ExecutionMode,
+) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
+    let cache_dir = cache::roc_cache_packages_dir();
+    let roc_cache_dir = RocCacheDir::Persistent(cache_dir.as_path());
+
+    let load = |reused_types| {
+        let filename = PathBuf::from("replfile.roc");
+        let src_dir = src_dir.to_path_buf();
+        let mut load_start =
+            LoadStart::from_str(arena, filename, None, module_src, roc_cache_dir, src_dir)?;
+
+        if let Some(defs_src) = opt_defs_src {
+            load_start = load_start.with_in_memory_module(REPL_DEFS_MODULE_NAME, defs_src);
+        }
+
+        // With a platform, this runs the expr rather than the platform's entry point
+        load_start = load_start.exposing_to_host(REPL_OUTPUT_NAME);
+
+        roc_load::load_reusing(
+            arena,
+            load_start,
+            roc_cache_dir,
+            LoadConfig {
+                target,
+                function_kind: FunctionKind::LambdaSet,
+                render: RenderTarget::ColorTerminal,
+                palette,
+                threading: Threading::Single,
+                exec_mode,
+            },
+            reused_types,
+        )
+    };
+
+    let reused_types = match opt_defs_src {
+        Some(defs_src) => solved_defs.reusable(defs_src),
+        None => MutMap::default(),
+    };
+
+    if reused_types.is_empty() {
+        return load(reused_types);
+    }
+
+    let reused_ids: Vec<ModuleId> = reused_types.keys().copied().collect();
+    let loaded = load(reused_types)?;
+
+    // If a change to the imports shuffled the module ids around, the reused types went to the
+    // wrong module, so solve everything again.
+    let interns = match &loaded {
+        LoadResult::TypeChecked(module) => &module.interns,
+        LoadResult::Monomorphized(module) => &module.interns,
+    };
+    let ids_match = reused_ids.iter().all(|module_id| {
+        interns
+            .module_ids
+            .get_name(*module_id)
+            .is_some_and(|name| name.as_str() == REPL_DEFS_MODULE_NAME)
+    });
+
+    if ids_match {
+        Ok(loaded)
+    } else {
+        load(MutMap::default())
+    }
+}
+
+/// A problem that stopped the REPL module from loading at all, like an imported file that's
+/// missing or doesn't parse
+fn loading_problems(problem: LoadingProblem, palette: Palette) -> Problems {
+    let report = report_loading_problem(
+        problem,
+        ModuleIds::default(),
+        RenderTarget::ColorTerminal,
+        palette,
+    );
+
+    Problems {
+        errors: vec![report],
+        warnings: Vec::new(),
+    }
+}
+
+/// Render the problems found while loading the REPL module. Problems in past defs were
+/// reported when they were entered, so they're left out here.
+fn report_problems(
+    interns: &Interns,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    module_src: &str,
+    bytes_before_expr: usize,
+    palette: Palette,
+) -> Problems {
     let mut problems = Problems::default();
 
     let errors = &mut problems.errors;
     let warnings = &mut problems.warnings;
 
     for (home, (module_path, src)) in sources.iter() {
+        // Problems in the past defs were reported when they were entered
+        if interns.module_name(*home).as_str() == REPL_DEFS_MODULE_NAME {
+            continue;
+        }
+
         let can_probs = can_problems.remove(home).unwrap_or_default();
         let type_probs = type_problems.remove(home).unwrap_or_default();
 
@@ -152,7 +606,21 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
             }
         }
 
+        let is_repl_module = **src == *module_src;
+
         for problem in type_probs {
+            // The stand-ins for what a platform requires come before the expr, and running the
+            // expr's effects at the top level is the whole point of having a platform.
+            let is_hidden = matches!(problem, TypeError::FxInTopLevel(..))
+                || (is_repl_module
+                    && problem
+                        .region()
+                        .is_some_and(|region| (region.end().offset as usize) < bytes_before_expr));
+
+            if is_hidden {
+                continue;
+            }
+
             if let Some(report) = type_problem(&alloc, &line_info, module_path.clone(), problem) {
                 let severity = report.severity;
                 let mut buf = String::new();
@@ -171,24 +639,96 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
         }
     }
 
-    (Some(loaded), problems)
+    problems
 }
 
-fn promote_expr_to_module<'a, 'i, I: Iterator<Item = &'i str>>(
+/// The source of the module the past defs go in, if there are any, and the source of the module
+/// that evaluates the expr, along with how many bytes come before the expr in it
+fn promote_expr_to_module<'a>(
     arena: &'a Bump,
-    defs: I,
+    past_defs: &PastDefs,
+    opt_platform: Option<&ReplPlatform>,
     expr: &str,
-) -> (usize, &'a str) {
-    const REPL_MODULE_HEADER: &str = "app \"app\" provides [repl_output] to \"./platform\"\n\n";
-    const REPL_MODULE_MAIN_DEF: &str = "repl_output =\n";
+) -> (Option<&'a str>, usize, &'a str) {
+    const REPL_MODULE_MAIN_DEF: &str = concatcp!(REPL_OUTPUT_NAME, " =\n");
     const INDENT: &str = "    ";
 
-    let mut buffer = bumpalo::collections::string::String::from_str_in(REPL_MODULE_HEADER, arena);
+    let opt_defs_src = if past_defs.defs.is_empty() {
+        None
+    } else {
+        let names: Vec<&str> = past_defs.defs.iter().map(|(name, _)| *name).collect();
+        let mut buffer = bumpalo::collections::string::String::new_in(arena);
 
-    for line in defs {
-        // don't indent the defs
-        buffer.push_str(line);
-        buffer.push_str("\n\n");
+        buffer.push_str("module [");
+        buffer.push_str(&names.join(", "));
+        buffer.push_str("]\n\n");
+
+        for import in past_defs.imports.iter() {
+            buffer.push_str(import);
+            buffer.push('\n');
+        }
+
+        for (_, src) in past_defs.defs.iter() {
+            buffer.push('\n');
+            buffer.push_str(src);
+            buffer.push('\n');
+        }
+
+        Some(buffer.into_bump_str())
+    };
+
+    let mut buffer = bumpalo::collections::string::String::new_in(arena);
+
+    match opt_platform {
+        Some(platform) => {
+            buffer.push_str("app [");
+            buffer.push_str(REPL_OUTPUT_NAME);
+
+            for name in platform.required.iter() {
+                buffer.push_str(", ");
+                buffer.push_str(name);
+            }
+
+            buffer.push_str("] { ");
+            buffer.push_str(REPL_PLATFORM_SHORTHAND);
+            buffer.push_str(": platform \"");
+            buffer.push_str(&platform.header_source(past_defs.src_dir));
+            buffer.push_str("\" }\n\n");
+        }
+        None => {
+            buffer.push_str("app \"app\" provides [repl_output] to \"./platform\"\n\n");
+        }
+    }
+
+    let header_len = buffer.len();
+
+    let exposed: Vec<&str> = past_defs
+        .defs
+        .iter()
+        .map(|(name, _)| *name)
+        .filter(|name| Some(*name) != past_defs.redefined)
+        .collect();
+
+    if opt_defs_src.is_some() && !exposed.is_empty() {
+        buffer.push_str("import ");
+        buffer.push_str(REPL_DEFS_MODULE_NAME);
+        buffer.push_str(" exposing [");
+        buffer.push_str(&exposed.join(", "));
+        buffer.push_str("]\n");
+    }
+
+    // The expr can use the imports too
+    for import in past_defs.imports.iter() {
+        buffer.push_str(import);
+        buffer.push('\n');
+    }
+
+    if buffer.len() > header_len {
+        buffer.push('\n');
+    }
+
+    if let Some(platform) = opt_platform {
+        buffer.push_str(&platform.required_defs);
     }
 
     buffer.push_str(REPL_MODULE_MAIN_DEF);
@@ -202,5 +742,5 @@ fn promote_expr_to_module<'a, 'i, I: Iterator<Item = &'i str>>(
         buffer.push('\n');
     }
 
-    (bytes_before_expr, buffer.into_bump_str())
+    (opt_defs_src, bytes_before_expr, buffer.into_bump_str())
 }
@@ -168,7 +168,7 @@ mod test {
 
         let global_layout_interner = layout_interner.into_global();
         for (_, expect_funcs) in expects_by_module {
-            let (_failed, _passed) = crate::run::run_expects_with_memory(
+            let _results = crate::run::run_expects_with_memory(
                 &mut writer,
                 RenderTarget::ColorTerminal,
                 arena,
@@ -178,6 +178,7 @@ mod test {
                 &mut expectations,
                 expect_funcs,
                 &mut memory,
+                None,
             )
             .unwrap();
         }
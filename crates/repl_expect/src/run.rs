@@ -1,6 +1,13 @@
-use std::sync::{
-    atomic::{AtomicBool, AtomicU32},
-    Arc,
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{ErrorKind, Read, Write},
+    os::unix::io::FromRawFd,
+    sync::{
+        atomic::{AtomicBool, AtomicU32},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
 use bumpalo::collections::Vec as BumpVec;
@@ -123,10 +130,15 @@ pub fn run_toplevel_expects<'a, W: std::io::Write>(
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
     expects: ExpectFunctions<'_>,
-) -> std::io::Result<(usize, usize)> {
+    opt_timeout: Option<Duration>,
+) -> std::io::Result<Vec<ExpectResult>> {
     let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
     let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
 
+    // Nothing else opens the buffer by name, so don't leave it behind in /dev/shm
+    let cstring = std::ffi::CString::new(shm_name).unwrap();
+    unsafe { libc::shm_unlink(cstring.as_ptr()) };
+
     run_expects_with_memory(
         writer,
         render_target,
@@ -137,6 +149,7 @@ pub fn run_toplevel_expects<'a, W: std::io::Write>(
         expectations,
         expects,
         &mut memory,
+        opt_timeout,
     )
 }
 
@@ -151,32 +164,50 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
     expectations: &mut VecMap<ModuleId, Expectations>,
     expects: ExpectFunctions<'_>,
     memory: &mut ExpectMemory,
-) -> std::io::Result<(usize, usize)> {
-    let mut failed = 0;
-    let mut passed = 0;
+    opt_timeout: Option<Duration>,
+) -> std::io::Result<Vec<ExpectResult>> {
+    let mut results = Vec::with_capacity(expects.pure.len());
 
     memory.set_shared_buffer(lib);
 
     for expect in expects.pure {
-        let result = run_expect_pure(
-            writer,
-            render_target,
-            arena,
-            interns,
-            layout_interner,
-            lib,
-            expectations,
-            memory,
-            expect,
-        )?;
+        let start = Instant::now();
+
+        let opt_failure = match opt_timeout {
+            None => run_expect_pure(
+                writer,
+                render_target,
+                arena,
+                interns,
+                layout_interner,
+                lib,
+                expectations,
+                memory,
+                expect,
+            )?,
+            Some(timeout) => run_expect_pure_with_timeout(
+                writer,
+                render_target,
+                arena,
+                interns,
+                layout_interner,
+                lib,
+                expectations,
+                memory,
+                expect,
+                timeout,
+            )?,
+        };
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
-        }
+        results.push(ExpectResult {
+            symbol: expect.symbol,
+            region: expect.region,
+            duration: start.elapsed(),
+            opt_failure,
+        });
     }
 
-    Ok((failed, passed))
+    Ok(results)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -190,7 +221,7 @@ fn run_expect_pure<'a, W: std::io::Write>(
     expectations: &mut VecMap<ModuleId, Expectations>,
     shared_memory: &mut ExpectMemory,
     expect: ToplevelExpect<'_>,
-) -> std::io::Result<bool> {
+) -> std::io::Result<Option<ExpectFailure>> {
     use roc_gen_llvm::try_run_jit_function;
 
     let sequence = ExpectSequence::new(shared_memory.ptr.cast());
@@ -209,14 +240,17 @@ fn run_expect_pure<'a, W: std::io::Write>(
 
         let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
 
+        let mut report = Vec::new();
+        let mut values = Vec::new();
+
         if let Err((roc_panic_message, _roc_panic_tag)) = result {
-            renderer.render_panic(writer, &roc_panic_message, expect.region)?;
+            renderer.render_panic(&mut report, &roc_panic_message, expect.region)?;
         } else {
             let mut offset = ExpectSequence::START_OFFSET;
 
             for _ in 0..sequence.count_failures() {
-                offset = render_expect_failure(
-                    writer,
+                let (next_offset, failure_values) = render_expect_failure(
+                    &mut report,
                     &renderer,
                     arena,
                     Some(expect),
@@ -226,14 +260,453 @@ fn run_expect_pure<'a, W: std::io::Write>(
                     shared_memory_ptr,
                     offset,
                 )?;
+
+                offset = next_offset;
+                values.extend(failure_values);
+            }
+        }
+
+        writeln!(report)?;
+        writer.write_all(&report)?;
+
+        Ok(Some(ExpectFailure {
+            report: String::from_utf8_lossy(&report).into_owned(),
+            values,
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Like `run_expect_pure`, but runs the expect in a child process, so that if it's still running
+/// after `timeout`, it can be stopped and reported as a failure instead of hanging forever.
+///
+/// The child renders any failures itself and sends them back through a pipe. The shared memory
+/// is mapped with `MAP_SHARED`, so the child sees the same buffer the dylib was given.
+#[allow(clippy::too_many_arguments)]
+fn run_expect_pure_with_timeout<'a, W: std::io::Write>(
+    writer: &mut W,
+    render_target: RenderTarget,
+    arena: &'a Bump,
+    interns: &'a Interns,
+    layout_interner: &GlobalLayoutInterner<'a>,
+    lib: &libloading::Library,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    shared_memory: &mut ExpectMemory,
+    expect: ToplevelExpect<'_>,
+    timeout: Duration,
+) -> std::io::Result<Option<ExpectFailure>> {
+    let (pid, mut pipe) = fork_with_pipe(|pipe| {
+        match run_expect_pure(
+            &mut std::io::sink(),
+            render_target,
+            arena,
+            interns,
+            layout_interner,
+            lib,
+            expectations,
+            shared_memory,
+            expect,
+        ) {
+            Ok(None) => 0,
+            Ok(Some(failure)) => match write_failure(pipe, &failure) {
+                Ok(()) => 1,
+                Err(_) => 2,
+            },
+            Err(_) => 2,
+        }
+    })?;
+
+    let start = Instant::now();
+    let mut status = 0;
+
+    let timed_out = loop {
+        pipe.drain()?;
+
+        match unsafe { libc::waitpid(pid, &mut status, libc::WNOHANG) } {
+            0 if start.elapsed() >= timeout => {
+                unsafe {
+                    libc::kill(pid, libc::SIGKILL);
+                    libc::waitpid(pid, &mut status, 0);
+                }
+
+                break true;
             }
+            0 => std::thread::sleep(Duration::from_millis(1)),
+            -1 => return Err(std::io::Error::last_os_error()),
+            _ => break false,
         }
+    };
+
+    pipe.drain()?;
+    let output = pipe.output;
+
+    if !timed_out && libc::WIFEXITED(status) {
+        return match libc::WEXITSTATUS(status) {
+            0 => Ok(None),
+            1 => {
+                let failure = read_failure(&mut PipeReader { bytes: &output })
+                    .ok_or_else(|| std::io::Error::other("failed to read a failed expect"))?;
+                writer.write_all(failure.report.as_bytes())?;
+
+                Ok(Some(failure))
+            }
+            _ => Err(std::io::Error::other("failed to render a failed expect")),
+        };
+    }
+
+    let module_id = expect.symbol.module_id();
+    let data = expectations.get_mut(&module_id).unwrap();
+
+    let filename = data.path.to_owned();
+    let source = std::fs::read_to_string(&data.path).unwrap();
+
+    let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
 
-        writeln!(writer)?;
+    let mut report = Vec::new();
 
-        Ok(false)
+    if timed_out {
+        renderer.render_timeout(&mut report, timeout, expect.region)?;
     } else {
-        Ok(true)
+        // e.g. a stack overflow, which the child can't report itself
+        let message = format!(
+            "The process running it was stopped by signal {}.",
+            libc::WTERMSIG(status)
+        );
+
+        renderer.render_panic(&mut report, &message, expect.region)?;
+    }
+
+    writeln!(report)?;
+    writer.write_all(&report)?;
+
+    Ok(Some(ExpectFailure {
+        report: String::from_utf8_lossy(&report).into_owned(),
+        values: Vec::new(),
+    }))
+}
+
+/// Fork a child process that runs `run_child` and then exits with the code it returns. Whatever
+/// the child writes to the pipe it's given can be collected by draining the returned `ChildPipe`.
+///
+/// The child goes on to allocate, which is only safe if no other thread could have been holding
+/// the allocator's lock when we forked. So callers must not have other threads running, and the
+/// pipe is read by polling it rather than from a thread of its own.
+fn fork_with_pipe(
+    run_child: impl FnOnce(&mut File) -> i32,
+) -> std::io::Result<(libc::pid_t, ChildPipe)> {
+    let mut fds = [0; 2];
+
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let [read_fd, write_fd] = fds;
+
+    match unsafe { libc::fork() } {
+        0 => {
+            // we are the child
+            unsafe { libc::close(read_fd) };
+
+            let mut pipe = unsafe { File::from_raw_fd(write_fd) };
+            let exit_code = run_child(&mut pipe);
+
+            // skip the parent's atexit handlers and buffered stdout, which aren't ours to flush
+            unsafe { libc::_exit(exit_code) }
+        }
+        -1 => {
+            let error = std::io::Error::last_os_error();
+
+            unsafe {
+                libc::close(read_fd);
+                libc::close(write_fd);
+            }
+
+            Err(error)
+        }
+        pid => {
+            unsafe {
+                libc::close(write_fd);
+
+                let flags = libc::fcntl(read_fd, libc::F_GETFL);
+                libc::fcntl(read_fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+            }
+
+            let pipe = ChildPipe {
+                file: unsafe { File::from_raw_fd(read_fd) },
+                output: Vec::new(),
+            };
+
+            Ok((pid, pipe))
+        }
+    }
+}
+
+/// The non-blocking read end of a child's pipe, and what's been read from it so far
+struct ChildPipe {
+    file: File,
+    output: Vec<u8>,
+}
+
+impl ChildPipe {
+    /// Read whatever the child has written since last time, without waiting for more. This needs
+    /// calling while the child runs, so a child with lots of output can't block on a full pipe.
+    fn drain(&mut self) -> std::io::Result<()> {
+        let mut buf = [0; 4096];
+
+        loop {
+            match self.file.read(&mut buf) {
+                Ok(0) => return Ok(()),
+                Ok(n) => self.output.extend_from_slice(&buf[..n]),
+                Err(error) if error.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+// Child processes send their results back to the parent as a sequence of integers and strings.
+// Integers are u64s in little endian, and strings are their length followed by their bytes.
+
+fn write_u64(pipe: &mut impl Write, n: u64) -> std::io::Result<()> {
+    pipe.write_all(&n.to_le_bytes())
+}
+
+fn write_str(pipe: &mut impl Write, string: &str) -> std::io::Result<()> {
+    write_u64(pipe, string.len() as u64)?;
+    pipe.write_all(string.as_bytes())
+}
+
+struct PipeReader<'b> {
+    bytes: &'b [u8],
+}
+
+impl PipeReader<'_> {
+    fn read_u64(&mut self) -> Option<u64> {
+        let (n, rest) = self.bytes.split_first_chunk::<8>()?;
+        self.bytes = rest;
+
+        Some(u64::from_le_bytes(*n))
+    }
+
+    fn read_str(&mut self) -> Option<String> {
+        let len = self.read_u64()? as usize;
+        let string = String::from_utf8_lossy(self.bytes.get(..len)?).into_owned();
+        self.bytes = &self.bytes[len..];
+
+        Some(string)
+    }
+}
+
+fn write_failure(pipe: &mut impl Write, failure: &ExpectFailure) -> std::io::Result<()> {
+    write_str(pipe, &failure.report)?;
+    write_u64(pipe, failure.values.len() as u64)?;
+
+    for (name, value) in failure.values.iter() {
+        write_str(pipe, name)?;
+        write_str(pipe, value)?;
+    }
+
+    Ok(())
+}
+
+fn read_failure(reader: &mut PipeReader) -> Option<ExpectFailure> {
+    let report = reader.read_str()?;
+    let values_len = reader.read_u64()?;
+    let mut values = Vec::new();
+
+    for _ in 0..values_len {
+        values.push((reader.read_str()?, reader.read_str()?));
+    }
+
+    Some(ExpectFailure { report, values })
+}
+
+fn write_results(pipe: &mut impl Write, results: &[ExpectResult]) -> std::io::Result<()> {
+    for result in results {
+        write_u64(pipe, result.duration.as_nanos() as u64)?;
+
+        match &result.opt_failure {
+            None => write_u64(pipe, 0)?,
+            Some(failure) => {
+                write_u64(pipe, 1)?;
+                write_failure(pipe, failure)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The inverse of `write_results`. The results don't include which expect they're for, so this
+/// takes the expects in the order they ran.
+fn read_results(bytes: &[u8], expects: &[(Symbol, Region)]) -> Vec<ExpectResult> {
+    let mut reader = PipeReader { bytes };
+
+    expects
+        .iter()
+        .map(|(symbol, region)| {
+            let opt_result = reader.read_u64().and_then(|nanos| {
+                let opt_failure = match reader.read_u64()? {
+                    0 => None,
+                    _ => Some(read_failure(&mut reader)?),
+                };
+
+                Some((Duration::from_nanos(nanos), opt_failure))
+            });
+
+            // If the worker crashed partway through, there's nothing more to go on than that
+            let (duration, opt_failure) = opt_result.unwrap_or_else(|| {
+                let failure = ExpectFailure {
+                    report: "The worker process running this expect stopped before it finished.\n"
+                        .to_string(),
+                    values: Vec::new(),
+                };
+
+                (Duration::ZERO, Some(failure))
+            });
+
+            ExpectResult {
+                symbol: *symbol,
+                region: *region,
+                duration,
+                opt_failure,
+            }
+        })
+        .collect()
+}
+
+/// A module's expects, running in a worker process
+struct Worker<T> {
+    tag: T,
+    pid: libc::pid_t,
+    pipe: ChildPipe,
+    expects: Vec<(Symbol, Region)>,
+    exited: bool,
+}
+
+/// Runs the expects of several modules at once, each module in its own worker process with its
+/// own shared memory. Results come back in the order the modules were spawned, regardless of
+/// which worker finishes first, so that output doesn't change from one run to the next.
+///
+/// Workers are forked from the thread that spawns them, and their output is collected by polling
+/// on that same thread, so nothing else may be running on other threads while they're in use.
+pub struct ExpectWorkers<T> {
+    max_workers: usize,
+    workers: VecDeque<Worker<T>>,
+}
+
+impl<T> ExpectWorkers<T> {
+    pub fn new(max_workers: usize) -> Self {
+        Self {
+            max_workers: max_workers.max(1),
+            workers: VecDeque::new(),
+        }
+    }
+
+    /// Start running a module's expects once fewer than `max_workers` are busy. `tag` comes back
+    /// with the results, to say which module they're for. Returns any results that are ready.
+    #[allow(clippy::too_many_arguments)]
+    pub fn spawn<'a>(
+        &mut self,
+        tag: T,
+        render_target: RenderTarget,
+        arena: &'a Bump,
+        interns: &'a Interns,
+        layout_interner: &GlobalLayoutInterner<'a>,
+        lib: &libloading::Library,
+        expectations: &mut VecMap<ModuleId, Expectations>,
+        expects: ExpectFunctions<'_>,
+        opt_timeout: Option<Duration>,
+    ) -> std::io::Result<Vec<(T, Vec<ExpectResult>)>> {
+        while self.busy_count()? >= self.max_workers {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let expect_locations = expects
+            .pure
+            .iter()
+            .map(|expect| (expect.symbol, expect.region))
+            .collect();
+
+        let (pid, pipe) = fork_with_pipe(|pipe| {
+            let results = run_toplevel_expects(
+                &mut std::io::sink(),
+                render_target,
+                arena,
+                interns,
+                layout_interner,
+                lib,
+                expectations,
+                expects,
+                opt_timeout,
+            );
+
+            match results.and_then(|results| write_results(pipe, &results)) {
+                Ok(()) => 0,
+                Err(_) => 1,
+            }
+        })?;
+
+        self.workers.push_back(Worker {
+            tag,
+            pid,
+            pipe,
+            expects: expect_locations,
+            exited: false,
+        });
+
+        self.take_finished()
+    }
+
+    /// Wait for every worker to finish, and return the results that weren't returned already
+    pub fn finish(mut self) -> std::io::Result<Vec<(T, Vec<ExpectResult>)>> {
+        while self.busy_count()? > 0 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        self.take_finished()
+    }
+
+    /// Collect what the workers have written, note which have exited, and return how many are
+    /// still running
+    fn busy_count(&mut self) -> std::io::Result<usize> {
+        let mut busy = 0;
+
+        for worker in self.workers.iter_mut().filter(|worker| !worker.exited) {
+            let mut status = 0;
+
+            worker.pipe.drain()?;
+
+            match unsafe { libc::waitpid(worker.pid, &mut status, libc::WNOHANG) } {
+                0 => busy += 1,
+                -1 => return Err(std::io::Error::last_os_error()),
+                _ => {
+                    // whatever it wrote before exiting
+                    worker.pipe.drain()?;
+                    worker.exited = true;
+                }
+            }
+        }
+
+        Ok(busy)
+    }
+
+    /// The results of the workers that have exited, up to the first one that's still running
+    fn take_finished(&mut self) -> std::io::Result<Vec<(T, Vec<ExpectResult>)>> {
+        let mut finished = Vec::new();
+
+        while self.workers.front().is_some_and(|worker| worker.exited) {
+            let worker = self.workers.pop_front().unwrap();
+
+            finished.push((
+                worker.tag,
+                read_results(&worker.pipe.output, &worker.expects),
+            ));
+        }
+
+        Ok(finished)
     }
 }
 
@@ -274,6 +747,7 @@ pub fn render_expects_in_memory<'a>(
         shared_ptr,
         ExpectSequence::START_OFFSET,
     )
+    .map(|(offset, _)| offset)
 }
 
 fn split_expect_lookups(subs: &Subs, lookups: &[ExpectLookup]) -> Vec<Symbol> {
@@ -308,7 +782,7 @@ fn render_expect_failure<'a>(
     layout_interner: &GlobalLayoutInterner<'a>,
     start: *const u8,
     offset: usize,
-) -> std::io::Result<usize> {
+) -> std::io::Result<(usize, Vec<(String, String)>)> {
     // we always run programs as the host
     let target = target_lexicon::Triple::host().into();
 
@@ -348,7 +822,7 @@ fn render_expect_failure<'a>(
         failure_region,
     )?;
 
-    Ok(offset)
+    Ok((offset, renderer.lookup_values(&symbols, &expressions)))
 }
 
 struct ExpectSequence {
@@ -439,6 +913,24 @@ impl ExpectFrame {
     }
 }
 
+/// What happened when a top-level expect ran
+#[derive(Debug)]
+pub struct ExpectResult {
+    pub symbol: Symbol,
+    pub region: Region,
+    pub duration: Duration,
+    /// `None` if the expect passed
+    pub opt_failure: Option<ExpectFailure>,
+}
+
+#[derive(Debug)]
+pub struct ExpectFailure {
+    /// The report that was written for the failure, rendered for the requested `RenderTarget`
+    pub report: String,
+    /// The name and value of each variable the failed expectation looked up
+    pub values: Vec<(String, String)>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ToplevelExpect<'a> {
     pub name: &'a str,
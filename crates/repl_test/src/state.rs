@@ -1,10 +1,12 @@
 use bumpalo::Bump;
 use indoc::indoc;
 use roc_repl_cli::{evaluate, ReplHelper};
-use roc_repl_ui::is_incomplete;
+use roc_repl_eval::gen::ReplPlatform;
 use roc_repl_ui::repl_state::{ReplAction, ReplState};
-use roc_reporting::report::DEFAULT_PALETTE;
+use roc_repl_ui::{format_output, is_incomplete};
+use roc_reporting::report::{ANSI_STYLE_CODES, DEFAULT_PALETTE};
 use rustyline::Editor;
+use std::path::Path;
 use target_lexicon::Triple;
 
 // These are tests of the REPL state machine. They work without actually
@@ -25,6 +27,17 @@ fn persisted_defs() {
     complete("y = 6", &mut state, "6 : Num *");
 }
 
+#[test]
+fn redefined_def() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, "5 : Num *");
+    complete("y = 6", &mut state, "6 : Num *");
+    complete("x = \"five\"", &mut state, "\"five\" : Str");
+    complete("Str.concat x \"!\"", &mut state, "\"five!\" : Str");
+    complete("y + 1", &mut state, "7 : Num *");
+}
+
 #[test]
 fn annotated_body() {
     let mut input = "t : [A, B, C]".to_string();
@@ -61,12 +74,12 @@ fn exhaustiveness_problem() {
 
         let expected_error: &str = indoc!(
             r#"
-            ── UNSAFE PATTERN ──────────────────────────────────────────────────────────────
+            ── UNSAFE PATTERN [E3020] ──────────────────────────────────────────────────────
 
             This when does not cover all the possibilities:
 
-            7│>      when t is
-            8│>          A -> 1
+            6│>      when t is
+            7│>          A -> 1
 
             Other possibilities include:
 
@@ -107,7 +120,7 @@ fn partial_record_definition() {
         input.push('\n');
         let expected_error: &str = indoc!(
             r#"
-            ── RECORD PARSE PROBLEM ────────────────────────────────────────────────────────
+            ── RECORD PARSE PROBLEM [E1042] ────────────────────────────────────────────────
 
             I am partway through parsing a record, but I got stuck here:
 
@@ -132,6 +145,29 @@ fn tips() {
     assert!(matches!(action, ReplAction::Help));
 }
 
+#[test]
+fn past_def_problems_not_repeated() {
+    let mut state = ReplState::new();
+
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let action = state.step(&arena, "f = \\n -> 1", target, DEFAULT_PALETTE);
+
+    match action {
+        ReplAction::Eval { problems, .. } => {
+            assert_eq!(problems.errors.len(), 0);
+            assert_eq!(problems.warnings.len(), 1);
+        }
+        _ => {
+            panic!("Unexpected action: {:?}", action);
+        }
+    }
+
+    // The unused argument was reported for the line that defined f, and only for that line
+    complete("f 2", &mut state, "1 : Num *");
+    complete("f 3", &mut state, "1 : Num *");
+}
+
 #[test]
 fn standalone_annotation() {
     let mut state = ReplState::new();
@@ -145,6 +181,272 @@ fn standalone_annotation() {
     assert!(matches!(action, ReplAction::Nothing));
 }
 
+#[test]
+fn type_without_evaluating() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, "5 : Num *");
+
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let action = state.step(&arena, ":type x + 1", target, DEFAULT_PALETTE);
+
+    match action {
+        ReplAction::Type {
+            opt_output,
+            problems,
+        } => {
+            let string = format_output(ANSI_STYLE_CODES, opt_output, problems);
+            let escaped =
+                std::string::String::from_utf8(strip_ansi_escapes::strip(string.trim()).unwrap())
+                    .unwrap();
+
+            assert_eq!(escaped, "x + 1 : Num *");
+        }
+        _ => {
+            panic!("Unexpected action: {:?}", action);
+        }
+    }
+}
+
+#[test]
+fn defs_and_reset() {
+    let mut state = ReplState::new();
+
+    complete("x = 5", &mut state, "5 : Num *");
+    complete("greeting = \"hi\"", &mut state, "\"hi\" : Str");
+    complete("y = 1.5", &mut state, "1.5 : Frac *");
+
+    message(":defs", &mut state, "x : Num *\ngreeting : Str\ny : Frac *");
+    message(":reset", &mut state, "Cleared all defs and imports.");
+    message(":defs", &mut state, "There are no defs yet.");
+}
+
+#[test]
+fn save_then_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Session.roc");
+    let path_str = path.to_str().unwrap();
+
+    let mut state = ReplState::new();
+    complete("x = 5", &mut state, "5 : Num *");
+    complete(
+        "double = \\n -> n * 2",
+        &mut state,
+        "<function> : Num a -> Num a",
+    );
+
+    message(
+        &format!(":save {path_str}"),
+        &mut state,
+        &format!("Saved 2 defs and imports to {path_str}."),
+    );
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "module [x, double]\n\nx = 5\n\ndouble = \\n -> n * 2\n"
+    );
+
+    let mut loaded_state = ReplState::new();
+    message(
+        &format!(":load {path_str}"),
+        &mut loaded_state,
+        &format!("Loaded 2 defs and imports from {path_str}."),
+    );
+    complete("double x", &mut loaded_state, "10 : Num *");
+}
+
+#[test]
+fn load_missing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Missing.roc");
+
+    let mut state = ReplState::new();
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let input = format!(":load {}", path.to_str().unwrap());
+    let action = state.step(&arena, &input, target, DEFAULT_PALETTE);
+
+    match action {
+        ReplAction::FileProblem { filename, error } => {
+            assert_eq!(filename, path);
+            assert_eq!(error, std::io::ErrorKind::NotFound);
+        }
+        _ => {
+            panic!("Unexpected action: {:?}", action);
+        }
+    }
+
+    message(":defs", &mut state, "There are no defs yet.");
+}
+
+#[test]
+fn load_file_that_fails_to_parse() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Broken.roc");
+    let path_str = path.to_str().unwrap();
+    std::fs::write(&path, "module [x]\n\nx = \n").unwrap();
+
+    let mut state = ReplState::new();
+    message(
+        &format!(":load {path_str}"),
+        &mut state,
+        &format!("I couldn't parse {path_str}. Run `roc check` on it to see the problem."),
+    );
+
+    // Nothing from the broken file was loaded, so the session carries on as before
+    message(":defs", &mut state, "There are no defs yet.");
+    complete("1 + 1", &mut state, "2 : Num *");
+}
+
+#[test]
+fn standalone_annotation_kept_with_its_body() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Session.roc");
+    let path_str = path.to_str().unwrap();
+
+    let mut state = ReplState::new();
+    let mut input = "x : Str".to_string();
+
+    incomplete(&mut input);
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let action = state.step(&arena, &input, target, DEFAULT_PALETTE);
+    assert!(matches!(action, ReplAction::Nothing));
+
+    complete("x = \"hi\"", &mut state, "\"hi\" : Str");
+
+    message(
+        &format!(":save {path_str}"),
+        &mut state,
+        &format!("Saved 1 defs and imports to {path_str}."),
+    );
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "module [x]\n\nx : Str\nx = \"hi\"\n"
+    );
+}
+
+#[test]
+fn load_resolves_imports_next_to_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Dep.roc"), "module [value]\n\nvalue = 5\n").unwrap();
+
+    let path = dir.path().join("Main.roc");
+    let path_str = path.to_str().unwrap();
+    std::fs::write(&path, "module [x]\n\nimport Dep\n\nx = Dep.value + 1\n").unwrap();
+
+    // The REPL isn't running in the file's dir, but the file's import is found next to it
+    let mut state = ReplState::new();
+    message(
+        &format!(":load {path_str}"),
+        &mut state,
+        &format!("Loaded 2 defs and imports from {path_str}."),
+    );
+    complete("x", &mut state, "6 : Num *");
+}
+
+#[test]
+fn import_from_platform() {
+    let platform_src = indoc!(
+        r#"
+        platform "effects"
+            requires {} { main! : {} => {} }
+            exposes [Effect]
+            packages {}
+            imports []
+            provides [main_for_host!]
+        "#
+    );
+    let platform = ReplPlatform::new(
+        "platform/main.roc",
+        Path::new("platform/main.roc"),
+        platform_src,
+    )
+    .unwrap();
+    let mut state = ReplState::with_platform(platform);
+
+    message(
+        "import pf.Stdout",
+        &mut state,
+        "The platform doesn't expose a module named Stdout.",
+    );
+
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let action = state.step(&arena, "import pf.Effect", target, DEFAULT_PALETTE);
+    assert!(matches!(action, ReplAction::Nothing));
+}
+
+#[test]
+#[cfg_attr(
+    windows,
+    ignore = "the platform's path would need escaping in the app header"
+)]
+fn required_def_from_platform() {
+    let main_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../cli/tests/test-projects/test-platform-effects-zig/main.roc");
+    let platform_src = std::fs::read_to_string(&main_path).unwrap();
+    let platform =
+        ReplPlatform::new(main_path.to_str().unwrap(), &main_path, &platform_src).unwrap();
+    let mut state = ReplState::with_platform(platform);
+
+    // The platform doesn't expose its hosted module, but apps can still import it
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let action = state.step(&arena, "import pf.Effect", target, DEFAULT_PALETTE);
+    assert!(matches!(action, ReplAction::Nothing));
+
+    // The stand-in for what the platform requires has the type the platform asks for
+    type_of(":type main!", &mut state, "main! : {} => {}");
+    type_of(
+        ":type Effect.put_line!",
+        &mut state,
+        "Effect.put_line! : Str => {}",
+    );
+}
+
+/// step the given `:type` meta-command, then check the type it shows, with ANSI escape codes
+/// stripped
+fn type_of(input: &str, state: &mut ReplState, expected: &str) {
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let action = state.step(&arena, input, target, DEFAULT_PALETTE);
+
+    match action {
+        ReplAction::Type {
+            opt_output,
+            problems,
+        } => {
+            let string = format_output(ANSI_STYLE_CODES, opt_output, problems);
+            let escaped =
+                std::string::String::from_utf8(strip_ansi_escapes::strip(string.trim()).unwrap())
+                    .unwrap();
+
+            assert_eq!(escaped, expected);
+        }
+        _ => {
+            panic!("Unexpected action: {:?}", action);
+        }
+    }
+}
+
+/// step the given meta-command, then check the message it shows
+fn message(input: &str, state: &mut ReplState, expected_message: &str) {
+    assert!(!is_incomplete(input));
+    let arena = Bump::new();
+    let target = Triple::host().into();
+    let action = state.step(&arena, input, target, DEFAULT_PALETTE);
+
+    match action {
+        ReplAction::Message(message) => {
+            assert_eq!(message, expected_message);
+        }
+        _ => {
+            panic!("Unexpected action: {:?}", action);
+        }
+    }
+}
+
 /// validate and step the given input, then check the Result vs the output
 /// with ANSI escape codes stripped.
 fn complete(input: &str, state: &mut ReplState, expected_start: &str) {
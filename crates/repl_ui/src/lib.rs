@@ -45,6 +45,38 @@ pub const TIPS: &str = concatcp!(
             "  - ",
             END_COL,
             GREEN,
+            ":type <expr>",
+            END_COL,
+            " shows an expression's type without evaluating it\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":defs",
+            END_COL,
+            " lists definitions, and ",
+            GREEN,
+            ":reset",
+            END_COL,
+            " clears them\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":load <file.roc>",
+            END_COL,
+            " loads a module's definitions\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
+            ":save <file.roc>",
+            END_COL,
+            " saves the definitions as a module\n",
+            CYAN,
+            "  - ",
+            END_COL,
+            GREEN,
             ":help",
             END_COL,
             " shows this text again\n",
@@ -84,9 +116,15 @@ pub fn is_incomplete(input: &str) -> bool {
                 false
             }
         }
-        ParseOutcome::Empty | ParseOutcome::Help | ParseOutcome::Exit | ParseOutcome::SyntaxErr => {
-            false
-        }
+        ParseOutcome::Empty
+        | ParseOutcome::Help
+        | ParseOutcome::Exit
+        | ParseOutcome::SyntaxErr
+        | ParseOutcome::Type(_)
+        | ParseOutcome::Load(_)
+        | ParseOutcome::Save(_)
+        | ParseOutcome::Defs
+        | ParseOutcome::Reset => false,
     }
 }
 
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{fs, io};
 
 use bumpalo::Bump;
@@ -6,23 +6,40 @@ use roc_collections::MutSet;
 use roc_load::MonomorphizedModule;
 use roc_parse::ast::{Defs, Expr, Pattern, StrLiteral, TypeDef, TypeHeader, ValueDef};
 use roc_parse::expr::parse_repl_defs_and_optional_expr;
+use roc_parse::header::{parse_header, parse_module_defs};
 use roc_parse::parser::EWhen;
 use roc_parse::parser::{EClosure, EExpr, EPattern};
 use roc_parse::state::State;
 use roc_region::all::Loc;
-use roc_repl_eval::gen::{compile_to_mono, Problems};
+use roc_repl_eval::gen::{
+    compile_to_mono, type_check, PastDefs, Problems, ReplOutput, ReplPlatform, SolvedDefs,
+    REPL_PLATFORM_SHORTHAND,
+};
 use roc_reporting::report::Palette;
 use roc_target::Target;
 
 #[derive(Debug, Clone, PartialEq)]
 enum PastDef {
-    Def { ident: String, src: String },
+    Def {
+        ident: String,
+        src: String,
+    },
+    /// A standalone type annotation, until the body it annotates gets entered
+    Annotation {
+        ident: String,
+        src: String,
+    },
     Import(String),
 }
 
 pub struct ReplState {
     past_defs: Vec<PastDef>,
     past_def_idents: MutSet<String>,
+    /// The dir the imports of files are resolved in, once there are any. That's the current
+    /// dir, unless they came from a module `:load`ed from another one.
+    imports_dir: Option<PathBuf>,
+    solved_defs: SolvedDefs,
+    platform: Option<ReplPlatform>,
 }
 
 impl Default for ReplState {
@@ -38,6 +55,13 @@ pub enum ReplAction<'a> {
         opt_mono: Option<MonomorphizedModule<'a>>,
         problems: Problems,
     },
+    /// The type of an expr, from `:type`
+    Type {
+        opt_output: Option<ReplOutput>,
+        problems: Problems,
+    },
+    /// Text to show the user, like the output of a meta-command
+    Message(String),
     Exit,
     Help,
     FileProblem {
@@ -52,6 +76,17 @@ impl ReplState {
         Self {
             past_defs: Default::default(),
             past_def_idents: Default::default(),
+            imports_dir: None,
+            solved_defs: Default::default(),
+            platform: None,
+        }
+    }
+
+    /// A REPL whose exprs can use the modules and effects of the given platform
+    pub fn with_platform(platform: ReplPlatform) -> Self {
+        Self {
+            platform: Some(platform),
+            ..Self::new()
         }
     }
 
@@ -66,6 +101,18 @@ impl ReplState {
         let src: &str = match parse_src(arena, line) {
             ParseOutcome::Empty | ParseOutcome::Help => return ReplAction::Help,
             ParseOutcome::Exit => return ReplAction::Exit,
+            ParseOutcome::Type(expr) => return self.type_of(arena, expr, target, palette),
+            ParseOutcome::Load(filename) => return self.load(arena, filename),
+            ParseOutcome::Save(filename) => return self.save(filename),
+            ParseOutcome::Defs => return self.defs(arena, target, palette),
+            ParseOutcome::Reset => {
+                *self = Self {
+                    platform: self.platform.take(),
+                    ..Self::new()
+                };
+
+                return ReplAction::Message("Cleared all defs and imports.".to_string());
+            }
             ParseOutcome::Incomplete | ParseOutcome::SyntaxErr => {
                 pending_past_def = None;
 
@@ -136,7 +183,7 @@ impl ReplState {
                                     _,
                                 ) => {
                                     // Record the standalone type annotation for future use.
-                                    self.add_past_def(
+                                    self.add_past_annotation(
                                         ident.trim_end().to_string(),
                                         line[vd.byte_range()].to_string(),
                                     );
@@ -197,11 +244,26 @@ impl ReplState {
                                     todo!("handle receiving an `expect` - what should the repl do for that?")
                                 }
                                 ValueDef::ModuleImport(import) => match import.name.value.package {
+                                    Some(REPL_PLATFORM_SHORTHAND) if self.platform.is_some() => {
+                                        let module_name = import.name.value.name.as_str();
+
+                                        if !self.imports_from_platform(module_name) {
+                                            return ReplAction::Message(format!(
+                                                "The platform doesn't expose a module named {module_name}."
+                                            ));
+                                        }
+
+                                        self.past_defs.push(PastDef::Import(
+                                            line[vd.byte_range()].to_string(),
+                                        ));
+
+                                        return ReplAction::Nothing;
+                                    }
                                     Some(_) => {
                                         todo!("handle importing a module from a package")
                                     }
                                     None => {
-                                        let mut filename = PathBuf::new();
+                                        let mut filename = self.imports_dir().to_path_buf();
 
                                         for part in import.name.value.name.parts() {
                                             filename.push(part);
@@ -219,16 +281,14 @@ impl ReplState {
                                             };
                                         }
 
-                                        self.past_defs.push(PastDef::Import(
-                                            line[vd.byte_range()].to_string(),
-                                        ));
+                                        self.add_file_import(line[vd.byte_range()].to_string());
 
                                         return ReplAction::Nothing;
                                     }
                                 },
                                 ValueDef::IngestedFileImport(file) => {
                                     if let StrLiteral::PlainLine(path) = file.path.value {
-                                        let filename = PathBuf::from(path);
+                                        let filename = self.imports_dir().join(path);
                                         if let Err(err) = fs::metadata(&filename) {
                                             return ReplAction::FileProblem {
                                                 filename,
@@ -237,8 +297,7 @@ impl ReplState {
                                         }
                                     }
 
-                                    self.past_defs
-                                        .push(PastDef::Import(line[vd.byte_range()].to_string()));
+                                    self.add_file_import(line[vd.byte_range()].to_string());
 
                                     return ReplAction::Nothing;
                                 }
@@ -257,12 +316,12 @@ impl ReplState {
             }
         };
 
+        let redefined = pending_past_def.as_ref().map(|(ident, _)| ident.as_str());
         let (opt_mono, problems) = compile_to_mono(
             arena,
-            self.past_defs.iter().map(|past_def| match past_def {
-                PastDef::Def { ident: _, src } => src.as_str(),
-                PastDef::Import(src) => src.as_str(),
-            }),
+            &past_defs_for(&self.past_defs, self.imports_dir.as_deref(), redefined),
+            &mut self.solved_defs,
+            self.platform.as_ref(),
             src,
             target,
             palette,
@@ -275,13 +334,362 @@ impl ReplState {
         ReplAction::Eval { opt_mono, problems }
     }
 
+    /// `:type` - print the type of an expr without evaluating it
+    fn type_of<'a>(
+        &mut self,
+        arena: &'a Bump,
+        expr: &str,
+        target: Target,
+        palette: Palette,
+    ) -> ReplAction<'a> {
+        if expr.is_empty() {
+            return ReplAction::Message("Usage: :type <expr>".to_string());
+        }
+
+        let (opt_types, problems) = type_check(
+            arena,
+            &past_defs_for(&self.past_defs, self.imports_dir.as_deref(), None),
+            &mut self.solved_defs,
+            self.platform.as_ref(),
+            expr,
+            target,
+            palette,
+        );
+        let opt_output = opt_types.map(|types| ReplOutput {
+            expr: expr.to_string(),
+            expr_type: types.expr_type,
+        });
+
+        ReplAction::Type {
+            opt_output,
+            problems,
+        }
+    }
+
+    /// `:defs` - list the defs and imports in this session, with the types of the values
+    fn defs<'a>(&mut self, arena: &'a Bump, target: Target, palette: Palette) -> ReplAction<'a> {
+        let latest_defs = latest_defs(&self.past_defs);
+
+        if latest_defs.is_empty() {
+            return ReplAction::Message("There are no defs yet.".to_string());
+        }
+
+        // Type check an empty record, just to solve the types of the defs
+        let (opt_types, _) = type_check(
+            arena,
+            &past_defs_for(&self.past_defs, self.imports_dir.as_deref(), None),
+            &mut self.solved_defs,
+            self.platform.as_ref(),
+            "{}",
+            target,
+            palette,
+        );
+        let def_types = opt_types.map(|types| types.def_types).unwrap_or_default();
+
+        let lines: Vec<String> = latest_defs
+            .into_iter()
+            .map(|past_def| match past_def {
+                PastDef::Def { ident, src } => {
+                    match def_types.iter().rev().find(|(name, _)| name == ident) {
+                        Some((name, def_type)) => format!("{name} : {def_type}"),
+                        // Type defs, and values with problems, get shown as they were entered
+                        None => src.clone(),
+                    }
+                }
+                PastDef::Annotation { src, .. } | PastDef::Import(src) => src.clone(),
+            })
+            .collect();
+
+        ReplAction::Message(lines.join("\n"))
+    }
+
+    /// `:load` - bring the defs and imports of a module into this session
+    fn load<'a>(&mut self, arena: &'a Bump, filename: &str) -> ReplAction<'a> {
+        if filename.is_empty() {
+            return ReplAction::Message("Usage: :load <file.roc>".to_string());
+        }
+
+        let filename = PathBuf::from(filename);
+        let src = match fs::read_to_string(&filename) {
+            Ok(src) => arena.alloc_str(&src),
+            Err(err) => {
+                return ReplAction::FileProblem {
+                    filename,
+                    error: err.kind(),
+                }
+            }
+        };
+
+        let opt_defs = parse_header(arena, State::new(src.as_bytes()))
+            .ok()
+            .and_then(|(_, state)| parse_module_defs(arena, state, Defs::default()).ok());
+        let Some(defs) = opt_defs else {
+            return ReplAction::Message(format!(
+                "I couldn't parse {}. Run `roc check` on it to see the problem.",
+                filename.display()
+            ));
+        };
+
+        // The file's imports are resolved next to it, like they would be if it were compiled
+        let file_dir = match filename.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let mut loaded = Vec::new();
+        let mut imports_files = false;
+        let mut skipped = 0;
+
+        for def in defs.loc_defs() {
+            match def {
+                Ok(td) => {
+                    let (TypeDef::Alias { header, .. }
+                    | TypeDef::Opaque { header, .. }
+                    | TypeDef::Ability { header, .. }) = td.value;
+                    let ident = header.name.value;
+
+                    loaded.push(PastDef::Def {
+                        ident: ident.trim_end().to_string(),
+                        src: src[td.byte_range()].to_string(),
+                    });
+                }
+                Err(vd) => match vd.value {
+                    ValueDef::Annotation(
+                        Loc {
+                            value: Pattern::Identifier { ident },
+                            ..
+                        },
+                        _,
+                    ) => {
+                        loaded.push(PastDef::Annotation {
+                            ident: ident.trim_end().to_string(),
+                            src: src[vd.byte_range()].to_string(),
+                        });
+                    }
+                    ValueDef::Body(
+                        Loc {
+                            value: Pattern::Identifier { ident },
+                            ..
+                        },
+                        _,
+                    )
+                    | ValueDef::AnnotatedBody {
+                        body_pattern:
+                            Loc {
+                                value: Pattern::Identifier { ident },
+                                ..
+                            },
+                        ..
+                    } => {
+                        loaded.push(PastDef::Def {
+                            ident: ident.trim_end().to_string(),
+                            src: src[vd.byte_range()].to_string(),
+                        });
+                    }
+                    ValueDef::ModuleImport(import) if import.name.value.package.is_none() => {
+                        let mut import_filename = file_dir.to_path_buf();
+
+                        for part in import.name.value.name.parts() {
+                            import_filename.push(part);
+                        }
+
+                        import_filename.set_extension("roc");
+
+                        if let Err(err) = fs::metadata(&import_filename) {
+                            return ReplAction::FileProblem {
+                                filename: import_filename,
+                                error: err.kind(),
+                            };
+                        }
+
+                        imports_files = true;
+                        loaded.push(PastDef::Import(src[vd.byte_range()].to_string()));
+                    }
+                    ValueDef::ModuleImport(import)
+                        if import.name.value.package == Some(REPL_PLATFORM_SHORTHAND)
+                            && self.imports_from_platform(import.name.value.name.as_str()) =>
+                    {
+                        loaded.push(PastDef::Import(src[vd.byte_range()].to_string()));
+                    }
+                    ValueDef::IngestedFileImport(_) => {
+                        imports_files = true;
+                        loaded.push(PastDef::Import(src[vd.byte_range()].to_string()));
+                    }
+                    _ => {
+                        // Expects, destructures and imports from packages aren't supported in the repl
+                        skipped += 1;
+                    }
+                },
+            }
+        }
+
+        if imports_files {
+            match &self.imports_dir {
+                Some(imports_dir) if imports_dir != file_dir => {
+                    return ReplAction::Message(format!(
+                        "{} imports files from {}, but this session already imports them from {}, and the repl can only import from one dir. Use :reset to start over.",
+                        filename.display(),
+                        file_dir.display(),
+                        imports_dir.display()
+                    ));
+                }
+                _ => self.imports_dir = Some(file_dir.to_path_buf()),
+            }
+        }
+
+        let count = loaded.len();
+
+        for past_def in loaded {
+            match past_def {
+                PastDef::Def { ident, src } => self.add_past_def(ident, src),
+                PastDef::Annotation { ident, src } => self.add_past_annotation(ident, src),
+                PastDef::Import(src) => self.past_defs.push(PastDef::Import(src)),
+            }
+        }
+
+        let mut message = format!(
+            "Loaded {count} defs and imports from {}.",
+            filename.display()
+        );
+
+        if skipped > 0 {
+            message.push_str(&format!(
+                " Skipped {skipped} that the repl doesn't support, like expects and package imports."
+            ));
+        }
+
+        ReplAction::Message(message)
+    }
+
+    /// `:save` - write the defs and imports in this session to a file, as a module
+    fn save<'a>(&self, filename: &str) -> ReplAction<'a> {
+        if filename.is_empty() {
+            return ReplAction::Message("Usage: :save <file.roc>".to_string());
+        }
+
+        let filename = Path::new(filename);
+        let latest_defs = latest_defs(&self.past_defs);
+        let mut exposes = Vec::new();
+        let mut body = String::new();
+
+        for past_def in latest_defs.iter() {
+            let src = match past_def {
+                PastDef::Def { ident, src } | PastDef::Annotation { ident, src } => {
+                    exposes.push(ident.as_str());
+                    src
+                }
+                PastDef::Import(src) => src,
+            };
+
+            body.push_str("\n\n");
+            body.push_str(src);
+        }
+
+        let module_src = format!("module [{}]{body}\n", exposes.join(", "));
+
+        match fs::write(filename, module_src) {
+            Ok(()) => ReplAction::Message(format!(
+                "Saved {} defs and imports to {}.",
+                latest_defs.len(),
+                filename.display()
+            )),
+            Err(err) => ReplAction::FileProblem {
+                filename: filename.to_path_buf(),
+                error: err.kind(),
+            },
+        }
+    }
+
+    /// Whether the REPL has a platform, and it exposes the module `module_name`
+    fn imports_from_platform(&self, module_name: &str) -> bool {
+        self.platform
+            .as_ref()
+            .is_some_and(|platform| platform.exposes_module(module_name))
+    }
+
+    /// The dir the imports of files are resolved in
+    fn imports_dir(&self) -> &Path {
+        self.imports_dir.as_deref().unwrap_or(Path::new("."))
+    }
+
+    fn add_file_import(&mut self, src: String) {
+        self.imports_dir.get_or_insert_with(|| PathBuf::from("."));
+
+        self.past_defs.push(PastDef::Import(src));
+    }
+
     fn add_past_def(&mut self, ident: String, src: String) {
         let existing_idents = &mut self.past_def_idents;
 
         existing_idents.insert(ident.clone());
 
+        // A body right after its standalone annotation goes together with it, the way the two
+        // would in a module, so the annotation isn't lost when a later def replaces the earlier.
+        let src = match self.past_defs.last() {
+            Some(PastDef::Annotation {
+                ident: annotated,
+                src: annotation,
+            }) if *annotated == ident => {
+                let src = format!("{annotation}\n{src}");
+                self.past_defs.pop();
+                src
+            }
+            _ => src,
+        };
+
         self.past_defs.push(PastDef::Def { ident, src });
     }
+
+    fn add_past_annotation(&mut self, ident: String, src: String) {
+        self.past_def_idents.insert(ident.clone());
+
+        self.past_defs.push(PastDef::Annotation { ident, src });
+    }
+}
+
+/// The past defs that haven't been redefined since, and all the imports
+fn latest_defs(past_defs: &[PastDef]) -> Vec<&PastDef> {
+    let mut seen_idents = MutSet::default();
+    let mut latest_defs: Vec<&PastDef> = past_defs
+        .iter()
+        .rev()
+        .filter(|past_def| match past_def {
+            PastDef::Def { ident, .. } | PastDef::Annotation { ident, .. } => {
+                seen_idents.insert(ident.as_str())
+            }
+            PastDef::Import(_) => true,
+        })
+        .collect();
+
+    latest_defs.reverse();
+
+    latest_defs
+}
+
+/// The latest past defs and imports, for the expr being evaluated to use. `redefined` is the
+/// name of a def the expr defines again, if it does.
+fn past_defs_for<'a>(
+    past_defs: &'a [PastDef],
+    imports_dir: Option<&'a Path>,
+    redefined: Option<&'a str>,
+) -> PastDefs<'a> {
+    let mut defs = Vec::new();
+    let mut imports = Vec::new();
+
+    for past_def in latest_defs(past_defs) {
+        match past_def {
+            PastDef::Def { ident, src } | PastDef::Annotation { ident, src } => {
+                defs.push((ident.as_str(), src.as_str()))
+            }
+            PastDef::Import(src) => imports.push(src.as_str()),
+        }
+    }
+
+    PastDefs {
+        defs,
+        imports,
+        src_dir: imports_dir.unwrap_or(Path::new(".")),
+        redefined,
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -292,6 +700,11 @@ pub enum ParseOutcome<'a> {
     Empty,
     Help,
     Exit,
+    Type(&'a str),
+    Load(&'a str),
+    Save(&'a str),
+    Defs,
+    Reset,
 }
 
 /// Special case some syntax errors to allow for multi-line inputs
@@ -315,7 +728,13 @@ pub fn parse_src<'a>(arena: &'a Bump, line: &'a str) -> ParseOutcome<'a> {
         // If you really need to evaluate `exit` for some reason,
         // you can do `foo = exit` and then evaluate `foo` instead.
         ":exit" | ":quit" | ":q" | "exit" | "quit" | "exit()" | "quit()" => ParseOutcome::Exit,
+        ":defs" => ParseOutcome::Defs,
+        ":reset" => ParseOutcome::Reset,
         _ => {
+            if let Some(outcome) = parse_meta_command(line) {
+                return outcome;
+            }
+
             let src_bytes = line.as_bytes();
 
             match parse_repl_defs_and_optional_expr(arena, State::new(src_bytes)) {
@@ -331,3 +750,17 @@ pub fn parse_src<'a>(arena: &'a Bump, line: &'a str) -> ParseOutcome<'a> {
         }
     }
 }
+
+/// Meta-commands that take the rest of the line as their argument, like `:type 1 + 1`
+fn parse_meta_command(line: &str) -> Option<ParseOutcome<'_>> {
+    let line = line.trim();
+    let (command, argument) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let argument = argument.trim();
+
+    match command.to_lowercase().as_str() {
+        ":type" | ":t" => Some(ParseOutcome::Type(argument)),
+        ":load" => Some(ParseOutcome::Load(argument)),
+        ":save" => Some(ParseOutcome::Save(argument)),
+        _ => None,
+    }
+}
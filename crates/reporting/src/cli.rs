@@ -3,10 +3,12 @@ use std::path::PathBuf;
 use roc_collections::MutMap;
 use roc_module::symbol::{Interns, ModuleId};
 use roc_problem::can::Problem;
-use roc_region::all::LineInfo;
+use roc_problem::Severity;
+use roc_region::all::{LineColumnRegion, LineInfo};
 use roc_solve_problem::TypeError;
 
-use crate::report::ANSI_STYLE_CODES;
+use crate::error::codes;
+use crate::report::{Fix, Palette, RenderTarget, Report, RocDocAllocator, ANSI_STYLE_CODES};
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct Problems {
@@ -60,13 +62,137 @@ impl Problems {
     }
 }
 
+/// A fix to apply to the file a report was about
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileFix {
+    pub filename: PathBuf,
+    pub fix: Fix,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WarningLevel {
+    /// Don't report it at all
+    Allow,
+    Warn,
+    /// Report it as an error
+    Deny,
+}
+
+/// How to treat each kind of warning, for the kinds that aren't just reported as warnings.
+///
+/// These come from `--allow` and `--deny` on the command line, and a module can override them
+/// for itself with directives in comments, like
+///
+/// ```text
+/// # roc: allow unused-definition
+/// # roc: deny unused-import, unused-argument
+/// ```
+///
+/// A kind is either its code, like `E2076`, or its name, like `unused-import`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WarningLevels {
+    by_code: MutMap<&'static str, WarningLevel>,
+}
+
+impl WarningLevels {
+    /// Returns the kind back as an error if it's not one we know.
+    pub fn set<'k>(&mut self, kind: &'k str, level: WarningLevel) -> Result<(), &'k str> {
+        let error_code = codes::lookup_kind(kind).ok_or(kind)?;
+
+        self.by_code.insert(error_code.code, level);
+
+        Ok(())
+    }
+
+    /// These levels with the directives in a module's source applied on top. Directives with
+    /// kinds we don't know are ignored, and those kinds are returned so they can be reported.
+    pub fn with_directives<'s>(&self, src: &'s str) -> (Self, Vec<&'s str>) {
+        let mut levels = self.clone();
+        let mut unknown = Vec::new();
+
+        for line in src.lines() {
+            let Some(directive) = line
+                .trim_start()
+                .strip_prefix('#')
+                .and_then(|comment| comment.trim_start().strip_prefix("roc:"))
+            else {
+                continue;
+            };
+
+            let mut words = directive.split(|c: char| c == ',' || c.is_whitespace());
+
+            let level = match words.find(|word| !word.is_empty()) {
+                Some("allow") => WarningLevel::Allow,
+                Some("warn") => WarningLevel::Warn,
+                Some("deny") => WarningLevel::Deny,
+                _ => continue,
+            };
+
+            for kind in words.filter(|word| !word.is_empty()) {
+                if let Err(kind) = levels.set(kind, level) {
+                    unknown.push(kind);
+                }
+            }
+        }
+
+        (levels, unknown)
+    }
+
+    /// Makes the report an error if its kind of warning is denied. Returns false if it's allowed,
+    /// and so shouldn't be reported.
+    pub fn apply(&self, report: &mut Report) -> bool {
+        if report.severity != Severity::Warning {
+            return true;
+        }
+
+        let level = report
+            .code()
+            .and_then(|code| self.by_code.get(code).copied())
+            .unwrap_or(WarningLevel::Warn);
+
+        match level {
+            WarningLevel::Allow => false,
+            WarningLevel::Warn => true,
+            WarningLevel::Deny => {
+                report.severity = Severity::RuntimeError;
+
+                true
+            }
+        }
+    }
+}
+
 pub fn report_problems(
     sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
     interns: &Interns,
     can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    render: RenderTarget,
+    warning_levels: &WarningLevels,
 ) -> Problems {
-    use crate::report::{can_problem, type_problem, Report, RocDocAllocator, DEFAULT_PALETTE};
+    let (problems, _fixes) = report_problems_with_fixes(
+        sources,
+        interns,
+        can_problems,
+        type_problems,
+        render,
+        warning_levels,
+    );
+
+    problems
+}
+
+/// Like `report_problems`, but also returns the fix for every report that suggested exactly one,
+/// since that's the only one it makes sense to apply without asking which.
+pub fn report_problems_with_fixes(
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &Interns,
+    can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    render: RenderTarget,
+    warning_levels: &WarningLevels,
+) -> (Problems, Vec<FileFix>) {
+    use crate::report::{can_problem, type_problem, DEFAULT_PALETTE};
     use roc_problem::Severity::*;
 
     let palette = DEFAULT_PALETTE;
@@ -85,6 +211,8 @@ pub fn report_problems(
     let mut warnings = Vec::with_capacity(total_problems);
     let mut errors = Vec::with_capacity(total_problems);
     let mut fatally_errored = false;
+    let mut fixes = Vec::new();
+    let mut allowed = 0;
 
     for (home, (module_path, src)) in sources.iter() {
         let mut src_lines: Vec<&str> = Vec::new();
@@ -96,14 +224,31 @@ pub fn report_problems(
         // Report parsing and canonicalization problems
         let alloc = RocDocAllocator::new(&src_lines, *home, interns);
 
+        let (warning_levels, unknown_kinds) = warning_levels.with_directives(src);
+
+        for kind in unknown_kinds {
+            eprintln!(
+                "Ignoring `# roc:` directive for unknown warning `{kind}` in {}",
+                module_path.display()
+            );
+        }
+
         let problems = type_problems.remove(home).unwrap_or_default();
 
         for problem in problems {
-            if let Some(report) = type_problem(&alloc, &lines, module_path.clone(), problem) {
+            let opt_region = problem.region().map(|region| lines.convert_region(region));
+
+            if let Some(mut report) = type_problem(&alloc, &lines, module_path.clone(), problem) {
+                if !warning_levels.apply(&mut report) {
+                    allowed += 1;
+                    continue;
+                }
+
                 let severity = report.severity;
+                fixes.extend(only_fix(&report));
                 let mut buf = String::new();
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+                render_report(report, render, opt_region, &mut buf, &alloc, &palette);
 
                 match severity {
                     Warning => {
@@ -137,11 +282,19 @@ pub fn report_problems(
         ordered.extend(shadowing_errs);
 
         for problem in ordered.into_iter() {
-            let report = can_problem(&alloc, &lines, module_path.clone(), problem);
+            let opt_region = problem.region().map(|region| lines.convert_region(region));
+            let mut report = can_problem(&alloc, &lines, module_path.clone(), problem);
+
+            if !warning_levels.apply(&mut report) {
+                allowed += 1;
+                continue;
+            }
+
             let severity = report.severity;
+            fixes.extend(only_fix(&report));
             let mut buf = String::new();
 
-            report.render_color_terminal(&mut buf, &alloc, &palette);
+            render_report(report, render, opt_region, &mut buf, &alloc, &palette);
 
             match severity {
                 Warning => {
@@ -159,12 +312,34 @@ pub fn report_problems(
     }
 
     debug_assert!(can_problems.is_empty() && type_problems.is_empty(), "After reporting problems, there were {:?} can_problems and {:?} type_problems that could not be reported because they did not have corresponding entries in `sources`.", can_problems.len(), type_problems.len());
-    debug_assert_eq!(errors.len() + warnings.len(), total_problems);
+    debug_assert_eq!(errors.len() + warnings.len() + allowed, total_problems);
 
     let problems_reported;
 
-    // Only print warnings if there are no errors
-    if errors.is_empty() {
+    if let RenderTarget::Json = render {
+        // Tools reading these can filter by severity themselves, so give them everything,
+        // one report per line.
+        for report in errors.iter().chain(warnings.iter()) {
+            println!("{report}");
+        }
+
+        problems_reported = 0;
+    } else if let RenderTarget::Generic = render {
+        // Plain reports are for runs whose stdout is spoken for, like `roc test --format junit`,
+        // so they go to stderr, and without the colored rule below.
+        let reports = if errors.is_empty() {
+            &warnings
+        } else {
+            &errors
+        };
+
+        for report in reports.iter() {
+            eprintln!("\n{report}\n");
+        }
+
+        problems_reported = 0;
+    } else if errors.is_empty() {
+        // Only print warnings if there are no errors
         problems_reported = warnings.len();
 
         for warning in warnings.iter() {
@@ -188,9 +363,39 @@ pub fn report_problems(
         println!("{}\u{001B}[0m\n", Report::horizontal_rule(&palette));
     }
 
-    Problems {
+    let problems = Problems {
         fatally_errored,
         errors: errors.len(),
         warnings: warnings.len(),
+    };
+
+    (problems, fixes)
+}
+
+fn only_fix(report: &Report) -> Option<FileFix> {
+    match report.fixes.as_slice() {
+        [fix] => Some(FileFix {
+            filename: report.filename.clone(),
+            fix: fix.clone(),
+        }),
+        _ => None,
+    }
+}
+
+fn render_report<'b>(
+    report: Report<'b>,
+    render: RenderTarget,
+    opt_region: Option<LineColumnRegion>,
+    buf: &mut String,
+    alloc: &'b RocDocAllocator<'b>,
+    palette: &'b Palette,
+) {
+    match render {
+        RenderTarget::Json => report.render_json(buf, alloc, opt_region),
+        RenderTarget::Generic => report.render_ci(buf, alloc),
+        // These have always been rendered in color, whatever the load was configured with
+        RenderTarget::ColorTerminal | RenderTarget::LanguageServer => {
+            report.render_color_terminal(buf, alloc, palette)
+        }
     }
 }
@@ -7,68 +7,18 @@ use roc_problem::can::{
     ScopeModuleSource, ShadowKind,
 };
 use roc_problem::Severity;
-use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Loc, Region};
+use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Loc, Position, Region};
 use roc_types::types::{AliasKind, EarlyReturnKind};
 use std::path::PathBuf;
 
+use crate::error::codes::{self, ErrorCode};
 use crate::error::r#type::suggest;
-use crate::report::{to_file_problem_report, Annotation, Report, RocDocAllocator, RocDocBuilder};
+use crate::report::{
+    new_import_line, to_file_problem_report, Annotation, Fix, Report, RocDocAllocator,
+    RocDocBuilder,
+};
 use ven_pretty::{text, DocAllocator};
 
-const SYNTAX_PROBLEM: &str = "SYNTAX PROBLEM";
-const NAMING_PROBLEM: &str = "NAMING PROBLEM";
-const UNRECOGNIZED_NAME: &str = "UNRECOGNIZED NAME";
-const UNUSED_DEF: &str = "UNUSED DEFINITION";
-const UNUSED_IMPORT: &str = "UNUSED IMPORT";
-const IMPORT_NAME_CONFLICT: &str = "IMPORT NAME CONFLICT";
-const EXPLICIT_BUILTIN_IMPORT: &str = "EXPLICIT BUILTIN IMPORT";
-const UNUSED_ALIAS_PARAM: &str = "UNUSED TYPE ALIAS PARAMETER";
-const UNDECLARED_TYPE_VARIABLE: &str = "UNDECLARED TYPE VARIABLE";
-const WILDCARD_NOT_ALLOWED: &str = "WILDCARD NOT ALLOWED HERE";
-const UNDERSCORE_NOT_ALLOWED: &str = "UNDERSCORE NOT ALLOWED HERE";
-const UNUSED_ARG: &str = "UNUSED ARGUMENT";
-const MISSING_DEFINITION: &str = "MISSING DEFINITION";
-const DUPLICATE_FIELD_NAME: &str = "DUPLICATE FIELD NAME";
-const DUPLICATE_TAG_NAME: &str = "DUPLICATE TAG NAME";
-const INVALID_UNICODE: &str = "INVALID UNICODE";
-pub const CIRCULAR_DEF: &str = "CIRCULAR DEFINITION";
-const DUPLICATE_NAME: &str = "DUPLICATE NAME";
-const VALUE_NOT_EXPOSED: &str = "NOT EXPOSED";
-const MODULE_NOT_IMPORTED: &str = "MODULE NOT IMPORTED";
-const INGESTED_FILE_ERROR: &str = "INGESTED FILE ERROR";
-const NESTED_DATATYPE: &str = "NESTED DATATYPE";
-const CONFLICTING_NUMBER_SUFFIX: &str = "CONFLICTING NUMBER SUFFIX";
-const NUMBER_OVERFLOWS_SUFFIX: &str = "NUMBER OVERFLOWS SUFFIX";
-const NUMBER_UNDERFLOWS_SUFFIX: &str = "NUMBER UNDERFLOWS SUFFIX";
-const OPAQUE_NOT_DEFINED: &str = "OPAQUE TYPE NOT DEFINED";
-const OPAQUE_DECLARED_OUTSIDE_SCOPE: &str = "OPAQUE TYPE DECLARED OUTSIDE SCOPE";
-const OPAQUE_NOT_APPLIED: &str = "OPAQUE TYPE NOT APPLIED";
-const OPAQUE_OVER_APPLIED: &str = "OPAQUE TYPE APPLIED TO TOO MANY ARGS";
-const INVALID_EXTENSION_TYPE: &str = "INVALID_EXTENSION_TYPE";
-const ABILITY_HAS_TYPE_VARIABLES: &str = "ABILITY HAS TYPE VARIABLES";
-const IMPLEMENTS_CLAUSE_IS_NOT_AN_ABILITY: &str = "IMPLEMENTS CLAUSE IS NOT AN ABILITY";
-const ILLEGAL_IMPLEMENTS_CLAUSE: &str = "ILLEGAL IMPLEMENTS CLAUSE";
-const ABILITY_MEMBER_MISSING_IMPLEMENTS_CLAUSE: &str = "ABILITY MEMBER MISSING IMPLEMENTS CLAUSE";
-const ABILITY_MEMBER_BINDS_MULTIPLE_VARIABLES: &str = "ABILITY MEMBER BINDS MULTIPLE VARIABLES";
-const ABILITY_NOT_ON_TOPLEVEL: &str = "ABILITY NOT ON TOP-LEVEL";
-const SPECIALIZATION_NOT_ON_TOPLEVEL: &str = "SPECIALIZATION NOT ON TOP-LEVEL";
-const ABILITY_USED_AS_TYPE: &str = "ABILITY USED AS TYPE";
-const ILLEGAL_DERIVE: &str = "ILLEGAL DERIVE";
-const IMPLEMENTATION_NOT_FOUND: &str = "IMPLEMENTATION NOT FOUND";
-const NOT_AN_ABILITY_MEMBER: &str = "NOT AN ABILITY MEMBER";
-const NOT_AN_ABILITY: &str = "NOT AN ABILITY";
-const OPTIONAL_ABILITY_IMPLEMENTATION: &str = "OPTIONAL ABILITY IMPLEMENTATION";
-const QUALIFIED_ABILITY_IMPLEMENTATION: &str = "QUALIFIED ABILITY IMPLEMENTATION";
-const ABILITY_IMPLEMENTATION_NOT_IDENTIFIER: &str = "ABILITY IMPLEMENTATION NOT IDENTIFIER";
-const DUPLICATE_IMPLEMENTATION: &str = "DUPLICATE IMPLEMENTATION";
-const UNNECESSARY_IMPLEMENTATIONS: &str = "UNNECESSARY IMPLEMENTATIONS";
-const INCOMPLETE_ABILITY_IMPLEMENTATION: &str = "INCOMPLETE ABILITY IMPLEMENTATION";
-const MISSING_EXCLAMATION: &str = "MISSING EXCLAMATION";
-const UNNECESSARY_EXCLAMATION: &str = "UNNECESSARY EXCLAMATION";
-const EMPTY_TUPLE_TYPE: &str = "EMPTY TUPLE TYPE";
-const UNBOUND_TYPE_VARS_IN_AS: &str = "UNBOUND TYPE VARIABLES IN AS";
-const INTERPOLATED_STRING_NOT_ALLOWED: &str = "INTERPOLATED STRING NOT ALLOWED";
-
 pub fn can_problem<'b>(
     alloc: &'b RocDocAllocator<'b>,
     lines: &LineInfo,
@@ -76,8 +26,9 @@ pub fn can_problem<'b>(
     problem: Problem,
 ) -> Report<'b> {
     let doc;
-    let title;
+    let code;
     let severity = problem.severity();
+    let fixes = can_problem_fixes(alloc, lines, &problem);
 
     match problem {
         Problem::UnusedDef(symbol, region) => {
@@ -95,7 +46,7 @@ pub fn can_problem<'b>(
                     .append(alloc.reflow(line)),
             ]);
 
-            title = UNUSED_DEF.to_string();
+            code = codes::UNUSED_DEFINITION;
         }
         Problem::UnusedImport(symbol, region) => {
             doc = alloc.stack([
@@ -111,7 +62,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = UNUSED_IMPORT.to_string();
+            code = codes::UNUSED_IMPORT;
         }
         Problem::UnusedModuleImport(module_id, region) => {
             doc = alloc.stack([
@@ -127,7 +78,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = UNUSED_IMPORT.to_string();
+            code = codes::UNUSED_IMPORT;
         }
         Problem::ImportNameConflict {
             name,
@@ -187,7 +138,7 @@ pub fn can_problem<'b>(
                     ])
                 },
             ]);
-            title = IMPORT_NAME_CONFLICT.to_string();
+            code = codes::IMPORT_NAME_CONFLICT;
         }
 
         Problem::ExplicitBuiltinImport(module_id, region) => {
@@ -202,7 +153,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("Tip: Learn more about builtins in the tutorial:\n<https://www.roc-lang.org/tutorial#builtin-modules>"),
             ]);
 
-            title = EXPLICIT_BUILTIN_IMPORT.to_string();
+            code = codes::EXPLICIT_BUILTIN_IMPORT;
         }
 
         Problem::ExplicitBuiltinTypeImport(symbol, region) => {
@@ -220,7 +171,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("Tip: Learn more about builtins in the tutorial:\n<https://www.roc-lang.org/tutorial#builtin-modules>"),
             ]);
 
-            title = EXPLICIT_BUILTIN_IMPORT.to_string();
+            code = codes::EXPLICIT_BUILTIN_IMPORT;
         }
 
         Problem::ImportShadowsSymbol {
@@ -247,7 +198,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = DUPLICATE_NAME.to_string();
+            code = codes::DUPLICATE_NAME;
         }
 
         Problem::DefsOnlyUsedInRecursion(1, region) => {
@@ -259,7 +210,7 @@ pub fn can_problem<'b>(
                 ),
             ]);
 
-            title = "DEFINITION ONLY USED IN RECURSION".to_string();
+            code = codes::DEFINITION_ONLY_USED_IN_RECURSION;
         }
         Problem::DefsOnlyUsedInRecursion(n, region) => {
             doc = alloc.stack([
@@ -274,7 +225,7 @@ pub fn can_problem<'b>(
                 ),
             ]);
 
-            title = "DEFINITIONS ONLY USED IN RECURSION".to_string();
+            code = codes::DEFINITIONS_ONLY_USED_IN_RECURSION;
         }
         Problem::ExposedButNotDefined(symbol) => {
             doc = alloc.stack([
@@ -289,7 +240,7 @@ pub fn can_problem<'b>(
                     .append(alloc.reflow(".")),
             ]);
 
-            title = MISSING_DEFINITION.to_string();
+            code = codes::MISSING_DEFINITION;
         }
         Problem::UnusedArgument(closure_symbol, is_anonymous, argument_symbol, region) => {
             let line = "\". Adding an underscore at the start of a variable name is a way of saying that the variable is not used.";
@@ -323,7 +274,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = UNUSED_ARG.to_string();
+            code = codes::UNUSED_ARGUMENT;
         }
         Problem::UnusedBranchDef(symbol, region) => {
             doc = alloc.stack([
@@ -343,7 +294,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = UNUSED_DEF.to_string();
+            code = codes::UNUSED_DEFINITION;
         }
         Problem::PrecedenceProblem(BothNonAssociative(region, left_bin_op, right_bin_op)) => {
             doc = alloc.stack([
@@ -371,7 +322,7 @@ pub fn can_problem<'b>(
                 alloc.region(lines.convert_region(region), severity),
             ]);
 
-            title = SYNTAX_PROBLEM.to_string();
+            code = codes::NON_ASSOCIATIVE_OPERATORS;
         }
         Problem::UnsupportedPattern(BadPattern::Unsupported(pattern_type), region) => {
             use roc_parse::pattern::PatternType::*;
@@ -402,18 +353,14 @@ pub fn can_problem<'b>(
                 alloc.concat(suggestion),
             ]);
 
-            title = SYNTAX_PROBLEM.to_string();
+            code = codes::UNSUPPORTED_PATTERN;
         }
         Problem::Shadowing {
             original_region,
             shadow,
             kind,
         } => {
-            let (res_title, res_doc) =
-                report_shadowing(alloc, lines, original_region, shadow, kind, severity);
-
-            doc = res_doc;
-            title = res_title.to_string();
+            (code, doc) = report_shadowing(alloc, lines, original_region, shadow, kind, severity);
         }
         Problem::CyclicAlias(symbol, region, others, alias_kind) => {
             let answer = crate::error::r#type::cyclic_alias(
@@ -421,7 +368,7 @@ pub fn can_problem<'b>(
             );
 
             doc = answer.0;
-            title = answer.1;
+            code = answer.1;
         }
         Problem::PhantomTypeArgument {
             typ: alias,
@@ -448,7 +395,7 @@ pub fn can_problem<'b>(
                 )),
             ]);
 
-            title = UNUSED_ALIAS_PARAM.to_string();
+            code = codes::UNUSED_TYPE_ALIAS_PARAMETER;
         }
         Problem::WildcardNotAllowed {
             typ: alias,
@@ -488,7 +435,7 @@ pub fn can_problem<'b>(
             ]));
             doc = alloc.stack(stack);
 
-            title = WILDCARD_NOT_ALLOWED.to_string();
+            code = codes::WILDCARD_NOT_ALLOWED_HERE;
         }
         Problem::UnderscoreNotAllowed {
             typ: alias,
@@ -529,7 +476,7 @@ pub fn can_problem<'b>(
             ]));
             doc = alloc.stack(stack);
 
-            title = UNDERSCORE_NOT_ALLOWED.to_string();
+            code = codes::UNDERSCORE_NOT_ALLOWED_HERE;
         }
         Problem::UndeclaredTypeVar {
             typ: alias,
@@ -575,11 +522,11 @@ pub fn can_problem<'b>(
             ])));
             doc = alloc.stack(stack);
 
-            title = UNDECLARED_TYPE_VARIABLE.to_string();
+            code = codes::UNDECLARED_TYPE_VARIABLE;
         }
         Problem::BadRecursion(entries) => {
             doc = to_circular_def_doc(alloc, lines, &entries, severity);
-            title = CIRCULAR_DEF.to_string();
+            code = codes::CIRCULAR_DEFINITION;
         }
         Problem::DuplicateRecordFieldValue {
             field_name,
@@ -613,7 +560,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = DUPLICATE_FIELD_NAME.to_string();
+            code = codes::DUPLICATE_FIELD_NAME;
         }
         Problem::InvalidOptionalValue {
             field_name,
@@ -675,7 +622,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = DUPLICATE_FIELD_NAME.to_string();
+            code = codes::DUPLICATE_FIELD_NAME;
         }
         Problem::DuplicateTag {
             tag_name,
@@ -709,7 +656,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = DUPLICATE_TAG_NAME.to_string();
+            code = codes::DUPLICATE_TAG_NAME;
         }
         Problem::SignatureDefMismatch {
             ref annotation_pattern,
@@ -726,7 +673,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("Is it a typo? If not, put either a newline or comment between them."),
             ]);
 
-            title = NAMING_PROBLEM.to_string();
+            code = codes::ANNOTATION_NAME_MISMATCH;
         }
         Problem::InvalidAliasRigid {
             alias_name: type_name,
@@ -748,7 +695,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = SYNTAX_PROBLEM.to_string();
+            code = codes::INVALID_ALIAS_VARIABLE;
         }
         Problem::InvalidHexadecimal(region) => {
             doc = alloc.stack([
@@ -764,7 +711,7 @@ pub fn can_problem<'b>(
                 alloc.reflow(r"Learn more about working with unicode in roc at TODO"),
             ]);
 
-            title = INVALID_UNICODE.to_string();
+            code = codes::INVALID_UNICODE;
         }
         Problem::InvalidUnicodeCodePt(region) => {
             doc = alloc.stack([
@@ -773,7 +720,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("Learn more about working with unicode in roc at TODO"),
             ]);
 
-            title = INVALID_UNICODE.to_string();
+            code = codes::INVALID_UNICODE;
         }
         Problem::InvalidInterpolation(region) => {
             doc = alloc.stack([
@@ -783,13 +730,13 @@ pub fn can_problem<'b>(
                 alloc.reflow(r"You can learn more about string interpolation at <https://www.roc-lang.org/tutorial#string-interpolation>"),
             ]);
 
-            title = SYNTAX_PROBLEM.to_string();
+            code = codes::INVALID_INTERPOLATION;
         }
         Problem::RuntimeError(runtime_error) => {
             let answer = pretty_runtime_error(alloc, lines, runtime_error);
 
             doc = answer.0;
-            title = answer.1.to_string();
+            code = answer.1;
         }
         Problem::NestedDatatype {
             alias,
@@ -816,7 +763,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = NESTED_DATATYPE.to_string();
+            code = codes::NESTED_DATATYPE;
         }
 
         Problem::InvalidExtensionType { region, kind } => {
@@ -843,7 +790,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = INVALID_EXTENSION_TYPE.to_string();
+            code = codes::INVALID_EXTENSION_TYPE;
         }
 
         Problem::AbilityHasTypeVariables {
@@ -861,7 +808,7 @@ pub fn can_problem<'b>(
                     "Abilities cannot depend on type variables, but their member values can!",
                 ),
             ]);
-            title = ABILITY_HAS_TYPE_VARIABLES.to_string();
+            code = codes::ABILITY_HAS_TYPE_VARIABLES;
         }
 
         Problem::ImplementsClauseIsNotAbility {
@@ -873,7 +820,7 @@ pub fn can_problem<'b>(
                 ),
                 alloc.region(lines.convert_region(clause_region), severity),
             ]);
-            title = IMPLEMENTS_CLAUSE_IS_NOT_AN_ABILITY.to_string();
+            code = codes::IMPLEMENTS_CLAUSE_IS_NOT_AN_ABILITY;
         }
 
         Problem::IllegalImplementsClause { region } => {
@@ -891,7 +838,7 @@ pub fn can_problem<'b>(
                     ),
                 ]),
             ]);
-            title = ILLEGAL_IMPLEMENTS_CLAUSE.to_string();
+            code = codes::ILLEGAL_IMPLEMENTS_CLAUSE;
         }
 
         Problem::DuplicateImplementsAbility { ability, region } => {
@@ -908,7 +855,7 @@ pub fn can_problem<'b>(
                     alloc.reflow(" clause!"),
                 ]),
             ]);
-            title = "DUPLICATE BOUND ABILITY".to_string();
+            code = codes::DUPLICATE_BOUND_ABILITY;
         }
 
         Problem::AbilityMemberMissingImplementsClause {
@@ -942,7 +889,7 @@ pub fn can_problem<'b>(
                 alloc.concat([alloc
                     .reflow("Otherwise, the function does not need to be part of the ability!")]),
             ]);
-            title = ABILITY_MEMBER_MISSING_IMPLEMENTS_CLAUSE.to_string();
+            code = codes::ABILITY_MEMBER_MISSING_IMPLEMENTS_CLAUSE;
         }
 
         Problem::AbilityMemberMultipleBoundVars {
@@ -969,7 +916,7 @@ pub fn can_problem<'b>(
                     alloc.reflow("?"),
                 ])
             ]);
-            title = ABILITY_MEMBER_BINDS_MULTIPLE_VARIABLES.to_string();
+            code = codes::ABILITY_MEMBER_BINDS_MULTIPLE_VARIABLES;
         }
 
         Problem::AbilityNotOnToplevel { region } => {
@@ -980,7 +927,7 @@ pub fn can_problem<'b>(
                 alloc.region(lines.convert_region(region), severity),
                 alloc.reflow("Abilities can only be defined on the top-level of a Roc module."),
             ]);
-            title = ABILITY_NOT_ON_TOPLEVEL.to_string();
+            code = codes::ABILITY_NOT_ON_TOP_LEVEL;
         }
 
         Problem::AbilityUsedAsType(suggested_var_name, ability, region) => {
@@ -1007,7 +954,7 @@ pub fn can_problem<'b>(
                     alloc.symbol_unqualified(ability),
                 ])),
             ]);
-            title = ABILITY_USED_AS_TYPE.to_string();
+            code = codes::ABILITY_USED_AS_TYPE;
         }
         Problem::NestedSpecialization(member, region) => {
             doc = alloc.stack([
@@ -1019,7 +966,7 @@ pub fn can_problem<'b>(
                 alloc.region(lines.convert_region(region), severity),
                 alloc.reflow("Specializations can only be defined on the top-level of a module."),
             ]);
-            title = SPECIALIZATION_NOT_ON_TOPLEVEL.to_string();
+            code = codes::SPECIALIZATION_NOT_ON_TOP_LEVEL;
         }
         Problem::IllegalDerivedAbility(region) => {
             doc = alloc.stack([
@@ -1030,7 +977,7 @@ pub fn can_problem<'b>(
                     .note("The builtin abilities are ")
                     .append(list_builtin_abilities(alloc)),
             ]);
-            title = ILLEGAL_DERIVE.to_string();
+            code = codes::ILLEGAL_DERIVE;
         }
         Problem::NotAnAbility(region) => {
             doc = alloc.stack([
@@ -1038,7 +985,7 @@ pub fn can_problem<'b>(
                 alloc.region(lines.convert_region(region), severity),
                 alloc.reflow("Only abilities can be implemented."),
             ]);
-            title = NOT_AN_ABILITY.to_string();
+            code = codes::NOT_AN_ABILITY;
         }
         Problem::NotAnAbilityMember {
             ability,
@@ -1052,7 +999,7 @@ pub fn can_problem<'b>(
                 alloc.region(lines.convert_region(region), severity),
                 alloc.reflow("Only implementations for members an ability has can be specified in this location.")
             ]);
-            title = NOT_AN_ABILITY_MEMBER.to_string();
+            code = codes::NOT_AN_ABILITY_MEMBER;
         }
         Problem::ImplementationNotFound { member, region } => {
             let member_str = member.as_str(alloc.interns);
@@ -1063,7 +1010,7 @@ pub fn can_problem<'b>(
                 alloc.region(lines.convert_region(region), severity),
                 alloc.tip().append(alloc.concat([alloc.reflow("consider adding a value of name "), alloc.symbol_unqualified(member), alloc.reflow(" in this scope, or using another variable that implements this ability member, like "), alloc.type_str(&format!("{{ {member_str}: my{member_str} }}"))]))
             ]);
-            title = IMPLEMENTATION_NOT_FOUND.to_string();
+            code = codes::IMPLEMENTATION_NOT_FOUND;
         }
         Problem::OptionalAbilityImpl { ability, region } => {
             let hint = if ability.is_builtin() {
@@ -1081,7 +1028,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("Custom implementations must be supplied fully."),
                 hint,
             ]);
-            title = OPTIONAL_ABILITY_IMPLEMENTATION.to_string();
+            code = codes::OPTIONAL_ABILITY_IMPLEMENTATION;
         }
         Problem::QualifiedAbilityImpl { region } => {
             doc = alloc.stack([
@@ -1091,7 +1038,7 @@ pub fn can_problem<'b>(
                     "Custom implementations must be defined in the local scope, and unqualified.",
                 ),
             ]);
-            title = QUALIFIED_ABILITY_IMPLEMENTATION.to_string();
+            code = codes::QUALIFIED_ABILITY_IMPLEMENTATION;
         }
         Problem::AbilityImplNotIdent { region } => {
             doc = alloc.stack([
@@ -1102,7 +1049,7 @@ pub fn can_problem<'b>(
                 ),
                 alloc.tip().append(alloc.reflow("consider defining this expression as a variable."))
             ]);
-            title = ABILITY_IMPLEMENTATION_NOT_IDENTIFIER.to_string();
+            code = codes::ABILITY_IMPLEMENTATION_NOT_IDENTIFIER;
         }
         Problem::DuplicateImpl {
             original,
@@ -1116,7 +1063,7 @@ pub fn can_problem<'b>(
                 alloc
                     .reflow("Only one custom implementation can be defined for an ability member."),
             ]);
-            title = DUPLICATE_IMPLEMENTATION.to_string();
+            code = codes::DUPLICATE_IMPLEMENTATION;
         }
         Problem::ImplementsNonRequired {
             region,
@@ -1140,7 +1087,7 @@ pub fn can_problem<'b>(
                     ),
                 ),
             ]);
-            title = UNNECESSARY_IMPLEMENTATIONS.to_string();
+            code = codes::UNNECESSARY_IMPLEMENTATIONS;
         }
         Problem::DoesNotImplementAbility {
             region,
@@ -1164,7 +1111,7 @@ pub fn can_problem<'b>(
                     ),
                 ),
             ]);
-            title = INCOMPLETE_ABILITY_IMPLEMENTATION.to_string();
+            code = codes::INCOMPLETE_ABILITY_IMPLEMENTATION;
         }
         Problem::NotBoundInAllPatterns {
             unbound_symbol,
@@ -1184,7 +1131,7 @@ pub fn can_problem<'b>(
                     alloc.reflow(" branch must be bound in all patterns of the branch. Otherwise, the program would crash when it tries to use an identifier that wasn't bound!"),
                 ]),
             ]);
-            title = "NAME NOT BOUND IN ALL PATTERNS".to_string();
+            code = codes::NAME_NOT_BOUND_IN_ALL_PATTERNS;
         }
         Problem::NoIdentifiersIntroduced(region) => {
             doc = alloc.stack([
@@ -1192,7 +1139,7 @@ pub fn can_problem<'b>(
                 alloc.region(lines.convert_region(region), severity),
                 alloc.reflow("If you don't need to use the value on the right-hand side of this assignment, consider removing the assignment. Since effects are not allowed at the top-level, assignments that don't introduce variables cannot affect a program's behavior"),
             ]);
-            title = "UNNECESSARY DEFINITION".to_string();
+            code = codes::UNNECESSARY_DEFINITION;
         }
         Problem::OverloadedSpecialization {
             ability_member,
@@ -1211,7 +1158,7 @@ pub fn can_problem<'b>(
                 ]),
                 alloc.reflow("Ability specializations can only provide implementations for one opaque type, since all opaque types are different!"),
             ]);
-            title = "OVERLOADED SPECIALIZATION".to_string();
+            code = codes::OVERLOADED_SPECIALIZATION;
         }
         Problem::UnnecessaryOutputWildcard { region } => {
             doc = alloc.stack([
@@ -1230,7 +1177,7 @@ pub fn can_problem<'b>(
                 ]),
                 alloc.reflow("You can safely remove this to make the code more concise without changing what it means."),
             ]);
-            title = "UNNECESSARY WILDCARD".to_string();
+            code = codes::UNNECESSARY_WILDCARD;
         }
         Problem::MultipleListRestPattern { region } => {
             doc = alloc.stack([
@@ -1242,7 +1189,7 @@ pub fn can_problem<'b>(
                     alloc.reflow(" pattern! Can you remove this additional one?"),
                 ]),
             ]);
-            title = "MULTIPLE LIST REST PATTERNS".to_string();
+            code = codes::MULTIPLE_LIST_REST_PATTERNS;
         }
         Problem::BadTypeArguments {
             symbol,
@@ -1277,10 +1224,10 @@ pub fn can_problem<'b>(
                 alloc.reflow("Are there missing parentheses?"),
             ]);
 
-            title = if type_got > alias_needs {
-                "TOO MANY TYPE ARGUMENTS".to_string()
+            code = if type_got > alias_needs {
+                codes::TOO_MANY_TYPE_ARGUMENTS
             } else {
-                "TOO FEW TYPE ARGUMENTS".to_string()
+                codes::TOO_FEW_TYPE_ARGUMENTS
             };
         }
         Problem::UnappliedCrash { region } => {
@@ -1294,7 +1241,7 @@ pub fn can_problem<'b>(
                     alloc.keyword("crash"), alloc.reflow(" can't be used as a value that's passed around, like functions can be - it must be applied immediately!"),
                 ])
             ]);
-            title = "UNAPPLIED CRASH".to_string();
+            code = codes::UNAPPLIED_CRASH;
         }
         Problem::OverAppliedCrash { region } => {
             doc = alloc.stack([
@@ -1309,7 +1256,7 @@ pub fn can_problem<'b>(
                     alloc.reflow(" must be given exactly one message to crash with."),
                 ]),
             ]);
-            title = "OVERAPPLIED CRASH".to_string();
+            code = codes::OVERAPPLIED_CRASH;
         }
         Problem::UnappliedDbg { region } => {
             doc = alloc.stack([
@@ -1322,7 +1269,7 @@ pub fn can_problem<'b>(
                     alloc.keyword("dbg"), alloc.reflow(" can't be used as a value that's passed around, like functions can be - it must be applied immediately!"),
                 ])
             ]);
-            title = "UNAPPLIED DBG".to_string();
+            code = codes::UNAPPLIED_DBG;
         }
         Problem::OverAppliedDbg { region } => {
             doc = alloc.stack([
@@ -1337,7 +1284,7 @@ pub fn can_problem<'b>(
                     alloc.reflow(" must be given exactly one value to print."),
                 ]),
             ]);
-            title = "OVERAPPLIED DBG".to_string();
+            code = codes::OVERAPPLIED_DBG;
         }
         Problem::UnderAppliedTry { region } => {
             doc = alloc.stack([
@@ -1352,12 +1299,12 @@ pub fn can_problem<'b>(
                     alloc.reflow(" must be given exactly one value to try."),
                 ]),
             ]);
-            title = "UNDERAPPLIED TRY".to_string();
+            code = codes::UNDERAPPLIED_TRY;
         }
         Problem::FileProblem { filename, error } => {
             let report = to_file_problem_report(alloc, filename, error);
             doc = report.doc;
-            title = report.title;
+            code = report.code.unwrap_or(codes::FILE_PROBLEM);
         }
 
         Problem::ReturnOutsideOfFunction {
@@ -1365,9 +1312,9 @@ pub fn can_problem<'b>(
             return_kind,
         } => {
             let return_keyword;
-            (title, return_keyword) = match return_kind {
-                EarlyReturnKind::Return => ("RETURN OUTSIDE OF FUNCTION".to_string(), "return"),
-                EarlyReturnKind::Try => ("TRY OUTSIDE OF FUNCTION".to_string(), "try"),
+            (code, return_keyword) = match return_kind {
+                EarlyReturnKind::Return => (codes::RETURN_OUTSIDE_OF_FUNCTION, "return"),
+                EarlyReturnKind::Try => (codes::TRY_OUTSIDE_OF_FUNCTION, "try"),
             };
 
             doc = alloc.stack([
@@ -1398,7 +1345,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = "UNREACHABLE CODE".to_string();
+            code = codes::UNREACHABLE_CODE;
         }
 
         Problem::ReturnAtEndOfFunction { region } => {
@@ -1418,7 +1365,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = "UNNECESSARY RETURN".to_string();
+            code = codes::UNNECESSARY_RETURN;
         }
 
         Problem::UnsuffixedEffectfulRecordField(region) => {
@@ -1434,7 +1381,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("This will help readers identify it as a source of effects."),
             ]);
 
-            title = MISSING_EXCLAMATION.to_string();
+            code = codes::MISSING_EXCLAMATION;
         }
 
         Problem::SuffixedPureRecordField(region) => {
@@ -1454,7 +1401,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
 
-            title = UNNECESSARY_EXCLAMATION.to_string();
+            code = codes::UNNECESSARY_EXCLAMATION;
         }
 
         Problem::EmptyTupleType(region) => {
@@ -1464,7 +1411,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("Empty tuples are not allowed in Roc."),
             ]);
 
-            title = EMPTY_TUPLE_TYPE.to_string();
+            code = codes::EMPTY_TUPLE_TYPE;
         }
         Problem::UnboundTypeVarsInAs(region) => {
             // NOTE for the enterprising contributor:
@@ -1477,7 +1424,7 @@ pub fn can_problem<'b>(
                 ),
             ]);
 
-            title = UNBOUND_TYPE_VARS_IN_AS.to_string();
+            code = codes::UNBOUND_TYPE_VARIABLES_IN_AS;
         }
         Problem::InterpolatedStringNotAllowed(region) => {
             doc = alloc.stack([
@@ -1486,15 +1433,193 @@ pub fn can_problem<'b>(
                 alloc.reflow(r#"Only plain strings like "foo" or "foo\n" are allowed."#),
             ]);
 
-            title = INTERPOLATED_STRING_NOT_ALLOWED.to_string();
+            code = codes::INTERPOLATED_STRING_NOT_ALLOWED;
         }
     };
 
     Report {
-        title,
+        title: code.title.to_string(),
+        code: Some(code),
         filename,
         doc,
         severity,
+        fixes,
+    }
+}
+
+/// The edits that would resolve a problem, for the problems where it's clear what they are
+fn can_problem_fixes(alloc: &RocDocAllocator, lines: &LineInfo, problem: &Problem) -> Vec<Fix> {
+    match problem {
+        Problem::UnusedModuleImport(module_id, region) => {
+            let module_name: &str = alloc.interns.module_name(*module_id);
+
+            vec![Fix::replace(
+                format!("Remove unused import of {module_name}"),
+                widen_to_whole_lines(alloc, lines, *region),
+                String::new(),
+            )]
+        }
+        Problem::UnusedImport(symbol, region) => {
+            match widen_to_list_separator(alloc, lines, *region) {
+                Some(region) => vec![Fix::replace(
+                    format!("Remove unused import of {}", symbol.as_str(alloc.interns)),
+                    region,
+                    String::new(),
+                )],
+                // It's the only thing exposed, so the whole `exposing [...]` should go, which
+                // we can't find the region of.
+                None => Vec::new(),
+            }
+        }
+        Problem::RuntimeError(RuntimeError::LookupNotInScope {
+            loc_name,
+            suggestion_options,
+            underscored_suggestion_region,
+        }) => {
+            let mut fixes = Vec::new();
+
+            if let Some(underscored_region) = underscored_suggestion_region {
+                let underscore_start = underscored_region.start();
+                let underscore_end = Position::new(underscore_start.offset + 1);
+
+                fixes.push(Fix::replace(
+                    format!("Remove the leading underscore from _{}", loc_name.value),
+                    Region::new(underscore_start, underscore_end),
+                    String::new(),
+                ));
+            }
+
+            // The same suggestions `not_found` lists, leaving out the ones too different to be
+            // what was meant
+            let name = loc_name.value.as_inline_str().as_str();
+            let mut suggestions = suggest::sort(
+                name,
+                suggestion_options.iter().map(|v| v.as_ref()).collect(),
+            );
+            suggestions.truncate(4);
+            suggestions.retain(|suggestion| suggest::is_close(name, suggestion));
+
+            fixes.extend(suggestions.into_iter().map(|suggestion| {
+                Fix::replace(
+                    format!("Change to {suggestion}"),
+                    loc_name.region,
+                    suggestion.to_string(),
+                )
+            }));
+
+            fixes
+        }
+        Problem::RuntimeError(RuntimeError::ModuleNotImported {
+            module_name,
+            module_exists: true,
+            ..
+        }) => add_import_fix(alloc, lines, module_name)
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Add `import <module_name>` below the imports the module starts with, or if there are none,
+/// before the first line after the header.
+fn add_import_fix(
+    alloc: &RocDocAllocator,
+    lines: &LineInfo,
+    module_name: &ModuleName,
+) -> Option<Fix> {
+    let (line, has_imports) = new_import_line(&alloc.src_lines.join("\n"));
+
+    if line >= lines.num_lines() as usize {
+        return None;
+    }
+
+    let replacement = if has_imports {
+        format!("import {module_name}\n")
+    } else {
+        format!("import {module_name}\n\n")
+    };
+    let position = lines.convert_line_column(LineColumn {
+        line: line as u32,
+        column: 0,
+    });
+
+    Some(Fix::replace(
+        format!("Import {module_name}"),
+        Region::new(position, position),
+        replacement,
+    ))
+}
+
+/// Widen `region` to the whole lines it's on, including the newline at the end, if there's
+/// nothing else on them.
+fn widen_to_whole_lines(alloc: &RocDocAllocator, lines: &LineInfo, region: Region) -> Region {
+    let lc_region = lines.convert_region(region);
+
+    let before = alloc
+        .src_lines
+        .get(lc_region.start.line as usize)
+        .and_then(|line| line.get(..lc_region.start.column as usize));
+    let after = alloc
+        .src_lines
+        .get(lc_region.end.line as usize)
+        .and_then(|line| line.get(lc_region.end.column as usize..));
+
+    match (before, after) {
+        (Some(before), Some(after))
+            if before.trim().is_empty()
+                && after.trim().is_empty()
+                && lc_region.end.line + 1 < lines.num_lines() =>
+        {
+            Region::new(
+                lines.convert_line_column(LineColumn {
+                    line: lc_region.start.line,
+                    column: 0,
+                }),
+                lines.convert_line_column(LineColumn {
+                    line: lc_region.end.line + 1,
+                    column: 0,
+                }),
+            )
+        }
+        _ => region,
+    }
+}
+
+/// Widen the region of an item in a comma-separated list to include the comma that separates it
+/// from its neighbor on the same line, so removing it leaves a valid list. Returns None if it has
+/// no neighbor there.
+fn widen_to_list_separator(
+    alloc: &RocDocAllocator,
+    lines: &LineInfo,
+    region: Region,
+) -> Option<Region> {
+    let lc_region = lines.convert_region(region);
+
+    if lc_region.start.line != lc_region.end.line {
+        return None;
+    }
+
+    let line = alloc.src_lines.get(lc_region.start.line as usize)?;
+    let before = line.get(..lc_region.start.column as usize)?;
+    let after = line.get(lc_region.end.column as usize..)?;
+
+    if let Some(rest) = after.trim_start().strip_prefix(',') {
+        // e.g. `[Foo, Bar]` -> `[Bar]`
+        let removed = after.len() - rest.trim_start().len();
+
+        Some(Region::new(
+            region.start(),
+            Position::new(region.end().offset + removed as u32),
+        ))
+    } else {
+        // e.g. `[Foo, Bar]` -> `[Foo]`
+        let rest = before.trim_end().strip_suffix(',')?;
+        let removed = before.len() - rest.len();
+
+        Some(Region::new(
+            Position::new(region.start().offset - removed as u32),
+            region.end(),
+        ))
     }
 }
 
@@ -1525,9 +1650,11 @@ fn to_invalid_optional_value_report<'b>(
 
     Report {
         title: "BAD OPTIONAL VALUE".to_string(),
+        code: Some(codes::BAD_OPTIONAL_VALUE),
         filename,
         doc,
         severity: Severity::RuntimeError,
+        fixes: Vec::new(),
     }
 }
 
@@ -1570,9 +1697,11 @@ fn to_invalid_ignored_value_report<'b>(
 
     Report {
         title: "BAD IGNORED VALUE".to_string(),
+        code: Some(codes::BAD_IGNORED_VALUE),
         filename,
         doc,
         severity: Severity::RuntimeError,
+        fixes: Vec::new(),
     }
 }
 
@@ -2042,7 +2171,7 @@ fn report_shadowing<'b>(
     shadow: Loc<Ident>,
     kind: ShadowKind,
     severity: Severity,
-) -> (&'static str, RocDocBuilder<'b>) {
+) -> (ErrorCode, RocDocBuilder<'b>) {
     let (what, what_plural, is_builtin) = match kind {
         ShadowKind::Variable => ("variable", "variables", false),
         ShadowKind::Alias(sym) => ("alias", "aliases", sym.is_builtin()),
@@ -2083,16 +2212,16 @@ fn report_shadowing<'b>(
         ])
     };
 
-    (DUPLICATE_NAME, doc)
+    (codes::DUPLICATE_NAME, doc)
 }
 
 fn pretty_runtime_error<'b>(
     alloc: &'b RocDocAllocator<'b>,
     lines: &LineInfo,
     runtime_error: RuntimeError,
-) -> (RocDocBuilder<'b>, &'static str) {
+) -> (RocDocBuilder<'b>, ErrorCode) {
     let doc;
-    let title;
+    let code;
 
     let severity = Severity::RuntimeError;
 
@@ -2113,7 +2242,7 @@ fn pretty_runtime_error<'b>(
             shadow,
             kind,
         } => {
-            (title, doc) = report_shadowing(alloc, lines, original_region, shadow, kind, severity);
+            (code, doc) = report_shadowing(alloc, lines, original_region, shadow, kind, severity);
         }
 
         RuntimeError::LookupNotInScope {
@@ -2130,11 +2259,11 @@ fn pretty_runtime_error<'b>(
                 underscored_suggestion_region,
                 severity,
             );
-            title = UNRECOGNIZED_NAME;
+            code = codes::UNRECOGNIZED_NAME;
         }
         RuntimeError::CircularDef(entries) => {
             doc = to_circular_def_doc(alloc, lines, &entries, severity);
-            title = CIRCULAR_DEF;
+            code = codes::CIRCULAR_DEFINITION;
         }
         RuntimeError::MalformedPattern(problem, region) => {
             use roc_parse::ast::Base;
@@ -2148,10 +2277,10 @@ fn pretty_runtime_error<'b>(
                 MalformedBase(Base::Octal) => " octal integer ",
                 MalformedBase(Base::Decimal) => " integer ",
                 BadIdent(bad_ident) => {
-                    title = NAMING_PROBLEM;
+                    code = codes::MALFORMED_NAME_IN_PATTERN;
                     doc = to_bad_ident_pattern_report(alloc, lines, bad_ident, region, severity);
 
-                    return (doc, title);
+                    return (doc, code);
                 }
                 Unknown | CantApplyPattern => " ",
                 QualifiedIdentifier => " qualified ",
@@ -2187,7 +2316,7 @@ fn pretty_runtime_error<'b>(
                 tip,
             ]);
 
-            title = SYNTAX_PROBLEM;
+            code = codes::MALFORMED_PATTERN;
         }
         RuntimeError::UnsupportedPattern(_) => {
             todo!("unsupported patterns are currently not parsed!")
@@ -2228,7 +2357,7 @@ fn pretty_runtime_error<'b>(
                 did_you_mean,
             ]);
 
-            title = VALUE_NOT_EXPOSED;
+            code = codes::NOT_EXPOSED;
         }
 
         RuntimeError::ModuleNotImported {
@@ -2247,7 +2376,7 @@ fn pretty_runtime_error<'b>(
                 severity,
             );
 
-            title = MODULE_NOT_IMPORTED;
+            code = codes::MODULE_NOT_IMPORTED;
         }
         RuntimeError::ReadIngestedFileError {
             filename,
@@ -2257,7 +2386,7 @@ fn pretty_runtime_error<'b>(
             let report = to_file_problem_report(alloc, filename, error);
 
             doc = report.doc;
-            title = INGESTED_FILE_ERROR;
+            code = codes::INGESTED_FILE_ERROR;
         }
         RuntimeError::IngestedFilePathError(region) => {
             doc = alloc.stack([
@@ -2265,7 +2394,7 @@ fn pretty_runtime_error<'b>(
                 alloc.region(lines.convert_region(region), severity),
             ]);
 
-            title = INGESTED_FILE_ERROR;
+            code = codes::INGESTED_FILE_ERROR;
         }
         RuntimeError::InvalidPrecedence(_, _) => {
             // do nothing, reported with PrecedenceProblem
@@ -2274,7 +2403,7 @@ fn pretty_runtime_error<'b>(
         RuntimeError::MalformedIdentifier(_box_str, bad_ident, surroundings) => {
             doc = to_bad_ident_expr_report(alloc, lines, bad_ident, surroundings, severity);
 
-            title = SYNTAX_PROBLEM;
+            code = codes::MALFORMED_IDENTIFIER;
         }
         RuntimeError::MalformedTypeName(_box_str, surroundings) => {
             doc = alloc.stack([
@@ -2290,7 +2419,7 @@ fn pretty_runtime_error<'b>(
                 ]),
             ]);
 
-            title = SYNTAX_PROBLEM;
+            code = codes::MALFORMED_TYPE_NAME;
         }
         RuntimeError::InvalidFloat(sign @ FloatErrorKind::PositiveInfinity, region, _raw_str)
         | RuntimeError::InvalidFloat(sign @ FloatErrorKind::NegativeInfinity, region, _raw_str) => {
@@ -2321,7 +2450,7 @@ fn pretty_runtime_error<'b>(
                 tip,
             ]);
 
-            title = SYNTAX_PROBLEM;
+            code = codes::INVALID_FLOAT;
         }
         RuntimeError::InvalidFloat(FloatErrorKind::Error, region, _raw_str) => {
             let tip = alloc
@@ -2339,7 +2468,7 @@ fn pretty_runtime_error<'b>(
                 tip,
             ]);
 
-            title = SYNTAX_PROBLEM;
+            code = codes::INVALID_FLOAT;
         }
         RuntimeError::InvalidFloat(FloatErrorKind::IntSuffix, region, _raw_str) => {
             doc = alloc.stack([
@@ -2349,7 +2478,7 @@ fn pretty_runtime_error<'b>(
                 alloc.region(lines.convert_region(region), severity),
             ]);
 
-            title = CONFLICTING_NUMBER_SUFFIX;
+            code = codes::CONFLICTING_NUMBER_SUFFIX;
         }
         RuntimeError::InvalidInt(error @ IntErrorKind::InvalidDigit, base, region, _raw_str)
         | RuntimeError::InvalidInt(error @ IntErrorKind::Empty, base, region, _raw_str) => {
@@ -2410,7 +2539,7 @@ fn pretty_runtime_error<'b>(
                 tip,
             ]);
 
-            title = SYNTAX_PROBLEM;
+            code = codes::INVALID_INTEGER;
         }
         RuntimeError::InvalidInt(error_kind @ IntErrorKind::Underflow, _base, region, _raw_str)
         | RuntimeError::InvalidInt(error_kind @ IntErrorKind::Overflow, _base, region, _raw_str) => {
@@ -2453,7 +2582,7 @@ fn pretty_runtime_error<'b>(
                 tip,
             ]);
 
-            title = SYNTAX_PROBLEM;
+            code = codes::INTEGER_OUT_OF_RANGE;
         }
         RuntimeError::InvalidInt(IntErrorKind::FloatSuffix, _base, region, _raw_str) => {
             doc = alloc.stack([
@@ -2463,7 +2592,7 @@ fn pretty_runtime_error<'b>(
                 alloc.region(lines.convert_region(region), severity),
             ]);
 
-            title = CONFLICTING_NUMBER_SUFFIX;
+            code = codes::CONFLICTING_NUMBER_SUFFIX;
         }
         RuntimeError::InvalidInt(
             IntErrorKind::OverflowsSuffix {
@@ -2487,7 +2616,7 @@ fn pretty_runtime_error<'b>(
                 ])),
             ]);
 
-            title = NUMBER_OVERFLOWS_SUFFIX;
+            code = codes::NUMBER_OVERFLOWS_SUFFIX;
         }
         RuntimeError::InvalidInt(
             IntErrorKind::UnderflowsSuffix {
@@ -2511,7 +2640,7 @@ fn pretty_runtime_error<'b>(
                 ])),
             ]);
 
-            title = NUMBER_UNDERFLOWS_SUFFIX;
+            code = codes::NUMBER_UNDERFLOWS_SUFFIX;
         }
         RuntimeError::InvalidTupleIndex(region) => {
             doc = alloc.stack([
@@ -2525,7 +2654,7 @@ fn pretty_runtime_error<'b>(
                 ])),
             ]);
 
-            title = NUMBER_UNDERFLOWS_SUFFIX;
+            code = codes::INVALID_TUPLE_INDEX;
         }
         RuntimeError::InvalidOptionalValue {
             field_name,
@@ -2540,7 +2669,7 @@ fn pretty_runtime_error<'b>(
                 record_region,
             );
 
-            title = SYNTAX_PROBLEM;
+            code = codes::OPTIONAL_VALUE_NOT_ALLOWED;
         }
         RuntimeError::InvalidIgnoredValue {
             field_name,
@@ -2555,7 +2684,7 @@ fn pretty_runtime_error<'b>(
                 record_region,
             );
 
-            title = SYNTAX_PROBLEM;
+            code = codes::IGNORED_VALUE_NOT_ALLOWED;
         }
         RuntimeError::InvalidRecordUpdate { region } => {
             doc = alloc.stack([
@@ -2567,7 +2696,7 @@ fn pretty_runtime_error<'b>(
                 alloc.reflow("Only variables can be updated with record update syntax."),
             ]);
 
-            title = SYNTAX_PROBLEM;
+            code = codes::INVALID_RECORD_UPDATE;
         }
         RuntimeError::InvalidHexadecimal(region) => {
             todo!(
@@ -2600,7 +2729,7 @@ fn pretty_runtime_error<'b>(
                 .append(alloc.module(symbol.module_id()))
                 .append(alloc.reflow(", but it was not defined anywhere in that module."))]);
 
-            title = MISSING_DEFINITION;
+            code = codes::MISSING_DEFINITION;
         }
         RuntimeError::EmptySingleQuote(region) => {
             let tip = alloc
@@ -2613,7 +2742,7 @@ fn pretty_runtime_error<'b>(
                 tip,
             ]);
 
-            title = SYNTAX_PROBLEM;
+            code = codes::EMPTY_CHARACTER_LITERAL;
         }
         RuntimeError::MultipleCharsInSingleQuote(region) => {
             let tip = alloc
@@ -2629,7 +2758,7 @@ fn pretty_runtime_error<'b>(
                 tip,
             ]);
 
-            title = SYNTAX_PROBLEM;
+            code = codes::OVERFULL_CHARACTER_LITERAL;
         }
         RuntimeError::OpaqueNotDefined {
             usage:
@@ -2679,7 +2808,7 @@ fn pretty_runtime_error<'b>(
 
             doc = alloc.stack(stack);
 
-            title = OPAQUE_NOT_DEFINED;
+            code = codes::OPAQUE_TYPE_NOT_DEFINED;
         }
         RuntimeError::OpaqueOutsideScope {
             opaque,
@@ -2700,7 +2829,7 @@ fn pretty_runtime_error<'b>(
                 ),
             ]);
 
-            title = OPAQUE_DECLARED_OUTSIDE_SCOPE;
+            code = codes::OPAQUE_TYPE_DECLARED_OUTSIDE_SCOPE;
         }
         RuntimeError::OpaqueNotApplied(loc_ident) => {
             doc = alloc.stack([
@@ -2709,7 +2838,7 @@ fn pretty_runtime_error<'b>(
                 alloc.note("Opaque types always wrap exactly one argument!"),
             ]);
 
-            title = OPAQUE_NOT_APPLIED;
+            code = codes::OPAQUE_TYPE_NOT_APPLIED;
         }
         RuntimeError::OpaqueAppliedToMultipleArgs(region) => {
             doc = alloc.stack([
@@ -2718,7 +2847,7 @@ fn pretty_runtime_error<'b>(
                 alloc.note("Opaque types always wrap exactly one argument!"),
             ]);
 
-            title = OPAQUE_OVER_APPLIED;
+            code = codes::OPAQUE_TYPE_APPLIED_TO_TOO_MANY_ARGS;
         }
         RuntimeError::DegenerateBranch(region) => {
             doc = alloc.stack([
@@ -2726,7 +2855,7 @@ fn pretty_runtime_error<'b>(
                 alloc.region(lines.convert_region(region), severity),
             ]);
 
-            title = "DEGENERATE BRANCH";
+            code = codes::DEGENERATE_BRANCH;
         }
         RuntimeError::EmptyRecordBuilder(region) => {
             doc = alloc.stack([
@@ -2735,7 +2864,7 @@ fn pretty_runtime_error<'b>(
                 alloc.reflow("I need at least two fields to combine their values into a record."),
             ]);
 
-            title = "EMPTY RECORD BUILDER";
+            code = codes::EMPTY_RECORD_BUILDER;
         }
         RuntimeError::SingleFieldRecordBuilder(region) => {
             doc = alloc.stack([
@@ -2744,7 +2873,7 @@ fn pretty_runtime_error<'b>(
                 alloc.reflow("I need at least two fields to combine their values into a record."),
             ]);
 
-            title = "NOT ENOUGH FIELDS IN RECORD BUILDER";
+            code = codes::NOT_ENOUGH_FIELDS_IN_RECORD_BUILDER;
         }
         RuntimeError::OptionalFieldInRecordBuilder {
             record: record_region,
@@ -2760,7 +2889,7 @@ fn pretty_runtime_error<'b>(
                 alloc.reflow("Record builders can only have required values for their fields."),
             ]);
 
-            title = "OPTIONAL FIELD IN RECORD BUILDER";
+            code = codes::OPTIONAL_FIELD_IN_RECORD_BUILDER;
         }
         RuntimeError::NonFunctionHostedAnnotation(region) => {
             doc = alloc.stack([
@@ -2769,11 +2898,11 @@ fn pretty_runtime_error<'b>(
                 alloc.reflow("Only functions can be configured for FFI with the host."),
             ]);
 
-            title = "NON-FUNCTION HOSTED ANNOTATION";
+            code = codes::NON_FUNCTION_HOSTED_ANNOTATION;
         }
     }
 
-    (doc, title)
+    (doc, code)
 }
 
 pub fn to_circular_def_doc<'b>(
@@ -0,0 +1,522 @@
+//! Stable codes for each kind of report, so they can be looked up with `roc explain` and linked
+//! to from docs. Each report is given its code where it's made.
+//!
+//! A kind is one problem, wherever it turns up: all the UNFINISHED PARENTHESES reports are the
+//! same problem in expressions, patterns and types, so they share a code. But different problems
+//! get different codes even when they're reported under the same title, like the many kinds of
+//! TYPE MISMATCH, so each can be explained, allowed or denied on its own.
+//!
+//! Codes are never renumbered or reused: new kinds get the next free code in their section, and a
+//! kind that goes away keeps its code reserved.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorCode {
+    pub code: &'static str,
+    pub title: &'static str,
+    /// The kind in kebab case, like `unused-import`, which is easier to type on the command line
+    /// and in `# roc:` directives than the code. It's usually the title, but kinds that share a
+    /// title each have their own.
+    pub kind: &'static str,
+}
+
+macro_rules! error_codes {
+    ($($name:ident = $code:literal, $title:literal, $kind:literal;)*) => {
+        $(
+            pub const $name: ErrorCode = ErrorCode {
+                code: $code,
+                title: $title,
+                kind: $kind,
+            };
+        )*
+
+        pub const ERROR_CODES: &[ErrorCode] = &[$($name),*];
+    };
+}
+
+error_codes! {
+    // Parsing
+    ARGUMENTS_BEFORE_EQUALS = "E1001", "ARGUMENTS BEFORE EQUALS", "arguments-before-equals";
+    ASCII_CONTROL_CHARACTER = "E1002", "ASCII CONTROL CHARACTER", "ascii-control-character";
+    BAD_REQUIRES = "E1003", "BAD REQUIRES", "bad-requires";
+    BAD_REQUIRES_RIGIDS = "E1004", "BAD REQUIRES RIGIDS", "bad-requires-rigids";
+    BAD_TYPE_VARIABLE = "E1005", "BAD TYPE VARIABLE", "bad-type-variable";
+    DOUBLE_COMMA = "E1006", "DOUBLE COMMA", "double-comma";
+    DOUBLE_DOT = "E1007", "DOUBLE DOT", "double-dot";
+    EMPTY_PARENTHESES = "E1008", "EMPTY PARENTHESES", "empty-parentheses";
+    END_OF_FILE = "E1009", "END OF FILE", "end-of-file";
+    ENDLESS_FORMAT = "E1010", "ENDLESS FORMAT", "endless-format";
+    ENDLESS_SCALAR = "E1011", "ENDLESS SCALAR", "endless-scalar";
+    ENDLESS_STRING = "E1012", "ENDLESS STRING", "endless-string";
+    EXPECTED_STRING = "E1013", "EXPECTED STRING", "expected-string";
+    IF_GUARD_NO_CONDITION = "E1014", "IF GUARD NO CONDITION", "if-guard-no-condition";
+    IGNORED_RECORD_FIELD_IN_MODULE_PARAMS = "E1015", "IGNORED RECORD FIELD IN MODULE PARAMS", "ignored-record-field-in-module-params";
+    INCOMPLETE_HEADER = "E1016", "INCOMPLETE HEADER", "incomplete-header";
+    INCORRECT_REST_PATTERN = "E1017", "INCORRECT REST PATTERN", "incorrect-rest-pattern";
+    INDENT_ENDS_AFTER_EXPRESSION = "E1018", "INDENT ENDS AFTER EXPRESSION", "indent-ends-after-expression";
+    INSUFFICIENT_INDENT_IN_MULTI_LINE_STRING = "E1019", "INSUFFICIENT INDENT IN MULTI-LINE STRING", "insufficient-indent-in-multi-line-string";
+    INVALID_NUMBER_LITERAL = "E1020", "INVALID NUMBER LITERAL", "invalid-number-literal";
+    INVALID_PACKAGE_NAME = "E1021", "INVALID PACKAGE NAME", "invalid-package-name";
+    INVALID_PLATFORM_NAME = "E1022", "INVALID PLATFORM NAME", "invalid-platform-name";
+    INVALID_SCALAR = "E1023", "INVALID SCALAR", "invalid-scalar";
+    INVALID_UNICODE_CODE_POINT = "E1024", "INVALID UNICODE CODE POINT", "invalid-unicode-code-point";
+    LOWERCASE_ALIAS = "E1025", "LOWERCASE ALIAS", "lowercase-alias";
+    MALFORMED_ARGS_LIST = "E1026", "MALFORMED ARGS LIST", "malformed-args-list";
+    MISPLACED_CARRIAGE_RETURN = "E1027", "MISPLACED CARRIAGE RETURN", "misplaced-carriage-return";
+    MISSING_ARROW = "E1028", "MISSING ARROW", "missing-arrow";
+    MISSING_EXPRESSION = "E1029", "MISSING EXPRESSION", "missing-expression";
+    MISSING_FINAL_EXPRESSION = "E1030", "MISSING FINAL EXPRESSION", "missing-final-expression";
+    MISSING_HEADER = "E1031", "MISSING HEADER", "missing-header";
+    MISSING_PACKAGES = "E1032", "MISSING PACKAGES", "missing-packages";
+    MISSING_REQUIRES = "E1033", "MISSING REQUIRES", "missing-requires";
+    NEED_MORE_INDENTATION = "E1034", "NEED MORE INDENTATION", "need-more-indentation";
+    NOT_AN_INLINE_ALIAS = "E1035", "NOT AN INLINE ALIAS", "not-an-inline-alias";
+    NOT_END_OF_FILE = "E1036", "NOT END OF FILE", "not-end-of-file";
+    PARSE_PROBLEM = "E1037", "PARSE PROBLEM", "parse-problem";
+    PROBLEM_IN_RECORD_PATTERN = "E1038", "PROBLEM IN RECORD PATTERN", "problem-in-record-pattern";
+    PROBLEM_IN_RECORD_TYPE = "E1039", "PROBLEM IN RECORD TYPE", "problem-in-record-type";
+    QUALIFIED_ALIAS_NAME = "E1040", "QUALIFIED ALIAS NAME", "qualified-alias-name";
+    RECORD_BUILDER_IN_MODULE_PARAMS = "E1041", "RECORD BUILDER IN MODULE PARAMS", "record-builder-in-module-params";
+    RECORD_PARSE_PROBLEM = "E1042", "RECORD PARSE PROBLEM", "record-parse-problem";
+    RECORD_UPDATE_IN_MODULE_PARAMS = "E1043", "RECORD UPDATE IN MODULE PARAMS", "record-update-in-module-params";
+    NON_ASSOCIATIVE_OPERATORS = "E1044", "SYNTAX PROBLEM", "non-associative-operators";
+    TAB_CHARACTER = "E1045", "TAB CHARACTER", "tab-character";
+    TRAILING_DOT = "E1046", "TRAILING DOT", "trailing-dot";
+    TRAILING_OPERATOR = "E1047", "TRAILING OPERATOR", "trailing-operator";
+    TYPE_ARGUMENT_NOT_LOWERCASE = "E1048", "TYPE ARGUMENT NOT LOWERCASE", "type-argument-not-lowercase";
+    UNEXPECTED_ARROW = "E1049", "UNEXPECTED ARROW", "unexpected-arrow";
+    UNEXPECTED_COMMA = "E1050", "UNEXPECTED COMMA", "unexpected-comma";
+    UNFINISHED_ABILITY = "E1051", "UNFINISHED ABILITY", "unfinished-ability";
+    UNFINISHED_ARGUMENT_LIST = "E1052", "UNFINISHED ARGUMENT LIST", "unfinished-argument-list";
+    UNFINISHED_FUNCTION = "E1053", "UNFINISHED FUNCTION", "unfinished-function";
+    UNFINISHED_IF = "E1054", "UNFINISHED IF", "unfinished-if";
+    UNFINISHED_IMPORT = "E1055", "UNFINISHED IMPORT", "unfinished-import";
+    UNFINISHED_INLINE_ALIAS = "E1056", "UNFINISHED INLINE ALIAS", "unfinished-inline-alias";
+    UNFINISHED_LIST = "E1057", "UNFINISHED LIST", "unfinished-list";
+    UNFINISHED_LIST_PATTERN = "E1058", "UNFINISHED LIST PATTERN", "unfinished-list-pattern";
+    UNFINISHED_PARENTHESES = "E1059", "UNFINISHED PARENTHESES", "unfinished-parentheses";
+    UNFINISHED_PATTERN = "E1060", "UNFINISHED PATTERN", "unfinished-pattern";
+    UNFINISHED_RECORD_PATTERN = "E1061", "UNFINISHED RECORD PATTERN", "unfinished-record-pattern";
+    UNFINISHED_RECORD_TYPE = "E1062", "UNFINISHED RECORD TYPE", "unfinished-record-type";
+    UNFINISHED_TAG_UNION_TYPE = "E1063", "UNFINISHED TAG UNION TYPE", "unfinished-tag-union-type";
+    UNFINISHED_TYPE = "E1064", "UNFINISHED TYPE", "unfinished-type";
+    UNFINISHED_WHEN = "E1065", "UNFINISHED WHEN", "unfinished-when";
+    UNHANDLED_PARSE_ERROR = "E1066", "UNHANDLED PARSE ERROR", "unhandled-parse-error";
+    UNICODE_CODE_POINT_TOO_LARGE = "E1067", "UNICODE CODE POINT TOO LARGE", "unicode-code-point-too-large";
+    UNKNOWN_OPERATOR = "E1068", "UNKNOWN OPERATOR", "unknown-operator";
+    WEIRD_APP_NAME = "E1069", "WEIRD APP NAME", "weird-app-name";
+    WEIRD_ARROW = "E1070", "WEIRD ARROW", "weird-arrow";
+    WEIRD_CODE_POINT = "E1071", "WEIRD CODE POINT", "weird-code-point";
+    WEIRD_ESCAPE = "E1072", "WEIRD ESCAPE", "weird-escape";
+    WEIRD_EXPOSES = "E1073", "WEIRD EXPOSES", "weird-exposes";
+    WEIRD_EXPOSING = "E1074", "WEIRD EXPOSING", "weird-exposing";
+    WEIRD_IDENTIFIER = "E1075", "WEIRD IDENTIFIER", "weird-identifier";
+    WEIRD_IMPORTS = "E1076", "WEIRD IMPORTS", "weird-imports";
+    WEIRD_MODULE_NAME = "E1077", "WEIRD MODULE NAME", "weird-module-name";
+    WEIRD_MODULE_PARAMS = "E1078", "WEIRD MODULE PARAMS", "weird-module-params";
+    WEIRD_PACKAGES_LIST = "E1079", "WEIRD PACKAGES LIST", "weird-packages-list";
+    WEIRD_PROVIDES = "E1080", "WEIRD PROVIDES", "weird-provides";
+    WEIRD_QUALIFIED_NAME = "E1081", "WEIRD QUALIFIED NAME", "weird-qualified-name";
+    WEIRD_TAG_NAME = "E1082", "WEIRD TAG NAME", "weird-tag-name";
+    CONFUSING_EXPRESSION = "E1083", "SYNTAX PROBLEM", "confusing-expression";
+    TOKENS_BEFORE_EQUALS = "E1084", "PARSE PROBLEM", "tokens-before-equals";
+    UNEXPECTED_END_OF_FIELD = "E1085", "PARSE PROBLEM", "unexpected-end-of-field";
+    OUTDENTED_TOO_FAR = "E1086", "PARSE PROBLEM", "outdented-too-far";
+    // Canonicalization
+    ABILITY_HAS_TYPE_VARIABLES = "E2001", "ABILITY HAS TYPE VARIABLES", "ability-has-type-variables";
+    ABILITY_IMPLEMENTATION_NOT_IDENTIFIER = "E2002", "ABILITY IMPLEMENTATION NOT IDENTIFIER", "ability-implementation-not-identifier";
+    ABILITY_MEMBER_BINDS_MULTIPLE_VARIABLES = "E2003", "ABILITY MEMBER BINDS MULTIPLE VARIABLES", "ability-member-binds-multiple-variables";
+    ABILITY_MEMBER_MISSING_IMPLEMENTS_CLAUSE = "E2004", "ABILITY MEMBER MISSING IMPLEMENTS CLAUSE", "ability-member-missing-implements-clause";
+    ABILITY_NOT_ON_TOP_LEVEL = "E2005", "ABILITY NOT ON TOP-LEVEL", "ability-not-on-top-level";
+    ABILITY_USED_AS_TYPE = "E2006", "ABILITY USED AS TYPE", "ability-used-as-type";
+    BAD_IGNORED_VALUE = "E2007", "BAD IGNORED VALUE", "bad-ignored-value";
+    BAD_OPTIONAL_VALUE = "E2008", "BAD OPTIONAL VALUE", "bad-optional-value";
+    CIRCULAR_DEFINITION = "E2009", "CIRCULAR DEFINITION", "circular-definition";
+    CONFLICTING_NUMBER_SUFFIX = "E2010", "CONFLICTING NUMBER SUFFIX", "conflicting-number-suffix";
+    DEFINITION_ONLY_USED_IN_RECURSION = "E2011", "DEFINITION ONLY USED IN RECURSION", "definition-only-used-in-recursion";
+    DEFINITIONS_ONLY_USED_IN_RECURSION = "E2012", "DEFINITIONS ONLY USED IN RECURSION", "definitions-only-used-in-recursion";
+    DEGENERATE_BRANCH = "E2013", "DEGENERATE BRANCH", "degenerate-branch";
+    DUPLICATE_BOUND_ABILITY = "E2014", "DUPLICATE BOUND ABILITY", "duplicate-bound-ability";
+    DUPLICATE_FIELD_NAME = "E2015", "DUPLICATE FIELD NAME", "duplicate-field-name";
+    DUPLICATE_IMPLEMENTATION = "E2016", "DUPLICATE IMPLEMENTATION", "duplicate-implementation";
+    DUPLICATE_NAME = "E2017", "DUPLICATE NAME", "duplicate-name";
+    DUPLICATE_TAG_NAME = "E2018", "DUPLICATE TAG NAME", "duplicate-tag-name";
+    EMPTY_RECORD_BUILDER = "E2019", "EMPTY RECORD BUILDER", "empty-record-builder";
+    EMPTY_TUPLE_TYPE = "E2020", "EMPTY TUPLE TYPE", "empty-tuple-type";
+    EXPLICIT_BUILTIN_IMPORT = "E2021", "EXPLICIT BUILTIN IMPORT", "explicit-builtin-import";
+    ILLEGAL_DERIVE = "E2022", "ILLEGAL DERIVE", "illegal-derive";
+    ILLEGAL_IMPLEMENTS_CLAUSE = "E2023", "ILLEGAL IMPLEMENTS CLAUSE", "illegal-implements-clause";
+    IMPLEMENTATION_NOT_FOUND = "E2024", "IMPLEMENTATION NOT FOUND", "implementation-not-found";
+    IMPLEMENTS_CLAUSE_IS_NOT_AN_ABILITY = "E2025", "IMPLEMENTS CLAUSE IS NOT AN ABILITY", "implements-clause-is-not-an-ability";
+    IMPORT_NAME_CONFLICT = "E2026", "IMPORT NAME CONFLICT", "import-name-conflict";
+    INCOMPLETE_ABILITY_IMPLEMENTATION = "E2027", "INCOMPLETE ABILITY IMPLEMENTATION", "incomplete-ability-implementation";
+    INGESTED_FILE_ERROR = "E2028", "INGESTED FILE ERROR", "ingested-file-error";
+    INTERPOLATED_STRING_NOT_ALLOWED = "E2029", "INTERPOLATED STRING NOT ALLOWED", "interpolated-string-not-allowed";
+    INVALID_UNICODE = "E2030", "INVALID UNICODE", "invalid-unicode";
+    INVALID_EXTENSION_TYPE = "E2031", "INVALID_EXTENSION_TYPE", "invalid-extension-type";
+    MISSING_DEFINITION = "E2032", "MISSING DEFINITION", "missing-definition";
+    MISSING_EXCLAMATION = "E2033", "MISSING EXCLAMATION", "missing-exclamation";
+    MODULE_NOT_IMPORTED = "E2034", "MODULE NOT IMPORTED", "module-not-imported";
+    MULTIPLE_LIST_REST_PATTERNS = "E2035", "MULTIPLE LIST REST PATTERNS", "multiple-list-rest-patterns";
+    NAME_NOT_BOUND_IN_ALL_PATTERNS = "E2036", "NAME NOT BOUND IN ALL PATTERNS", "name-not-bound-in-all-patterns";
+    MALFORMED_NAME_IN_PATTERN = "E2037", "NAMING PROBLEM", "malformed-name-in-pattern";
+    NESTED_DATATYPE = "E2038", "NESTED DATATYPE", "nested-datatype";
+    NON_FUNCTION_HOSTED_ANNOTATION = "E2039", "NON-FUNCTION HOSTED ANNOTATION", "non-function-hosted-annotation";
+    NOT_AN_ABILITY = "E2040", "NOT AN ABILITY", "not-an-ability";
+    NOT_AN_ABILITY_MEMBER = "E2041", "NOT AN ABILITY MEMBER", "not-an-ability-member";
+    NOT_ENOUGH_FIELDS_IN_RECORD_BUILDER = "E2042", "NOT ENOUGH FIELDS IN RECORD BUILDER", "not-enough-fields-in-record-builder";
+    NOT_EXPOSED = "E2043", "NOT EXPOSED", "not-exposed";
+    NUMBER_OVERFLOWS_SUFFIX = "E2044", "NUMBER OVERFLOWS SUFFIX", "number-overflows-suffix";
+    NUMBER_UNDERFLOWS_SUFFIX = "E2045", "NUMBER UNDERFLOWS SUFFIX", "number-underflows-suffix";
+    OPAQUE_TYPE_APPLIED_TO_TOO_MANY_ARGS = "E2046", "OPAQUE TYPE APPLIED TO TOO MANY ARGS", "opaque-type-applied-to-too-many-args";
+    OPAQUE_TYPE_DECLARED_OUTSIDE_SCOPE = "E2047", "OPAQUE TYPE DECLARED OUTSIDE SCOPE", "opaque-type-declared-outside-scope";
+    OPAQUE_TYPE_NOT_APPLIED = "E2048", "OPAQUE TYPE NOT APPLIED", "opaque-type-not-applied";
+    OPAQUE_TYPE_NOT_DEFINED = "E2049", "OPAQUE TYPE NOT DEFINED", "opaque-type-not-defined";
+    OPTIONAL_ABILITY_IMPLEMENTATION = "E2050", "OPTIONAL ABILITY IMPLEMENTATION", "optional-ability-implementation";
+    OPTIONAL_FIELD_IN_RECORD_BUILDER = "E2051", "OPTIONAL FIELD IN RECORD BUILDER", "optional-field-in-record-builder";
+    OVERAPPLIED_CRASH = "E2052", "OVERAPPLIED CRASH", "overapplied-crash";
+    OVERAPPLIED_DBG = "E2053", "OVERAPPLIED DBG", "overapplied-dbg";
+    OVERLOADED_SPECIALIZATION = "E2054", "OVERLOADED SPECIALIZATION", "overloaded-specialization";
+    QUALIFIED_ABILITY_IMPLEMENTATION = "E2055", "QUALIFIED ABILITY IMPLEMENTATION", "qualified-ability-implementation";
+    RETURN_OUTSIDE_OF_FUNCTION = "E2056", "RETURN OUTSIDE OF FUNCTION", "return-outside-of-function";
+    SPECIALIZATION_NOT_ON_TOP_LEVEL = "E2057", "SPECIALIZATION NOT ON TOP-LEVEL", "specialization-not-on-top-level";
+    TOO_FEW_TYPE_ARGUMENTS = "E2058", "TOO FEW TYPE ARGUMENTS", "too-few-type-arguments";
+    TOO_MANY_TYPE_ARGUMENTS = "E2059", "TOO MANY TYPE ARGUMENTS", "too-many-type-arguments";
+    TRY_OUTSIDE_OF_FUNCTION = "E2060", "TRY OUTSIDE OF FUNCTION", "try-outside-of-function";
+    UNAPPLIED_CRASH = "E2061", "UNAPPLIED CRASH", "unapplied-crash";
+    UNAPPLIED_DBG = "E2062", "UNAPPLIED DBG", "unapplied-dbg";
+    UNBOUND_TYPE_VARIABLES_IN_AS = "E2063", "UNBOUND TYPE VARIABLES IN AS", "unbound-type-variables-in-as";
+    UNDECLARED_TYPE_VARIABLE = "E2064", "UNDECLARED TYPE VARIABLE", "undeclared-type-variable";
+    UNDERAPPLIED_TRY = "E2065", "UNDERAPPLIED TRY", "underapplied-try";
+    UNDERSCORE_NOT_ALLOWED_HERE = "E2066", "UNDERSCORE NOT ALLOWED HERE", "underscore-not-allowed-here";
+    UNNECESSARY_DEFINITION = "E2067", "UNNECESSARY DEFINITION", "unnecessary-definition";
+    UNNECESSARY_EXCLAMATION = "E2068", "UNNECESSARY EXCLAMATION", "unnecessary-exclamation";
+    UNNECESSARY_IMPLEMENTATIONS = "E2069", "UNNECESSARY IMPLEMENTATIONS", "unnecessary-implementations";
+    UNNECESSARY_RETURN = "E2070", "UNNECESSARY RETURN", "unnecessary-return";
+    UNNECESSARY_WILDCARD = "E2071", "UNNECESSARY WILDCARD", "unnecessary-wildcard";
+    UNREACHABLE_CODE = "E2072", "UNREACHABLE CODE", "unreachable-code";
+    UNRECOGNIZED_NAME = "E2073", "UNRECOGNIZED NAME", "unrecognized-name";
+    UNUSED_ARGUMENT = "E2074", "UNUSED ARGUMENT", "unused-argument";
+    UNUSED_DEFINITION = "E2075", "UNUSED DEFINITION", "unused-definition";
+    UNUSED_IMPORT = "E2076", "UNUSED IMPORT", "unused-import";
+    UNUSED_TYPE_ALIAS_PARAMETER = "E2077", "UNUSED TYPE ALIAS PARAMETER", "unused-type-alias-parameter";
+    WILDCARD_NOT_ALLOWED_HERE = "E2078", "WILDCARD NOT ALLOWED HERE", "wildcard-not-allowed-here";
+    UNSUPPORTED_PATTERN = "E2079", "SYNTAX PROBLEM", "unsupported-pattern";
+    INVALID_ALIAS_VARIABLE = "E2080", "SYNTAX PROBLEM", "invalid-alias-variable";
+    INVALID_INTERPOLATION = "E2081", "SYNTAX PROBLEM", "invalid-interpolation";
+    MALFORMED_PATTERN = "E2082", "SYNTAX PROBLEM", "malformed-pattern";
+    MALFORMED_IDENTIFIER = "E2083", "SYNTAX PROBLEM", "malformed-identifier";
+    MALFORMED_TYPE_NAME = "E2084", "SYNTAX PROBLEM", "malformed-type-name";
+    INVALID_FLOAT = "E2085", "SYNTAX PROBLEM", "invalid-float";
+    INVALID_INTEGER = "E2086", "SYNTAX PROBLEM", "invalid-integer";
+    INTEGER_OUT_OF_RANGE = "E2087", "SYNTAX PROBLEM", "integer-out-of-range";
+    OPTIONAL_VALUE_NOT_ALLOWED = "E2088", "SYNTAX PROBLEM", "optional-value-not-allowed";
+    IGNORED_VALUE_NOT_ALLOWED = "E2089", "SYNTAX PROBLEM", "ignored-value-not-allowed";
+    INVALID_RECORD_UPDATE = "E2090", "SYNTAX PROBLEM", "invalid-record-update";
+    EMPTY_CHARACTER_LITERAL = "E2091", "SYNTAX PROBLEM", "empty-character-literal";
+    OVERFULL_CHARACTER_LITERAL = "E2092", "SYNTAX PROBLEM", "overfull-character-literal";
+    ANNOTATION_NAME_MISMATCH = "E2093", "NAMING PROBLEM", "annotation-name-mismatch";
+    INVALID_TUPLE_INDEX = "E2094", "NUMBER UNDERFLOWS SUFFIX", "invalid-tuple-index";
+    // Type checking
+    CIRCULAR_TYPE = "E3001", "CIRCULAR TYPE", "circular-type";
+    CYCLIC_ALIAS = "E3002", "CYCLIC ALIAS", "cyclic-alias";
+    EFFECT_IN_PURE_FUNCTION = "E3003", "EFFECT IN PURE FUNCTION", "effect-in-pure-function";
+    EFFECT_IN_TOP_LEVEL = "E3004", "EFFECT IN TOP-LEVEL", "effect-in-top-level";
+    IGNORED_RESULT = "E3005", "IGNORED RESULT", "ignored-result";
+    ILLEGAL_SPECIALIZATION = "E3006", "ILLEGAL SPECIALIZATION", "illegal-specialization";
+    INVALID_TRY_TARGET = "E3007", "INVALID TRY TARGET", "invalid-try-target";
+    INVALID_TYPE_FOR_INGESTED_FILE = "E3008", "INVALID TYPE FOR INGESTED FILE", "invalid-type-for-ingested-file";
+    INVALID_UTF_8 = "E3009", "INVALID UTF-8", "invalid-utf-8";
+    LEFTOVER_STATEMENT = "E3010", "LEFTOVER STATEMENT", "leftover-statement";
+    MISSING_MODULE_PARAMS = "E3011", "MISSING MODULE PARAMS", "missing-module-params";
+    MODULE_PARAMS_MISMATCH = "E3012", "MODULE PARAMS MISMATCH", "module-params-mismatch";
+    REDUNDANT_PATTERN = "E3013", "REDUNDANT PATTERN", "redundant-pattern";
+    TOO_FEW_ARGS = "E3014", "TOO FEW ARGS", "too-few-args";
+    TOO_MANY_ARGS = "E3015", "TOO MANY ARGS", "too-many-args";
+    TYPE_MISMATCH = "E3016", "TYPE MISMATCH", "type-mismatch";
+    TYPE_VARIABLE_IS_NOT_GENERIC = "E3017", "TYPE VARIABLE IS NOT GENERIC", "type-variable-is-not-generic";
+    UNEXPECTED_MODULE_PARAMS = "E3018", "UNEXPECTED MODULE PARAMS", "unexpected-module-params";
+    UNMATCHABLE_PATTERN = "E3019", "UNMATCHABLE PATTERN", "unmatchable-pattern";
+    UNSAFE_PATTERN = "E3020", "UNSAFE PATTERN", "unsafe-pattern";
+    WRONG_SPECIALIZATION_TYPE = "E3021", "WRONG SPECIALIZATION TYPE", "wrong-specialization-type";
+    ANNOTATION_MISMATCH = "E3022", "TYPE MISMATCH", "annotation-mismatch";
+    MISSING_ABILITY = "E3023", "TYPE MISMATCH", "missing-ability";
+    WHEN_CONDITION_MISMATCH = "E3024", "TYPE MISMATCH", "when-condition-mismatch";
+    ANNOTATED_ARGUMENT_MISMATCH = "E3025", "TYPE MISMATCH", "annotated-argument-mismatch";
+    CRASH_ARGUMENT_MISMATCH = "E3026", "TYPE MISMATCH", "crash-argument-mismatch";
+    RETURN_TYPE_MISMATCH = "E3027", "TYPE MISMATCH", "return-type-mismatch";
+    PATTERN_MISMATCH = "E3028", "TYPE MISMATCH", "pattern-mismatch";
+    RECORD_FIELD_MISMATCH = "E3029", "TYPE MISMATCH", "record-field-mismatch";
+    // Loading files and packages
+    FILE_NOT_FOUND = "E4001", "FILE NOT FOUND", "file-not-found";
+    FILE_PERMISSION_DENIED = "E4002", "FILE PERMISSION DENIED", "file-permission-denied";
+    FILE_PROBLEM = "E4003", "FILE PROBLEM", "file-problem";
+    FILE_TOO_LARGE = "E4004", "FILE TOO LARGE", "file-too-large";
+    HTTP_ERROR = "E4005", "HTTP ERROR", "http-error";
+    HTTPS_MANDATORY = "E4006", "HTTPS MANDATORY", "https-mandatory";
+    IMPORT_CYCLE = "E4007", "IMPORT CYCLE", "import-cycle";
+    INCORRECT_MODULE_NAME = "E4008", "INCORRECT MODULE NAME", "incorrect-module-name";
+    INVALID_CONTENT_HASH = "E4009", "INVALID CONTENT HASH", "invalid-content-hash";
+    INVALID_EXTENSION = "E4010", "INVALID EXTENSION", "invalid-extension";
+    INVALID_EXTENSION_SUFFIX = "E4011", "INVALID EXTENSION SUFFIX", "invalid-extension-suffix";
+    INVALID_FRAGMENT = "E4012", "INVALID FRAGMENT", "invalid-fragment";
+    INVALID_PACKAGE_MANIFEST = "E4013", "INVALID PACKAGE MANIFEST", "invalid-package-manifest";
+    IO_ERROR = "E4014", "IO ERROR", "io-error";
+    MISLEADING_CHARACTERS = "E4015", "MISLEADING CHARACTERS", "misleading-characters";
+    MISSING_PACKAGE_HASH = "E4016", "MISSING PACKAGE HASH", "missing-package-hash";
+    MULTIPLE_ENCODINGS = "E4017", "MULTIPLE ENCODINGS", "multiple-encodings";
+    MULTIPLE_PLATFORMS = "E4018", "MULTIPLE PLATFORMS", "multiple-platforms";
+    NO_PLATFORM = "E4019", "NO PLATFORM", "no-platform";
+    ROOT_IS_NOT_AN_APP = "E4020", "NO PLATFORM", "root-is-not-an-app";
+    NOT_A_ROC_FILE = "E4021", "NOT A ROC FILE", "not-a-roc-file";
+    NOTFOUND = "E4022", "NOTFOUND", "notfound";
+    PACKAGE_FILES_CHANGED = "E4023", "PACKAGE FILES CHANGED", "package-files-changed";
+    UNRECOGNIZED_PACKAGE = "E4024", "UNRECOGNIZED PACKAGE", "unrecognized-package";
+    UNSPECIFIED_PLATFORM = "E4025", "UNSPECIFIED PLATFORM", "unspecified-platform";
+    UNSUPPORTED_ENCODING = "E4026", "UNSUPPORTED ENCODING", "unsupported-encoding";
+    UNVERIFIED_PACKAGE = "E4027", "UNVERIFIED PACKAGE", "unverified-package";
+    VENDORED_PACKAGE_CHANGED = "E4028", "VENDORED PACKAGE CHANGED", "vendored-package-changed";
+    // Running expects
+    EXPECT_FAILED = "E5001", "EXPECT FAILED", "expect-failed";
+    EXPECT_PANICKED = "E5002", "EXPECT PANICKED", "expect-panicked";
+    EXPECT_TIMED_OUT = "E5003", "EXPECT TIMED OUT", "expect-timed-out";
+    // Docs
+    INVALID_DOCS_LINK = "E6001", "INVALID DOCS LINK", "invalid-docs-link";
+}
+
+impl ErrorCode {
+    /// A long-form explanation with an example, for `roc explain` to print. Not every code has
+    /// one yet.
+    pub fn explanation(&self) -> Option<&'static str> {
+        let explanation = match self.code {
+            "E1001" => include_str!("explanations/E1001.md"),
+            "E1002" => include_str!("explanations/E1002.md"),
+            "E1003" => include_str!("explanations/E1003.md"),
+            "E1004" => include_str!("explanations/E1004.md"),
+            "E1005" => include_str!("explanations/E1005.md"),
+            "E1006" => include_str!("explanations/E1006.md"),
+            "E1007" => include_str!("explanations/E1007.md"),
+            "E1008" => include_str!("explanations/E1008.md"),
+            "E1009" => include_str!("explanations/E1009.md"),
+            "E1010" => include_str!("explanations/E1010.md"),
+            "E1011" => include_str!("explanations/E1011.md"),
+            "E1012" => include_str!("explanations/E1012.md"),
+            "E1013" => include_str!("explanations/E1013.md"),
+            "E1014" => include_str!("explanations/E1014.md"),
+            "E1015" => include_str!("explanations/E1015.md"),
+            "E1016" => include_str!("explanations/E1016.md"),
+            "E1017" => include_str!("explanations/E1017.md"),
+            "E1018" => include_str!("explanations/E1018.md"),
+            "E1019" => include_str!("explanations/E1019.md"),
+            "E1020" => include_str!("explanations/E1020.md"),
+            "E1021" => include_str!("explanations/E1021.md"),
+            "E1022" => include_str!("explanations/E1022.md"),
+            "E1023" => include_str!("explanations/E1023.md"),
+            "E1024" => include_str!("explanations/E1024.md"),
+            "E1025" => include_str!("explanations/E1025.md"),
+            "E1026" => include_str!("explanations/E1026.md"),
+            "E1027" => include_str!("explanations/E1027.md"),
+            "E1028" => include_str!("explanations/E1028.md"),
+            "E1029" => include_str!("explanations/E1029.md"),
+            "E1030" => include_str!("explanations/E1030.md"),
+            "E1031" => include_str!("explanations/E1031.md"),
+            "E1032" => include_str!("explanations/E1032.md"),
+            "E1033" => include_str!("explanations/E1033.md"),
+            "E1034" => include_str!("explanations/E1034.md"),
+            "E1035" => include_str!("explanations/E1035.md"),
+            "E1036" => include_str!("explanations/E1036.md"),
+            "E1037" => include_str!("explanations/E1037.md"),
+            "E1038" => include_str!("explanations/E1038.md"),
+            "E1039" => include_str!("explanations/E1039.md"),
+            "E1040" => include_str!("explanations/E1040.md"),
+            "E1041" => include_str!("explanations/E1041.md"),
+            "E1042" => include_str!("explanations/E1042.md"),
+            "E1043" => include_str!("explanations/E1043.md"),
+            "E1044" => include_str!("explanations/E1044.md"),
+            "E1045" => include_str!("explanations/E1045.md"),
+            "E1046" => include_str!("explanations/E1046.md"),
+            "E1047" => include_str!("explanations/E1047.md"),
+            "E1048" => include_str!("explanations/E1048.md"),
+            "E1049" => include_str!("explanations/E1049.md"),
+            "E1050" => include_str!("explanations/E1050.md"),
+            "E1051" => include_str!("explanations/E1051.md"),
+            "E1052" => include_str!("explanations/E1052.md"),
+            "E1053" => include_str!("explanations/E1053.md"),
+            "E1054" => include_str!("explanations/E1054.md"),
+            "E1055" => include_str!("explanations/E1055.md"),
+            "E1056" => include_str!("explanations/E1056.md"),
+            "E1057" => include_str!("explanations/E1057.md"),
+            "E1058" => include_str!("explanations/E1058.md"),
+            "E1059" => include_str!("explanations/E1059.md"),
+            "E1060" => include_str!("explanations/E1060.md"),
+            "E1061" => include_str!("explanations/E1061.md"),
+            "E1062" => include_str!("explanations/E1062.md"),
+            "E1063" => include_str!("explanations/E1063.md"),
+            "E1064" => include_str!("explanations/E1064.md"),
+            "E1065" => include_str!("explanations/E1065.md"),
+            "E1066" => include_str!("explanations/E1066.md"),
+            "E1067" => include_str!("explanations/E1067.md"),
+            "E1068" => include_str!("explanations/E1068.md"),
+            "E1069" => include_str!("explanations/E1069.md"),
+            "E1070" => include_str!("explanations/E1070.md"),
+            "E1071" => include_str!("explanations/E1071.md"),
+            "E1072" => include_str!("explanations/E1072.md"),
+            "E1073" => include_str!("explanations/E1073.md"),
+            "E1074" => include_str!("explanations/E1074.md"),
+            "E1075" => include_str!("explanations/E1075.md"),
+            "E1076" => include_str!("explanations/E1076.md"),
+            "E1077" => include_str!("explanations/E1077.md"),
+            "E1078" => include_str!("explanations/E1078.md"),
+            "E1079" => include_str!("explanations/E1079.md"),
+            "E1080" => include_str!("explanations/E1080.md"),
+            "E1081" => include_str!("explanations/E1081.md"),
+            "E1082" => include_str!("explanations/E1082.md"),
+            "E1083" => include_str!("explanations/E1083.md"),
+            "E1084" => include_str!("explanations/E1084.md"),
+            "E1085" => include_str!("explanations/E1085.md"),
+            "E1086" => include_str!("explanations/E1086.md"),
+            "E2001" => include_str!("explanations/E2001.md"),
+            "E2002" => include_str!("explanations/E2002.md"),
+            "E2003" => include_str!("explanations/E2003.md"),
+            "E2004" => include_str!("explanations/E2004.md"),
+            "E2005" => include_str!("explanations/E2005.md"),
+            "E2006" => include_str!("explanations/E2006.md"),
+            "E2007" => include_str!("explanations/E2007.md"),
+            "E2008" => include_str!("explanations/E2008.md"),
+            "E2009" => include_str!("explanations/E2009.md"),
+            "E2010" => include_str!("explanations/E2010.md"),
+            "E2011" => include_str!("explanations/E2011.md"),
+            "E2012" => include_str!("explanations/E2012.md"),
+            "E2013" => include_str!("explanations/E2013.md"),
+            "E2014" => include_str!("explanations/E2014.md"),
+            "E2015" => include_str!("explanations/E2015.md"),
+            "E2016" => include_str!("explanations/E2016.md"),
+            "E2017" => include_str!("explanations/E2017.md"),
+            "E2018" => include_str!("explanations/E2018.md"),
+            "E2019" => include_str!("explanations/E2019.md"),
+            "E2020" => include_str!("explanations/E2020.md"),
+            "E2021" => include_str!("explanations/E2021.md"),
+            "E2022" => include_str!("explanations/E2022.md"),
+            "E2023" => include_str!("explanations/E2023.md"),
+            "E2024" => include_str!("explanations/E2024.md"),
+            "E2025" => include_str!("explanations/E2025.md"),
+            "E2026" => include_str!("explanations/E2026.md"),
+            "E2027" => include_str!("explanations/E2027.md"),
+            "E2028" => include_str!("explanations/E2028.md"),
+            "E2029" => include_str!("explanations/E2029.md"),
+            "E2030" => include_str!("explanations/E2030.md"),
+            "E2031" => include_str!("explanations/E2031.md"),
+            "E2032" => include_str!("explanations/E2032.md"),
+            "E2033" => include_str!("explanations/E2033.md"),
+            "E2034" => include_str!("explanations/E2034.md"),
+            "E2035" => include_str!("explanations/E2035.md"),
+            "E2036" => include_str!("explanations/E2036.md"),
+            "E2037" => include_str!("explanations/E2037.md"),
+            "E2038" => include_str!("explanations/E2038.md"),
+            "E2039" => include_str!("explanations/E2039.md"),
+            "E2040" => include_str!("explanations/E2040.md"),
+            "E2041" => include_str!("explanations/E2041.md"),
+            "E2042" => include_str!("explanations/E2042.md"),
+            "E2043" => include_str!("explanations/E2043.md"),
+            "E2044" => include_str!("explanations/E2044.md"),
+            "E2045" => include_str!("explanations/E2045.md"),
+            "E2046" => include_str!("explanations/E2046.md"),
+            "E2047" => include_str!("explanations/E2047.md"),
+            "E2048" => include_str!("explanations/E2048.md"),
+            "E2049" => include_str!("explanations/E2049.md"),
+            "E2050" => include_str!("explanations/E2050.md"),
+            "E2051" => include_str!("explanations/E2051.md"),
+            "E2052" => include_str!("explanations/E2052.md"),
+            "E2053" => include_str!("explanations/E2053.md"),
+            "E2054" => include_str!("explanations/E2054.md"),
+            "E2055" => include_str!("explanations/E2055.md"),
+            "E2056" => include_str!("explanations/E2056.md"),
+            "E2057" => include_str!("explanations/E2057.md"),
+            "E2058" => include_str!("explanations/E2058.md"),
+            "E2059" => include_str!("explanations/E2059.md"),
+            "E2060" => include_str!("explanations/E2060.md"),
+            "E2061" => include_str!("explanations/E2061.md"),
+            "E2062" => include_str!("explanations/E2062.md"),
+            "E2063" => include_str!("explanations/E2063.md"),
+            "E2064" => include_str!("explanations/E2064.md"),
+            "E2065" => include_str!("explanations/E2065.md"),
+            "E2066" => include_str!("explanations/E2066.md"),
+            "E2067" => include_str!("explanations/E2067.md"),
+            "E2068" => include_str!("explanations/E2068.md"),
+            "E2069" => include_str!("explanations/E2069.md"),
+            "E2070" => include_str!("explanations/E2070.md"),
+            "E2071" => include_str!("explanations/E2071.md"),
+            "E2072" => include_str!("explanations/E2072.md"),
+            "E2073" => include_str!("explanations/E2073.md"),
+            "E2074" => include_str!("explanations/E2074.md"),
+            "E2075" => include_str!("explanations/E2075.md"),
+            "E2076" => include_str!("explanations/E2076.md"),
+            "E2077" => include_str!("explanations/E2077.md"),
+            "E2078" => include_str!("explanations/E2078.md"),
+            "E2079" => include_str!("explanations/E2079.md"),
+            "E2080" => include_str!("explanations/E2080.md"),
+            "E2081" => include_str!("explanations/E2081.md"),
+            "E2082" => include_str!("explanations/E2082.md"),
+            "E2083" => include_str!("explanations/E2083.md"),
+            "E2084" => include_str!("explanations/E2084.md"),
+            "E2085" => include_str!("explanations/E2085.md"),
+            "E2086" => include_str!("explanations/E2086.md"),
+            "E2087" => include_str!("explanations/E2087.md"),
+            "E2088" => include_str!("explanations/E2088.md"),
+            "E2089" => include_str!("explanations/E2089.md"),
+            "E2090" => include_str!("explanations/E2090.md"),
+            "E2091" => include_str!("explanations/E2091.md"),
+            "E2092" => include_str!("explanations/E2092.md"),
+            "E2093" => include_str!("explanations/E2093.md"),
+            "E2094" => include_str!("explanations/E2094.md"),
+            "E3001" => include_str!("explanations/E3001.md"),
+            "E3002" => include_str!("explanations/E3002.md"),
+            "E3003" => include_str!("explanations/E3003.md"),
+            "E3004" => include_str!("explanations/E3004.md"),
+            "E3005" => include_str!("explanations/E3005.md"),
+            "E3006" => include_str!("explanations/E3006.md"),
+            "E3007" => include_str!("explanations/E3007.md"),
+            "E3008" => include_str!("explanations/E3008.md"),
+            "E3009" => include_str!("explanations/E3009.md"),
+            "E3010" => include_str!("explanations/E3010.md"),
+            "E3011" => include_str!("explanations/E3011.md"),
+            "E3012" => include_str!("explanations/E3012.md"),
+            "E3013" => include_str!("explanations/E3013.md"),
+            "E3014" => include_str!("explanations/E3014.md"),
+            "E3015" => include_str!("explanations/E3015.md"),
+            "E3016" => include_str!("explanations/E3016.md"),
+            "E3017" => include_str!("explanations/E3017.md"),
+            "E3018" => include_str!("explanations/E3018.md"),
+            "E3019" => include_str!("explanations/E3019.md"),
+            "E3020" => include_str!("explanations/E3020.md"),
+            "E3021" => include_str!("explanations/E3021.md"),
+            "E3022" => include_str!("explanations/E3022.md"),
+            "E3023" => include_str!("explanations/E3023.md"),
+            "E3024" => include_str!("explanations/E3024.md"),
+            "E3025" => include_str!("explanations/E3025.md"),
+            "E3026" => include_str!("explanations/E3026.md"),
+            "E3027" => include_str!("explanations/E3027.md"),
+            "E3028" => include_str!("explanations/E3028.md"),
+            "E3029" => include_str!("explanations/E3029.md"),
+            "E6001" => include_str!("explanations/E6001.md"),
+            _ => return None,
+        };
+
+        Some(explanation)
+    }
+}
+
+/// Looks up a code like `E3016`, ignoring case
+pub fn lookup(code: &str) -> Option<&'static ErrorCode> {
+    ERROR_CODES
+        .iter()
+        .find(|error_code| error_code.code.eq_ignore_ascii_case(code))
+}
+
+/// Looks up a code like `E2076`, or a kind's name like `unused-import`
+pub fn lookup_kind(kind: &str) -> Option<&'static ErrorCode> {
+    lookup(kind).or_else(|| {
+        ERROR_CODES
+            .iter()
+            .find(|error_code| error_code.kind == kind)
+    })
+}
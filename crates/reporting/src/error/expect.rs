@@ -11,6 +11,7 @@ use roc_types::{
     types::{ErrorType, Polarity},
 };
 
+use crate::error::codes;
 use crate::report::{RenderTarget, RocDocAllocator, RocDocBuilder};
 
 pub struct Renderer<'a> {
@@ -50,12 +51,7 @@ impl<'a> Renderer<'a> {
         error_type_to_doc(&self.alloc, error_type)
     }
 
-    fn render_lookup(
-        &'a self,
-        symbol: Symbol,
-        expr: &Expr<'_>,
-        error_type: ErrorType,
-    ) -> RocDocBuilder<'a> {
+    fn format_value(&self, expr: &Expr<'_>) -> &'a str {
         use roc_fmt::annotation::Formattable;
 
         let mut buf = roc_fmt::Buf::new_in(
@@ -67,6 +63,15 @@ impl<'a> Renderer<'a> {
         );
         expr.format(&mut buf, 0);
 
+        buf.into_bump_str()
+    }
+
+    fn render_lookup(
+        &'a self,
+        symbol: Symbol,
+        expr: &Expr<'_>,
+        error_type: ErrorType,
+    ) -> RocDocBuilder<'a> {
         self.alloc.vcat([
             self.alloc
                 .symbol_unqualified(symbol)
@@ -75,10 +80,28 @@ impl<'a> Renderer<'a> {
             self.alloc
                 .symbol_unqualified(symbol)
                 .append(" = ")
-                .append(buf.into_bump_str()),
+                .append(self.format_value(expr)),
         ])
     }
 
+    /// The name and value of each variable, formatted the way `render_failure` shows them
+    pub fn lookup_values(
+        &self,
+        symbols: &[Symbol],
+        expressions: &[Expr<'_>],
+    ) -> Vec<(String, String)> {
+        symbols
+            .iter()
+            .zip(expressions)
+            .map(|(symbol, expr)| {
+                (
+                    symbol.as_str(self.alloc.interns).to_string(),
+                    self.format_value(expr).to_string(),
+                )
+            })
+            .collect()
+    }
+
     fn render_lookups(
         &'a self,
         subs: &mut Subs,
@@ -169,9 +192,11 @@ impl<'a> Renderer<'a> {
 
         let report = Report {
             title: "EXPECT FAILED".into(),
+            code: Some(codes::EXPECT_FAILED),
             doc,
             filename: self.filename.clone(),
             severity,
+            fixes: Vec::new(),
         };
 
         let mut buf = String::new();
@@ -247,9 +272,63 @@ impl<'a> Renderer<'a> {
 
         let report = Report {
             title: "EXPECT PANICKED".into(),
+            code: Some(codes::EXPECT_PANICKED),
+            doc,
+            filename: self.filename.clone(),
+            severity,
+            fixes: Vec::new(),
+        };
+
+        let mut buf = String::new();
+
+        report.render(
+            self.render_target,
+            &mut buf,
+            &self.alloc,
+            &crate::report::DEFAULT_PALETTE,
+        );
+
+        write!(writer, "{buf}")
+    }
+
+    pub fn render_timeout<W>(
+        &self,
+        writer: &mut W,
+        timeout: std::time::Duration,
+        expect_region: Region,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use crate::report::Report;
+        use ven_pretty::DocAllocator;
+
+        let line_col_region = self.line_info.convert_region(expect_region);
+        let severity = Severity::RuntimeError;
+
+        let doc = self.alloc.stack([
+            self.alloc.text(format!(
+                "This expectation was still running after {} ms, so I stopped it:",
+                timeout.as_millis()
+            )),
+            self.alloc.region(line_col_region, severity),
+            self.alloc.concat([
+                self.alloc.tip(),
+                self.alloc.reflow("If it needs more time, pass a larger "),
+                self.alloc.keyword("--timeout"),
+                self.alloc.reflow(" to "),
+                self.alloc.keyword("roc test"),
+                self.alloc.reflow("."),
+            ]),
+        ]);
+
+        let report = Report {
+            title: "EXPECT TIMED OUT".into(),
+            code: Some(codes::EXPECT_TIMED_OUT),
             doc,
             filename: self.filename.clone(),
             severity,
+            fixes: Vec::new(),
         };
 
         let mut buf = String::new();
@@ -3,6 +3,7 @@ use roc_problem::Severity;
 use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Position, Region};
 use std::path::PathBuf;
 
+use crate::error::codes;
 use crate::report::{Report, RocDocAllocator, RocDocBuilder};
 use ven_pretty::DocAllocator;
 
@@ -75,7 +76,9 @@ fn to_syntax_report<'a>(
         filename: filename.clone(),
         doc,
         title: "PARSE PROBLEM".to_string(),
+        code: Some(codes::PARSE_PROBLEM),
         severity,
+        fixes: Vec::new(),
     };
 
     match parse_problem {
@@ -89,7 +92,9 @@ fn to_syntax_report<'a>(
                 filename,
                 doc,
                 title: "PARSE PROBLEM".to_string(),
+                code: Some(codes::TOKENS_BEFORE_EQUALS),
                 severity,
+                fixes: Vec::new(),
             }
         }
         Unexpected(region) => {
@@ -121,7 +126,9 @@ fn to_syntax_report<'a>(
                 filename,
                 doc,
                 title: "NOT END OF FILE".to_string(),
+                code: Some(codes::NOT_END_OF_FILE),
                 severity,
+                fixes: Vec::new(),
             }
         }
         SyntaxError::Eof(region) => {
@@ -134,7 +141,9 @@ fn to_syntax_report<'a>(
                 filename,
                 doc,
                 title: "PARSE PROBLEM".to_string(),
+                code: Some(codes::UNEXPECTED_END_OF_FIELD),
                 severity,
+                fixes: Vec::new(),
             }
         }
         SyntaxError::OutdentedTooFar => {
@@ -144,7 +153,9 @@ fn to_syntax_report<'a>(
                 filename,
                 doc,
                 title: "PARSE PROBLEM".to_string(),
+                code: Some(codes::OUTDENTED_TOO_FAR),
                 severity,
+                fixes: Vec::new(),
             }
         }
         Type(typ) => to_type_report(alloc, lines, filename, typ, Position::default()),
@@ -236,7 +247,9 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "ARGUMENTS BEFORE EQUALS".to_string(),
+                code: Some(codes::ARGUMENTS_BEFORE_EQUALS),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -352,7 +365,9 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "UNKNOWN OPERATOR".to_string(),
+                code: Some(codes::UNKNOWN_OPERATOR),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -379,14 +394,16 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD IDENTIFIER".to_string(),
+                code: Some(codes::WEIRD_IDENTIFIER),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
         EExpr::Start(pos) | EExpr::IndentStart(pos) => {
-            let (title, expecting) = match &context {
+            let (code, expecting) = match &context {
                 Context::InNode { .. } | Context::InDef { .. } => (
-                    "MISSING EXPRESSION",
+                    codes::MISSING_EXPRESSION,
                     alloc.concat([
                         alloc.reflow("I was expecting to see an expression like "),
                         alloc.parser_suggestion("42"),
@@ -396,7 +413,7 @@ fn to_expr_report<'a>(
                     ]),
                 ),
                 Context::InDefFinalExpr { .. } => (
-                    "MISSING FINAL EXPRESSION",
+                    codes::MISSING_FINAL_EXPRESSION,
                     alloc.stack([
                         alloc.concat([
                             alloc.reflow("This definition is missing a final expression."),
@@ -461,8 +478,10 @@ fn to_expr_report<'a>(
             Report {
                 filename,
                 doc,
-                title: title.to_string(),
+                title: code.title.to_string(),
+                code: Some(code),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -490,7 +509,9 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "MISSING FINAL EXPRESSION".to_string(),
+                code: Some(codes::MISSING_FINAL_EXPRESSION),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -521,7 +542,9 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "SYNTAX PROBLEM".to_string(),
+                code: Some(codes::CONFUSING_EXPRESSION),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -544,7 +567,9 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "ARGUMENTS BEFORE EQUALS".to_string(),
+                code: Some(codes::ARGUMENTS_BEFORE_EQUALS),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -602,7 +627,9 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "INDENT ENDS AFTER EXPRESSION".to_string(),
+                code: Some(codes::INDENT_ENDS_AFTER_EXPRESSION),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EExpr::Expect(e_expect, _position) => {
@@ -629,7 +656,9 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "TRAILING OPERATOR".to_string(),
+                code: Some(codes::TRAILING_OPERATOR),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EExpr::UnexpectedComma(pos) => {
@@ -646,7 +675,9 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "UNEXPECTED COMMA".to_string(),
+                code: Some(codes::UNEXPECTED_COMMA),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EExpr::Return(EReturn::Return(pos) | EReturn::IndentReturnValue(pos), start) => {
@@ -734,7 +765,9 @@ fn to_unhandled_parse_error_report<'a>(
         filename,
         doc,
         title: "UNHANDLED PARSE ERROR".to_string(),
+        code: Some(codes::UNHANDLED_PARSE_ERROR),
         severity,
+        fixes: Vec::new(),
     }
 }
 
@@ -760,7 +793,9 @@ fn to_record_report<'a>(
         filename,
         doc,
         title: "RECORD PARSE PROBLEM".to_string(),
+        code: Some(codes::RECORD_PARSE_PROBLEM),
         severity,
+        fixes: Vec::new(),
     }
 }
 
@@ -794,7 +829,9 @@ fn to_lambda_report<'a>(
                 filename,
                 doc,
                 title: "MALFORMED ARGS LIST".to_string(),
+                code: Some(codes::MALFORMED_ARGS_LIST),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EClosure::Arrow(pos) => match what_is_next(alloc.src_lines, lines.convert_pos(pos)) {
@@ -817,7 +854,9 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "WEIRD ARROW".to_string(),
+                    code: Some(codes::WEIRD_ARROW),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             _ => {
@@ -839,7 +878,9 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "MISSING ARROW".to_string(),
+                    code: Some(codes::MISSING_ARROW),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
         },
@@ -864,7 +905,9 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "WEIRD ARROW".to_string(),
+                    code: Some(codes::WEIRD_ARROW),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             _ => {
@@ -886,7 +929,9 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "MISSING ARROW".to_string(),
+                    code: Some(codes::MISSING_ARROW),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
         },
@@ -910,7 +955,9 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED ARGUMENT LIST".to_string(),
+                    code: Some(codes::UNFINISHED_ARGUMENT_LIST),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             _ => {
@@ -931,7 +978,9 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "MISSING ARROW".to_string(),
+                    code: Some(codes::MISSING_ARROW),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
         },
@@ -1013,7 +1062,9 @@ fn to_unfinished_lambda_report<'a>(
         filename,
         doc,
         title: "UNFINISHED FUNCTION".to_string(),
+        code: Some(codes::UNFINISHED_FUNCTION),
         severity,
+        fixes: Vec::new(),
     }
 }
 
@@ -1078,7 +1129,9 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD ESCAPE".to_string(),
+                code: Some(codes::WEIRD_ESCAPE),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EString::CodePtOpen(pos) | EString::CodePtEnd(pos) => {
@@ -1104,7 +1157,9 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD CODE POINT".to_string(),
+                code: Some(codes::WEIRD_CODE_POINT),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EString::FormatEnd(pos) => {
@@ -1125,7 +1180,9 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "ENDLESS FORMAT".to_string(),
+                code: Some(codes::ENDLESS_FORMAT),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EString::EndlessSingleQuote(pos) => {
@@ -1148,7 +1205,9 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "ENDLESS SCALAR".to_string(),
+                code: Some(codes::ENDLESS_SCALAR),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EString::InvalidSingleQuote(e, pos) => {
@@ -1214,7 +1273,9 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "INVALID SCALAR".to_string(),
+                code: Some(codes::INVALID_SCALAR),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EString::EndlessSingleLine(pos) => {
@@ -1237,7 +1298,9 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "ENDLESS STRING".to_string(),
+                code: Some(codes::ENDLESS_STRING),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EString::ExpectedDoubleQuoteGotSingleQuote(pos) => {
@@ -1261,7 +1324,9 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "EXPECTED STRING".to_string(),
+                code: Some(codes::EXPECTED_STRING),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EString::EndlessMultiLine(pos) => {
@@ -1284,7 +1349,9 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "ENDLESS STRING".to_string(),
+                code: Some(codes::ENDLESS_STRING),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EString::MultilineInsufficientIndent(pos) => {
@@ -1305,7 +1372,9 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "INSUFFICIENT INDENT IN MULTI-LINE STRING".to_string(),
+                code: Some(codes::INSUFFICIENT_INDENT_IN_MULTI_LINE_STRING),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EString::InvalidUnicodeCodepoint(region) => {
@@ -1330,7 +1399,9 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "INVALID UNICODE CODE POINT".to_string(),
+                code: Some(codes::INVALID_UNICODE_CODE_POINT),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EString::UnicodeEscapeTooLarge(region) => {
@@ -1351,7 +1422,9 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "UNICODE CODE POINT TOO LARGE".to_string(),
+                code: Some(codes::UNICODE_CODE_POINT_TOO_LARGE),
                 severity,
+                fixes: Vec::new(),
             }
         }
     }
@@ -1393,7 +1466,9 @@ fn to_expr_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "EMPTY PARENTHESES".to_string(),
+                code: Some(codes::EMPTY_PARENTHESES),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EInParens::End(pos) => {
@@ -1417,7 +1492,9 @@ fn to_expr_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
+                code: Some(codes::UNFINISHED_PARENTHESES),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EInParens::Open(pos) => {
@@ -1442,7 +1519,9 @@ fn to_expr_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
+                code: Some(codes::UNFINISHED_PARENTHESES),
                 severity,
+                fixes: Vec::new(),
             }
         }
     }
@@ -1496,7 +1575,9 @@ fn to_list_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED LIST".to_string(),
+                        code: Some(codes::UNFINISHED_LIST),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 _ => {
@@ -1533,7 +1614,9 @@ fn to_list_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED LIST".to_string(),
+                        code: Some(codes::UNFINISHED_LIST),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
             }
@@ -1659,7 +1742,9 @@ fn to_import_report<'a>(
                 filename,
                 doc,
                 title: "IGNORED RECORD FIELD IN MODULE PARAMS".to_string(),
+                code: Some(codes::IGNORED_RECORD_FIELD_IN_MODULE_PARAMS),
                 severity,
+                fixes: Vec::new(),
             }
         }
         Params(EImportParams::RecordUpdateFound(region), _) => {
@@ -1676,7 +1761,9 @@ fn to_import_report<'a>(
                 filename,
                 doc,
                 title: "RECORD UPDATE IN MODULE PARAMS".to_string(),
+                code: Some(codes::RECORD_UPDATE_IN_MODULE_PARAMS),
                 severity,
+                fixes: Vec::new(),
             }
         }
         Params(EImportParams::RecordBuilderFound(region), _) => {
@@ -1693,7 +1780,9 @@ fn to_import_report<'a>(
                 filename,
                 doc,
                 title: "RECORD BUILDER IN MODULE PARAMS".to_string(),
+                code: Some(codes::RECORD_BUILDER_IN_MODULE_PARAMS),
                 severity,
+                fixes: Vec::new(),
             }
         }
         IndentAlias(pos) | Alias(pos) => to_unfinished_import_report(
@@ -1722,7 +1811,9 @@ fn to_import_report<'a>(
                 filename,
                 doc,
                 title: "LOWERCASE ALIAS".to_string(),
+                code: Some(codes::LOWERCASE_ALIAS),
                 severity,
+                fixes: Vec::new(),
             }
         }
         ExposingListStart(pos) => to_unfinished_import_report(
@@ -1757,7 +1848,9 @@ fn to_import_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD EXPOSING".to_string(),
+                code: Some(codes::WEIRD_EXPOSING),
                 severity,
+                fixes: Vec::new(),
             }
         }
         IndentIngestedName(pos) | IngestedName(pos) => to_unfinished_import_report(
@@ -1819,7 +1912,9 @@ fn to_unfinished_import_report<'a>(
         filename,
         doc,
         title: "UNFINISHED IMPORT".to_string(),
+        code: Some(codes::UNFINISHED_IMPORT),
         severity,
+        fixes: Vec::new(),
     }
 }
 
@@ -1932,7 +2027,9 @@ fn to_unfinished_if_report<'a>(
         filename,
         doc,
         title: "UNFINISHED IF".to_string(),
+        code: Some(codes::UNFINISHED_IF),
         severity,
+        fixes: Vec::new(),
     }
 }
 
@@ -1969,7 +2066,9 @@ fn to_when_report<'a>(
                         filename,
                         doc,
                         title: "IF GUARD NO CONDITION".to_string(),
+                        code: Some(codes::IF_GUARD_NO_CONDITION),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 _ => to_expr_report(
@@ -2001,7 +2100,9 @@ fn to_when_report<'a>(
                 filename,
                 doc,
                 title: "MISSING ARROW".to_string(),
+                code: Some(codes::MISSING_ARROW),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2156,7 +2257,9 @@ fn to_unfinished_when_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED WHEN".to_string(),
+                code: Some(codes::UNFINISHED_WHEN),
                 severity,
+                fixes: Vec::new(),
             }
         }
     }
@@ -2198,7 +2301,9 @@ fn to_unexpected_arrow_report<'a>(
         filename,
         doc,
         title: "UNEXPECTED ARROW".to_string(),
+        code: Some(codes::UNEXPECTED_ARROW),
         severity,
+        fixes: Vec::new(),
     }
 }
 
@@ -2275,7 +2380,9 @@ fn to_pattern_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PATTERN".to_string(),
+                code: Some(codes::UNFINISHED_PATTERN),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EPattern::Str(err, pos) => to_str_report(alloc, lines, filename, err, *pos),
@@ -2343,7 +2450,9 @@ fn to_precord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
+                    code: Some(codes::UNFINISHED_RECORD_PATTERN),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             _ => {
@@ -2364,7 +2473,9 @@ fn to_precord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
+                    code: Some(codes::UNFINISHED_RECORD_PATTERN),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
         },
@@ -2389,7 +2500,9 @@ fn to_precord_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED RECORD PATTERN".to_string(),
+                        code: Some(codes::UNFINISHED_RECORD_PATTERN),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 _ => {
@@ -2409,7 +2522,9 @@ fn to_precord_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED RECORD PATTERN".to_string(),
+                        code: Some(codes::UNFINISHED_RECORD_PATTERN),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
             }
@@ -2434,7 +2549,9 @@ fn to_precord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
+                    code: Some(codes::UNFINISHED_RECORD_PATTERN),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             Next::Other(Some(',')) => todo!(),
@@ -2459,7 +2576,9 @@ fn to_precord_report<'a>(
                     filename,
                     doc,
                     title: "PROBLEM IN RECORD PATTERN".to_string(),
+                    code: Some(codes::PROBLEM_IN_RECORD_PATTERN),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
         },
@@ -2509,7 +2628,9 @@ fn to_plist_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED LIST PATTERN".to_string(),
+                code: Some(codes::UNFINISHED_LIST_PATTERN),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2531,7 +2652,9 @@ fn to_plist_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED LIST PATTERN".to_string(),
+                code: Some(codes::UNFINISHED_LIST_PATTERN),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2553,7 +2676,9 @@ fn to_plist_report<'a>(
                 filename,
                 doc,
                 title: "INCORRECT REST PATTERN".to_string(),
+                code: Some(codes::INCORRECT_REST_PATTERN),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2598,7 +2723,9 @@ fn to_pattern_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
+                code: Some(codes::UNFINISHED_PARENTHESES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2620,7 +2747,9 @@ fn to_pattern_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "EMPTY PARENTHESES".to_string(),
+                code: Some(codes::EMPTY_PARENTHESES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2644,7 +2773,9 @@ fn to_pattern_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
+                code: Some(codes::UNFINISHED_PARENTHESES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2673,7 +2804,9 @@ fn to_malformed_number_literal_report<'a>(
         filename,
         doc,
         title: "INVALID NUMBER LITERAL".to_string(),
+        code: Some(codes::INVALID_NUMBER_LITERAL),
         severity,
+        fixes: Vec::new(),
     }
 }
 
@@ -2714,7 +2847,9 @@ fn to_type_report<'a>(
                         filename,
                         doc,
                         title: "DOUBLE COMMA".to_string(),
+                        code: Some(codes::DOUBLE_COMMA),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 Next::Other(_) | Next::Keyword(_) | Next::Close(_, _) | Next::Token(_) => todo!(),
@@ -2741,7 +2876,9 @@ fn to_type_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
+                code: Some(codes::UNFINISHED_TYPE),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2759,7 +2896,9 @@ fn to_type_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
+                code: Some(codes::UNFINISHED_TYPE),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2777,7 +2916,9 @@ fn to_type_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
+                code: Some(codes::UNFINISHED_TYPE),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2795,7 +2936,9 @@ fn to_type_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED INLINE ALIAS".to_string(),
+                code: Some(codes::UNFINISHED_INLINE_ALIAS),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2812,7 +2955,9 @@ fn to_type_report<'a>(
                 filename,
                 doc,
                 title: "BAD TYPE VARIABLE".to_string(),
+                code: Some(codes::BAD_TYPE_VARIABLE),
                 severity,
+                fixes: Vec::new(),
             }
         }
         // If you're adding or changing syntax, please handle the case with a
@@ -2866,7 +3011,9 @@ fn to_trecord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
+                    code: Some(codes::UNFINISHED_RECORD_TYPE),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             _ => {
@@ -2891,7 +3038,9 @@ fn to_trecord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
+                    code: Some(codes::UNFINISHED_RECORD_TYPE),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
         },
@@ -2916,7 +3065,9 @@ fn to_trecord_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
+                        code: Some(codes::UNFINISHED_RECORD_TYPE),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 _ => {
@@ -2936,7 +3087,9 @@ fn to_trecord_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
+                        code: Some(codes::UNFINISHED_RECORD_TYPE),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
             }
@@ -2961,7 +3114,9 @@ fn to_trecord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
+                    code: Some(codes::UNFINISHED_RECORD_TYPE),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             Next::Other(Some(',')) => todo!(),
@@ -2986,7 +3141,9 @@ fn to_trecord_report<'a>(
                     filename,
                     doc,
                     title: "PROBLEM IN RECORD TYPE".to_string(),
+                    code: Some(codes::PROBLEM_IN_RECORD_TYPE),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
         },
@@ -3019,7 +3176,9 @@ fn to_trecord_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED RECORD TYPE".to_string(),
+                code: Some(codes::UNFINISHED_RECORD_TYPE),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3043,7 +3202,9 @@ fn to_trecord_report<'a>(
                         filename,
                         doc,
                         title: "NEED MORE INDENTATION".to_string(),
+                        code: Some(codes::NEED_MORE_INDENTATION),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 None => {
@@ -3072,7 +3233,9 @@ fn to_trecord_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
+                        code: Some(codes::UNFINISHED_RECORD_TYPE),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
             }
@@ -3121,7 +3284,9 @@ fn to_ttag_union_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED TAG UNION TYPE".to_string(),
+                    code: Some(codes::UNFINISHED_TAG_UNION_TYPE),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             Next::Other(Some(c)) if c.is_alphabetic() => {
@@ -3147,7 +3312,9 @@ fn to_ttag_union_report<'a>(
                     filename,
                     doc,
                     title: "WEIRD TAG NAME".to_string(),
+                    code: Some(codes::WEIRD_TAG_NAME),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             _ => {
@@ -3172,7 +3339,9 @@ fn to_ttag_union_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED TAG UNION TYPE".to_string(),
+                    code: Some(codes::UNFINISHED_TAG_UNION_TYPE),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
         },
@@ -3202,7 +3371,9 @@ fn to_ttag_union_report<'a>(
                         filename,
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
+                        code: Some(codes::WEIRD_TAG_NAME),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 _ => {
@@ -3222,7 +3393,9 @@ fn to_ttag_union_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED TAG UNION TYPE".to_string(),
+                        code: Some(codes::UNFINISHED_TAG_UNION_TYPE),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
             }
@@ -3267,7 +3440,9 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
+                        code: Some(codes::UNFINISHED_PARENTHESES),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 Next::Other(Some(c)) if c.is_alphabetic() => {
@@ -3289,7 +3464,9 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
+                        code: Some(codes::WEIRD_TAG_NAME),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 _ => {
@@ -3316,7 +3493,9 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
+                        code: Some(codes::UNFINISHED_PARENTHESES),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
             }
@@ -3339,7 +3518,9 @@ fn to_tinparens_report<'a>(
                 filename,
                 doc,
                 title: "EMPTY PARENTHESES".to_string(),
+                code: Some(codes::EMPTY_PARENTHESES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3365,7 +3546,9 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
+                        code: Some(codes::WEIRD_TAG_NAME),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 _ => {
@@ -3385,7 +3568,9 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
+                        code: Some(codes::UNFINISHED_PARENTHESES),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
             }
@@ -3413,7 +3598,9 @@ fn to_tinparens_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
+                code: Some(codes::UNFINISHED_PARENTHESES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3437,7 +3624,9 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "NEED MORE INDENTATION".to_string(),
+                        code: Some(codes::NEED_MORE_INDENTATION),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 None => {
@@ -3462,7 +3651,9 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
+                        code: Some(codes::UNFINISHED_PARENTHESES),
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
             }
@@ -3496,7 +3687,9 @@ fn to_tapply_report<'a>(
                 filename,
                 doc,
                 title: "DOUBLE DOT".to_string(),
+                code: Some(codes::DOUBLE_DOT),
                 severity,
+                fixes: Vec::new(),
             }
         }
         ETypeApply::TrailingDot(pos) => {
@@ -3518,7 +3711,9 @@ fn to_tapply_report<'a>(
                 filename,
                 doc,
                 title: "TRAILING DOT".to_string(),
+                code: Some(codes::TRAILING_DOT),
                 severity,
+                fixes: Vec::new(),
             }
         }
         ETypeApply::StartIsNumber(pos) => {
@@ -3540,7 +3735,9 @@ fn to_tapply_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD QUALIFIED NAME".to_string(),
+                code: Some(codes::WEIRD_QUALIFIED_NAME),
                 severity,
+                fixes: Vec::new(),
             }
         }
         ETypeApply::StartNotUppercase(pos) => {
@@ -3562,7 +3759,9 @@ fn to_tapply_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD QUALIFIED NAME".to_string(),
+                code: Some(codes::WEIRD_QUALIFIED_NAME),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3580,7 +3779,9 @@ fn to_tapply_report<'a>(
                 filename,
                 doc,
                 title: "END OF FILE".to_string(),
+                code: Some(codes::END_OF_FILE),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3621,7 +3822,9 @@ fn to_talias_report<'a>(
                 filename,
                 doc,
                 title: "NOT AN INLINE ALIAS".to_string(),
+                code: Some(codes::NOT_AN_INLINE_ALIAS),
                 severity,
+                fixes: Vec::new(),
             }
         }
         ETypeInlineAlias::Qualified(pos) => {
@@ -3637,7 +3840,9 @@ fn to_talias_report<'a>(
                 filename,
                 doc,
                 title: "QUALIFIED ALIAS NAME".to_string(),
+                code: Some(codes::QUALIFIED_ALIAS_NAME),
                 severity,
+                fixes: Vec::new(),
             }
         }
         ETypeInlineAlias::ArgumentNotLowercase(pos) => {
@@ -3653,7 +3858,9 @@ fn to_talias_report<'a>(
                 filename,
                 doc,
                 title: "TYPE ARGUMENT NOT LOWERCASE".to_string(),
+                code: Some(codes::TYPE_ARGUMENT_NOT_LOWERCASE),
                 severity,
+                fixes: Vec::new(),
             }
         }
     }
@@ -3703,7 +3910,9 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "INCOMPLETE HEADER".to_string(),
+                code: Some(codes::INCOMPLETE_HEADER),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3745,7 +3954,9 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "MISSING HEADER".to_string(),
+                code: Some(codes::MISSING_HEADER),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3769,7 +3980,9 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
+                code: Some(codes::WEIRD_MODULE_NAME),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3796,7 +4009,9 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
+                code: Some(codes::WEIRD_MODULE_NAME),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3820,7 +4035,9 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD APP NAME".to_string(),
+                code: Some(codes::WEIRD_APP_NAME),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3842,7 +4059,9 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "INVALID PACKAGE NAME".to_string(),
+                code: Some(codes::INVALID_PACKAGE_NAME),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3865,7 +4084,9 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "INVALID PLATFORM NAME".to_string(),
+                code: Some(codes::INVALID_PLATFORM_NAME),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3906,7 +4127,9 @@ fn to_provides_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
+                code: Some(codes::WEIRD_PROVIDES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3931,7 +4154,9 @@ fn to_provides_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
+                code: Some(codes::WEIRD_PROVIDES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3958,7 +4183,9 @@ fn to_provides_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
+                code: Some(codes::WEIRD_PROVIDES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -3979,7 +4206,9 @@ fn to_provides_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
+                code: Some(codes::WEIRD_PROVIDES),
                 severity,
+                fixes: Vec::new(),
             }
         }
         // If you're adding or changing syntax, please handle the case with a
@@ -4029,7 +4258,9 @@ fn to_params_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD MODULE PARAMS".to_string(),
+                code: Some(codes::WEIRD_MODULE_PARAMS),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4073,7 +4304,9 @@ fn to_exposes_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD EXPOSES".to_string(),
+                code: Some(codes::WEIRD_EXPOSES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4096,7 +4329,9 @@ fn to_exposes_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD EXPOSES".to_string(),
+                code: Some(codes::WEIRD_EXPOSES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4133,7 +4368,9 @@ fn to_exposes_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD EXPOSES".to_string(),
+                code: Some(codes::WEIRD_EXPOSES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4183,7 +4420,9 @@ fn to_imports_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
+                code: Some(codes::WEIRD_IMPORTS),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4208,7 +4447,9 @@ fn to_imports_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
+                code: Some(codes::WEIRD_IMPORTS),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4234,7 +4475,9 @@ fn to_imports_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
+                code: Some(codes::WEIRD_MODULE_NAME),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4253,7 +4496,9 @@ fn to_imports_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
+                code: Some(codes::WEIRD_IMPORTS),
                 severity,
+                fixes: Vec::new(),
             }
         }
         // If you're adding or changing syntax, please handle the case with a
@@ -4314,7 +4559,9 @@ fn to_requires_report<'a>(
                 filename,
                 doc,
                 title: "MISSING REQUIRES".to_string(),
+                code: Some(codes::MISSING_REQUIRES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4341,7 +4588,9 @@ fn to_requires_report<'a>(
                 filename,
                 doc,
                 title: "MISSING REQUIRES".to_string(),
+                code: Some(codes::MISSING_REQUIRES),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4370,7 +4619,9 @@ fn to_requires_report<'a>(
                 filename,
                 doc,
                 title: "BAD REQUIRES RIGIDS".to_string(),
+                code: Some(codes::BAD_REQUIRES_RIGIDS),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4399,7 +4650,9 @@ fn to_requires_report<'a>(
                 filename,
                 doc,
                 title: "BAD REQUIRES".to_string(),
+                code: Some(codes::BAD_REQUIRES),
                 severity,
+                fixes: Vec::new(),
             }
         }
         // If you're adding or changing syntax, please handle the case with a
@@ -4448,7 +4701,9 @@ fn to_packages_report<'a>(
                 filename,
                 doc,
                 title: "MISSING PACKAGES".to_string(),
+                code: Some(codes::MISSING_PACKAGES),
                 severity,
+                fixes: Vec::new(),
             }
         }
         EPackages::ListEnd(pos) => {
@@ -4470,7 +4725,9 @@ fn to_packages_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD PACKAGES LIST".to_string(),
+                code: Some(codes::WEIRD_PACKAGES_LIST),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4521,7 +4778,9 @@ fn to_space_report<'a>(
                 filename,
                 doc,
                 title: "TAB CHARACTER".to_string(),
+                code: Some(codes::TAB_CHARACTER),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4538,7 +4797,9 @@ fn to_space_report<'a>(
                 filename,
                 doc,
                 title: "ASCII CONTROL CHARACTER".to_string(),
+                code: Some(codes::ASCII_CONTROL_CHARACTER),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4555,7 +4816,9 @@ fn to_space_report<'a>(
                 filename,
                 doc,
                 title: "MISPLACED CARRIAGE RETURN".to_string(),
+                code: Some(codes::MISPLACED_CARRIAGE_RETURN),
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -4646,7 +4909,9 @@ fn to_unfinished_ability_report<'a>(
         filename,
         doc,
         title: "UNFINISHED ABILITY".to_string(),
+        code: Some(codes::UNFINISHED_ABILITY),
         severity,
+        fixes: Vec::new(),
     }
 }
 
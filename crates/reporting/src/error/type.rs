@@ -1,7 +1,8 @@
 #![allow(clippy::too_many_arguments)]
 
-use crate::error::canonicalize::{to_circular_def_doc, CIRCULAR_DEF};
-use crate::report::{Annotation, Report, RocDocAllocator, RocDocBuilder};
+use crate::error::canonicalize::to_circular_def_doc;
+use crate::error::codes::{self, ErrorCode};
+use crate::report::{Annotation, Fix, Report, RocDocAllocator, RocDocBuilder};
 use itertools::EitherOrBoth;
 use itertools::Itertools;
 use roc_can::constraint::{ExpectEffectfulReason, FxCallKind, FxSuffixKind};
@@ -15,7 +16,7 @@ use roc_module::called_via::{BinOp, CalledVia};
 use roc_module::ident::{IdentStr, Lowercase, TagName};
 use roc_module::symbol::Symbol;
 use roc_problem::Severity;
-use roc_region::all::{LineInfo, Region};
+use roc_region::all::{LineInfo, Position, Region};
 use roc_solve_problem::{
     NotDerivableContext, NotDerivableEq, TypeError, UnderivableReason, Unfulfilled,
 };
@@ -47,12 +48,14 @@ pub fn type_problem<'b>(
     let severity = problem.severity();
 
     let report =
-        move |title: String, doc: RocDocBuilder<'b>, filename: PathBuf| -> Option<Report<'b>> {
+        move |code: ErrorCode, doc: RocDocBuilder<'b>, filename: PathBuf| -> Option<Report<'b>> {
             Some(Report {
-                title,
+                title: code.title.to_string(),
+                code: Some(code),
                 filename,
                 doc,
                 severity,
+                fixes: Vec::new(),
             })
         };
 
@@ -73,7 +76,6 @@ pub fn type_problem<'b>(
             overall_type,
         )),
         UnexposedLookup(_, symbol) => {
-            let title = "UNRECOGNIZED NAME".to_string();
             let doc = alloc
                 .stack(vec![alloc
                     .reflow("The ")
@@ -82,14 +84,12 @@ pub fn type_problem<'b>(
                     .append(alloc.symbol_unqualified(symbol))])
                 .append(alloc.reflow("."));
 
-            report(title, doc, filename)
+            report(codes::UNRECOGNIZED_NAME, doc, filename)
         }
         UnfulfilledAbility(incomplete) => {
-            let title = "INCOMPLETE ABILITY IMPLEMENTATION".to_string();
-
             let doc = report_unfulfilled_ability(alloc, lines, incomplete, severity);
 
-            report(title, doc, filename)
+            report(codes::INCOMPLETE_ABILITY_IMPLEMENTATION, doc, filename)
         }
         BadExprMissingAbility(region, _category, _found, incomplete) => {
             if region == roc_can::DERIVED_REGION {
@@ -111,9 +111,11 @@ pub fn type_problem<'b>(
 
             let report = Report {
                 title: "TYPE MISMATCH".to_string(),
+                code: Some(codes::MISSING_ABILITY),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             };
             Some(report)
         }
@@ -133,22 +135,25 @@ pub fn type_problem<'b>(
 
             let report = Report {
                 title: "TYPE MISMATCH".to_string(),
+                code: Some(codes::MISSING_ABILITY),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             };
             Some(report)
         }
         Exhaustive(problem) => Some(exhaustive_problem(alloc, lines, filename, problem)),
         CircularDef(entries) => {
             let doc = to_circular_def_doc(alloc, lines, &entries, severity);
-            let title = CIRCULAR_DEF.to_string();
 
             Some(Report {
-                title,
+                title: codes::CIRCULAR_DEFINITION.title.to_string(),
+                code: Some(codes::CIRCULAR_DEFINITION),
                 filename,
                 doc,
                 severity,
+                fixes: Vec::new(),
             })
         }
         StructuralSpecialization {
@@ -176,9 +181,11 @@ pub fn type_problem<'b>(
 
             Some(Report {
                 title: "ILLEGAL SPECIALIZATION".to_string(),
+                code: Some(codes::ILLEGAL_SPECIALIZATION),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         WrongSpecialization {
@@ -205,9 +212,11 @@ pub fn type_problem<'b>(
 
             Some(Report {
                 title: "WRONG SPECIALIZATION TYPE".to_string(),
+                code: Some(codes::WRONG_SPECIALIZATION_TYPE),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         IngestedFileBadUtf8(file_path, utf8_err) => {
@@ -221,9 +230,11 @@ pub fn type_problem<'b>(
             ];
             Some(Report {
                 title: "INVALID UTF-8".to_string(),
+                code: Some(codes::INVALID_UTF_8),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         IngestedFileUnsupportedType(file_path, typ) => {
@@ -244,9 +255,11 @@ pub fn type_problem<'b>(
             ];
             Some(Report {
                 title: "INVALID TYPE FOR INGESTED FILE".to_string(),
+                code: Some(codes::INVALID_TYPE_FOR_INGESTED_FILE),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         UnexpectedModuleParams(region, module_id) => {
@@ -264,9 +277,11 @@ pub fn type_problem<'b>(
 
             Some(Report {
                 title: "UNEXPECTED MODULE PARAMS".to_string(),
+                code: Some(codes::UNEXPECTED_MODULE_PARAMS),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         MissingModuleParams(region, module_id, expected) => {
@@ -286,9 +301,11 @@ pub fn type_problem<'b>(
             ];
             Some(Report {
                 title: "MISSING MODULE PARAMS".to_string(),
+                code: Some(codes::MISSING_MODULE_PARAMS),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         ModuleParamsMismatch(region, module_id, actual_type, expected_type) => {
@@ -311,9 +328,11 @@ pub fn type_problem<'b>(
             ];
             Some(Report {
                 title: "MODULE PARAMS MISMATCH".to_string(),
+                code: Some(codes::MODULE_PARAMS_MISMATCH),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         FxInPureFunction(fx_call_region, fx_call_kind, ann_region) => {
@@ -345,8 +364,10 @@ pub fn type_problem<'b>(
             Some(Report {
                 filename,
                 title: "EFFECT IN PURE FUNCTION".to_string(),
+                code: Some(codes::EFFECT_IN_PURE_FUNCTION),
                 doc: alloc.stack(lines),
                 severity,
+                fixes: Vec::new(),
             })
         }
         FxInTopLevel(call_region, fx_call_kind) => {
@@ -365,8 +386,10 @@ pub fn type_problem<'b>(
             Some(Report {
                 filename,
                 title: "EFFECT IN TOP-LEVEL".to_string(),
+                code: Some(codes::EFFECT_IN_TOP_LEVEL),
                 doc: alloc.stack(lines),
                 severity,
+                fixes: Vec::new(),
             })
         }
         ExpectedEffectful(region, ExpectEffectfulReason::Stmt) => {
@@ -380,9 +403,11 @@ pub fn type_problem<'b>(
             ];
             Some(Report {
                 title: "LEFTOVER STATEMENT".to_string(),
+                code: Some(codes::LEFTOVER_STATEMENT),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         ExpectedEffectful(region, ExpectEffectfulReason::Ignored) => {
@@ -394,9 +419,11 @@ pub fn type_problem<'b>(
 
             Some(Report {
                 title: "UNNECESSARY DEFINITION".to_string(),
+                code: Some(codes::UNNECESSARY_DEFINITION),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         UnsuffixedEffectfulFunction(
@@ -423,9 +450,11 @@ pub fn type_problem<'b>(
             ];
             Some(Report {
                 title: "MISSING EXCLAMATION".to_string(),
+                code: Some(codes::MISSING_EXCLAMATION),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         UnsuffixedEffectfulFunction(region, FxSuffixKind::UnsuffixedRecordField) => {
@@ -442,9 +471,11 @@ pub fn type_problem<'b>(
             ];
             Some(Report {
                 title: "MISSING EXCLAMATION".to_string(),
+                code: Some(codes::MISSING_EXCLAMATION),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         SuffixedPureFunction(region, kind) => {
@@ -468,9 +499,11 @@ pub fn type_problem<'b>(
 
             Some(Report {
                 title: "UNNECESSARY EXCLAMATION".to_string(),
+                code: Some(codes::UNNECESSARY_EXCLAMATION),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         InvalidTryTarget(region, actual_type, try_kind) => {
@@ -508,9 +541,11 @@ pub fn type_problem<'b>(
 
             Some(Report {
                 title: "INVALID TRY TARGET".to_string(),
+                code: Some(codes::INVALID_TRY_TARGET),
                 filename,
                 doc: alloc.stack(stack),
                 severity,
+                fixes: Vec::new(),
             })
         }
         TypeIsNotGeneralized(region, actual_type, generalizable) => {
@@ -540,9 +575,11 @@ pub fn type_problem<'b>(
 
             Some(Report {
                 title: "TYPE VARIABLE IS NOT GENERIC".to_string(),
+                code: Some(codes::TYPE_VARIABLE_IS_NOT_GENERIC),
                 filename,
                 doc,
                 severity,
+                fixes: Vec::new(),
             })
         }
     }
@@ -746,7 +783,7 @@ pub fn cyclic_alias<'b>(
     others: Vec<Symbol>,
     alias_kind: AliasKind,
     severity: Severity,
-) -> (RocDocBuilder<'b>, String) {
+) -> (RocDocBuilder<'b>, ErrorCode) {
     let when_is_recursion_legal =
         alloc.reflow("Recursion in ")
         .append(alloc.reflow(alias_kind.as_str_plural()))
@@ -793,7 +830,7 @@ pub fn cyclic_alias<'b>(
         ])
     };
 
-    (doc, "CYCLIC ALIAS".to_string())
+    (doc, codes::CYCLIC_ALIAS)
 }
 
 fn report_mismatch<'b>(
@@ -836,9 +873,11 @@ fn report_mismatch<'b>(
 
     Report {
         title: "TYPE MISMATCH".to_string(),
+        code: Some(codes::TYPE_MISMATCH),
         filename,
         doc: alloc.stack(lines),
         severity,
+        fixes: Vec::new(),
     }
 }
 
@@ -880,9 +919,11 @@ fn report_bad_type<'b>(
 
     Report {
         title: "TYPE MISMATCH".to_string(),
+        code: Some(codes::TYPE_MISMATCH),
         filename,
         doc: alloc.stack(lines),
         severity,
+        fixes: Vec::new(),
     }
 }
 
@@ -964,12 +1005,14 @@ fn to_expr_report<'b>(
             Report {
                 filename,
                 title: "TYPE MISMATCH".to_string(),
+                code: Some(codes::TYPE_MISMATCH),
                 doc: alloc.stack([
                     alloc.text("This expression is used in an unexpected way:"),
                     alloc.region(lines.convert_region(expr_region), severity),
                     comparison,
                 ]),
                 severity,
+                fixes: Vec::new(),
             }
         }
         Expected::FromAnnotation(name, _arity, annotation_source, expected_type) => {
@@ -1105,6 +1148,7 @@ fn to_expr_report<'b>(
 
             Report {
                 title: "TYPE MISMATCH".to_string(),
+                code: Some(codes::ANNOTATION_MISMATCH),
                 filename,
                 doc: alloc.stack([
                     alloc.text("Something is off with the ").append(thing),
@@ -1121,6 +1165,7 @@ fn to_expr_report<'b>(
                     comparison,
                 ]),
                 severity,
+                fixes: Vec::new(),
             }
         }
         Expected::ForReason(reason, expected_type, region) => match reason {
@@ -1503,8 +1548,10 @@ fn to_expr_report<'b>(
                     Report {
                         filename,
                         title: "TOO MANY ARGS".to_string(),
+                        code: Some(codes::TOO_MANY_ARGS),
                         doc,
                         severity,
+                        fixes: Vec::new(),
                     }
                 }
                 DescribedFunction::Arguments(n) => {
@@ -1538,8 +1585,10 @@ fn to_expr_report<'b>(
                         Report {
                             filename,
                             title: "TOO MANY ARGS".to_string(),
+                            code: Some(codes::TOO_MANY_ARGS),
                             doc: alloc.stack(lines),
                             severity,
+                            fixes: Vec::new(),
                         }
                     } else {
                         let lines = vec![
@@ -1565,8 +1614,10 @@ fn to_expr_report<'b>(
                         Report {
                             filename,
                             title: "TOO FEW ARGS".to_string(),
+                            code: Some(codes::TOO_FEW_ARGS),
                             doc: alloc.stack(lines),
                             severity,
+                            fixes: Vec::new(),
                         }
                     }
                 }
@@ -1766,9 +1817,11 @@ fn to_expr_report<'b>(
 
                 Report {
                     title: "TYPE MISMATCH".to_string(),
+                    code: Some(codes::WHEN_CONDITION_MISMATCH),
                     filename,
                     doc: alloc.stack(lines),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
 
@@ -1804,8 +1857,10 @@ fn to_expr_report<'b>(
                 Report {
                     filename,
                     title: "TYPE MISMATCH".to_string(),
+                    code: Some(codes::ANNOTATED_ARGUMENT_MISMATCH),
                     doc,
                     severity,
+                    fixes: Vec::new(),
                 }
             }
 
@@ -1840,8 +1895,10 @@ fn to_expr_report<'b>(
                 Report {
                     filename,
                     title: "TYPE MISMATCH".to_string(),
+                    code: Some(codes::CRASH_ARGUMENT_MISMATCH),
                     doc: alloc.stack(lines),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
 
@@ -1895,6 +1952,7 @@ fn to_expr_report<'b>(
 
                 Report {
                     title: "TYPE MISMATCH".to_string(),
+                    code: Some(codes::RETURN_TYPE_MISMATCH),
                     filename,
                     doc: alloc.stack([
                         problem,
@@ -1906,6 +1964,7 @@ fn to_expr_report<'b>(
                         comparison,
                     ]),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
 
@@ -1944,8 +2003,10 @@ fn to_expr_report<'b>(
                 Report {
                     filename,
                     title: "IGNORED RESULT".to_string(),
+                    code: Some(codes::IGNORED_RESULT),
                     doc: alloc.stack(lines),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
 
@@ -1974,6 +2035,7 @@ fn to_expr_report<'b>(
 
                 Report {
                     title: "TYPE MISMATCH".to_string(),
+                    code: Some(codes::RETURN_TYPE_MISMATCH),
                     filename,
                     doc: alloc.stack([
                         problem,
@@ -1985,6 +2047,7 @@ fn to_expr_report<'b>(
                         comparison,
                     ]),
                     severity,
+                    fixes: Vec::new(),
                 }
             }
         },
@@ -2391,8 +2454,10 @@ fn to_pattern_report<'b>(
             Report {
                 filename,
                 title: "TYPE MISMATCH".to_string(),
+                code: Some(codes::PATTERN_MISMATCH),
                 doc,
                 severity,
+                fixes: Vec::new(),
             }
         }
 
@@ -2434,8 +2499,10 @@ fn to_pattern_report<'b>(
                 Report {
                     filename,
                     title: "TYPE MISMATCH".to_string(),
+                    code: Some(codes::ANNOTATED_ARGUMENT_MISMATCH),
                     doc,
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             PReason::WhenMatch { index, sub_pattern } => {
@@ -2516,8 +2583,10 @@ fn to_pattern_report<'b>(
                 Report {
                     filename,
                     title: "TYPE MISMATCH".to_string(),
+                    code: Some(codes::PATTERN_MISMATCH),
                     doc,
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             PReason::ListElem => {
@@ -2543,8 +2612,10 @@ fn to_pattern_report<'b>(
                 Report {
                     filename,
                     title: "TYPE MISMATCH".to_string(),
+                    code: Some(codes::PATTERN_MISMATCH),
                     doc,
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             PReason::TagArg { .. } | PReason::PatternGuard => {
@@ -2627,6 +2698,7 @@ fn to_circular_report<'b>(
 ) -> Report<'b> {
     Report {
         title: "CIRCULAR TYPE".to_string(),
+        code: Some(codes::CIRCULAR_TYPE),
         filename,
         doc: {
             alloc.stack([
@@ -2646,6 +2718,7 @@ fn to_circular_report<'b>(
             ])
         },
         severity,
+        fixes: Vec::new(),
     }
 }
 
@@ -2729,6 +2802,12 @@ pub mod suggest {
 
         options
     }
+
+    /// Whether `option` is close enough to `typo` to be what was meant, rather than just the
+    /// least different of some unrelated names: at most one edit for every three characters.
+    pub fn is_close(typo: &str, option: &str) -> bool {
+        distance::damerau_levenshtein(typo, option) * 3 <= typo.chars().count()
+    }
 }
 
 pub struct Comparison<'b> {
@@ -5286,6 +5365,15 @@ fn report_record_field_typo<'b>(
         actual_fields.into_iter().collect::<Vec<_>>(),
     );
 
+    let fixes = match suggestions.first() {
+        Some((nearest, _)) if suggest::is_close(field.as_str(), nearest.as_str()) => {
+            field_typo_fix(alloc, lines, field_region, field, nearest)
+                .into_iter()
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
     let doc = alloc.stack([
         header,
         alloc.region(lines.convert_region(field_region), severity),
@@ -5332,11 +5420,55 @@ fn report_record_field_typo<'b>(
     Report {
         filename,
         title: "TYPE MISMATCH".to_string(),
+        code: Some(codes::RECORD_FIELD_MISMATCH),
         doc,
         severity,
+        fixes,
     }
 }
 
+/// Replace the misspelled field name, whether `field_region` is a record access like `rec.fild`
+/// or a field of a record update like `fild: value`.
+fn field_typo_fix(
+    alloc: &RocDocAllocator,
+    lines: &LineInfo,
+    field_region: Region,
+    field: &Lowercase,
+    nearest: &Lowercase,
+) -> Option<Fix> {
+    let lc_region = lines.convert_region(field_region);
+    let field_len = field.as_str().len() as u32;
+
+    let end_line = alloc.src_lines.get(lc_region.end.line as usize)?;
+    let before_end = end_line.get(..lc_region.end.column as usize)?;
+
+    let start_line = alloc.src_lines.get(lc_region.start.line as usize)?;
+    let after_start = start_line.get(lc_region.start.column as usize..)?;
+
+    let region = if before_end.ends_with(&format!(".{field}")) {
+        let end = field_region.end();
+
+        Region::new(Position::new(end.offset - field_len), end)
+    } else if after_start
+        .strip_prefix(field.as_str())
+        .map_or(false, |rest| {
+            !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        })
+    {
+        let start = field_region.start();
+
+        Region::new(start, Position::new(start.offset + field_len))
+    } else {
+        return None;
+    };
+
+    Some(Fix::replace(
+        format!("Change {field} to {nearest}"),
+        region,
+        nearest.to_string(),
+    ))
+}
+
 fn exhaustive_problem<'a>(
     alloc: &'a RocDocAllocator<'a>,
     lines: &LineInfo,
@@ -5369,8 +5501,10 @@ fn exhaustive_problem<'a>(
                 Report {
                     filename,
                     title: "UNSAFE PATTERN".to_string(),
+                    code: Some(codes::UNSAFE_PATTERN),
                     doc,
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             BadDestruct => {
@@ -5393,8 +5527,10 @@ fn exhaustive_problem<'a>(
                 Report {
                     filename,
                     title: "UNSAFE PATTERN".to_string(),
+                    code: Some(codes::UNSAFE_PATTERN),
                     doc,
                     severity,
+                    fixes: Vec::new(),
                 }
             }
             BadCase => {
@@ -5417,8 +5553,10 @@ fn exhaustive_problem<'a>(
                 Report {
                     filename,
                     title: "UNSAFE PATTERN".to_string(),
+                    code: Some(codes::UNSAFE_PATTERN),
                     doc,
                     severity,
+                    fixes: Vec::new(),
                 }
             }
         },
@@ -5447,8 +5585,10 @@ fn exhaustive_problem<'a>(
             Report {
                 filename,
                 title: "REDUNDANT PATTERN".to_string(),
+                code: Some(codes::REDUNDANT_PATTERN),
                 doc,
                 severity,
+                fixes: Vec::new(),
             }
         }
         Unmatchable {
@@ -5476,8 +5616,10 @@ fn exhaustive_problem<'a>(
             Report {
                 filename,
                 title: "UNMATCHABLE PATTERN".to_string(),
+                code: Some(codes::UNMATCHABLE_PATTERN),
                 doc,
                 severity,
+                fixes: Vec::new(),
             }
         }
     }
@@ -1,8 +1,12 @@
+use crate::error::codes::{self, ErrorCode};
 use roc_module::ident::Ident;
 use roc_module::ident::{Lowercase, ModuleName, TagName, Uppercase};
 use roc_module::symbol::{Interns, ModuleId, ModuleIds, PQModuleName, PackageQualified, Symbol};
+use roc_parse::header::parse_header;
+use roc_parse::state::State;
 use roc_problem::Severity;
-use roc_region::all::LineColumnRegion;
+use roc_region::all::{LineColumnRegion, LineInfo, Region};
+use serde_json::json;
 use std::path::{Path, PathBuf};
 use std::{fmt, io};
 use ven_pretty::{text, BoxAllocator, DocAllocator, DocBuilder, Render, RenderAnnotated};
@@ -11,6 +15,8 @@ use ven_pretty::{text, BoxAllocator, DocAllocator, DocBuilder, Render, RenderAnn
 use byte_unit::Byte;
 #[cfg(not(target_family = "wasm"))]
 use roc_packaging::https::Problem;
+#[cfg(not(target_family = "wasm"))]
+use roc_packaging::tarball::{ManifestProblem, MANIFEST_FILENAME};
 
 pub use crate::error::canonicalize::can_problem;
 pub use crate::error::parse::parse_problem;
@@ -116,14 +122,110 @@ pub enum RenderTarget {
     ColorTerminal,
     Generic,
     LanguageServer,
+    /// One JSON object per report, for tools other than language servers to consume
+    Json,
 }
 
 /// A textual report.
 pub struct Report<'b> {
     pub title: String,
+    /// The kind of problem this is, which `roc explain` can tell you more about. Only reports that
+    /// aren't about a problem, like the summary of a test run, go without.
+    pub code: Option<ErrorCode>,
     pub filename: PathBuf,
     pub doc: RocDocBuilder<'b>,
     pub severity: Severity,
+    /// Edits to the source that would each resolve the problem, for editors to offer as quick
+    /// fixes. `roc check --fix` applies the fix when there's only one to choose from.
+    pub fixes: Vec<Fix>,
+}
+
+/// A machine-applicable change to the file a report is about
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fix {
+    /// What the fix does, e.g. for an editor to show in its quick fix menu
+    pub title: String,
+    pub edits: Vec<Edit>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Edit {
+    pub region: Region,
+    pub replacement: String,
+}
+
+impl Fix {
+    pub fn replace(title: String, region: Region, replacement: String) -> Self {
+        Fix {
+            title,
+            edits: vec![Edit {
+                region,
+                replacement,
+            }],
+        }
+    }
+}
+
+/// The line of the module `src` that a new import goes on, and whether it has imports already.
+///
+/// With imports, that's the line after the last of the ones following the header, counting the
+/// lines a multi-line import like `import Foo exposing [\n    bar,\n]` continues on. Without,
+/// it's the first line after the header that isn't blank, which the caller should keep apart from
+/// the new import with a blank line. It can be one past the last line, if that's all there is.
+pub fn new_import_line(src: &str) -> (usize, bool) {
+    let arena = bumpalo::Bump::new();
+    let header_end = match parse_header(&arena, State::new(src.as_bytes())) {
+        Ok((_, state)) => state.pos().offset as usize,
+        Err(_) => 0,
+    };
+
+    // The header can end partway through its last line, if it doesn't take the newline with it
+    let header = &src[..header_end];
+    let header_lines = header.matches('\n').count();
+    let first_line = if header.is_empty() || header.ends_with('\n') {
+        header_lines
+    } else {
+        header_lines + 1
+    };
+
+    let mut last_import_end = None;
+    let mut first_content = None;
+    let mut open_brackets = 0;
+    let mut in_import = false;
+
+    for (index, line) in src.lines().enumerate().skip(first_line) {
+        let is_blank = line.trim().is_empty();
+        let continues_import = in_import
+            && (open_brackets > 0 || (line.starts_with(char::is_whitespace) && !is_blank));
+
+        if line.starts_with("import ") || continues_import {
+            for char in line.chars() {
+                match char {
+                    '(' | '[' | '{' => open_brackets += 1,
+                    ')' | ']' | '}' => open_brackets -= 1,
+                    _ => {}
+                }
+            }
+
+            last_import_end = Some(index + 1);
+            in_import = true;
+        } else if is_blank || line.starts_with('#') {
+            if !is_blank {
+                first_content.get_or_insert(index);
+            }
+
+            in_import = false;
+        } else {
+            first_content.get_or_insert(index);
+
+            break;
+        }
+    }
+
+    match last_import_end {
+        Some(line) => (line, true),
+        None => (first_content.unwrap_or(src.lines().count()), false),
+    }
 }
 
 impl<'b> Report<'b> {
@@ -138,6 +240,7 @@ impl<'b> Report<'b> {
             RenderTarget::Generic => self.render_ci(buf, alloc),
             RenderTarget::ColorTerminal => self.render_color_terminal(buf, alloc, palette),
             RenderTarget::LanguageServer => self.render_language_server(buf, alloc),
+            RenderTarget::Json => self.render_json(buf, alloc, None),
         }
     }
 
@@ -167,14 +270,24 @@ impl<'b> Report<'b> {
             .expect(err_msg);
     }
 
+    /// The stable code for this kind of report, which `roc explain` can tell you more about
+    pub fn code(&self) -> Option<&'static str> {
+        self.code.map(|error_code| error_code.code)
+    }
+
     pub fn pretty(self, alloc: &'b RocDocAllocator<'b>) -> RocDocBuilder<'b> {
         if self.title.is_empty() {
             self.doc
         } else {
+            let title = match self.code() {
+                Some(code) => format!("{} [{}]", self.title, code),
+                None => self.title,
+            };
+
             let header = if self.filename == PathBuf::from("replfile.roc") {
-                crate::report::pretty_header(&self.title)
+                crate::report::pretty_header(&title)
             } else {
-                crate::report::pretty_header_with_path(&self.title, &self.filename)
+                crate::report::pretty_header_with_path(&title, &self.filename)
             };
 
             alloc.stack([alloc.text(header).annotate(Annotation::Header), self.doc])
@@ -193,6 +306,78 @@ impl<'b> Report<'b> {
             .expect(err_msg)
     }
 
+    /// Render as a single line of JSON. The message is the plain text `render_ci` would give,
+    /// minus the header, and any typo or syntax fixes the report suggests are listed separately.
+    /// Machine-applicable `fixes` have their edits' regions given as 1-based lines and columns.
+    pub fn render_json(
+        self,
+        buf: &mut String,
+        alloc: &'b RocDocAllocator<'b>,
+        opt_region: Option<LineColumnRegion>,
+    ) {
+        let err_msg = "<buffer is not a utf-8 encoded string>";
+        let mut message = String::new();
+        let mut suggestions = Vec::new();
+
+        self.doc
+            .1
+            .render_raw(70, &mut JsonWrite::new(&mut message, &mut suggestions))
+            .expect(err_msg);
+
+        let severity = match self.severity {
+            Severity::RuntimeError => "error",
+            Severity::Fatal => "fatal",
+            Severity::Warning => "warning",
+        };
+        // 1-based, like an editor shows it
+        let region = opt_region.map(|region| {
+            json!({
+                "start": { "line": region.start.line + 1, "column": region.start.column + 1 },
+                "end": { "line": region.end.line + 1, "column": region.end.column + 1 },
+            })
+        });
+
+        let fixes: Vec<_> = if self.fixes.is_empty() {
+            Vec::new()
+        } else {
+            let lines = LineInfo::new(&alloc.src_lines.join("\n"));
+
+            self.fixes
+                .iter()
+                .map(|fix| {
+                    let edits: Vec<_> = fix
+                        .edits
+                        .iter()
+                        .map(|edit| {
+                            let region = lines.convert_region(edit.region);
+
+                            json!({
+                                "start": { "line": region.start.line + 1, "column": region.start.column + 1 },
+                                "end": { "line": region.end.line + 1, "column": region.end.column + 1 },
+                                "replacement": edit.replacement,
+                            })
+                        })
+                        .collect();
+
+                    json!({ "title": fix.title, "edits": edits })
+                })
+                .collect()
+        };
+
+        let report = json!({
+            "title": self.title,
+            "code": self.code(),
+            "severity": severity,
+            "file": self.filename.display().to_string(),
+            "region": region,
+            "message": message.trim_end(),
+            "suggestions": suggestions,
+            "fixes": fixes,
+        });
+
+        buf.push_str(&report.to_string());
+    }
+
     pub fn horizontal_rule(palette: &'b Palette) -> String {
         format!("{}{}", palette.header, "─".repeat(80))
     }
@@ -981,6 +1166,23 @@ impl<W> CiWrite<W> {
     }
 }
 
+/// Render like `CiWrite`, but also collect the replacements the report suggests
+struct JsonWrite<'s, W> {
+    ci: CiWrite<W>,
+    suggestions: &'s mut Vec<String>,
+    opt_suggestion: Option<String>,
+}
+
+impl<'s, W> JsonWrite<'s, W> {
+    fn new(upstream: W, suggestions: &'s mut Vec<String>) -> JsonWrite<'s, W> {
+        JsonWrite {
+            ci: CiWrite::new(upstream),
+            suggestions,
+            opt_suggestion: None,
+        }
+    }
+}
+
 /// Render with fancy formatting
 pub struct ColorWrite<'a, W> {
     style_stack: Vec<Annotation>,
@@ -1085,6 +1287,55 @@ where
     }
 }
 
+impl<'s, W> Render for JsonWrite<'s, W>
+where
+    W: fmt::Write,
+{
+    type Error = fmt::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<usize, fmt::Error> {
+        self.write_str_all(s).map(|_| s.len())
+    }
+
+    fn write_str_all(&mut self, s: &str) -> fmt::Result {
+        if let Some(suggestion) = self.opt_suggestion.as_mut() {
+            suggestion.push_str(s);
+        }
+
+        self.ci.write_str_all(s)
+    }
+}
+
+impl<'s, W> RenderAnnotated<Annotation> for JsonWrite<'s, W>
+where
+    W: fmt::Write,
+{
+    fn push_annotation(&mut self, annotation: &Annotation) -> Result<(), Self::Error> {
+        self.ci.push_annotation(annotation)?;
+
+        // Suggestions highlighted inside a code block are parts of the source, not replacements
+        if let Annotation::TypoSuggestion | Annotation::ParserSuggestion = annotation {
+            if !self.ci.in_code_block && !self.ci.in_type_block && self.opt_suggestion.is_none() {
+                self.opt_suggestion = Some(String::new());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn pop_annotation(&mut self) -> Result<(), Self::Error> {
+        if let Some(Annotation::TypoSuggestion | Annotation::ParserSuggestion) =
+            self.ci.style_stack.last()
+        {
+            if let Some(suggestion) = self.opt_suggestion.take() {
+                self.suggestions.push(suggestion);
+            }
+        }
+
+        self.ci.pop_annotation()
+    }
+}
+
 impl<'a, W> Render for ColorWrite<'a, W>
 where
     W: fmt::Write,
@@ -1266,7 +1517,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "UNSUPPORTED ENCODING".to_string(),
+                code: Some(codes::UNSUPPORTED_ENCODING),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         Problem::MultipleEncodings(multiple_encodings) => {
@@ -1301,7 +1554,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "MULTIPLE ENCODINGS".to_string(),
+                code: Some(codes::MULTIPLE_ENCODINGS),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         Problem::InvalidContentHash { expected, actual } => {
@@ -1336,7 +1591,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "INVALID CONTENT HASH".to_string(),
+                code: Some(codes::INVALID_CONTENT_HASH),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         Problem::NotFound => {
@@ -1353,7 +1610,163 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "NOTFOUND".to_string(),
+                code: Some(codes::NOTFOUND),
+                severity: Severity::Fatal,
+                fixes: Vec::new(),
+            }
+        }
+        Problem::ContentsChanged(package_dir) => {
+            let doc = alloc.stack([
+                alloc.reflow(r"I found the package for this URL:"),
+                alloc
+                    .string((&url).to_string())
+                    .annotate(Annotation::Url)
+                    .indent(4),
+                alloc.reflow(r"in this directory:"),
+                alloc
+                    .string(package_dir.display().to_string())
+                    .annotate(Annotation::PlainText)
+                    .indent(4),
+                alloc.concat([
+                    alloc.reflow(r"But its files have changed since they were "),
+                    alloc.reflow(r"extracted from the package I verified, so they "),
+                    alloc.reflow(r"may have been tampered with."),
+                ]),
+                alloc.reflow(r"To keep you secure, I will not execute this untrusted code."),
+                alloc.concat([
+                    alloc.tip(),
+                    alloc.reflow(r"Delete the directory and I will download the package again."),
+                ]),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "PACKAGE FILES CHANGED".to_string(),
+                code: Some(codes::PACKAGE_FILES_CHANGED),
+                severity: Severity::Fatal,
+                fixes: Vec::new(),
+            }
+        }
+        Problem::ContentsHashMissing(package_dir) => {
+            let doc = alloc.stack([
+                alloc.reflow(r"I found the package for this URL:"),
+                alloc
+                    .string((&url).to_string())
+                    .annotate(Annotation::Url)
+                    .indent(4),
+                alloc.reflow(r"in this directory:"),
+                alloc
+                    .string(package_dir.display().to_string())
+                    .annotate(Annotation::PlainText)
+                    .indent(4),
+                alloc.concat([
+                    alloc.reflow(r"But there's no record of the files that were "),
+                    alloc.reflow(r"extracted into it, so I can't check that they "),
+                    alloc.reflow(r"haven't been tampered with."),
+                ]),
+                alloc.reflow(r"To keep you secure, I will not execute this unverified code."),
+                alloc.concat([
+                    alloc.tip(),
+                    alloc.reflow(r"Delete the directory and I will download the package again."),
+                ]),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "UNVERIFIED PACKAGE".to_string(),
+                code: Some(codes::UNVERIFIED_PACKAGE),
+                severity: Severity::Fatal,
+                fixes: Vec::new(),
+            }
+        }
+        Problem::VendoredPackageChanged(tarball_path) => {
+            let doc = alloc.stack([
+                alloc.reflow(r"I found the vendored package for this URL:"),
+                alloc
+                    .string((&url).to_string())
+                    .annotate(Annotation::Url)
+                    .indent(4),
+                alloc.reflow(r"in this file:"),
+                alloc
+                    .string(tarball_path.display().to_string())
+                    .annotate(Annotation::PlainText)
+                    .indent(4),
+                alloc.concat([
+                    alloc.reflow(r"But it isn't the package the URL's hash is for, "),
+                    alloc.reflow(r"so it may have been tampered with."),
+                ]),
+                alloc.reflow(r"To keep you secure, I will not execute this untrusted code."),
+                alloc.concat([
+                    alloc.tip(),
+                    alloc.reflow(r"Run "),
+                    alloc.keyword(r"roc vendor"),
+                    alloc.reflow(r" again to restore it."),
+                ]),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "VENDORED PACKAGE CHANGED".to_string(),
+                code: Some(codes::VENDORED_PACKAGE_CHANGED),
+                severity: Severity::Fatal,
+                fixes: Vec::new(),
+            }
+        }
+        Problem::InvalidManifest(problem) => {
+            let problem_doc = match problem {
+                ManifestProblem::IoErr(io_error) => alloc.concat([
+                    alloc.reflow(r"But I couldn't read its files: "),
+                    alloc.string(io_error.to_string()),
+                ]),
+                ManifestProblem::Malformed(line) => alloc.concat([
+                    alloc.reflow(r"But line "),
+                    alloc.string(line.to_string()),
+                    alloc.reflow(r" of its "),
+                    alloc.keyword(MANIFEST_FILENAME),
+                    alloc.reflow(r" isn't a hash, size and path."),
+                ]),
+                ManifestProblem::Mismatch(path) => alloc.concat([
+                    alloc.reflow(r"But its "),
+                    alloc.string(path).annotate(Annotation::Emphasized),
+                    alloc.reflow(r" file is missing, or isn't the one listed in its "),
+                    alloc.keyword(MANIFEST_FILENAME),
+                    alloc.reflow(r"."),
+                ]),
+                ManifestProblem::Unlisted(path) => alloc.concat([
+                    alloc.reflow(r"But its "),
+                    alloc.string(path).annotate(Annotation::Emphasized),
+                    alloc.reflow(r" file isn't listed in its "),
+                    alloc.keyword(MANIFEST_FILENAME),
+                    alloc.reflow(r"."),
+                ]),
+            };
+
+            let doc = alloc.stack([
+                alloc.reflow(r"I downloaded and verified the package at this URL:"),
+                alloc
+                    .string((&url).to_string())
+                    .annotate(Annotation::Url)
+                    .indent(4),
+                problem_doc,
+                alloc.reflow(r"To keep you secure, I will not execute this untrusted code."),
+                alloc.concat([
+                    alloc.tip(),
+                    alloc.reflow(r"This package's archive was built incorrectly. Ask its author to bundle it again with "),
+                    alloc.keyword(r"roc build --bundle"),
+                    alloc.reflow(r"."),
+                ]),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "INVALID PACKAGE MANIFEST".to_string(),
+                code: Some(codes::INVALID_PACKAGE_MANIFEST),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         // TODO: The reporting text for IoErr and FsExtraErr could probably be unified
@@ -1380,7 +1793,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "IO ERROR".to_string(),
+                code: Some(codes::IO_ERROR),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         // TODO: The reporting text for IoErr and FsExtraErr could probably be unified
@@ -1407,7 +1822,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "IO ERROR".to_string(),
+                code: Some(codes::IO_ERROR),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         Problem::HttpErr(reqwest_error) => {
@@ -1436,7 +1853,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "HTTP ERROR".to_string(),
+                code: Some(codes::HTTP_ERROR),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         Problem::InvalidUrl(roc_packaging::https::UrlProblem::InvalidExtensionSuffix(
@@ -1464,8 +1883,10 @@ pub fn to_https_problem_report<'b>(
                     alloc.keyword(r".tar"),
                     alloc.reflow(r", "),
                     alloc.keyword(r".tar.gz"),
-                    alloc.reflow(r" and "),
+                    alloc.reflow(r", "),
                     alloc.keyword(r".tar.br"),
+                    alloc.reflow(r" and "),
+                    alloc.keyword(r".tar.zst"),
                 ]),
                 alloc.concat([
                     alloc.tip(),
@@ -1477,7 +1898,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "INVALID EXTENSION SUFFIX".to_string(),
+                code: Some(codes::INVALID_EXTENSION_SUFFIX),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         Problem::InvalidUrl(roc_packaging::https::UrlProblem::MissingTarExt) => {
@@ -1497,8 +1920,10 @@ pub fn to_https_problem_report<'b>(
                     alloc.keyword(r".tar"),
                     alloc.reflow(r", "),
                     alloc.keyword(r".tar.gz"),
-                    alloc.reflow(r" and "),
+                    alloc.reflow(r", "),
                     alloc.keyword(r".tar.br"),
+                    alloc.reflow(r" and "),
+                    alloc.keyword(r".tar.zst"),
                 ]),
                 alloc.concat([
                     alloc.tip(),
@@ -1510,7 +1935,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "INVALID EXTENSION".to_string(),
+                code: Some(codes::INVALID_EXTENSION),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         Problem::InvalidUrl(roc_packaging::https::UrlProblem::InvalidFragment(
@@ -1548,7 +1975,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "INVALID FRAGMENT".to_string(),
+                code: Some(codes::INVALID_FRAGMENT),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         Problem::InvalidUrl(roc_packaging::https::UrlProblem::MissingHash) => {
@@ -1586,7 +2015,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "MISSING PACKAGE HASH".to_string(),
+                code: Some(codes::MISSING_PACKAGE_HASH),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         Problem::InvalidUrl(roc_packaging::https::UrlProblem::MissingHttps) => {
@@ -1612,7 +2043,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "HTTPS MANDATORY".to_string(),
+                code: Some(codes::HTTPS_MANDATORY),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         Problem::InvalidUrl(roc_packaging::https::UrlProblem::MisleadingCharacter) => {
@@ -1655,7 +2088,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "MISLEADING CHARACTERS".to_string(),
+                code: Some(codes::MISLEADING_CHARACTERS),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         Problem::DownloadTooBig(content_len) => {
@@ -1685,7 +2120,9 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "FILE TOO LARGE".to_string(),
+                code: Some(codes::FILE_TOO_LARGE),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
     }
@@ -1740,7 +2177,9 @@ pub fn to_file_problem_report<'b>(
                 filename,
                 doc,
                 title: "FILE NOT FOUND".to_string(),
+                code: Some(codes::FILE_NOT_FOUND),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         io::ErrorKind::PermissionDenied => {
@@ -1758,7 +2197,9 @@ pub fn to_file_problem_report<'b>(
                 filename,
                 doc,
                 title: "FILE PERMISSION DENIED".to_string(),
+                code: Some(codes::FILE_PERMISSION_DENIED),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         io::ErrorKind::Unsupported => {
@@ -1794,7 +2235,9 @@ pub fn to_file_problem_report<'b>(
                 filename,
                 doc,
                 title: "NOT A ROC FILE".to_string(),
+                code: Some(codes::NOT_A_ROC_FILE),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
         _ => {
@@ -1814,7 +2257,9 @@ pub fn to_file_problem_report<'b>(
                 filename,
                 doc,
                 title: "FILE PROBLEM".to_string(),
+                code: Some(codes::FILE_PROBLEM),
                 severity: Severity::Fatal,
+                fixes: Vec::new(),
             }
         }
     }
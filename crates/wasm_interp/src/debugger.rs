@@ -0,0 +1,366 @@
+use std::fmt::{self, Write as _};
+use std::io::{self, BufRead, Write};
+use std::iter::once;
+use std::process::exit;
+
+use roc_wasm_module::opcodes::OpCode;
+use roc_wasm_module::{ExportType, WasmModule};
+
+use crate::instance::Instance;
+use crate::ImportDispatcher;
+
+const HELP: &str = "\
+Commands:
+  s, step              Run the next instruction, stepping into calls
+  n, next              Run the next instruction, stepping over calls
+  f, finish            Run until the current function returns
+  c, continue          Run until the next breakpoint
+  b, break [FUNCTION]  Stop whenever FUNCTION is called, by name or index. Lists breakpoints if no FUNCTION.
+  d, delete FUNCTION   Remove the breakpoint on FUNCTION
+  l, locals            Print the arguments and locals of the current function
+  st, stack            Print the value stack of the current function
+  m, memory ADDR [LEN] Print LEN bytes of memory from ADDR (default 64). Numbers can be decimal or 0x hex.
+  bt, backtrace        Print the functions that led here
+  h, help              Print this message
+  q, quit              Exit the program
+";
+
+/// Interactive debugger for stepping through a WebAssembly program, one instruction at a time.
+/// It prints its state and reads commands whenever the program stops.
+pub struct Debugger {
+    input: Box<dyn BufRead>,
+    output: Box<dyn Write>,
+    /// Functions to stop at when they're called, by index
+    breakpoints: Vec<usize>,
+    run_mode: RunMode,
+    /// How many frames were on the call stack before the last instruction, to detect function entry
+    previous_depth: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RunMode {
+    /// Stop before the next instruction
+    Step,
+    /// Stop before the next instruction that's at most this many frames deep
+    StepOver(usize),
+    /// Stop before the next instruction that's less than this many frames deep
+    Finish(usize),
+    /// Only stop at breakpoints
+    Continue,
+}
+
+impl fmt::Debug for Debugger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Debugger")
+            .field("breakpoints", &self.breakpoints)
+            .field("run_mode", &self.run_mode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Debugger {
+    fn default() -> Self {
+        Debugger::new(Box::new(io::stdin().lock()), Box::new(io::stdout()))
+    }
+}
+
+impl Debugger {
+    /// Create a debugger that stops before the first instruction
+    pub fn new(input: Box<dyn BufRead>, output: Box<dyn Write>) -> Self {
+        Debugger {
+            input,
+            output,
+            breakpoints: vec![],
+            run_mode: RunMode::Step,
+            previous_depth: None,
+        }
+    }
+
+    /// Don't stop until reaching a breakpoint
+    pub fn run_to_breakpoint(&mut self) {
+        self.run_mode = RunMode::Continue;
+    }
+
+    /// Stop whenever `function` is called. It can be a function name or index.
+    pub fn add_breakpoint(&mut self, module: &WasmModule, function: &str) -> Result<usize, String> {
+        let fn_index = look_up_function(module, function)?;
+        if !self.breakpoints.contains(&fn_index) {
+            self.breakpoints.push(fn_index);
+        }
+        Ok(fn_index)
+    }
+
+    fn should_stop(&mut self, depth: usize, fn_index: usize) -> bool {
+        let is_function_entry = self
+            .previous_depth
+            .map_or(true, |previous_depth| depth > previous_depth);
+        self.previous_depth = Some(depth);
+
+        if is_function_entry && self.breakpoints.contains(&fn_index) {
+            return true;
+        }
+
+        match self.run_mode {
+            RunMode::Step => true,
+            RunMode::StepOver(stop_depth) => depth <= stop_depth,
+            RunMode::Finish(stop_depth) => depth < stop_depth,
+            RunMode::Continue => false,
+        }
+    }
+
+    fn print(&mut self, text: &str) {
+        self.output.write_all(text.as_bytes()).unwrap();
+    }
+
+    /// Read a command, or None if there's no more input
+    fn read_line(&mut self) -> Option<String> {
+        self.print("(wasm) ");
+        self.output.flush().unwrap();
+
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line),
+        }
+    }
+}
+
+/// Find a function by index, or by name in the debug info or exports
+fn look_up_function(module: &WasmModule, function: &str) -> Result<usize, String> {
+    let import_count = module.import.function_count();
+    let function_count = import_count + module.code.function_count as usize;
+
+    let fn_index = match function.parse::<usize>() {
+        Ok(index) => index,
+        Err(_) => module
+            .names
+            .function_names
+            .iter()
+            .find(|(_, name)| *name == function)
+            .map(|(index, _)| *index)
+            .or_else(|| {
+                module
+                    .export
+                    .exports
+                    .iter()
+                    .find(|ex| ex.ty == ExportType::Func && ex.name == function)
+                    .map(|ex| ex.index)
+            })
+            .ok_or_else(|| format!("I couldn't find a function named '{function}'"))?
+            as usize,
+    };
+
+    if fn_index < import_count {
+        Err(format!(
+            "func[{fn_index}] is imported, so it has no code to stop in"
+        ))
+    } else if fn_index >= function_count {
+        Err(format!(
+            "There is no func[{fn_index}]. This module has {function_count} functions."
+        ))
+    } else {
+        Ok(fn_index)
+    }
+}
+
+fn parse_number(text: &str) -> Option<usize> {
+    match text.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+impl<'a, I: ImportDispatcher> Instance<'a, I> {
+    /// Give the debugger a chance to stop before the next instruction
+    pub(crate) fn debugger_hook(&mut self, module: &WasmModule<'a>) {
+        let Some(mut debugger) = self.debugger.take() else {
+            return;
+        };
+
+        let depth = self.previous_frames.len();
+        if debugger.should_stop(depth, self.current_frame.fn_index) {
+            self.debugger_prompt(&mut debugger, module);
+        }
+
+        self.debugger = Some(debugger);
+    }
+
+    fn debugger_prompt(&self, debugger: &mut Debugger, module: &WasmModule<'a>) {
+        let location = self.debug_location(module);
+        debugger.print(&location);
+
+        loop {
+            let Some(line) = debugger.read_line() else {
+                // Out of input, so let the program run to the end
+                debugger.breakpoints.clear();
+                debugger.run_mode = RunMode::Continue;
+                return;
+            };
+
+            let mut words = line.split_whitespace();
+            let command = words.next().unwrap_or_default();
+            let arguments: Vec<&str> = words.collect();
+            let depth = self.previous_frames.len();
+
+            let text = match command {
+                "s" | "step" => {
+                    debugger.run_mode = RunMode::Step;
+                    return;
+                }
+                "n" | "next" => {
+                    debugger.run_mode = RunMode::StepOver(depth);
+                    return;
+                }
+                "f" | "finish" => {
+                    debugger.run_mode = RunMode::Finish(depth);
+                    return;
+                }
+                "c" | "continue" => {
+                    debugger.run_mode = RunMode::Continue;
+                    return;
+                }
+                "b" | "break" => match arguments.first() {
+                    Some(function) => match debugger.add_breakpoint(module, function) {
+                        Ok(fn_index) => format!(
+                            "Breakpoint at func[{fn_index}] {}\n",
+                            self.debug_function_name(fn_index)
+                        ),
+                        Err(message) => format!("{message}\n"),
+                    },
+                    None if debugger.breakpoints.is_empty() => "No breakpoints\n".to_string(),
+                    None => {
+                        let mut text = String::new();
+                        for i in debugger.breakpoints.iter() {
+                            writeln!(text, "func[{i}] {}", self.debug_function_name(*i)).unwrap();
+                        }
+                        text
+                    }
+                },
+                "d" | "delete" => match arguments.first() {
+                    Some(function) => match look_up_function(module, function) {
+                        Ok(fn_index) if debugger.breakpoints.contains(&fn_index) => {
+                            debugger.breakpoints.retain(|i| *i != fn_index);
+                            format!("Deleted the breakpoint at func[{fn_index}]\n")
+                        }
+                        Ok(fn_index) => format!("There's no breakpoint at func[{fn_index}]\n"),
+                        Err(message) => format!("{message}\n"),
+                    },
+                    None => "Which breakpoint should I delete?\n".to_string(),
+                },
+                "l" | "locals" => self.debug_locals(),
+                "st" | "stack" => self.debug_value_stack(),
+                "m" | "memory" => {
+                    let addr = arguments.first().and_then(|arg| parse_number(arg));
+                    let len = arguments.get(1).map_or(Some(64), |arg| parse_number(arg));
+                    match (addr, len) {
+                        (Some(addr), Some(len)) => self.debug_memory(addr, len),
+                        _ => "Usage: memory ADDR [LEN]\n".to_string(),
+                    }
+                }
+                "bt" | "backtrace" => self.debug_backtrace(),
+                "h" | "help" => HELP.to_string(),
+                "q" | "quit" => exit(0),
+                "" => continue,
+                _ => format!("Unknown command '{command}'. Type `help` for a list of commands.\n"),
+            };
+
+            debugger.print(&text);
+        }
+    }
+
+    /// The function we're in, and the instruction about to run
+    fn debug_location(&self, module: &WasmModule<'a>) -> String {
+        let fn_index = self.current_frame.fn_index;
+        let file_offset = self.program_counter + module.code.section_offset as usize;
+        let op_code = OpCode::from(module.code.bytes[self.program_counter]);
+        format!(
+            "func[{fn_index}] {} at {file_offset:06x}: {op_code:?}\n",
+            self.debug_function_name(fn_index)
+        )
+    }
+
+    fn debug_locals(&self) -> String {
+        let frame = &self.current_frame;
+        let arg_count = self.debug_arg_count(frame.fn_index);
+
+        let mut text = String::new();
+        for local_index in 0..frame.locals_count {
+            let kind = if local_index < arg_count {
+                "arg"
+            } else {
+                "local"
+            };
+            let value = self
+                .value_store
+                .get(frame.locals_start + local_index)
+                .unwrap();
+            writeln!(text, "  {kind} {local_index}: {value:?}").unwrap();
+        }
+        if text.is_empty() {
+            text.push_str("  no args or locals\n");
+        }
+        text
+    }
+
+    fn debug_value_stack(&self) -> String {
+        let stack_start = self.current_frame.locals_start + self.current_frame.locals_count;
+        let values: Vec<String> = self
+            .value_store
+            .iter()
+            .skip(stack_start)
+            .map(|value| format!("{value:?}"))
+            .collect();
+
+        format!("  [{}]\n", values.join(", "))
+    }
+
+    /// Hex dump of `len` bytes of memory from `addr`, 16 to a line
+    fn debug_memory(&self, addr: usize, len: usize) -> String {
+        let end = addr.saturating_add(len).min(self.memory.len());
+        if addr >= end {
+            return format!(
+                "Address {addr:#x} is outside of memory, which is {:#x} bytes\n",
+                self.memory.len()
+            );
+        }
+
+        let mut text = String::new();
+        for (i, line) in self.memory[addr..end].chunks(16).enumerate() {
+            write!(text, "  {:08x} ", addr + 16 * i).unwrap();
+            for byte in line {
+                write!(text, " {byte:02x}").unwrap();
+            }
+            writeln!(text).unwrap();
+        }
+        text
+    }
+
+    /// The functions on the call stack, innermost first, with the address each one is at
+    fn debug_backtrace(&self) -> String {
+        let frames: Vec<_> = self
+            .previous_frames
+            .iter()
+            .chain(once(&self.current_frame))
+            .collect();
+        let section_offset = self.module.code.section_offset as usize;
+
+        let mut text = String::new();
+        for (depth, frame) in frames.iter().enumerate().rev() {
+            // Callers are at the call instruction for the frame above them
+            let addr = match frames.get(depth + 1) {
+                Some(callee) => self.debug_return_addr_to_call_addr(callee.return_addr),
+                None => self.program_counter,
+            };
+            writeln!(
+                text,
+                "  #{} func[{}] {} at {:06x}",
+                frames.len() - 1 - depth,
+                frame.fn_index,
+                self.debug_function_name(frame.fn_index),
+                addr + section_offset
+            )
+            .unwrap();
+        }
+        text
+    }
+}
@@ -8,7 +8,9 @@ use roc_wasm_module::sections::{ImportDesc, MemorySection, SignatureParamsIter};
 use roc_wasm_module::{ExportType, WasmModule};
 use roc_wasm_module::{Value, ValueType};
 
+use crate::debugger::Debugger;
 use crate::frame::Frame;
+use crate::profiler::Profiler;
 use crate::value_store::ValueStore;
 use crate::{Error, ImportDispatcher};
 
@@ -49,7 +51,7 @@ pub struct Instance<'a, I: ImportDispatcher> {
     /// The current call frame
     pub(crate) current_frame: Frame,
     /// Previous call frames
-    previous_frames: Vec<'a, Frame>,
+    pub(crate) previous_frames: Vec<'a, Frame>,
     /// The WebAssembly stack machine's stack of values
     pub(crate) value_store: ValueStore<'a>,
     /// Values of any global variables
@@ -61,13 +63,17 @@ pub struct Instance<'a, I: ImportDispatcher> {
     /// Cache for branching instructions, split into buckets for each function.
     branch_cache: Vec<'a, Vec<'a, BranchCacheEntry>>,
     /// Number of imports in the module
-    import_count: usize,
+    pub(crate) import_count: usize,
     /// Import dispatcher from user code
     pub import_dispatcher: I,
     /// Temporary storage for import arguments
     import_arguments: Vec<'a, Value>,
     /// temporary storage for output using the --debug option
     debug_string: Option<String>,
+    /// Interactive debugger to stop at breakpoints and step through instructions
+    pub debugger: Option<Debugger>,
+    /// Instruction counts and timings per function
+    pub profiler: Option<Profiler>,
 }
 
 impl<'a, I: ImportDispatcher> Instance<'a, I> {
@@ -97,6 +103,8 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             import_dispatcher,
             import_arguments: Vec::new_in(arena),
             debug_string: Some(String::new()),
+            debugger: None,
+            profiler: None,
         }
     }
 
@@ -165,6 +173,8 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             import_dispatcher,
             import_arguments: Vec::new_in(arena),
             debug_string,
+            debugger: None,
+            profiler: None,
         })
     }
 
@@ -319,12 +329,35 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             vstack: self.value_store.depth(),
         });
 
+        let result = self.execute_to_end(module);
+
+        // The last instruction's time counts whether the program finished or failed
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.stop();
+        }
+
+        result?;
+
+        let return_value = if !self.value_store.is_empty() {
+            Some(self.value_store.pop())
+        } else {
+            None
+        };
+
+        Ok(return_value)
+    }
+
+    fn execute_to_end(&mut self, module: &WasmModule<'a>) -> Result<(), String> {
         loop {
+            if self.debugger.is_some() {
+                self.debugger_hook(module);
+            }
+            if let Some(profiler) = self.profiler.as_mut() {
+                profiler.record_instruction(&self.previous_frames, &self.current_frame);
+            }
             match self.execute_next_instruction(module) {
                 Ok(Action::Continue) => {}
-                Ok(Action::Break) => {
-                    break;
-                }
+                Ok(Action::Break) => return Ok(()),
                 Err(e) => {
                     let file_offset = self.program_counter + module.code.section_offset as usize;
                     let mut message = e.to_string_at(file_offset);
@@ -333,14 +366,6 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                 }
             };
         }
-
-        let return_value = if !self.value_store.is_empty() {
-            Some(self.value_store.pop())
-        } else {
-            None
-        };
-
-        Ok(return_value)
     }
 
     fn fetch_immediate_u32(&mut self, module: &WasmModule<'a>) -> u32 {
@@ -1843,26 +1868,8 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                 ..
             } = frame;
 
-            let arg_count = {
-                let signature_index = if *fn_index < self.import_count {
-                    match self.module.import.imports[*fn_index].description {
-                        ImportDesc::Func { signature_index } => signature_index,
-                        _ => unreachable!(),
-                    }
-                } else {
-                    self.module.function.signatures[fn_index - self.import_count]
-                };
-                self.module.types.look_up(signature_index).0.len()
-            };
-
-            let fn_name = self
-                .module
-                .names
-                .function_names
-                .iter()
-                .find(|(idx, _)| *idx == *fn_index as u32)
-                .map(|(_, name)| *name)
-                .unwrap_or("");
+            let arg_count = self.debug_arg_count(*fn_index);
+            let fn_name = self.debug_function_name(*fn_index);
 
             // Function and address match wasm-objdump formatting, for easy copy & find
             writeln!(buffer, "func[{fn_index}]  {fn_name}")?;
@@ -1898,8 +1905,32 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         Ok(())
     }
 
+    /// Number of arguments the function takes, as opposed to its other locals
+    pub(crate) fn debug_arg_count(&self, fn_index: usize) -> usize {
+        let signature_index = if fn_index < self.import_count {
+            match self.module.import.imports[fn_index].description {
+                ImportDesc::Func { signature_index } => signature_index,
+                _ => unreachable!(),
+            }
+        } else {
+            self.module.function.signatures[fn_index - self.import_count]
+        };
+        self.module.types.look_up(signature_index).0.len()
+    }
+
+    /// Name of the function from the debug info, or an empty string if there isn't one
+    pub(crate) fn debug_function_name(&self, fn_index: usize) -> &'a str {
+        self.module
+            .names
+            .function_names
+            .iter()
+            .find(|(idx, _)| *idx == fn_index as u32)
+            .map(|(_, name)| *name)
+            .unwrap_or("")
+    }
+
     // Call address is more intuitive than the return address in the stack trace. Search backward for it.
-    fn debug_return_addr_to_call_addr(&self, return_addr: usize) -> usize {
+    pub(crate) fn debug_return_addr_to_call_addr(&self, return_addr: usize) -> usize {
         // return_addr is pointing at the next instruction after the CALL/CALLINDIRECT.
         // Just before that is the LEB-128 function index or type index.
         // The last LEB-128 byte is <128, but the others are >=128 so we can't mistake them for CALL/CALLINDIRECT
@@ -1,17 +1,24 @@
 use bumpalo::{collections::Vec, Bump};
 use clap::ArgAction;
 use clap::{Arg, Command};
+use std::env;
 use std::fs;
 use std::io;
 use std::iter::once;
 use std::process;
 
-use roc_wasm_interp::{DefaultImportDispatcher, Instance};
+use roc_wasm_interp::{Debugger, DefaultImportDispatcher, Instance, Profiler, WasiClock};
 use roc_wasm_module::WasmModule;
 
 pub const FLAG_FUNCTION: &str = "function";
 pub const FLAG_DEBUG: &str = "debug";
 pub const FLAG_HEX: &str = "hex";
+pub const FLAG_DEBUGGER: &str = "debugger";
+pub const FLAG_BREAK: &str = "break";
+pub const FLAG_PROFILE: &str = "profile";
+pub const FLAG_DIR: &str = "dir";
+pub const FLAG_ENV: &str = "env";
+pub const FLAG_FAKE_CLOCK: &str = "fake-clock";
 pub const WASM_FILE: &str = "WASM_FILE";
 pub const ARGS_FOR_APP: &str = "ARGS_FOR_APP";
 
@@ -32,12 +39,51 @@ fn main() -> io::Result<()> {
         .action(ArgAction::SetTrue)
         .required(false);
 
+    let flag_debugger = Arg::new(FLAG_DEBUGGER)
+        .long(FLAG_DEBUGGER)
+        .help("Step through the program interactively, starting at the first instruction.")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
+    let flag_break = Arg::new(FLAG_BREAK)
+        .long(FLAG_BREAK)
+        .value_name("FUNCTION")
+        .help("Run the program in the debugger, stopping whenever FUNCTION is called. FUNCTION is a name or index.\nCan be used more than once.")
+        .action(ArgAction::Append)
+        .required(false);
+
+    let flag_profile = Arg::new(FLAG_PROFILE)
+        .long(FLAG_PROFILE)
+        .value_name("FILE")
+        .help("Count instructions and time per function. Writes instruction counts per call stack to FILE\nin the folded stacks format used by flamegraph tools, and prints a summary per function.")
+        .required(false);
+
     let flag_hex = Arg::new(FLAG_HEX)
         .long(FLAG_HEX)
         .help("If the called function returns a value, print it in hexadecimal format.")
         .action(ArgAction::SetTrue)
         .required(false);
 
+    let flag_dir = Arg::new(FLAG_DIR)
+        .long(FLAG_DIR)
+        .value_name("HOST_DIR[::GUEST_DIR]")
+        .help("Give the app access to a directory on the host, which it sees as GUEST_DIR (or HOST_DIR if not given).\nThe app can't open anything outside of its directories. Can be used more than once.")
+        .action(ArgAction::Append)
+        .required(false);
+
+    let flag_env = Arg::new(FLAG_ENV)
+        .long(FLAG_ENV)
+        .value_name("NAME[=VALUE]")
+        .help("Pass an environment variable to the app, either from the host or with the given value.\nCan be used more than once.")
+        .action(ArgAction::Append)
+        .required(false);
+
+    let flag_fake_clock = Arg::new(FLAG_FAKE_CLOCK)
+        .long(FLAG_FAKE_CLOCK)
+        .help("Give the app a clock that starts at zero and only moves when it's read or the app sleeps, for reproducible runs.")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
     let wasm_file_to_run = Arg::new(WASM_FILE)
         .help("The .wasm file to run")
         .required(true);
@@ -50,7 +96,13 @@ fn main() -> io::Result<()> {
         .about("Run the given .wasm file")
         .arg(flag_function)
         .arg(flag_debug)
+        .arg(flag_debugger)
+        .arg(flag_break)
+        .arg(flag_profile)
         .arg(flag_hex)
+        .arg(flag_dir)
+        .arg(flag_env)
+        .arg(flag_fake_clock)
         .arg(wasm_file_to_run)
         .arg(args_for_app);
 
@@ -59,7 +111,14 @@ fn main() -> io::Result<()> {
     let matches = app.get_matches();
     let start_fn_name = matches.get_one::<String>(FLAG_FUNCTION).unwrap();
     let is_debug_mode = matches.get_flag(FLAG_DEBUG);
+    let is_debugger = matches.get_flag(FLAG_DEBUGGER);
+    let breakpoints: std::vec::Vec<&String> = matches
+        .get_many::<String>(FLAG_BREAK)
+        .unwrap_or_default()
+        .collect();
     let is_hex_format = matches.get_flag(FLAG_HEX);
+    let is_fake_clock = matches.get_flag(FLAG_FAKE_CLOCK);
+    let profile_path = matches.get_one::<String>(FLAG_PROFILE);
     let start_arg_strings = matches.get_many::<String>(ARGS_FOR_APP).unwrap_or_default();
     let wasm_path = matches.get_one::<String>(WASM_FILE).unwrap();
     // WASI expects the .wasm file to be argv[0]
@@ -87,17 +146,59 @@ fn main() -> io::Result<()> {
 
     // Create an execution instance
 
-    let dispatcher = DefaultImportDispatcher::new(&wasi_argv);
+    let mut dispatcher = DefaultImportDispatcher::new(&wasi_argv);
+    for dir in matches.get_many::<String>(FLAG_DIR).unwrap_or_default() {
+        let (host_dir, guest_dir) = dir.split_once("::").unwrap_or((dir.as_str(), dir.as_str()));
+        if let Err(e) = dispatcher.wasi.preopen_dir(host_dir, guest_dir) {
+            eprintln!("I couldn't give the app access to the directory {host_dir}: {e}");
+            process::exit(1);
+        }
+    }
+    for var in matches.get_many::<String>(FLAG_ENV).unwrap_or_default() {
+        if var.contains('=') {
+            dispatcher.wasi.env.push(var.clone());
+        } else if let Ok(value) = env::var(var) {
+            dispatcher.wasi.env.push(format!("{var}={value}"));
+        }
+    }
+    if is_fake_clock {
+        dispatcher.wasi.clock = WasiClock::fake();
+    }
     let mut inst =
         Instance::for_module(&arena, &module, dispatcher, is_debug_mode).unwrap_or_else(|e| {
             eprintln!("{e}");
             process::exit(2);
         });
 
+    if is_debugger || !breakpoints.is_empty() {
+        let mut debugger = Debugger::default();
+        for function in breakpoints {
+            if let Err(message) = debugger.add_breakpoint(&module, function) {
+                eprintln!("{message}");
+                process::exit(1);
+            }
+        }
+        if !is_debugger {
+            debugger.run_to_breakpoint();
+        }
+        inst.debugger = Some(debugger);
+    }
+
+    if profile_path.is_some() {
+        inst.profiler = Some(Profiler::new());
+    }
+
     // Run
 
     let result = inst.call_export_from_cli(&module, start_fn_name, &wasi_argv);
 
+    // Write out the profile, if any
+
+    if let (Some(path), Some(profiler)) = (profile_path, &inst.profiler) {
+        fs::write(path, profiler.folded_stacks(&module))?;
+        eprint!("{}", profiler.summary(&module));
+    }
+
     // Print out return value, if any
 
     match result {
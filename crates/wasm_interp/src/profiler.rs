@@ -0,0 +1,188 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use roc_wasm_module::WasmModule;
+
+use crate::frame::Frame;
+
+/// Counts the instructions executed and the time spent in each call stack.
+///
+/// Instruction counts only depend on the program and its input, so they're the thing to compare
+/// when checking the effect of a code gen change. Times are there for finding slow host calls.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    /// Call stacks seen so far, as function indices, outermost first
+    stacks: Vec<Vec<usize>>,
+    /// Statistics for each of `stacks`
+    stats: Vec<StackStats>,
+    /// Index in `stacks` of each call stack
+    stack_indices: HashMap<Vec<usize>, usize>,
+    /// Index in `stacks` of the call stack we're in
+    current: Option<usize>,
+    /// Number of times each function was called
+    calls: HashMap<usize, u64>,
+    /// When we last started an instruction
+    last_instant: Option<Instant>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct StackStats {
+    instructions: u64,
+    time: Duration,
+}
+
+/// Totals for one function, over all the call stacks it appears in
+#[derive(Debug, Default, Clone, Copy)]
+struct FunctionStats {
+    calls: u64,
+    inclusive: StackStats,
+    exclusive: StackStats,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Profiler::default()
+    }
+
+    /// Count the instruction about to be executed
+    pub(crate) fn record_instruction(&mut self, previous_frames: &[Frame], current_frame: &Frame) {
+        let now = Instant::now();
+        self.stop_timer(now);
+        self.last_instant = Some(now);
+
+        // Only one call or return can happen per instruction, so this finds any change of stack
+        let depth = previous_frames.len() + 1;
+        let is_same_stack = self.current.map_or(false, |current| {
+            let stack = &self.stacks[current];
+            stack.len() == depth && stack.last() == Some(&current_frame.fn_index)
+        });
+
+        if !is_same_stack {
+            let previous_depth = self.current.map_or(0, |current| self.stacks[current].len());
+            if depth > previous_depth {
+                *self.calls.entry(current_frame.fn_index).or_default() += 1;
+            }
+
+            let stack: Vec<usize> = previous_frames
+                .iter()
+                .chain(std::iter::once(current_frame))
+                .map(|frame| frame.fn_index)
+                .collect();
+            let index = match self.stack_indices.get(&stack) {
+                Some(index) => *index,
+                None => {
+                    let index = self.stacks.len();
+                    self.stack_indices.insert(stack.clone(), index);
+                    self.stacks.push(stack);
+                    self.stats.push(StackStats::default());
+                    index
+                }
+            };
+            self.current = Some(index);
+        }
+
+        if let Some(current) = self.current {
+            self.stats[current].instructions += 1;
+        }
+    }
+
+    /// Stop timing, at the end of the program
+    pub(crate) fn stop(&mut self) {
+        self.stop_timer(Instant::now());
+        self.last_instant = None;
+    }
+
+    fn stop_timer(&mut self, now: Instant) {
+        if let (Some(last_instant), Some(current)) = (self.last_instant, self.current) {
+            self.stats[current].time += now - last_instant;
+        }
+    }
+
+    /// Instruction counts per call stack, in the "folded stacks" format used by flamegraph
+    /// tools like inferno and `flamegraph.pl`. One line per stack, like `main;foo;bar 123`.
+    pub fn folded_stacks(&self, module: &WasmModule) -> String {
+        let mut lines: Vec<String> = self
+            .stacks
+            .iter()
+            .zip(self.stats.iter())
+            .map(|(stack, stats)| {
+                let names: Vec<String> = stack
+                    .iter()
+                    .map(|fn_index| function_name(module, *fn_index).replace(';', ":"))
+                    .collect();
+                format!("{} {}\n", names.join(";"), stats.instructions)
+            })
+            .collect();
+        lines.sort();
+        lines.concat()
+    }
+
+    /// A table of instructions and time per function, both including and excluding the
+    /// functions it calls. The functions that executed the most instructions themselves come first.
+    pub fn summary(&self, module: &WasmModule) -> String {
+        let mut functions: HashMap<usize, FunctionStats> = HashMap::new();
+
+        for (stack, stats) in self.stacks.iter().zip(self.stats.iter()) {
+            // Recursive calls would be counted more than once
+            let mut counted = Vec::with_capacity(stack.len());
+            for fn_index in stack {
+                if !counted.contains(fn_index) {
+                    counted.push(*fn_index);
+                    let inclusive = &mut functions.entry(*fn_index).or_default().inclusive;
+                    inclusive.instructions += stats.instructions;
+                    inclusive.time += stats.time;
+                }
+            }
+
+            if let Some(fn_index) = stack.last() {
+                let exclusive = &mut functions.entry(*fn_index).or_default().exclusive;
+                exclusive.instructions += stats.instructions;
+                exclusive.time += stats.time;
+            }
+        }
+
+        for (fn_index, calls) in self.calls.iter() {
+            functions.entry(*fn_index).or_default().calls = *calls;
+        }
+
+        let mut functions: Vec<(usize, FunctionStats)> = functions.into_iter().collect();
+        functions
+            .sort_by_key(|(fn_index, stats)| (Reverse(stats.exclusive.instructions), *fn_index));
+
+        let mut table = String::new();
+        writeln!(
+            table,
+            "{:>12} {:>12} {:>12} {:>12} {:>12}  function",
+            "calls", "instrs incl", "instrs excl", "time incl", "time excl"
+        )
+        .unwrap();
+        for (fn_index, stats) in functions {
+            let inclusive_time = format!("{:.3?}", stats.inclusive.time);
+            let exclusive_time = format!("{:.3?}", stats.exclusive.time);
+            writeln!(
+                table,
+                "{:>12} {:>12} {:>12} {:>12} {:>12}  {}",
+                stats.calls,
+                stats.inclusive.instructions,
+                stats.exclusive.instructions,
+                inclusive_time,
+                exclusive_time,
+                function_name(module, fn_index),
+            )
+            .unwrap();
+        }
+        table
+    }
+}
+
+/// Name of the function from the debug info, or its index if it has no name
+fn function_name(module: &WasmModule, fn_index: usize) -> String {
+    module
+        .names
+        .function_names
+        .iter()
+        .find(|(index, _)| *index == fn_index as u32)
+        .map_or_else(|| format!("func[{fn_index}]"), |(_, name)| name.to_string())
+}
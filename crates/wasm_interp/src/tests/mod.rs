@@ -2,11 +2,14 @@
 
 mod test_basics;
 mod test_convert;
+mod test_debugger;
 mod test_f32;
 mod test_f64;
 mod test_i32;
 mod test_i64;
 mod test_mem;
+mod test_profiler;
+mod test_wasi;
 
 use crate::{DefaultImportDispatcher, Instance};
 use bumpalo::{collections::Vec, Bump};
@@ -156,3 +159,45 @@ pub fn create_exported_function_with_locals<'a, F>(
     module.code.function_count += 1;
     module.code.function_offsets.push(offset as u32);
 }
+
+/// A module where func[0] `test` returns the result of calling func[1] `double` on 21
+pub fn create_call_double_module(arena: &Bump) -> WasmModule<'_> {
+    let mut module = WasmModule::new(arena);
+
+    create_exported_function_no_locals(
+        &mut module,
+        "test",
+        Signature {
+            param_types: Vec::new_in(arena),
+            ret_type: Some(ValueType::I32),
+        },
+        |buf| {
+            buf.push(OpCode::I32CONST as u8);
+            buf.push(21);
+            buf.push(OpCode::CALL as u8);
+            buf.push(1);
+            buf.push(OpCode::END as u8);
+        },
+    );
+
+    create_exported_function_no_locals(
+        &mut module,
+        "double",
+        Signature {
+            param_types: bumpalo::vec![in arena; ValueType::I32],
+            ret_type: Some(ValueType::I32),
+        },
+        |buf| {
+            buf.push(OpCode::GETLOCAL as u8);
+            buf.push(0);
+            buf.push(OpCode::GETLOCAL as u8);
+            buf.push(0);
+            buf.push(OpCode::I32ADD as u8);
+            buf.push(OpCode::END as u8);
+        },
+    );
+
+    module.names.append_function(0, "test");
+    module.names.append_function(1, "double");
+    module
+}
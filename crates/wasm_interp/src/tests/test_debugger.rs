@@ -0,0 +1,58 @@
+use crate::tests::create_call_double_module;
+use crate::{Debugger, DefaultImportDispatcher, Instance};
+use bumpalo::Bump;
+use roc_wasm_module::Value;
+use std::cell::RefCell;
+use std::io::{self, Cursor, Write};
+use std::rc::Rc;
+
+/// Debugger output that the test can still read after giving it to the debugger
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_debugger_session() {
+    let arena = Bump::new();
+    let module = create_call_double_module(&arena);
+
+    let commands = "break double\ncontinue\nlocals\nbacktrace\nfinish\nstack\ncontinue\n";
+    let output = SharedBuffer::default();
+    let debugger = Debugger::new(Box::new(Cursor::new(commands)), Box::new(output.clone()));
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.debugger = Some(debugger);
+    let return_val = inst.call_export("test", []).unwrap();
+    assert_eq!(return_val, Some(Value::I32(42)));
+
+    let output = String::from_utf8(output.0.take()).unwrap();
+    let lines: Vec<&str> = output
+        .split("(wasm) ")
+        .flat_map(|chunk| chunk.lines())
+        .map(|line| line.split(" at ").next().unwrap())
+        .collect();
+
+    assert_eq!(
+        lines,
+        [
+            "func[0] test",
+            "Breakpoint",
+            "func[1] double",
+            "  arg 0: I32(21)",
+            "  #0 func[1] double",
+            "  #1 func[0] test",
+            "func[0] test",
+            "  [I32(42)]",
+        ]
+    );
+}
@@ -0,0 +1,63 @@
+use crate::tests::{create_call_double_module, create_exported_function_no_locals};
+use crate::{DefaultImportDispatcher, Instance, Profiler};
+use bumpalo::Bump;
+use roc_wasm_module::{opcodes::OpCode, Signature, Value, WasmModule};
+
+#[test]
+fn test_profile_instruction_counts() {
+    let arena = Bump::new();
+    let module = create_call_double_module(&arena);
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.profiler = Some(Profiler::new());
+    let return_val = inst.call_export("test", []).unwrap();
+    assert_eq!(return_val, Some(Value::I32(42)));
+
+    let profiler = inst.profiler.as_ref().unwrap();
+
+    // test: i32.const, call, end. double: local.get, local.get, i32.add, end
+    assert_eq!(profiler.folded_stacks(&module), "test 3\ntest;double 4\n");
+
+    let summary = profiler.summary(&module);
+    let rows: Vec<Vec<&str>> = summary
+        .lines()
+        .skip(1)
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    // calls, inclusive instructions, exclusive instructions, then times and the name
+    assert_eq!(rows[0][..3], ["1", "4", "4"]);
+    assert_eq!(rows[0].last(), Some(&"double"));
+    assert_eq!(rows[1][..3], ["1", "7", "3"]);
+    assert_eq!(rows[1].last(), Some(&"test"));
+}
+
+#[test]
+fn test_profile_survives_a_trap() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+
+    create_exported_function_no_locals(
+        &mut module,
+        "test",
+        Signature {
+            param_types: bumpalo::vec![in &arena],
+            ret_type: None,
+        },
+        |buf| {
+            buf.push(OpCode::NOP as u8);
+            buf.push(OpCode::UNREACHABLE as u8);
+            buf.push(OpCode::END as u8);
+        },
+    );
+    module.names.append_function(0, "test");
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.profiler = Some(Profiler::new());
+    assert!(inst.call_export("test", []).is_err());
+
+    // Everything up to and including the instruction that trapped is counted
+    let profiler = inst.profiler.as_ref().unwrap();
+    assert_eq!(profiler.folded_stacks(&module), "test 2\n");
+}
@@ -0,0 +1,335 @@
+use crate::wasi::{Errno, WasiClock, WasiDispatcher};
+use roc_wasm_module::Value;
+use std::fs;
+
+const PTR_OUT: i32 = 0x10;
+const PTR_PATH: i32 = 0x100;
+const PTR_IOVS: i32 = 0x200;
+const PTR_DATA: i32 = 0x300;
+
+const OFLAGS_CREAT: i32 = 1;
+const RIGHTS_FD_READ: i64 = 1 << 1;
+const RIGHTS_FD_WRITE: i64 = 1 << 6;
+
+const SUCCESS: i32 = Errno::Success as i32;
+
+/// Call a WASI function, returning its error code
+fn call(wasi: &mut WasiDispatcher, memory: &mut [u8], name: &str, args: &[Value]) -> i32 {
+    wasi.dispatch(name, args, memory)
+        .unwrap()
+        .expect_i32()
+        .unwrap()
+}
+
+fn read_out(memory: &[u8]) -> u32 {
+    u32::from_le_bytes(memory[PTR_OUT as usize..][..4].try_into().unwrap())
+}
+
+/// Call `path_open` relative to the first preopened directory, returning the new file descriptor
+fn path_open(
+    wasi: &mut WasiDispatcher,
+    memory: &mut [u8],
+    path: &str,
+    oflags: i32,
+    rights: i64,
+) -> Result<u32, i32> {
+    memory[PTR_PATH as usize..][..path.len()].copy_from_slice(path.as_bytes());
+    let args = [
+        Value::I32(3),
+        Value::I32(0),
+        Value::I32(PTR_PATH),
+        Value::I32(path.len() as i32),
+        Value::I32(oflags),
+        Value::I64(rights),
+        Value::I64(rights),
+        Value::I32(0),
+        Value::I32(PTR_OUT),
+    ];
+    match call(wasi, memory, "path_open", &args) {
+        SUCCESS => Ok(read_out(memory)),
+        errno => Err(errno),
+    }
+}
+
+fn set_iov(memory: &mut [u8], len: usize) {
+    memory[PTR_IOVS as usize..][..4].copy_from_slice(&(PTR_DATA as u32).to_le_bytes());
+    memory[PTR_IOVS as usize + 4..][..4].copy_from_slice(&(len as u32).to_le_bytes());
+}
+
+#[test]
+fn test_write_then_read_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut wasi = WasiDispatcher::default();
+    wasi.preopen_dir(dir.path(), "/data").unwrap();
+    let mut memory = vec![0; 0x1000];
+
+    let fd = path_open(
+        &mut wasi,
+        &mut memory,
+        "greeting.txt",
+        OFLAGS_CREAT,
+        RIGHTS_FD_WRITE,
+    )
+    .unwrap();
+    let text = b"Hello from Roc!";
+    memory[PTR_DATA as usize..][..text.len()].copy_from_slice(text);
+    set_iov(&mut memory, text.len());
+    let write_args = [
+        Value::I32(fd as i32),
+        Value::I32(PTR_IOVS),
+        Value::I32(1),
+        Value::I32(PTR_OUT),
+    ];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_write", &write_args),
+        SUCCESS
+    );
+    assert_eq!(read_out(&memory), text.len() as u32);
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_close", &[Value::I32(fd as i32)]),
+        SUCCESS
+    );
+
+    assert_eq!(
+        fs::read(dir.path().join("greeting.txt")).unwrap(),
+        text.to_vec()
+    );
+
+    // The closed file descriptor gets reused
+    let fd_again = path_open(&mut wasi, &mut memory, "greeting.txt", 0, RIGHTS_FD_READ).unwrap();
+    assert_eq!(fd_again, fd);
+    memory[PTR_DATA as usize..][..text.len()].fill(0);
+    set_iov(&mut memory, 100);
+    let read_args = [
+        Value::I32(fd as i32),
+        Value::I32(PTR_IOVS),
+        Value::I32(1),
+        Value::I32(PTR_OUT),
+    ];
+    assert_eq!(call(&mut wasi, &mut memory, "fd_read", &read_args), SUCCESS);
+    assert_eq!(read_out(&memory), text.len() as u32);
+    assert_eq!(&memory[PTR_DATA as usize..][..text.len()], text);
+}
+
+#[test]
+fn test_paths_stay_in_sandbox() {
+    let parent = tempfile::tempdir().unwrap();
+    let sandbox = parent.path().join("sandbox");
+    fs::create_dir(&sandbox).unwrap();
+    fs::write(parent.path().join("secret.txt"), "secret").unwrap();
+    fs::write(sandbox.join("public.txt"), "public").unwrap();
+
+    let mut wasi = WasiDispatcher::default();
+    wasi.preopen_dir(&sandbox, ".").unwrap();
+    let mut memory = vec![0; 0x1000];
+
+    let read = RIGHTS_FD_READ;
+    let notcapable = Err(Errno::Notcapable as i32);
+    let absolute = parent.path().join("secret.txt");
+    let absolute = absolute.to_str().unwrap();
+
+    assert!(path_open(&mut wasi, &mut memory, "public.txt", 0, read).is_ok());
+    assert!(path_open(&mut wasi, &mut memory, "./public.txt", 0, read).is_ok());
+    assert_eq!(
+        path_open(&mut wasi, &mut memory, "../secret.txt", 0, read),
+        notcapable
+    );
+    assert_eq!(
+        path_open(&mut wasi, &mut memory, "../sandbox/public.txt", 0, read),
+        notcapable
+    );
+    assert_eq!(
+        path_open(&mut wasi, &mut memory, absolute, 0, read),
+        notcapable
+    );
+    assert_eq!(
+        path_open(&mut wasi, &mut memory, "missing.txt", 0, read),
+        Err(Errno::Noent as i32)
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlinks_stay_in_sandbox() {
+    use std::os::unix::fs::symlink;
+
+    let parent = tempfile::tempdir().unwrap();
+    let sandbox = parent.path().join("sandbox");
+    fs::create_dir(&sandbox).unwrap();
+    fs::create_dir(sandbox.join("docs")).unwrap();
+    fs::write(parent.path().join("secret.txt"), "secret").unwrap();
+    fs::write(sandbox.join("docs/public.txt"), "public").unwrap();
+
+    symlink("docs/public.txt", sandbox.join("inside.txt")).unwrap();
+    let real_docs = sandbox.canonicalize().unwrap().join("docs");
+    symlink(real_docs, sandbox.join("absolute")).unwrap();
+    symlink("../secret.txt", sandbox.join("escape.txt")).unwrap();
+    symlink(parent.path(), sandbox.join("parent")).unwrap();
+    symlink("docs/../..", sandbox.join("sneaky")).unwrap();
+    symlink("loop", sandbox.join("loop")).unwrap();
+
+    let mut wasi = WasiDispatcher::default();
+    wasi.preopen_dir(&sandbox, ".").unwrap();
+    let mut memory = vec![0; 0x1000];
+
+    let read = RIGHTS_FD_READ;
+    let notcapable = Err(Errno::Notcapable as i32);
+
+    assert!(path_open(&mut wasi, &mut memory, "inside.txt", 0, read).is_ok());
+    assert!(path_open(&mut wasi, &mut memory, "absolute/public.txt", 0, read).is_ok());
+    assert_eq!(
+        path_open(&mut wasi, &mut memory, "escape.txt", 0, read),
+        notcapable
+    );
+    assert_eq!(
+        path_open(&mut wasi, &mut memory, "parent/secret.txt", 0, read),
+        notcapable
+    );
+    assert_eq!(
+        path_open(&mut wasi, &mut memory, "sneaky/secret.txt", 0, read),
+        notcapable
+    );
+    assert_eq!(
+        path_open(&mut wasi, &mut memory, "loop", 0, read),
+        Err(Errno::Loop as i32)
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dangling_symlinks_stay_in_sandbox() {
+    use std::os::unix::fs::symlink;
+
+    let parent = tempfile::tempdir().unwrap();
+    let sandbox = parent.path().join("sandbox");
+    fs::create_dir(&sandbox).unwrap();
+
+    // Neither target exists yet, so there's nothing to canonicalize
+    symlink("../planted.txt", sandbox.join("escape.txt")).unwrap();
+    symlink("created.txt", sandbox.join("inside.txt")).unwrap();
+
+    let mut wasi = WasiDispatcher::default();
+    wasi.preopen_dir(&sandbox, ".").unwrap();
+    let mut memory = vec![0; 0x1000];
+
+    let write = RIGHTS_FD_WRITE;
+
+    assert_eq!(
+        path_open(&mut wasi, &mut memory, "escape.txt", OFLAGS_CREAT, write),
+        Err(Errno::Notcapable as i32)
+    );
+    assert!(!parent.path().join("planted.txt").exists());
+
+    assert!(path_open(&mut wasi, &mut memory, "inside.txt", OFLAGS_CREAT, write).is_ok());
+    assert!(sandbox.join("created.txt").exists());
+}
+
+#[test]
+fn test_readdir() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("b.txt"), "").unwrap();
+    fs::write(dir.path().join("a.txt"), "").unwrap();
+    fs::create_dir(dir.path().join("c")).unwrap();
+
+    let mut wasi = WasiDispatcher::default();
+    wasi.preopen_dir(dir.path(), ".").unwrap();
+    let mut memory = vec![0; 0x1000];
+
+    let args = [
+        Value::I32(3),
+        Value::I32(PTR_DATA),
+        Value::I32(0x100),
+        Value::I64(0),
+        Value::I32(PTR_OUT),
+    ];
+    assert_eq!(call(&mut wasi, &mut memory, "fd_readdir", &args), SUCCESS);
+    let used = read_out(&memory) as usize;
+
+    let mut names = vec![];
+    let mut entries = &memory[PTR_DATA as usize..][..used];
+    while !entries.is_empty() {
+        let name_len = u32::from_le_bytes(entries[16..20].try_into().unwrap()) as usize;
+        let filetype = entries[20];
+        names.push((
+            std::str::from_utf8(&entries[24..][..name_len]).unwrap(),
+            filetype,
+        ));
+        entries = &entries[24 + name_len..];
+    }
+
+    assert_eq!(names, [("a.txt", 4), ("b.txt", 4), ("c", 3)]);
+}
+
+#[test]
+fn test_fake_clock_sleep() {
+    let mut wasi = WasiDispatcher {
+        clock: WasiClock::fake(),
+        ..Default::default()
+    };
+    let mut memory = vec![0; 0x1000];
+    let monotonic = Value::I32(1);
+
+    let time_args = [monotonic, Value::I64(1), Value::I32(PTR_OUT)];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "clock_time_get", &time_args),
+        SUCCESS
+    );
+    let before = u64::from_le_bytes(memory[PTR_OUT as usize..][..8].try_into().unwrap());
+
+    // Sleep for a second, the way WASI libc does it: one relative clock subscription
+    let subscription = &mut memory[PTR_DATA as usize..][..48];
+    subscription[0..8].copy_from_slice(&42u64.to_le_bytes()); // userdata
+    subscription[8] = 0; // clock
+    subscription[16..20].copy_from_slice(&1u32.to_le_bytes()); // monotonic
+    subscription[24..32].copy_from_slice(&1_000_000_000u64.to_le_bytes());
+    let poll_args = [
+        Value::I32(PTR_DATA),
+        Value::I32(PTR_IOVS),
+        Value::I32(1),
+        Value::I32(PTR_OUT),
+    ];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "poll_oneoff", &poll_args),
+        SUCCESS
+    );
+    assert_eq!(read_out(&memory), 1);
+    assert_eq!(memory[PTR_IOVS as usize..][..8], 42u64.to_le_bytes());
+
+    assert_eq!(
+        call(&mut wasi, &mut memory, "clock_time_get", &time_args),
+        SUCCESS
+    );
+    let after = u64::from_le_bytes(memory[PTR_OUT as usize..][..8].try_into().unwrap());
+    assert!((1_000_000_000..1_001_000_000).contains(&(after - before)));
+}
+
+#[test]
+fn test_environ() {
+    let mut wasi = WasiDispatcher {
+        env: vec!["HOME=/home/roc".into(), "LANG=C".into()],
+        ..Default::default()
+    };
+    let mut memory = vec![0; 0x1000];
+
+    let sizes_args = [Value::I32(PTR_OUT), Value::I32(PTR_OUT + 4)];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "environ_sizes_get", &sizes_args),
+        SUCCESS
+    );
+    assert_eq!(read_out(&memory), 2);
+    assert_eq!(memory[PTR_OUT as usize + 4], 22);
+
+    let get_args = [Value::I32(PTR_IOVS), Value::I32(PTR_DATA)];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "environ_get", &get_args),
+        SUCCESS
+    );
+    assert_eq!(
+        &memory[PTR_DATA as usize..][..22],
+        b"HOME=/home/roc\0LANG=C\0"
+    );
+    assert_eq!(
+        memory[PTR_IOVS as usize + 4..][..4],
+        (PTR_DATA as u32 + 15).to_le_bytes()
+    );
+}
@@ -1,7 +1,12 @@
 use rand::prelude::*;
 use roc_wasm_module::Value;
-use std::io::{self, Read, StderrLock, StdoutLock, Write};
+use std::ffi::OsString;
+use std::fs::{self, File, FileType, Metadata, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, StderrLock, StdoutLock, Write};
+use std::path::{Component, Path, PathBuf};
 use std::process::exit;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 pub const MODULE_NAME: &str = "wasi_snapshot_preview1";
 
@@ -9,6 +14,9 @@ pub struct WasiDispatcher<'a> {
     pub args: &'a [&'a [u8]],
     pub rng: ThreadRng,
     pub files: Vec<WasiFile>,
+    /// Environment variables for the app, each written as `NAME=value`
+    pub env: Vec<String>,
+    pub clock: WasiClock,
 }
 
 impl Default for WasiDispatcher<'_> {
@@ -22,14 +30,129 @@ pub enum WasiFile {
     WriteOnly(Vec<u8>),
     ReadWrite(Vec<u8>),
     HostSystemFile,
+    /// A file on the host, opened inside a preopened directory
+    HostFile(File),
+    /// A directory on the host, either preopened or opened inside a preopened directory
+    HostDirectory(WasiDirectory),
+    /// A file descriptor that has been closed, free to be reused by `path_open`
+    Closed,
+}
+
+pub struct WasiDirectory {
+    /// Where the directory is on the host
+    pub host_path: PathBuf,
+    /// The preopened directory this one is inside. Paths opened through it can't lead outside.
+    pub sandbox: PathBuf,
+    /// The name the app knows a preopened directory by, like `/data`.
+    /// None for directories the app opened itself.
+    pub preopen_name: Option<String>,
+}
+
+/// Where the app gets the time from
+pub enum WasiClock {
+    /// The host's clocks. The monotonic and CPU time clocks count from `start`.
+    Host { start: Instant },
+    /// A clock for reproducible tests, starting at zero. It only moves when the app sleeps,
+    /// and a little on every read, so apps waiting in a loop for time to pass still finish.
+    Fake { now: u64 },
+}
+
+impl WasiClock {
+    pub fn host() -> Self {
+        WasiClock::Host {
+            start: Instant::now(),
+        }
+    }
+
+    pub fn fake() -> Self {
+        WasiClock::Fake { now: 0 }
+    }
+
+    /// The current time on the clock `clock_id`, in nanoseconds
+    fn now(&mut self, clock_id: u32) -> Result<u64, Errno> {
+        if clock_id > CLOCKID_THREAD_CPUTIME_ID {
+            return Err(Errno::Inval);
+        }
+
+        match self {
+            WasiClock::Host { start } => {
+                if clock_id == CLOCKID_REALTIME {
+                    let since_epoch = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default();
+                    Ok(since_epoch.as_nanos() as u64)
+                } else {
+                    Ok(start.elapsed().as_nanos() as u64)
+                }
+            }
+            WasiClock::Fake { now } => {
+                *now += FAKE_CLOCK_TICK;
+                Ok(*now)
+            }
+        }
+    }
+
+    /// The resolution of the clock `clock_id`, in nanoseconds
+    fn resolution(&self, clock_id: u32) -> Result<u64, Errno> {
+        if clock_id > CLOCKID_THREAD_CPUTIME_ID {
+            return Err(Errno::Inval);
+        }
+
+        match self {
+            WasiClock::Host { .. } => Ok(1),
+            WasiClock::Fake { .. } => Ok(FAKE_CLOCK_TICK),
+        }
+    }
+
+    fn sleep(&mut self, nanos: u64) {
+        match self {
+            WasiClock::Host { .. } => thread::sleep(Duration::from_nanos(nanos)),
+            WasiClock::Fake { now } => *now += nanos,
+        }
+    }
 }
 
 enum WriteLock<'a> {
     StdOut(StdoutLock<'a>),
     Stderr(StderrLock<'a>),
     RegularFile(&'a mut Vec<u8>),
+    HostFile(&'a mut File),
 }
 
+// https://github.com/WebAssembly/WASI/blob/snapshot-01/phases/snapshot/docs.md
+const FILETYPE_UNKNOWN: u8 = 0;
+const FILETYPE_CHARACTER_DEVICE: u8 = 2;
+const FILETYPE_DIRECTORY: u8 = 3;
+const FILETYPE_REGULAR_FILE: u8 = 4;
+const FILETYPE_SYMBOLIC_LINK: u8 = 7;
+
+const LOOKUPFLAGS_SYMLINK_FOLLOW: u32 = 1 << 0;
+
+const OFLAGS_CREAT: u32 = 1 << 0;
+const OFLAGS_DIRECTORY: u32 = 1 << 1;
+const OFLAGS_EXCL: u32 = 1 << 2;
+const OFLAGS_TRUNC: u32 = 1 << 3;
+
+const FDFLAGS_APPEND: u32 = 1 << 0;
+
+const RIGHTS_FD_READ: u64 = 1 << 1;
+const RIGHTS_FD_WRITE: u64 = 1 << 6;
+
+const CLOCKID_REALTIME: u32 = 0;
+const CLOCKID_THREAD_CPUTIME_ID: u32 = 3;
+
+const EVENTTYPE_CLOCK: u8 = 0;
+const EVENTTYPE_FD_READ: u8 = 1;
+const EVENTTYPE_FD_WRITE: u8 = 2;
+
+const SUBCLOCKFLAGS_SUBSCRIPTION_CLOCK_ABSTIME: u16 = 1 << 0;
+
+/// How far the fake clock moves every time it's read, in nanoseconds
+const FAKE_CLOCK_TICK: u64 = 1_000;
+
+/// How many symlinks we follow while resolving one path before giving up, like Linux does
+const MAX_SYMLINKS: usize = 40;
+
 /// Implementation of WASI syscalls
 /// References for other engines:
 /// https://github.com/wasmerio/wasmer/blob/ef8d2f651ed29b4b06fdc2070eb8189922c54d82/lib/wasi/src/syscalls/mod.rs
@@ -44,7 +167,30 @@ impl<'a> WasiDispatcher<'a> {
                 WasiFile::HostSystemFile,
                 WasiFile::HostSystemFile,
             ],
+            env: vec![],
+            clock: WasiClock::host(),
+        }
+    }
+
+    /// Give the app access to a directory on the host, which it will know as `guest_path`.
+    /// Paths the app opens through it are sandboxed, so they can't lead outside of it.
+    /// Preopened directories must be added before the app starts running.
+    pub fn preopen_dir(&mut self, host_path: impl AsRef<Path>, guest_path: &str) -> io::Result<()> {
+        let host_path = host_path.as_ref().canonicalize()?;
+        if !host_path.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is not a directory", host_path.display()),
+            ));
         }
+
+        self.files.push(WasiFile::HostDirectory(WasiDirectory {
+            sandbox: host_path.clone(),
+            host_path,
+            preopen_name: Some(guest_path.to_string()),
+        }));
+
+        Ok(())
     }
 
     pub fn dispatch(
@@ -89,8 +235,19 @@ impl<'a> WasiDispatcher<'a> {
                 success_code
             }
             "environ_get" => {
-                // `environ_sizes_get` always reports 0 environment variables
-                // so we don't have to do anything here.
+                // uint8_t ** environ,
+                let mut ptr_ptr_environ = arguments[0].expect_i32().unwrap() as usize;
+                // uint8_t * environ_buf
+                let mut ptr_environ_buf = arguments[1].expect_i32().unwrap() as usize;
+
+                for var in self.env.iter() {
+                    write_u32(memory, ptr_ptr_environ, ptr_environ_buf as u32);
+                    let bytes_target = &mut memory[ptr_environ_buf..][..var.len()];
+                    bytes_target.copy_from_slice(var.as_bytes());
+                    memory[ptr_environ_buf + var.len()] = 0; // C string zero termination
+                    ptr_environ_buf += var.len() + 1;
+                    ptr_ptr_environ += 4;
+                }
 
                 success_code
             }
@@ -99,20 +256,58 @@ impl<'a> WasiDispatcher<'a> {
                 let size_env_ptr = arguments[1].expect_i32().unwrap() as usize;
 
                 // Calculate the total size required for environment variables
-                let total_size = 0;
-                let count = 0;
+                let total_size: usize = self.env.iter().map(|var| 1 + var.len()).sum();
+                let count = self.env.len() as u32;
 
                 write_u32(memory, num_env_ptr, count);
                 write_u32(memory, size_env_ptr, total_size as u32);
 
                 success_code
             }
-            "clock_res_get" => success_code, // this dummy implementation seems to be good enough for some functions
-            "clock_time_get" => success_code,
+            "clock_res_get" => {
+                // The clock to query
+                let clock_id = arguments[0].expect_i32().unwrap() as u32;
+                // Out param: the resolution in nanoseconds
+                let ptr_resolution = arguments[1].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .clock
+                    .resolution(clock_id)
+                    .map(|resolution| write_u64(memory, ptr_resolution, resolution));
+
+                errno_value(result)
+            }
+            "clock_time_get" => {
+                // The clock to read
+                let clock_id = arguments[0].expect_i32().unwrap() as u32;
+                // arguments[1] is the precision the app would like, which we don't need
+                // Out param: the time in nanoseconds
+                let ptr_time = arguments[2].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .clock
+                    .now(clock_id)
+                    .map(|time| write_u64(memory, ptr_time, time));
+
+                errno_value(result)
+            }
             "fd_advise" => todo!("WASI {}({:?})", function_name, arguments),
             "fd_allocate" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_close" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_datasync" => todo!("WASI {}({:?})", function_name, arguments),
+            "fd_close" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+
+                match self.files.get_mut(fd) {
+                    None | Some(WasiFile::Closed) => Some(Value::I32(Errno::Badf as i32)),
+                    Some(file) => {
+                        *file = WasiFile::Closed;
+                        success_code
+                    }
+                }
+            }
+            "fd_datasync" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                errno_value(self.host_file(fd).and_then(|file| Ok(file.sync_data()?)))
+            }
             "fd_fdstat_get" => {
                 // (i32, i32) -> i32
 
@@ -122,8 +317,8 @@ impl<'a> WasiDispatcher<'a> {
                 let stat_mut_ptr = arguments[1].expect_i32().unwrap() as usize;
 
                 match fd {
-                    1 | 2 => {
-                        // Tell WASI that stdout and stderr are a tty (no seek or tell)
+                    0..=2 => {
+                        // Tell WASI that stdin, stdout and stderr are a tty (no seek or tell)
                         // https://github.com/WebAssembly/wasi-libc/blob/659ff414560721b1660a19685110e484a081c3d4/libc-bottom-half/sources/isatty.c
                         // *Not* a tty if:
                         //     (statbuf.fs_filetype != __WASI_FILETYPE_CHARACTER_DEVICE ||
@@ -132,24 +327,90 @@ impl<'a> WasiDispatcher<'a> {
                         //     .fs_filetype = __WASI_FILETYPE_CHARACTER_DEVICE
                         //     .fs_rights_base = 0
 
-                        const WASI_FILETYPE_CHARACTER_DEVICE: u8 = 2;
-                        memory[stat_mut_ptr] = WASI_FILETYPE_CHARACTER_DEVICE;
+                        memory[stat_mut_ptr] = FILETYPE_CHARACTER_DEVICE;
 
                         for b in memory[stat_mut_ptr + 1..stat_mut_ptr + 24].iter_mut() {
                             *b = 0;
                         }
                     }
-                    _ => todo!("WASI {}({:?})", function_name, arguments),
+                    _ => {
+                        let filetype = match self.files.get(fd) {
+                            Some(WasiFile::HostDirectory(_)) => FILETYPE_DIRECTORY,
+                            Some(WasiFile::Closed) | None => {
+                                return Some(Value::I32(Errno::Badf as i32));
+                            }
+                            Some(_) => FILETYPE_REGULAR_FILE,
+                        };
+
+                        // struct fdstat { u8 filetype; u16 flags; u64 rights_base; u64 rights_inheriting; }
+                        memory[stat_mut_ptr..][..24].fill(0);
+                        memory[stat_mut_ptr] = filetype;
+                        write_u64(memory, stat_mut_ptr + 8, u64::MAX);
+                        write_u64(memory, stat_mut_ptr + 16, u64::MAX);
+                    }
                 }
 
                 success_code
             }
             "fd_fdstat_set_flags" => todo!("WASI {}({:?})", function_name, arguments),
             "fd_fdstat_set_rights" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_filestat_get" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_filestat_set_size" => todo!("WASI {}({:?})", function_name, arguments),
+            "fd_filestat_get" => {
+                // file descriptor
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                // ptr to a wasi_filestat_t
+                let ptr_buf = arguments[1].expect_i32().unwrap() as usize;
+
+                use WasiFile::*;
+                let result = match self.files.get(fd) {
+                    Some(HostFile(file)) => file
+                        .metadata()
+                        .map(|metadata| write_filestat(memory, ptr_buf, &metadata))
+                        .map_err(Errno::from),
+                    Some(HostDirectory(dir)) => fs::metadata(&dir.host_path)
+                        .map(|metadata| write_filestat(memory, ptr_buf, &metadata))
+                        .map_err(Errno::from),
+                    Some(HostSystemFile) => {
+                        write_filestat_fields(memory, ptr_buf, FILETYPE_CHARACTER_DEVICE, 0, 0, 0);
+                        Ok(())
+                    }
+                    Some(ReadOnly(content) | WriteOnly(content) | ReadWrite(content)) => {
+                        let size = content.len() as u64;
+                        write_filestat_fields(memory, ptr_buf, FILETYPE_REGULAR_FILE, size, 0, 0);
+                        Ok(())
+                    }
+                    Some(Closed) | None => Err(Errno::Badf),
+                };
+
+                errno_value(result)
+            }
+            "fd_filestat_set_size" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let size = arguments[1].expect_i64().unwrap() as u64;
+                errno_value(self.host_file(fd).and_then(|file| Ok(file.set_len(size)?)))
+            }
             "fd_filestat_set_times" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_pread" => todo!("WASI {}({:?})", function_name, arguments),
+            "fd_pread" => {
+                // file descriptor
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                // Array of IO vectors
+                let ptr_iovs = arguments[1].expect_i32().unwrap() as usize;
+                // Length of array
+                let iovs_len = arguments[2].expect_i32().unwrap();
+                // Offset in the file to read from
+                let offset = arguments[3].expect_i64().unwrap() as u64;
+                // Out param: number of bytes read
+                let ptr_nread = arguments[4].expect_i32().unwrap() as usize;
+
+                let result = self.host_file(fd).and_then(|file| {
+                    let n_read = at_offset(file, offset, |file| {
+                        read_iovs(file, memory, ptr_iovs, iovs_len)
+                    })?;
+                    write_u32(memory, ptr_nread, n_read as u32);
+                    Ok(())
+                });
+
+                errno_value(result)
+            }
             "fd_prestat_get" => {
                 // The preopened file descriptor to query
                 let fd = arguments[0].expect_i32().unwrap() as usize;
@@ -157,19 +418,62 @@ impl<'a> WasiDispatcher<'a> {
                 //  preopen type: 4 bytes, where 0=dir is the only one supported, it seems
                 //  preopen name length: 4 bytes
                 let ptr_buf = arguments[1].expect_i32().unwrap() as usize;
-                memory[ptr_buf..][..8].copy_from_slice(&0u64.to_le_bytes());
-                if fd < self.files.len() {
-                    success_code
-                } else {
-                    println!("WASI warning: file descriptor {fd} does not exist");
-                    Some(Value::I32(Errno::Badf as i32))
+
+                // WASI libc asks about file descriptors from 3 upwards, until it gets `Badf`
+                match self.files.get(fd) {
+                    Some(WasiFile::HostDirectory(WasiDirectory {
+                        preopen_name: Some(name),
+                        ..
+                    })) => {
+                        write_u32(memory, ptr_buf, 0);
+                        write_u32(memory, ptr_buf + 4, name.len() as u32);
+                        success_code
+                    }
+                    _ => Some(Value::I32(Errno::Badf as i32)),
                 }
             }
             "fd_prestat_dir_name" => {
-                // We're not giving names to any of our files so just return success
-                success_code
+                // The preopened file descriptor to query
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                // Where to write the name
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                // The size of the buffer, which `fd_prestat_get` told the app to allocate
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                match self.files.get(fd) {
+                    Some(WasiFile::HostDirectory(WasiDirectory {
+                        preopen_name: Some(name),
+                        ..
+                    })) => {
+                        let len = name.len().min(path_len);
+                        memory[ptr_path..][..len].copy_from_slice(&name.as_bytes()[..len]);
+                        success_code
+                    }
+                    _ => Some(Value::I32(Errno::Badf as i32)),
+                }
+            }
+            "fd_pwrite" => {
+                // file descriptor
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                // Array of IO vectors
+                let ptr_iovs = arguments[1].expect_i32().unwrap() as usize;
+                // Length of array
+                let iovs_len = arguments[2].expect_i32().unwrap();
+                // Offset in the file to write at
+                let offset = arguments[3].expect_i64().unwrap() as u64;
+                // Out param: number of bytes written
+                let ptr_nwritten = arguments[4].expect_i32().unwrap() as usize;
+
+                let result = self.host_file(fd).and_then(|file| {
+                    let n_written = at_offset(file, offset, |file| {
+                        write_iovs(file, memory, ptr_iovs, iovs_len)
+                    })?;
+                    write_u32(memory, ptr_nwritten, n_written as u32);
+                    Ok(())
+                });
+
+                errno_value(result)
             }
-            "fd_pwrite" => todo!("WASI {}({:?})", function_name, arguments),
             "fd_read" => {
                 use WasiFile::*;
 
@@ -189,7 +493,7 @@ impl<'a> WasiDispatcher<'a> {
                 // };
 
                 let mut n_read: usize = 0;
-                match self.files.get(fd) {
+                match self.files.get_mut(fd) {
                     Some(ReadOnly(content) | ReadWrite(content)) => {
                         for _ in 0..iovs_len {
                             let iov_base = read_u32(memory, ptr_iovs) as usize;
@@ -218,17 +522,79 @@ impl<'a> WasiDispatcher<'a> {
                             }
                         }
                     }
+                    Some(HostFile(file)) => match read_iovs(file, memory, ptr_iovs, iovs_len) {
+                        Ok(n) => n_read = n,
+                        Err(e) => return Some(Value::I32(Errno::from(e) as i32)),
+                    },
+                    Some(HostDirectory(_)) => return Some(Value::I32(Errno::Isdir as i32)),
                     _ => return Some(Value::I32(Errno::Badf as i32)),
                 };
 
                 memory[ptr_nread..][..4].copy_from_slice(&(n_read as u32).to_le_bytes());
                 success_code
             }
-            "fd_readdir" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_renumber" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_seek" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_sync" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_tell" => todo!("WASI {}({:?})", function_name, arguments),
+            "fd_readdir" => errno_value(self.fd_readdir(arguments, memory)),
+            "fd_renumber" => {
+                // The file descriptor to move
+                let from = arguments[0].expect_i32().unwrap() as usize;
+                // The file descriptor to move it to, closing whatever was there
+                let to = arguments[1].expect_i32().unwrap() as usize;
+
+                let is_open =
+                    |fd: usize| !matches!(self.files.get(fd), None | Some(WasiFile::Closed));
+                if !is_open(from) || !is_open(to) {
+                    return Some(Value::I32(Errno::Badf as i32));
+                }
+
+                if from != to {
+                    self.files[to] = std::mem::replace(&mut self.files[from], WasiFile::Closed);
+                }
+
+                success_code
+            }
+            "fd_seek" => {
+                // file descriptor
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                // Number of bytes to move
+                let offset = arguments[1].expect_i64().unwrap();
+                // What the offset is relative to: 0=start, 1=current position, 2=end
+                let whence = arguments[2].expect_i32().unwrap();
+                // Out param: the new offset from the start of the file
+                let ptr_newoffset = arguments[3].expect_i32().unwrap() as usize;
+
+                let seek_from = match whence {
+                    0 if offset >= 0 => SeekFrom::Start(offset as u64),
+                    1 => SeekFrom::Current(offset),
+                    2 => SeekFrom::End(offset),
+                    _ => return Some(Value::I32(Errno::Inval as i32)),
+                };
+
+                let result = self.host_file(fd).and_then(|file| {
+                    let new_offset = file.seek(seek_from)?;
+                    write_u64(memory, ptr_newoffset, new_offset);
+                    Ok(())
+                });
+
+                errno_value(result)
+            }
+            "fd_sync" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                errno_value(self.host_file(fd).and_then(|file| Ok(file.sync_all()?)))
+            }
+            "fd_tell" => {
+                // file descriptor
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                // Out param: the current offset from the start of the file
+                let ptr_offset = arguments[1].expect_i32().unwrap() as usize;
+
+                let result = self.host_file(fd).and_then(|file| {
+                    let offset = file.stream_position()?;
+                    write_u64(memory, ptr_offset, offset);
+                    Ok(())
+                });
+
+                errno_value(result)
+            }
             "fd_write" => {
                 use WasiFile::*;
 
@@ -252,6 +618,7 @@ impl<'a> WasiDispatcher<'a> {
                     Some(WriteOnly(content) | ReadWrite(content)) => {
                         WriteLock::RegularFile(content)
                     }
+                    Some(HostFile(file)) => WriteLock::HostFile(file),
                     _ => return Some(Value::I32(Errno::Badf as i32)),
                 };
 
@@ -280,6 +647,7 @@ impl<'a> WasiDispatcher<'a> {
                         WriteLock::StdOut(stdout) => stdout.write_all(bytes),
                         WriteLock::Stderr(stderr) => stderr.write_all(bytes),
                         WriteLock::RegularFile(content) => content.write_all(bytes),
+                        WriteLock::HostFile(file) => file.write_all(bytes),
                     };
                     if write_result.is_err() {
                         break;
@@ -300,23 +668,115 @@ impl<'a> WasiDispatcher<'a> {
                     Err(_) => Some(Value::I32(Errno::Io as i32)),
                 }
             }
-            "path_create_directory" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_filestat_get" => todo!("WASI {}({:?})", function_name, arguments),
+            "path_create_directory" => {
+                // The directory the path is relative to
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .resolve_path(fd, memory, ptr_path, path_len, false)
+                    .and_then(|path| Ok(fs::create_dir(path)?));
+
+                errno_value(result)
+            }
+            "path_filestat_get" => {
+                // The directory the path is relative to
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                // Whether to follow a symlink at the end of the path
+                let lookup_flags = arguments[1].expect_i32().unwrap() as u32;
+                let ptr_path = arguments[2].expect_i32().unwrap() as usize;
+                let path_len = arguments[3].expect_i32().unwrap() as usize;
+                // ptr to a wasi_filestat_t
+                let ptr_buf = arguments[4].expect_i32().unwrap() as usize;
+
+                let follow = lookup_flags & LOOKUPFLAGS_SYMLINK_FOLLOW != 0;
+                let result = self
+                    .resolve_path(fd, memory, ptr_path, path_len, follow)
+                    .and_then(|path| {
+                        let metadata = if follow {
+                            fs::metadata(path)?
+                        } else {
+                            fs::symlink_metadata(path)?
+                        };
+                        write_filestat(memory, ptr_buf, &metadata);
+                        Ok(())
+                    });
+
+                errno_value(result)
+            }
             "path_filestat_set_times" => todo!("WASI {}({:?})", function_name, arguments),
             "path_link" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_open" => todo!("WASI {}({:?})", function_name, arguments),
+            "path_open" => errno_value(self.path_open(arguments, memory)),
             "path_readlink" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_remove_directory" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_rename" => todo!("WASI {}({:?})", function_name, arguments),
+            "path_remove_directory" => {
+                // The directory the path is relative to
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .resolve_path(fd, memory, ptr_path, path_len, false)
+                    .and_then(|path| {
+                        // The io::ErrorKind for this isn't stable in our Rust version
+                        if fs::read_dir(&path)?.next().is_some() {
+                            return Err(Errno::Notempty);
+                        }
+                        Ok(fs::remove_dir(path)?)
+                    });
+
+                errno_value(result)
+            }
+            "path_rename" => {
+                // The directory the old path is relative to
+                let old_fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_old_path = arguments[1].expect_i32().unwrap() as usize;
+                let old_path_len = arguments[2].expect_i32().unwrap() as usize;
+                // The directory the new path is relative to
+                let new_fd = arguments[3].expect_i32().unwrap() as usize;
+                let ptr_new_path = arguments[4].expect_i32().unwrap() as usize;
+                let new_path_len = arguments[5].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .resolve_path(old_fd, memory, ptr_old_path, old_path_len, false)
+                    .and_then(|old_path| {
+                        let new_path =
+                            self.resolve_path(new_fd, memory, ptr_new_path, new_path_len, false)?;
+                        Ok(fs::rename(old_path, new_path)?)
+                    });
+
+                errno_value(result)
+            }
             "path_symlink" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_unlink_file" => todo!("WASI {}({:?})", function_name, arguments),
-            "poll_oneoff" => todo!("WASI {}({:?})", function_name, arguments),
+            "path_unlink_file" => {
+                // The directory the path is relative to
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .resolve_path(fd, memory, ptr_path, path_len, false)
+                    .and_then(|path| {
+                        if fs::symlink_metadata(&path)?.is_dir() {
+                            return Err(Errno::Isdir);
+                        }
+                        Ok(fs::remove_file(path)?)
+                    });
+
+                errno_value(result)
+            }
+            "poll_oneoff" => errno_value(self.poll_oneoff(arguments, memory)),
             "proc_exit" => {
                 let exit_code = arguments[0].expect_i32().unwrap();
                 exit(exit_code);
             }
             "proc_raise" => todo!("WASI {}({:?})", function_name, arguments),
-            "sched_yield" => todo!("WASI {}({:?})", function_name, arguments),
+            "sched_yield" => {
+                if let WasiClock::Host { .. } = self.clock {
+                    thread::yield_now();
+                }
+                success_code
+            }
             "random_get" => {
                 // A pointer to a buffer where the random bytes will be written
                 let ptr_buf = arguments[0].expect_i32().unwrap() as usize;
@@ -333,6 +793,406 @@ impl<'a> WasiDispatcher<'a> {
             _ => panic!("Unknown WASI function {function_name}({arguments:?})"),
         }
     }
+
+    fn path_open(&mut self, arguments: &[Value], memory: &mut [u8]) -> Result<(), Errno> {
+        // The directory the path is relative to
+        let dir_fd = arguments[0].expect_i32().unwrap() as usize;
+        // arguments[1] is lookupflags. We always follow symlinks that stay in the sandbox.
+        let ptr_path = arguments[2].expect_i32().unwrap() as usize;
+        let path_len = arguments[3].expect_i32().unwrap() as usize;
+        // Whether to create, truncate, or require a directory
+        let oflags = arguments[4].expect_i32().unwrap() as u32;
+        // What the app is allowed to do with the new file descriptor
+        let rights = arguments[5].expect_i64().unwrap() as u64;
+        // arguments[6] is the rights for file descriptors opened from this one. Not enforced.
+        let fdflags = arguments[7].expect_i32().unwrap() as u32;
+        // Out param: the new file descriptor
+        let ptr_fd = arguments[8].expect_i32().unwrap() as usize;
+
+        let path = self.resolve_path(dir_fd, memory, ptr_path, path_len, true)?;
+
+        let file = if oflags & OFLAGS_DIRECTORY != 0 || path.is_dir() {
+            if !fs::metadata(&path)?.is_dir() {
+                return Err(Errno::Notdir);
+            }
+            let sandbox = match &self.files[dir_fd] {
+                WasiFile::HostDirectory(dir) => dir.sandbox.clone(),
+                _ => unreachable!("resolve_path only accepts directories"),
+            };
+            WasiFile::HostDirectory(WasiDirectory {
+                host_path: path,
+                sandbox,
+                preopen_name: None,
+            })
+        } else {
+            let write = rights & RIGHTS_FD_WRITE != 0;
+            let create = oflags & OFLAGS_CREAT != 0;
+            let file = OpenOptions::new()
+                .read(rights & RIGHTS_FD_READ != 0 || !write)
+                .write(write)
+                .append(fdflags & FDFLAGS_APPEND != 0)
+                .create(create)
+                .create_new(create && oflags & OFLAGS_EXCL != 0)
+                .truncate(oflags & OFLAGS_TRUNC != 0)
+                .open(path)?;
+            WasiFile::HostFile(file)
+        };
+
+        let fd = self.insert_file(file);
+        write_u32(memory, ptr_fd, fd as u32);
+        Ok(())
+    }
+
+    fn fd_readdir(&self, arguments: &[Value], memory: &mut [u8]) -> Result<(), Errno> {
+        // file descriptor
+        let fd = arguments[0].expect_i32().unwrap() as usize;
+        // Where to write the directory entries
+        let ptr_buf = arguments[1].expect_i32().unwrap() as usize;
+        let buf_len = arguments[2].expect_i32().unwrap() as usize;
+        // The index of the entry to start from, which is 0 or the `d_next` of an earlier entry
+        let cookie = arguments[3].expect_i64().unwrap() as u64;
+        // Out param: number of bytes written. Less than buf_len means the end of the directory.
+        let ptr_bufused = arguments[4].expect_i32().unwrap() as usize;
+
+        let dir = match self.files.get(fd) {
+            Some(WasiFile::HostDirectory(dir)) => dir,
+            Some(WasiFile::Closed) | None => return Err(Errno::Badf),
+            Some(_) => return Err(Errno::Notdir),
+        };
+
+        // Cookies are indices, so the entries need to come in the same order every time
+        let mut entries = vec![];
+        for entry in fs::read_dir(&dir.host_path)? {
+            let entry = entry?;
+            let filetype = entry.file_type().map_or(FILETYPE_UNKNOWN, filetype_of);
+            entries.push((entry.file_name().to_string_lossy().into_owned(), filetype));
+        }
+        entries.sort();
+
+        // struct dirent { u64 d_next; u64 d_ino; u32 d_namlen; u8 d_type; }, followed by the name
+        let mut bytes = vec![];
+        for (index, (name, filetype)) in entries.iter().enumerate().skip(cookie as usize) {
+            if bytes.len() >= buf_len {
+                break;
+            }
+            bytes.extend((index as u64 + 1).to_le_bytes());
+            bytes.extend(0u64.to_le_bytes());
+            bytes.extend((name.len() as u32).to_le_bytes());
+            bytes.extend([*filetype, 0, 0, 0]);
+            bytes.extend(name.as_bytes());
+        }
+
+        // If the last entry doesn't fit, WASI libc tries again with a bigger buffer
+        let used = bytes.len().min(buf_len);
+        memory[ptr_buf..][..used].copy_from_slice(&bytes[..used]);
+        write_u32(memory, ptr_bufused, used as u32);
+        Ok(())
+    }
+
+    /// Wait for the first of a list of events, like a timeout or a file being ready to read.
+    /// This is how WASI libc implements `sleep`.
+    fn poll_oneoff(&mut self, arguments: &[Value], memory: &mut [u8]) -> Result<(), Errno> {
+        // Array of subscriptions to events
+        let ptr_subscriptions = arguments[0].expect_i32().unwrap() as usize;
+        // Out param: array of events that happened, with the same capacity as the subscriptions
+        let ptr_events = arguments[1].expect_i32().unwrap() as usize;
+        let n_subscriptions = arguments[2].expect_i32().unwrap() as usize;
+        // Out param: number of events that happened
+        let ptr_nevents = arguments[3].expect_i32().unwrap() as usize;
+
+        if n_subscriptions == 0 {
+            return Err(Errno::Inval);
+        }
+
+        // (userdata, eventtype) of the events that happened
+        let mut events = vec![];
+        // (userdata, nanoseconds to wait) of the clock subscriptions
+        let mut timeouts = vec![];
+
+        for i in 0..n_subscriptions {
+            // struct subscription { u64 userdata; u8 tag; union { clock, fd_readwrite } u; }
+            // struct clock { u32 id; u64 timeout; u64 precision; u16 flags; }
+            // struct fd_readwrite { u32 fd; }
+            let ptr_subscription = ptr_subscriptions + 48 * i;
+            let userdata = read_u64(memory, ptr_subscription);
+            let eventtype = memory[ptr_subscription + 8];
+
+            match eventtype {
+                EVENTTYPE_CLOCK => {
+                    let clock_id = read_u32(memory, ptr_subscription + 16);
+                    let timeout = read_u64(memory, ptr_subscription + 24);
+                    let flags = u16::from_le_bytes([
+                        memory[ptr_subscription + 40],
+                        memory[ptr_subscription + 41],
+                    ]);
+
+                    let wait = if flags & SUBCLOCKFLAGS_SUBSCRIPTION_CLOCK_ABSTIME != 0 {
+                        timeout.saturating_sub(self.clock.now(clock_id)?)
+                    } else {
+                        timeout
+                    };
+                    timeouts.push((userdata, wait));
+                }
+                EVENTTYPE_FD_READ | EVENTTYPE_FD_WRITE => {
+                    // All our IO is blocking, so files are always ready
+                    events.push((userdata, eventtype));
+                }
+                _ => return Err(Errno::Inval),
+            }
+        }
+
+        if events.is_empty() {
+            if let Some(shortest) = timeouts.iter().map(|(_, wait)| *wait).min() {
+                self.clock.sleep(shortest);
+                events.extend(
+                    timeouts
+                        .iter()
+                        .filter(|(_, wait)| *wait <= shortest)
+                        .map(|(userdata, _)| (*userdata, EVENTTYPE_CLOCK)),
+                );
+            }
+        }
+
+        for (i, (userdata, eventtype)) in events.iter().enumerate() {
+            // struct event { u64 userdata; u16 error; u8 type; struct { u64 nbytes; u16 flags; } fd_readwrite; }
+            let ptr_event = ptr_events + 32 * i;
+            memory[ptr_event..][..32].fill(0);
+            write_u64(memory, ptr_event, *userdata);
+            memory[ptr_event + 10] = *eventtype;
+        }
+        write_u32(memory, ptr_nevents, events.len() as u32);
+
+        Ok(())
+    }
+
+    /// Find where a path the app gave us is on the host, relative to the directory `fd`.
+    /// The result is inside the directory's sandbox, even if the path has `..` or symlinks.
+    ///
+    /// We follow symlinks ourselves, one component at a time, so the result has none left for the
+    /// host to follow. Only a symlink at the very end is kept when `follow_last` is false, for
+    /// operations like unlinking that act on the symlink itself rather than what it points to.
+    fn resolve_path(
+        &self,
+        fd: usize,
+        memory: &[u8],
+        ptr_path: usize,
+        path_len: usize,
+        follow_last: bool,
+    ) -> Result<PathBuf, Errno> {
+        let dir = match self.files.get(fd) {
+            Some(WasiFile::HostDirectory(dir)) => dir,
+            Some(WasiFile::Closed) | None => return Err(Errno::Badf),
+            Some(_) => return Err(Errno::Notdir),
+        };
+        let path =
+            std::str::from_utf8(&memory[ptr_path..][..path_len]).map_err(|_| Errno::Ilseq)?;
+
+        // The components still to resolve, last one first
+        let mut pending = Vec::new();
+        push_components(&mut pending, Path::new(path))?;
+
+        let mut resolved = dir.host_path.clone();
+        let mut symlinks_followed = 0;
+
+        while let Some(name) = pending.pop() {
+            match Path::new(&name).components().next() {
+                Some(Component::ParentDir) => {
+                    resolved.pop();
+                }
+                Some(Component::Normal(name)) => {
+                    resolved.push(name);
+
+                    let is_symlink = fs::symlink_metadata(&resolved)
+                        .map_or(false, |metadata| metadata.file_type().is_symlink());
+
+                    if is_symlink && (follow_last || !pending.is_empty()) {
+                        symlinks_followed += 1;
+                        if symlinks_followed > MAX_SYMLINKS {
+                            return Err(Errno::Loop);
+                        }
+
+                        let target = fs::read_link(&resolved)?;
+                        resolved.pop();
+
+                        if target.is_absolute() {
+                            // Only absolute symlinks into the sandbox can be followed
+                            let inside = target
+                                .strip_prefix(&dir.sandbox)
+                                .map_err(|_| Errno::Notcapable)?;
+                            resolved = dir.sandbox.clone();
+                            push_components(&mut pending, inside)?;
+                        } else {
+                            push_components(&mut pending, &target)?;
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            if !resolved.starts_with(&dir.sandbox) {
+                return Err(Errno::Notcapable);
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    /// The host file behind `fd`, for operations that only make sense on a real file
+    fn host_file(&mut self, fd: usize) -> Result<&mut File, Errno> {
+        match self.files.get_mut(fd) {
+            Some(WasiFile::HostFile(file)) => Ok(file),
+            Some(WasiFile::HostDirectory(_)) => Err(Errno::Isdir),
+            Some(WasiFile::Closed) | None => Err(Errno::Badf),
+            // stdio and in-memory files can't seek
+            Some(_) => Err(Errno::Spipe),
+        }
+    }
+
+    /// Add a newly opened file, reusing the lowest closed file descriptor if there is one
+    fn insert_file(&mut self, file: WasiFile) -> usize {
+        let closed = self
+            .files
+            .iter()
+            .skip(3)
+            .position(|f| matches!(f, WasiFile::Closed));
+
+        match closed {
+            Some(index) => {
+                let fd = 3 + index;
+                self.files[fd] = file;
+                fd
+            }
+            None => {
+                self.files.push(file);
+                self.files.len() - 1
+            }
+        }
+    }
+}
+
+/// Queue the components of a relative `path` for [WasiDispatcher::resolve_path], so that its first
+/// component is the next to be resolved. Absolute paths can't be resolved relative to a directory.
+fn push_components(pending: &mut Vec<OsString>, path: &Path) -> Result<(), Errno> {
+    let start = pending.len();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(_) | Component::ParentDir => {
+                pending.push(component.as_os_str().to_os_string())
+            }
+            Component::CurDir => {}
+            Component::RootDir | Component::Prefix(_) => return Err(Errno::Notcapable),
+        }
+    }
+
+    pending[start..].reverse();
+
+    Ok(())
+}
+
+fn errno_value(result: Result<(), Errno>) -> Option<Value> {
+    let errno = result.err().unwrap_or(Errno::Success);
+    Some(Value::I32(errno as i32))
+}
+
+/// Read from `file` into an array of IO vectors, until one isn't filled.
+fn read_iovs(
+    file: &mut impl Read,
+    memory: &mut [u8],
+    ptr_iovs: usize,
+    iovs_len: i32,
+) -> io::Result<usize> {
+    let mut n_read = 0;
+    for i in 0..iovs_len {
+        let ptr_iov = ptr_iovs + (8 * i as usize);
+        let iov_base = read_u32(memory, ptr_iov) as usize;
+        let iov_len = read_u32(memory, ptr_iov + 4) as usize;
+        let n = file.read(&mut memory[iov_base..][..iov_len])?;
+        n_read += n;
+        if n < iov_len {
+            break;
+        }
+    }
+    Ok(n_read)
+}
+
+/// Write an array of IO vectors to `file`
+fn write_iovs(
+    file: &mut impl Write,
+    memory: &[u8],
+    ptr_iovs: usize,
+    iovs_len: i32,
+) -> io::Result<usize> {
+    let mut n_written = 0;
+    for i in 0..iovs_len {
+        let ptr_iov = ptr_iovs + (8 * i as usize);
+        let iov_base = read_u32(memory, ptr_iov) as usize;
+        let iov_len = read_u32(memory, ptr_iov + 4) as usize;
+        file.write_all(&memory[iov_base..][..iov_len])?;
+        n_written += iov_len;
+    }
+    Ok(n_written)
+}
+
+/// Do some IO at `offset` in `file`, leaving its current position as it was
+fn at_offset<T>(
+    file: &mut File,
+    offset: u64,
+    io: impl FnOnce(&mut File) -> io::Result<T>,
+) -> Result<T, Errno> {
+    let position = file.stream_position()?;
+    file.seek(SeekFrom::Start(offset))?;
+    let result = io(file);
+    file.seek(SeekFrom::Start(position))?;
+    Ok(result?)
+}
+
+fn filetype_of(file_type: FileType) -> u8 {
+    if file_type.is_dir() {
+        FILETYPE_DIRECTORY
+    } else if file_type.is_symlink() {
+        FILETYPE_SYMBOLIC_LINK
+    } else if file_type.is_file() {
+        FILETYPE_REGULAR_FILE
+    } else {
+        FILETYPE_UNKNOWN
+    }
+}
+
+fn write_filestat(memory: &mut [u8], addr: usize, metadata: &Metadata) {
+    let nanos = |time: io::Result<SystemTime>| {
+        time.ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |duration| duration.as_nanos() as u64)
+    };
+
+    write_filestat_fields(
+        memory,
+        addr,
+        filetype_of(metadata.file_type()),
+        metadata.len(),
+        nanos(metadata.accessed()),
+        nanos(metadata.modified()),
+    );
+}
+
+fn write_filestat_fields(
+    memory: &mut [u8],
+    addr: usize,
+    filetype: u8,
+    size: u64,
+    accessed: u64,
+    modified: u64,
+) {
+    // struct filestat { u64 dev; u64 ino; u8 filetype; u64 nlink; u64 size; u64 atim; u64 mtim; u64 ctim; }
+    // We leave dev and ino as zero, since nothing we've seen depends on them.
+    memory[addr..][..64].fill(0);
+    memory[addr + 16] = filetype;
+    write_u64(memory, addr + 24, 1);
+    write_u64(memory, addr + 32, size);
+    write_u64(memory, addr + 40, accessed);
+    write_u64(memory, addr + 48, modified);
+    write_u64(memory, addr + 56, modified);
 }
 
 fn read_u32(memory: &[u8], addr: usize) -> u32 {
@@ -341,6 +1201,12 @@ fn read_u32(memory: &[u8], addr: usize) -> u32 {
     u32::from_le_bytes(bytes)
 }
 
+fn read_u64(memory: &[u8], addr: usize) -> u64 {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&memory[addr..][..8]);
+    u64::from_le_bytes(bytes)
+}
+
 fn read_i32(memory: &[u8], addr: usize) -> i32 {
     let mut bytes = [0; 4];
     bytes.copy_from_slice(&memory[addr..][..4]);
@@ -355,6 +1221,10 @@ fn write_i32(memory: &mut [u8], addr: usize, value: i32) {
     memory[addr..][..4].copy_from_slice(&value.to_le_bytes());
 }
 
+fn write_u64(memory: &mut [u8], addr: usize, value: u64) {
+    memory[addr..][..8].copy_from_slice(&value.to_le_bytes());
+}
+
 /// Error codes returned by functions.
 /// Not all of these error codes are returned by the functions provided by this
 /// API; some are used in higher-level library layers, and others are provided
@@ -517,3 +1387,18 @@ pub enum Errno {
     /// Extension: Capabilities insufficient.
     Notcapable,
 }
+
+impl From<io::Error> for Errno {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => Errno::Noent,
+            io::ErrorKind::PermissionDenied => Errno::Access,
+            io::ErrorKind::AlreadyExists => Errno::Exist,
+            io::ErrorKind::InvalidInput => Errno::Inval,
+            io::ErrorKind::Interrupted => Errno::Intr,
+            io::ErrorKind::WouldBlock => Errno::Again,
+            io::ErrorKind::Unsupported => Errno::Notsup,
+            _ => Errno::Io,
+        }
+    }
+}